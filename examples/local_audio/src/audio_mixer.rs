@@ -1,14 +1,21 @@
 use crate::db_meter::calculate_db_level;
-use std::sync::{Arc, Mutex};
+use livekit::webrtc::{
+    audio_stream::native::NativeAudioStream,
+    native::audio_playout_buffer::{AudioPlayoutBuffer, AudioPlayoutBufferOptions},
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 use tokio::sync::mpsc;
 
 #[derive(Clone)]
 pub struct AudioMixer {
-    buffer: Arc<Mutex<std::collections::VecDeque<i16>>>,
+    // One jitter buffer per remote audio track, keyed by track sid
+    streams: Arc<Mutex<HashMap<String, AudioPlayoutBuffer>>>,
     sample_rate: u32,
     channels: u32,
     volume: f32,
-    max_buffer_size: usize,
     db_tx: Option<mpsc::UnboundedSender<f32>>,
     // Channel to send reference audio for echo cancellation
     reference_audio_tx: Option<mpsc::UnboundedSender<Vec<i16>>>,
@@ -16,17 +23,11 @@ pub struct AudioMixer {
 
 impl AudioMixer {
     pub fn new(sample_rate: u32, channels: u32, volume: f32) -> Self {
-        // Buffer for 1 second of audio
-        let max_buffer_size = sample_rate as usize * channels as usize;
-
         Self {
-            buffer: Arc::new(Mutex::new(std::collections::VecDeque::with_capacity(
-                max_buffer_size,
-            ))),
+            streams: Arc::new(Mutex::new(HashMap::new())),
             sample_rate,
             channels,
             volume: volume.clamp(0.0, 1.0),
-            max_buffer_size,
             db_tx: None,
             reference_audio_tx: None,
         }
@@ -38,17 +39,11 @@ impl AudioMixer {
         volume: f32,
         db_tx: mpsc::UnboundedSender<f32>,
     ) -> Self {
-        // Buffer for 1 second of audio
-        let max_buffer_size = sample_rate as usize * channels as usize;
-
         Self {
-            buffer: Arc::new(Mutex::new(std::collections::VecDeque::with_capacity(
-                max_buffer_size,
-            ))),
+            streams: Arc::new(Mutex::new(HashMap::new())),
             sample_rate,
             channels,
             volume: volume.clamp(0.0, 1.0),
-            max_buffer_size,
             db_tx: Some(db_tx),
             reference_audio_tx: None,
         }
@@ -61,47 +56,46 @@ impl AudioMixer {
         db_tx: mpsc::UnboundedSender<f32>,
         reference_audio_tx: mpsc::UnboundedSender<Vec<i16>>,
     ) -> Self {
-        // Buffer for 1 second of audio
-        let max_buffer_size = sample_rate as usize * channels as usize;
-
         Self {
-            buffer: Arc::new(Mutex::new(std::collections::VecDeque::with_capacity(
-                max_buffer_size,
-            ))),
+            streams: Arc::new(Mutex::new(HashMap::new())),
             sample_rate,
             channels,
             volume: volume.clamp(0.0, 1.0),
-            max_buffer_size,
             db_tx: Some(db_tx),
             reference_audio_tx: Some(reference_audio_tx),
         }
     }
 
-    pub fn add_audio_data(&self, data: &[i16]) {
-        let mut buffer = self.buffer.lock().unwrap();
-
-        // Apply volume scaling and add to buffer
-        for &sample in data.iter() {
-            let scaled_sample = (sample as f32 * self.volume) as i16;
-            buffer.push_back(scaled_sample);
+    /// Plays `stream` until it ends or is removed, the stream must have the mixer's channels.
+    pub fn add_stream(&self, key: String, stream: NativeAudioStream) {
+        let options = AudioPlayoutBufferOptions {
+            sample_rate: self.sample_rate,
+            num_channels: self.channels,
+            ..Default::default()
+        };
+        self.streams.lock().unwrap().insert(key, AudioPlayoutBuffer::new(stream, options));
+    }
 
-            // Prevent buffer from growing too large
-            if buffer.len() > self.max_buffer_size {
-                buffer.pop_front();
-            }
-        }
+    pub fn remove_stream(&self, key: &str) {
+        self.streams.lock().unwrap().remove(key);
     }
 
     pub fn get_samples(&self, requested_samples: usize) -> Vec<i16> {
-        let mut buffer = self.buffer.lock().unwrap();
-        let mut result = Vec::with_capacity(requested_samples);
-
-        // Fill the requested samples
-        for _ in 0..requested_samples {
-            if let Some(sample) = buffer.pop_front() {
-                result.push(sample);
-            } else {
-                result.push(0); // Silence when no data available
+        let mut result = vec![0i16; requested_samples];
+        let mut samples = vec![0i16; requested_samples];
+
+        {
+            let mut streams = self.streams.lock().unwrap();
+            // Drop the streams that ended once all of their audio has been played
+            streams.retain(|_, buffer| !buffer.is_finished());
+
+            // Pull each stream at the output clock and mix them, applying volume scaling
+            for buffer in streams.values_mut() {
+                buffer.read(&mut samples);
+                for (mixed, &sample) in result.iter_mut().zip(&samples) {
+                    let scaled_sample = (sample as f32 * self.volume) as i16;
+                    *mixed = mixed.saturating_add(scaled_sample);
+                }
             }
         }
 
@@ -130,8 +124,4 @@ impl AudioMixer {
 
         result
     }
-
-    pub fn buffer_size(&self) -> usize {
-        self.buffer.lock().unwrap().len()
-    }
 }
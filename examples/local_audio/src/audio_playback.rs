@@ -64,6 +64,7 @@ impl AudioPlayback {
                     return;
                 }
 
+                // Pull the remote streams through their jitter buffers at the device clock
                 let mixed_samples = mixer.get_samples(data.len());
 
                 // Convert mixed i16 samples to output format
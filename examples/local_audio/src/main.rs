@@ -11,7 +11,6 @@ use clap::Parser;
 use cpal::traits::{DeviceTrait, HostTrait};
 use cpal::{Device, SampleRate, StreamConfig};
 use db_meter::display_dual_db_meters;
-use futures_util::StreamExt;
 use livekit::{
     options::TrackPublishOptions,
     track::{LocalAudioTrack, LocalTrack, TrackSource},
@@ -408,30 +407,15 @@ async fn handle_remote_audio_streams(
                     info!("Setting up audio stream for participant: {}", participant_identity);
 
                     // Create audio stream for this remote track (fixed to 1 channel)
-                    let mut audio_stream = NativeAudioStream::new(
+                    let audio_stream = NativeAudioStream::new(
                         audio_track.rtc_track(),
                         sample_rate as i32,
                         1, // Fixed to mono
                     );
 
-                    // Start processing audio frames from this participant
-                    let stream_key = participant_identity.clone();
-                    let mixer_clone = mixer.clone();
-
-                    tokio::spawn(async move {
-                        info!("Starting audio stream processing for participant: {}", stream_key);
-
-                        while let Some(audio_frame) = audio_stream.next().await {
-                            // Add this participant's audio to the mixer
-                            mixer_clone.add_audio_data(audio_frame.data.as_ref());
-
-                            debug!("Received audio frame from {}: {} samples, {} channels, {} Hz, buffer size: {}",
-                                stream_key, audio_frame.data.len(), audio_frame.num_channels,
-                                audio_frame.sample_rate, mixer_clone.buffer_size());
-                        }
-
-                        info!("Audio stream ended for participant: {}", stream_key);
-                    });
+                    // The mixer pulls this participant's audio through a jitter buffer
+                    // at the playback device clock
+                    mixer.add_stream(audio_track.sid().to_string(), audio_stream);
                 }
             }
 
@@ -443,11 +427,11 @@ async fn handle_remote_audio_streams(
                     track.kind()
                 );
 
-                if let livekit::track::RemoteTrack::Audio(_) = track {
+                if let livekit::track::RemoteTrack::Audio(audio_track) = track {
                     let participant_identity = participant.identity().to_string();
                     info!("Stopping audio stream for participant: {}", participant_identity);
 
-                    // Audio stream will be automatically cleaned up when the task ends
+                    mixer.remove_stream(audio_track.sid().as_str());
                 }
             }
 
@@ -455,7 +439,7 @@ async fn handle_remote_audio_streams(
                 let participant_identity = participant.identity().to_string();
                 info!("Participant disconnected: {}", participant_identity);
 
-                // Audio stream will be automatically cleaned up when the task ends
+                // The mixer drops the participant's streams once they end
             }
 
             _ => {
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-mod assert;
+pub(crate) mod assert;
 
 macro_rules! x420_to_rgba {
     ($rust_fnc:ident, $yuv_sys_fnc:ident) => {
@@ -13,3 +13,5 @@
 // limitations under the License.
 
 pub mod colorcvt;
+pub mod overlay;
+pub mod transform;
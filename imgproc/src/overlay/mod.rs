@@ -0,0 +1,584 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Alpha blending of an RGBA image (e.g. a logo) or of text onto I420 and NV12 frames.
+//!
+//! The RGBA image uses straight (non premultiplied) alpha and is converted with BT.601 limited
+//! range coefficients, like [`crate::colorcvt::abgr_to_i420`]. Text is drawn with a built-in 8x8
+//! bitmap font covering printable ASCII.
+
+mod font;
+
+use crate::colorcvt::{self, assert};
+use font::GLYPH_SIZE;
+
+/// Blend `src_rgba` over the frame with its top-left corner at (`x`, `y`). The image is clipped
+/// to the frame, `x` and `y` must be even so the chroma planes stay aligned.
+pub fn i420_overlay_rgba(
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_u: &mut [u8],
+    dst_stride_u: u32,
+    dst_v: &mut [u8],
+    dst_stride_v: u32,
+    width: u32,
+    height: u32,
+    src_rgba: &[u8],
+    src_stride_rgba: u32,
+    src_width: u32,
+    src_height: u32,
+    x: u32,
+    y: u32,
+) {
+    assert::valid_420(dst_y, dst_stride_y, dst_u, dst_stride_u, dst_v, dst_stride_v, width, height);
+    assert::valid_rgba(src_rgba, src_stride_rgba, src_width, src_height);
+    assert!(x.is_multiple_of(2) && y.is_multiple_of(2));
+
+    let Some((w, h)) = clip(x, y, src_width, src_height, width, height) else {
+        return;
+    };
+
+    // Convert the visible part of the image to I420 with its alpha plane, then let libyuv
+    // blend it in place
+    let chroma_w = w.div_ceil(2);
+    let mut overlay_y = vec![0u8; (w * h) as usize];
+    let mut overlay_u = vec![0u8; (chroma_w * h.div_ceil(2)) as usize];
+    let mut overlay_v = vec![0u8; (chroma_w * h.div_ceil(2)) as usize];
+    let mut alpha = vec![0u8; (w * h) as usize];
+
+    // libyuv names formats after the little-endian word, RGBA bytes are "ABGR"
+    colorcvt::abgr_to_i420(
+        src_rgba,
+        src_stride_rgba,
+        &mut overlay_y,
+        w,
+        &mut overlay_u,
+        chroma_w,
+        &mut overlay_v,
+        chroma_w,
+        w,
+        h,
+        false,
+    );
+    assert!(unsafe {
+        yuv_sys::rs_ARGBExtractAlpha(
+            src_rgba.as_ptr(),
+            src_stride_rgba as i32,
+            alpha.as_mut_ptr(),
+            w as i32,
+            w as i32,
+            h as i32,
+        ) == 0
+    });
+
+    let dst_y = dst_y[(y * dst_stride_y + x) as usize..].as_mut_ptr();
+    let dst_u = dst_u[(y / 2 * dst_stride_u + x / 2) as usize..].as_mut_ptr();
+    let dst_v = dst_v[(y / 2 * dst_stride_v + x / 2) as usize..].as_mut_ptr();
+
+    // The frame is both the background and the destination, libyuv blends row by row
+    assert!(unsafe {
+        yuv_sys::rs_I420Blend(
+            overlay_y.as_ptr(),
+            w as i32,
+            overlay_u.as_ptr(),
+            chroma_w as i32,
+            overlay_v.as_ptr(),
+            chroma_w as i32,
+            dst_y,
+            dst_stride_y as i32,
+            dst_u,
+            dst_stride_u as i32,
+            dst_v,
+            dst_stride_v as i32,
+            alpha.as_ptr(),
+            w as i32,
+            dst_y,
+            dst_stride_y as i32,
+            dst_u,
+            dst_stride_u as i32,
+            dst_v,
+            dst_stride_v as i32,
+            w as i32,
+            h as i32,
+        ) == 0
+    });
+}
+
+/// Blend `src_rgba` over the frame with its top-left corner at (`x`, `y`). The image is clipped
+/// to the frame, `x` and `y` must be even so the chroma plane stays aligned.
+///
+/// libyuv has no NV12 blending, this is a scalar implementation.
+///
+pub fn nv12_overlay_rgba(
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_uv: &mut [u8],
+    dst_stride_uv: u32,
+    width: u32,
+    height: u32,
+    src_rgba: &[u8],
+    src_stride_rgba: u32,
+    src_width: u32,
+    src_height: u32,
+    x: u32,
+    y: u32,
+) {
+    assert::valid_nv12(dst_y, dst_stride_y, dst_uv, dst_stride_uv, width, height);
+    assert::valid_rgba(src_rgba, src_stride_rgba, src_width, src_height);
+    assert!(x.is_multiple_of(2) && y.is_multiple_of(2));
+
+    let Some((w, h)) = clip(x, y, src_width, src_height, width, height) else {
+        return;
+    };
+    let (x, y, w, h) = (x as usize, y as usize, w as usize, h as usize);
+    let (dst_stride_y, dst_stride_uv) = (dst_stride_y as usize, dst_stride_uv as usize);
+    let src_stride_rgba = src_stride_rgba as usize;
+    let pixel = |px: usize, py: usize| {
+        let i = py * src_stride_rgba + px * 4;
+        [src_rgba[i], src_rgba[i + 1], src_rgba[i + 2], src_rgba[i + 3]]
+    };
+
+    for py in 0..h {
+        for px in 0..w {
+            let [r, g, b, a] = pixel(px, py);
+            let sample = &mut dst_y[(y + py) * dst_stride_y + x + px];
+            *sample = blend(rgb_to_y(r, g, b), *sample, a);
+        }
+    }
+
+    // Chroma of each 2x2 block from its average color and alpha
+    for cy in 0..h.div_ceil(2) {
+        for cx in 0..w.div_ceil(2) {
+            let mut sum = [0u32; 4];
+            let mut count = 0;
+            for py in cy * 2..(cy * 2 + 2).min(h) {
+                for px in cx * 2..(cx * 2 + 2).min(w) {
+                    for (sum, value) in sum.iter_mut().zip(pixel(px, py)) {
+                        *sum += value as u32;
+                    }
+                    count += 1;
+                }
+            }
+            let [r, g, b, a] = sum.map(|sum| ((sum + count / 2) / count) as u8);
+            let (u, v) = rgb_to_uv(r, g, b);
+
+            let i = (y / 2 + cy) * dst_stride_uv + x + cx * 2;
+            dst_uv[i] = blend(u, dst_uv[i], a);
+            dst_uv[i + 1] = blend(v, dst_uv[i + 1], a);
+        }
+    }
+}
+
+/// Size in pixels of `text` drawn at `scale`, each character being 8x8 pixels at scale 1.
+pub fn text_size(text: &str, scale: u32) -> (u32, u32) {
+    let columns = text.lines().map(|line| line.chars().count()).max().unwrap_or(0) as u32;
+    let rows = text.lines().count() as u32;
+    (columns * GLYPH_SIZE * scale, rows * GLYPH_SIZE * scale)
+}
+
+/// Rasterize `text` to an RGBA image of [`text_size`], `color` where the glyphs are set and
+/// transparent elsewhere. Characters outside printable ASCII are drawn as `?`.
+pub fn rasterize_text(text: &str, scale: u32, color: [u8; 4]) -> Vec<u8> {
+    assert!(scale > 0);
+
+    let (width, height) = text_size(text, scale);
+    let mut rgba = vec![0u8; (width * height * 4) as usize];
+    let glyph_size = (GLYPH_SIZE * scale) as usize;
+    let (width, scale) = (width as usize, scale as usize);
+    for (row, line) in text.lines().enumerate() {
+        for (column, c) in line.chars().enumerate() {
+            for (gy, bits) in font::glyph(c).iter().enumerate() {
+                for gx in (0..8).filter(|gx| bits >> gx & 1 != 0) {
+                    for py in 0..scale {
+                        let y = row * glyph_size + gy * scale + py;
+                        let x = column * glyph_size + gx * scale;
+                        let i = (y * width + x) * 4;
+                        for pixel in rgba[i..i + scale * 4].chunks_exact_mut(4) {
+                            pixel.copy_from_slice(&color);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    rgba
+}
+
+/// Draw `text` over the frame with its top-left corner at (`x`, `y`), see [`rasterize_text`].
+/// The text is clipped to the frame, `x` and `y` must be even.
+pub fn i420_overlay_text(
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_u: &mut [u8],
+    dst_stride_u: u32,
+    dst_v: &mut [u8],
+    dst_stride_v: u32,
+    width: u32,
+    height: u32,
+    text: &str,
+    scale: u32,
+    color: [u8; 4],
+    x: u32,
+    y: u32,
+) {
+    let (text_width, text_height) = text_size(text, scale);
+    if text_width == 0 {
+        return;
+    }
+    i420_overlay_rgba(
+        dst_y,
+        dst_stride_y,
+        dst_u,
+        dst_stride_u,
+        dst_v,
+        dst_stride_v,
+        width,
+        height,
+        &rasterize_text(text, scale, color),
+        text_width * 4,
+        text_width,
+        text_height,
+        x,
+        y,
+    );
+}
+
+/// Draw `text` over the frame with its top-left corner at (`x`, `y`), see [`rasterize_text`].
+/// The text is clipped to the frame, `x` and `y` must be even.
+pub fn nv12_overlay_text(
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_uv: &mut [u8],
+    dst_stride_uv: u32,
+    width: u32,
+    height: u32,
+    text: &str,
+    scale: u32,
+    color: [u8; 4],
+    x: u32,
+    y: u32,
+) {
+    let (text_width, text_height) = text_size(text, scale);
+    if text_width == 0 {
+        return;
+    }
+    nv12_overlay_rgba(
+        dst_y,
+        dst_stride_y,
+        dst_uv,
+        dst_stride_uv,
+        width,
+        height,
+        &rasterize_text(text, scale, color),
+        text_width * 4,
+        text_width,
+        text_height,
+        x,
+        y,
+    );
+}
+
+/// Visible size of a `src_width` x `src_height` image at (`x`, `y`) of the frame.
+fn clip(
+    x: u32,
+    y: u32,
+    src_width: u32,
+    src_height: u32,
+    width: u32,
+    height: u32,
+) -> Option<(u32, u32)> {
+    if x >= width || y >= height {
+        return None;
+    }
+    Some((src_width.min(width - x), src_height.min(height - y)))
+}
+
+// Same rounding as libyuv BlendPlaneRow
+fn blend(fg: u8, bg: u8, alpha: u8) -> u8 {
+    let alpha = alpha as u32;
+    ((fg as u32 * alpha + bg as u32 * (255 - alpha) + 255) >> 8) as u8
+}
+
+fn rgb_to_y(r: u8, g: u8, b: u8) -> u8 {
+    let (r, g, b) = (r as i32, g as i32, b as i32);
+    ((66 * r + 129 * g + 25 * b + 0x1080) >> 8) as u8
+}
+
+fn rgb_to_uv(r: u8, g: u8, b: u8) -> (u8, u8) {
+    let (r, g, b) = (r as i32, g as i32, b as i32);
+    let u = (112 * b - 74 * g - 38 * r + 0x8080) >> 8;
+    let v = (112 * r - 94 * g - 18 * b + 0x8080) >> 8;
+    (u.clamp(0, 255) as u8, v.clamp(0, 255) as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WIDTH: u32 = 16;
+    const HEIGHT: u32 = 10;
+
+    fn rgba(width: u32, height: u32, color: [u8; 4]) -> Vec<u8> {
+        color.repeat((width * height) as usize)
+    }
+
+    struct Frames {
+        y: Vec<u8>,
+        u: Vec<u8>,
+        v: Vec<u8>,
+        nv12_y: Vec<u8>,
+        uv: Vec<u8>,
+    }
+
+    impl Frames {
+        /// The same gradient as I420 and NV12
+        fn new() -> Self {
+            let chroma = (WIDTH / 2 * HEIGHT / 2) as usize;
+            let y: Vec<u8> = (0..WIDTH * HEIGHT).map(|i| (16 + i) as u8).collect();
+            let u: Vec<u8> = (0..chroma).map(|i| (64 + i) as u8).collect();
+            let v: Vec<u8> = (0..chroma).map(|i| (192 - i) as u8).collect();
+            let uv = u.iter().zip(&v).flat_map(|(u, v)| [*u, *v]).collect();
+            Self { nv12_y: y.clone(), y, u, v, uv }
+        }
+
+        fn overlay(&mut self, image: &[u8], width: u32, height: u32, x: u32, y: u32) {
+            i420_overlay_rgba(
+                &mut self.y,
+                WIDTH,
+                &mut self.u,
+                WIDTH / 2,
+                &mut self.v,
+                WIDTH / 2,
+                WIDTH,
+                HEIGHT,
+                image,
+                width * 4,
+                width,
+                height,
+                x,
+                y,
+            );
+            nv12_overlay_rgba(
+                &mut self.nv12_y,
+                WIDTH,
+                &mut self.uv,
+                WIDTH,
+                WIDTH,
+                HEIGHT,
+                image,
+                width * 4,
+                width,
+                height,
+                x,
+                y,
+            );
+        }
+
+        fn nv12_u(&self) -> Vec<u8> {
+            self.uv.iter().step_by(2).copied().collect()
+        }
+
+        fn nv12_v(&self) -> Vec<u8> {
+            self.uv.iter().skip(1).step_by(2).copied().collect()
+        }
+    }
+
+    fn inside(x: u32, y: u32, rect: (u32, u32, u32, u32)) -> bool {
+        x >= rect.0 && x < rect.0 + rect.2 && y >= rect.1 && y < rect.1 + rect.3
+    }
+
+    #[test]
+    fn transparent_overlay_is_a_no_op() {
+        let mut frames = Frames::new();
+        frames.overlay(&rgba(8, 6, [255, 0, 0, 0]), 8, 6, 2, 2);
+
+        let original = Frames::new();
+        assert_eq!(frames.y, original.y);
+        assert_eq!(frames.u, original.u);
+        assert_eq!(frames.v, original.v);
+        assert_eq!(frames.nv12_y, original.nv12_y);
+        assert_eq!(frames.uv, original.uv);
+    }
+
+    #[test]
+    fn opaque_overlay_replaces_the_region_only() {
+        for (x, y, width, height) in [(0, 0, 4, 4), (2, 4, 6, 2), (10, 6, 6, 4), (4, 2, 5, 3)] {
+            let mut frames = Frames::new();
+            frames.overlay(&rgba(width, height, [255, 255, 255, 255]), width, height, x, y);
+            let original = Frames::new();
+
+            for (i, (sample, nv12_sample)) in frames.y.iter().zip(&frames.nv12_y).enumerate() {
+                let (px, py) = (i as u32 % WIDTH, i as u32 / WIDTH);
+                let expected =
+                    if inside(px, py, (x, y, width, height)) { 235 } else { original.y[i] };
+                assert_eq!(*sample, expected, "i420 y at {px},{py}");
+                assert_eq!(*nv12_sample, expected, "nv12 y at {px},{py}");
+            }
+
+            let chroma_rect = (x / 2, y / 2, width.div_ceil(2), height.div_ceil(2));
+            for (i, (u, nv12_u)) in frames.u.iter().zip(frames.nv12_u()).enumerate() {
+                let (cx, cy) = (i as u32 % (WIDTH / 2), i as u32 / (WIDTH / 2));
+                if !inside(cx, cy, chroma_rect) {
+                    assert_eq!(*u, original.u[i], "i420 u at {cx},{cy}");
+                    assert_eq!(nv12_u, original.u[i], "nv12 u at {cx},{cy}");
+                } else if width % 2 == 0 && height % 2 == 0 {
+                    // Blocks fully covered by the image
+                    assert_eq!(*u, 128, "i420 u at {cx},{cy}");
+                    assert_eq!(nv12_u, 128, "nv12 u at {cx},{cy}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn overlay_is_clipped_to_the_frame() {
+        let mut frames = Frames::new();
+        frames.overlay(&rgba(8, 8, [0, 0, 0, 255]), 8, 8, 12, 6);
+        let original = Frames::new();
+
+        for (i, sample) in frames.y.iter().enumerate() {
+            let (px, py) = (i as u32 % WIDTH, i as u32 / WIDTH);
+            let expected = if px >= 12 && py >= 6 { 16 } else { original.y[i] };
+            assert_eq!(*sample, expected);
+            assert_eq!(frames.nv12_y[i], expected);
+        }
+
+        // Entirely outside
+        let mut frames = Frames::new();
+        frames.overlay(&rgba(4, 4, [0, 0, 0, 255]), 4, 4, WIDTH, 0);
+        assert_eq!(frames.y, original.y);
+        assert_eq!(frames.uv, original.uv);
+    }
+
+    #[test]
+    fn nv12_matches_i420() {
+        // Semi-transparent colors, libyuv and the scalar path may round differently
+        for color in [[255, 0, 0, 128], [0, 200, 50, 64], [30, 60, 220, 200]] {
+            let mut frames = Frames::new();
+            frames.overlay(&rgba(8, 6, color), 8, 6, 4, 2);
+
+            let close = |a: &[u8], b: &[u8]| {
+                a.iter().zip(b).all(|(a, b)| (*a as i32 - *b as i32).abs() <= 2)
+            };
+            assert!(close(&frames.y, &frames.nv12_y), "{color:?}");
+            assert!(close(&frames.u, &frames.nv12_u()), "{color:?}");
+            assert!(close(&frames.v, &frames.nv12_v()), "{color:?}");
+        }
+    }
+
+    #[test]
+    fn text_size_counts_lines_and_columns() {
+        assert_eq!(text_size("", 1), (0, 0));
+        assert_eq!(text_size("Hi", 1), (16, 8));
+        assert_eq!(text_size("Hi\nthere", 2), (80, 32));
+        assert_eq!(text_size("é", 1), (8, 8));
+    }
+
+    #[test]
+    fn rasterized_glyphs_match_the_font() {
+        const COLOR: [u8; 4] = [10, 20, 30, 255];
+        for scale in 1..=3 {
+            for (index, bits) in font::GLYPHS.iter().enumerate() {
+                let c = char::from(0x20 + index as u8);
+                let rgba = rasterize_text(&c.to_string(), scale, COLOR);
+                let size = GLYPH_SIZE * scale;
+                assert_eq!(rgba.len(), (size * size * 4) as usize);
+
+                for (i, pixel) in rgba.chunks_exact(4).enumerate() {
+                    let (px, py) = (i as u32 % size / scale, i as u32 / size / scale);
+                    let set = bits[py as usize] >> px & 1 != 0;
+                    let expected = if set { COLOR } else { [0; 4] };
+                    assert_eq!(pixel, expected, "{c:?} at scale {scale}, pixel {i}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn text_lines_and_unknown_characters() {
+        let white = [255; 4];
+        let lines = rasterize_text("ab\nc", 1, white);
+        let expected: Vec<u8> = [
+            [rasterize_text("a", 1, white), rasterize_text("b", 1, white)],
+            [rasterize_text("c", 1, white), rasterize_text(" ", 1, white)],
+        ]
+        .iter()
+        .flat_map(|row| {
+            (0..8).flat_map(move |y| row.iter().flat_map(move |glyph| &glyph[y * 32..y * 32 + 32]))
+        })
+        .copied()
+        .collect();
+        assert_eq!(lines, expected);
+
+        assert_eq!(rasterize_text("é", 1, white), rasterize_text("?", 1, white));
+    }
+
+    #[test]
+    fn opaque_text_sets_the_glyph_pixels_only() {
+        let original = Frames::new();
+        let mut frames = Frames::new();
+        i420_overlay_text(
+            &mut frames.y,
+            WIDTH,
+            &mut frames.u,
+            WIDTH / 2,
+            &mut frames.v,
+            WIDTH / 2,
+            WIDTH,
+            HEIGHT,
+            "Hi",
+            1,
+            [255; 4],
+            0,
+            2,
+        );
+        nv12_overlay_text(
+            &mut frames.nv12_y,
+            WIDTH,
+            &mut frames.uv,
+            WIDTH,
+            WIDTH,
+            HEIGHT,
+            "Hi",
+            1,
+            [255; 4],
+            0,
+            2,
+        );
+
+        let text = rasterize_text("Hi", 1, [255; 4]);
+        for (i, (sample, nv12_sample)) in frames.y.iter().zip(&frames.nv12_y).enumerate() {
+            let (px, py) = (i as u32 % WIDTH, i as u32 / WIDTH);
+            let set = py >= 2 && text[(((py - 2) * 16 + px) * 4 + 3) as usize] == 255;
+            let expected = if set { 235 } else { original.y[i] };
+            assert_eq!(*sample, expected, "i420 y at {px},{py}");
+            assert_eq!(*nv12_sample, expected, "nv12 y at {px},{py}");
+        }
+
+        // Text entirely outside of the frame
+        let mut frames = Frames::new();
+        frames.overlay(&rasterize_text("x", 1, [255; 4]), 8, 8, 0, HEIGHT);
+        assert_eq!(frames.y, original.y);
+    }
+
+    #[test]
+    fn blend_is_exact_at_the_extremes() {
+        for fg in 0..=255 {
+            for bg in [0, 17, 128, 255] {
+                assert_eq!(blend(fg, bg, 255), fg);
+                assert_eq!(blend(fg, bg, 0), bg);
+            }
+        }
+    }
+}
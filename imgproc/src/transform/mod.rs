@@ -0,0 +1,1173 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Geometric transforms of I420 and NV12 frames: scale, crop, rotate, mirror and pad.
+//!
+//! Planes are passed as slices with their strides, like in [`crate::colorcvt`]. libyuv (with
+//! its SIMD paths) is used whenever it supports the operation, interleaved UV planes that it
+//! can't handle go through a scalar implementation.
+
+use crate::colorcvt::assert;
+
+pub(crate) mod scalar;
+
+/// Clockwise rotation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    Rotate0,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+}
+
+impl Rotation {
+    /// Size of a `width` x `height` frame once rotated.
+    pub fn rotated_size(&self, width: u32, height: u32) -> (u32, u32) {
+        match self {
+            Rotation::Rotate0 | Rotation::Rotate180 => (width, height),
+            Rotation::Rotate90 | Rotation::Rotate270 => (height, width),
+        }
+    }
+
+    // libyuv RotationMode
+    fn to_libyuv(self) -> u32 {
+        match self {
+            Rotation::Rotate0 => 0,
+            Rotation::Rotate90 => 90,
+            Rotation::Rotate180 => 180,
+            Rotation::Rotate270 => 270,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScaleFilter {
+    /// Nearest neighbor
+    None,
+    /// Horizontal filtering only
+    Linear,
+    Bilinear,
+    /// Highest quality when downscaling
+    #[default]
+    Box,
+}
+
+impl ScaleFilter {
+    // libyuv FilterMode
+    fn to_libyuv(self) -> u32 {
+        match self {
+            ScaleFilter::None => 0,
+            ScaleFilter::Linear => 1,
+            ScaleFilter::Bilinear => 2,
+            ScaleFilter::Box => 3,
+        }
+    }
+}
+
+/// Largest rectangle with the aspect ratio of `src_width` x `src_height` that fits in
+/// `dst_width` x `dst_height`, centered. Returns `(x, y, width, height)`, all even so the result
+/// can be used with [`i420_pad`] and [`nv12_pad`]. The destination must be at least 2x2.
+pub fn letterbox_rect(
+    src_width: u32,
+    src_height: u32,
+    dst_width: u32,
+    dst_height: u32,
+) -> (u32, u32, u32, u32) {
+    assert!(src_width > 0 && src_height > 0);
+    assert!(dst_width >= 2 && dst_height >= 2, "letterbox destination must be at least 2x2");
+
+    let (src_w, src_h) = (src_width as u64, src_height as u64);
+    let (dst_w, dst_h) = (dst_width as u64, dst_height as u64);
+
+    let (width, height) = if src_w * dst_h > src_h * dst_w {
+        (dst_w, src_h * dst_w / src_w)
+    } else {
+        (src_w * dst_h / src_h, dst_h)
+    };
+
+    let width = ((width as u32) & !1).max(2);
+    let height = ((height as u32) & !1).max(2);
+    let x = ((dst_width - width) / 2) & !1;
+    let y = ((dst_height - height) / 2) & !1;
+    (x, y, width, height)
+}
+
+pub fn i420_scale(
+    src_y: &[u8],
+    src_stride_y: u32,
+    src_u: &[u8],
+    src_stride_u: u32,
+    src_v: &[u8],
+    src_stride_v: u32,
+    src_width: u32,
+    src_height: u32,
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_u: &mut [u8],
+    dst_stride_u: u32,
+    dst_v: &mut [u8],
+    dst_stride_v: u32,
+    dst_width: u32,
+    dst_height: u32,
+    filter: ScaleFilter,
+) {
+    assert::valid_420(
+        src_y,
+        src_stride_y,
+        src_u,
+        src_stride_u,
+        src_v,
+        src_stride_v,
+        src_width,
+        src_height,
+    );
+    assert::valid_420(
+        dst_y,
+        dst_stride_y,
+        dst_u,
+        dst_stride_u,
+        dst_v,
+        dst_stride_v,
+        dst_width,
+        dst_height,
+    );
+
+    assert!(unsafe {
+        yuv_sys::rs_I420Scale(
+            src_y.as_ptr(),
+            src_stride_y as i32,
+            src_u.as_ptr(),
+            src_stride_u as i32,
+            src_v.as_ptr(),
+            src_stride_v as i32,
+            src_width as i32,
+            src_height as i32,
+            dst_y.as_mut_ptr(),
+            dst_stride_y as i32,
+            dst_u.as_mut_ptr(),
+            dst_stride_u as i32,
+            dst_v.as_mut_ptr(),
+            dst_stride_v as i32,
+            dst_width as i32,
+            dst_height as i32,
+            filter.to_libyuv() as _,
+        ) == 0
+    });
+}
+
+pub fn nv12_scale(
+    src_y: &[u8],
+    src_stride_y: u32,
+    src_uv: &[u8],
+    src_stride_uv: u32,
+    src_width: u32,
+    src_height: u32,
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_uv: &mut [u8],
+    dst_stride_uv: u32,
+    dst_width: u32,
+    dst_height: u32,
+    filter: ScaleFilter,
+) {
+    assert::valid_nv12(src_y, src_stride_y, src_uv, src_stride_uv, src_width, src_height);
+    assert::valid_nv12(dst_y, dst_stride_y, dst_uv, dst_stride_uv, dst_width, dst_height);
+
+    assert!(unsafe {
+        yuv_sys::rs_NV12Scale(
+            src_y.as_ptr(),
+            src_stride_y as i32,
+            src_uv.as_ptr(),
+            src_stride_uv as i32,
+            src_width as i32,
+            src_height as i32,
+            dst_y.as_mut_ptr(),
+            dst_stride_y as i32,
+            dst_uv.as_mut_ptr(),
+            dst_stride_uv as i32,
+            dst_width as i32,
+            dst_height as i32,
+            filter.to_libyuv() as _,
+        ) == 0
+    });
+}
+
+/// Copy the `width` x `height` region at (`x`, `y`) of the source frame. `x` and `y` must be
+/// even so the chroma planes stay aligned.
+pub fn i420_crop(
+    src_y: &[u8],
+    src_stride_y: u32,
+    src_u: &[u8],
+    src_stride_u: u32,
+    src_v: &[u8],
+    src_stride_v: u32,
+    src_width: u32,
+    src_height: u32,
+    x: u32,
+    y: u32,
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_u: &mut [u8],
+    dst_stride_u: u32,
+    dst_v: &mut [u8],
+    dst_stride_v: u32,
+    width: u32,
+    height: u32,
+) {
+    assert::valid_420(
+        src_y,
+        src_stride_y,
+        src_u,
+        src_stride_u,
+        src_v,
+        src_stride_v,
+        src_width,
+        src_height,
+    );
+    assert::valid_420(dst_y, dst_stride_y, dst_u, dst_stride_u, dst_v, dst_stride_v, width, height);
+    assert_valid_region(x, y, width, height, src_width, src_height);
+
+    let y_offset = (y * src_stride_y + x) as usize;
+    let u_offset = (y / 2 * src_stride_u + x / 2) as usize;
+    let v_offset = (y / 2 * src_stride_v + x / 2) as usize;
+
+    // The region was validated above, the offset planes are shorter than `stride * height`
+    assert!(unsafe {
+        yuv_sys::rs_I420Copy(
+            src_y[y_offset..].as_ptr(),
+            src_stride_y as i32,
+            src_u[u_offset..].as_ptr(),
+            src_stride_u as i32,
+            src_v[v_offset..].as_ptr(),
+            src_stride_v as i32,
+            dst_y.as_mut_ptr(),
+            dst_stride_y as i32,
+            dst_u.as_mut_ptr(),
+            dst_stride_u as i32,
+            dst_v.as_mut_ptr(),
+            dst_stride_v as i32,
+            width as i32,
+            height as i32,
+        ) == 0
+    });
+}
+
+/// Copy the `width` x `height` region at (`x`, `y`) of the source frame. `x` and `y` must be
+/// even so the chroma plane stays aligned.
+pub fn nv12_crop(
+    src_y: &[u8],
+    src_stride_y: u32,
+    src_uv: &[u8],
+    src_stride_uv: u32,
+    src_width: u32,
+    src_height: u32,
+    x: u32,
+    y: u32,
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_uv: &mut [u8],
+    dst_stride_uv: u32,
+    width: u32,
+    height: u32,
+) {
+    assert::valid_nv12(src_y, src_stride_y, src_uv, src_stride_uv, src_width, src_height);
+    assert::valid_nv12(dst_y, dst_stride_y, dst_uv, dst_stride_uv, width, height);
+    assert_valid_region(x, y, width, height, src_width, src_height);
+
+    let y_offset = (y * src_stride_y + x) as usize;
+    let uv_offset = (y / 2 * src_stride_uv + x) as usize;
+
+    // The region was validated above, the offset planes are shorter than `stride * height`
+    assert!(unsafe {
+        yuv_sys::rs_NV12Copy(
+            src_y[y_offset..].as_ptr(),
+            src_stride_y as i32,
+            src_uv[uv_offset..].as_ptr(),
+            src_stride_uv as i32,
+            dst_y.as_mut_ptr(),
+            dst_stride_y as i32,
+            dst_uv.as_mut_ptr(),
+            dst_stride_uv as i32,
+            width as i32,
+            height as i32,
+        ) == 0
+    });
+}
+
+/// Rotate a `width` x `height` frame, the destination is `rotation.rotated_size(width, height)`.
+pub fn i420_rotate(
+    src_y: &[u8],
+    src_stride_y: u32,
+    src_u: &[u8],
+    src_stride_u: u32,
+    src_v: &[u8],
+    src_stride_v: u32,
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_u: &mut [u8],
+    dst_stride_u: u32,
+    dst_v: &mut [u8],
+    dst_stride_v: u32,
+    width: u32,
+    height: u32,
+    rotation: Rotation,
+) {
+    let (dst_width, dst_height) = rotation.rotated_size(width, height);
+    assert::valid_420(src_y, src_stride_y, src_u, src_stride_u, src_v, src_stride_v, width, height);
+    assert::valid_420(
+        dst_y,
+        dst_stride_y,
+        dst_u,
+        dst_stride_u,
+        dst_v,
+        dst_stride_v,
+        dst_width,
+        dst_height,
+    );
+
+    assert!(unsafe {
+        yuv_sys::rs_I420Rotate(
+            src_y.as_ptr(),
+            src_stride_y as i32,
+            src_u.as_ptr(),
+            src_stride_u as i32,
+            src_v.as_ptr(),
+            src_stride_v as i32,
+            dst_y.as_mut_ptr(),
+            dst_stride_y as i32,
+            dst_u.as_mut_ptr(),
+            dst_stride_u as i32,
+            dst_v.as_mut_ptr(),
+            dst_stride_v as i32,
+            width as i32,
+            height as i32,
+            rotation.to_libyuv() as _,
+        ) == 0
+    });
+}
+
+/// Rotate a `width` x `height` frame, the destination is `rotation.rotated_size(width, height)`.
+///
+/// libyuv can't rotate an interleaved UV plane into another one, so the chroma plane is rotated
+/// by the scalar fallback.
+///
+pub fn nv12_rotate(
+    src_y: &[u8],
+    src_stride_y: u32,
+    src_uv: &[u8],
+    src_stride_uv: u32,
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_uv: &mut [u8],
+    dst_stride_uv: u32,
+    width: u32,
+    height: u32,
+    rotation: Rotation,
+) {
+    let (dst_width, dst_height) = rotation.rotated_size(width, height);
+    assert::valid_nv12(src_y, src_stride_y, src_uv, src_stride_uv, width, height);
+    assert::valid_nv12(dst_y, dst_stride_y, dst_uv, dst_stride_uv, dst_width, dst_height);
+
+    assert!(unsafe {
+        yuv_sys::rs_RotatePlane(
+            src_y.as_ptr(),
+            src_stride_y as i32,
+            dst_y.as_mut_ptr(),
+            dst_stride_y as i32,
+            width as i32,
+            height as i32,
+            rotation.to_libyuv() as _,
+        ) == 0
+    });
+
+    scalar::rotate_plane(
+        src_uv,
+        src_stride_uv as usize,
+        dst_uv,
+        dst_stride_uv as usize,
+        width.div_ceil(2) as usize,
+        height.div_ceil(2) as usize,
+        2,
+        rotation,
+    );
+}
+
+/// Mirror a frame horizontally.
+pub fn i420_mirror(
+    src_y: &[u8],
+    src_stride_y: u32,
+    src_u: &[u8],
+    src_stride_u: u32,
+    src_v: &[u8],
+    src_stride_v: u32,
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_u: &mut [u8],
+    dst_stride_u: u32,
+    dst_v: &mut [u8],
+    dst_stride_v: u32,
+    width: u32,
+    height: u32,
+) {
+    assert::valid_420(src_y, src_stride_y, src_u, src_stride_u, src_v, src_stride_v, width, height);
+    assert::valid_420(dst_y, dst_stride_y, dst_u, dst_stride_u, dst_v, dst_stride_v, width, height);
+
+    assert!(unsafe {
+        yuv_sys::rs_I420Mirror(
+            src_y.as_ptr(),
+            src_stride_y as i32,
+            src_u.as_ptr(),
+            src_stride_u as i32,
+            src_v.as_ptr(),
+            src_stride_v as i32,
+            dst_y.as_mut_ptr(),
+            dst_stride_y as i32,
+            dst_u.as_mut_ptr(),
+            dst_stride_u as i32,
+            dst_v.as_mut_ptr(),
+            dst_stride_v as i32,
+            width as i32,
+            height as i32,
+        ) == 0
+    });
+}
+
+/// Mirror a frame horizontally.
+pub fn nv12_mirror(
+    src_y: &[u8],
+    src_stride_y: u32,
+    src_uv: &[u8],
+    src_stride_uv: u32,
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_uv: &mut [u8],
+    dst_stride_uv: u32,
+    width: u32,
+    height: u32,
+) {
+    assert::valid_nv12(src_y, src_stride_y, src_uv, src_stride_uv, width, height);
+    assert::valid_nv12(dst_y, dst_stride_y, dst_uv, dst_stride_uv, width, height);
+
+    assert!(unsafe {
+        yuv_sys::rs_NV12Mirror(
+            src_y.as_ptr(),
+            src_stride_y as i32,
+            src_uv.as_ptr(),
+            src_stride_uv as i32,
+            dst_y.as_mut_ptr(),
+            dst_stride_y as i32,
+            dst_uv.as_mut_ptr(),
+            dst_stride_uv as i32,
+            width as i32,
+            height as i32,
+        ) == 0
+    });
+}
+
+/// Copy the source frame at (`x`, `y`) of the destination frame and fill the rest with the
+/// `color` given as `[y, u, v]`. `x` and `y` must be even, see [`letterbox_rect`].
+pub fn i420_pad(
+    src_y: &[u8],
+    src_stride_y: u32,
+    src_u: &[u8],
+    src_stride_u: u32,
+    src_v: &[u8],
+    src_stride_v: u32,
+    src_width: u32,
+    src_height: u32,
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_u: &mut [u8],
+    dst_stride_u: u32,
+    dst_v: &mut [u8],
+    dst_stride_v: u32,
+    dst_width: u32,
+    dst_height: u32,
+    x: u32,
+    y: u32,
+    color: [u8; 3],
+) {
+    assert::valid_420(
+        src_y,
+        src_stride_y,
+        src_u,
+        src_stride_u,
+        src_v,
+        src_stride_v,
+        src_width,
+        src_height,
+    );
+    assert::valid_420(
+        dst_y,
+        dst_stride_y,
+        dst_u,
+        dst_stride_u,
+        dst_v,
+        dst_stride_v,
+        dst_width,
+        dst_height,
+    );
+    assert_valid_region(x, y, src_width, src_height, dst_width, dst_height);
+
+    let (chroma_width, chroma_height) = (dst_width.div_ceil(2), dst_height.div_ceil(2));
+    unsafe {
+        yuv_sys::rs_SetPlane(
+            dst_y.as_mut_ptr(),
+            dst_stride_y as i32,
+            dst_width as i32,
+            dst_height as i32,
+            color[0] as u32,
+        );
+        yuv_sys::rs_SetPlane(
+            dst_u.as_mut_ptr(),
+            dst_stride_u as i32,
+            chroma_width as i32,
+            chroma_height as i32,
+            color[1] as u32,
+        );
+        yuv_sys::rs_SetPlane(
+            dst_v.as_mut_ptr(),
+            dst_stride_v as i32,
+            chroma_width as i32,
+            chroma_height as i32,
+            color[2] as u32,
+        );
+    }
+
+    let y_offset = (y * dst_stride_y + x) as usize;
+    let u_offset = (y / 2 * dst_stride_u + x / 2) as usize;
+    let v_offset = (y / 2 * dst_stride_v + x / 2) as usize;
+
+    assert!(unsafe {
+        yuv_sys::rs_I420Copy(
+            src_y.as_ptr(),
+            src_stride_y as i32,
+            src_u.as_ptr(),
+            src_stride_u as i32,
+            src_v.as_ptr(),
+            src_stride_v as i32,
+            dst_y[y_offset..].as_mut_ptr(),
+            dst_stride_y as i32,
+            dst_u[u_offset..].as_mut_ptr(),
+            dst_stride_u as i32,
+            dst_v[v_offset..].as_mut_ptr(),
+            dst_stride_v as i32,
+            src_width as i32,
+            src_height as i32,
+        ) == 0
+    });
+}
+
+/// Copy the source frame at (`x`, `y`) of the destination frame and fill the rest with the
+/// `color` given as `[y, u, v]`. `x` and `y` must be even, see [`letterbox_rect`].
+pub fn nv12_pad(
+    src_y: &[u8],
+    src_stride_y: u32,
+    src_uv: &[u8],
+    src_stride_uv: u32,
+    src_width: u32,
+    src_height: u32,
+    dst_y: &mut [u8],
+    dst_stride_y: u32,
+    dst_uv: &mut [u8],
+    dst_stride_uv: u32,
+    dst_width: u32,
+    dst_height: u32,
+    x: u32,
+    y: u32,
+    color: [u8; 3],
+) {
+    assert::valid_nv12(src_y, src_stride_y, src_uv, src_stride_uv, src_width, src_height);
+    assert::valid_nv12(dst_y, dst_stride_y, dst_uv, dst_stride_uv, dst_width, dst_height);
+    assert_valid_region(x, y, src_width, src_height, dst_width, dst_height);
+
+    unsafe {
+        yuv_sys::rs_SetPlane(
+            dst_y.as_mut_ptr(),
+            dst_stride_y as i32,
+            dst_width as i32,
+            dst_height as i32,
+            color[0] as u32,
+        );
+    }
+    scalar::fill_plane(
+        dst_uv,
+        dst_stride_uv as usize,
+        dst_width.div_ceil(2) as usize,
+        dst_height.div_ceil(2) as usize,
+        &color[1..],
+    );
+
+    let y_offset = (y * dst_stride_y + x) as usize;
+    let uv_offset = (y / 2 * dst_stride_uv + x) as usize;
+
+    assert!(unsafe {
+        yuv_sys::rs_NV12Copy(
+            src_y.as_ptr(),
+            src_stride_y as i32,
+            src_uv.as_ptr(),
+            src_stride_uv as i32,
+            dst_y[y_offset..].as_mut_ptr(),
+            dst_stride_y as i32,
+            dst_uv[uv_offset..].as_mut_ptr(),
+            dst_stride_uv as i32,
+            src_width as i32,
+            src_height as i32,
+        ) == 0
+    });
+}
+
+/// The `width` x `height` region at (`x`, `y`) must fit in the frame, with an even origin.
+fn assert_valid_region(
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    frame_width: u32,
+    frame_height: u32,
+) {
+    assert!(x.is_multiple_of(2) && y.is_multiple_of(2));
+    assert!(x + width <= frame_width);
+    assert!(y + height <= frame_height);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct I420 {
+        width: u32,
+        height: u32,
+        y: Vec<u8>,
+        u: Vec<u8>,
+        v: Vec<u8>,
+    }
+
+    impl I420 {
+        fn new(width: u32, height: u32) -> Self {
+            let chroma = (width.div_ceil(2) * height.div_ceil(2)) as usize;
+            Self {
+                width,
+                height,
+                y: vec![0; (width * height) as usize],
+                u: vec![0; chroma],
+                v: vec![0; chroma],
+            }
+        }
+
+        /// Every sample is different (modulo 256) so misplaced ones are caught
+        fn pattern(width: u32, height: u32) -> Self {
+            let mut frame = Self::new(width, height);
+            fill_pattern(&mut frame.y, 0);
+            fill_pattern(&mut frame.u, 85);
+            fill_pattern(&mut frame.v, 170);
+            frame
+        }
+
+        fn stride_uv(&self) -> u32 {
+            self.width.div_ceil(2)
+        }
+
+        fn rotate(&self, rotation: Rotation) -> Self {
+            let (width, height) = rotation.rotated_size(self.width, self.height);
+            let mut dst = Self::new(width, height);
+            let (dst_stride_y, dst_stride_uv) = (dst.width, dst.stride_uv());
+            i420_rotate(
+                &self.y,
+                self.width,
+                &self.u,
+                self.stride_uv(),
+                &self.v,
+                self.stride_uv(),
+                &mut dst.y,
+                dst_stride_y,
+                &mut dst.u,
+                dst_stride_uv,
+                &mut dst.v,
+                dst_stride_uv,
+                self.width,
+                self.height,
+                rotation,
+            );
+            dst
+        }
+
+        fn mirror(&self) -> Self {
+            let mut dst = Self::new(self.width, self.height);
+            let stride_uv = self.stride_uv();
+            i420_mirror(
+                &self.y,
+                self.width,
+                &self.u,
+                stride_uv,
+                &self.v,
+                stride_uv,
+                &mut dst.y,
+                self.width,
+                &mut dst.u,
+                stride_uv,
+                &mut dst.v,
+                stride_uv,
+                self.width,
+                self.height,
+            );
+            dst
+        }
+
+        fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> Self {
+            let mut dst = Self::new(width, height);
+            let dst_stride_uv = dst.stride_uv();
+            i420_crop(
+                &self.y,
+                self.width,
+                &self.u,
+                self.stride_uv(),
+                &self.v,
+                self.stride_uv(),
+                self.width,
+                self.height,
+                x,
+                y,
+                &mut dst.y,
+                width,
+                &mut dst.u,
+                dst_stride_uv,
+                &mut dst.v,
+                dst_stride_uv,
+                width,
+                height,
+            );
+            dst
+        }
+
+        fn pad(&self, width: u32, height: u32, x: u32, y: u32, color: [u8; 3]) -> Self {
+            let mut dst = Self::new(width, height);
+            let dst_stride_uv = dst.stride_uv();
+            i420_pad(
+                &self.y,
+                self.width,
+                &self.u,
+                self.stride_uv(),
+                &self.v,
+                self.stride_uv(),
+                self.width,
+                self.height,
+                &mut dst.y,
+                width,
+                &mut dst.u,
+                dst_stride_uv,
+                &mut dst.v,
+                dst_stride_uv,
+                width,
+                height,
+                x,
+                y,
+                color,
+            );
+            dst
+        }
+
+        fn scale(&self, width: u32, height: u32, filter: ScaleFilter) -> Self {
+            let mut dst = Self::new(width, height);
+            let dst_stride_uv = dst.stride_uv();
+            i420_scale(
+                &self.y,
+                self.width,
+                &self.u,
+                self.stride_uv(),
+                &self.v,
+                self.stride_uv(),
+                self.width,
+                self.height,
+                &mut dst.y,
+                width,
+                &mut dst.u,
+                dst_stride_uv,
+                &mut dst.v,
+                dst_stride_uv,
+                width,
+                height,
+                filter,
+            );
+            dst
+        }
+
+        fn assert_eq(&self, other: &Self) {
+            assert_eq!((self.width, self.height), (other.width, other.height));
+            assert_eq!(self.y, other.y, "y plane");
+            assert_eq!(self.u, other.u, "u plane");
+            assert_eq!(self.v, other.v, "v plane");
+        }
+    }
+
+    struct Nv12 {
+        width: u32,
+        height: u32,
+        y: Vec<u8>,
+        uv: Vec<u8>,
+    }
+
+    impl Nv12 {
+        fn new(width: u32, height: u32) -> Self {
+            let stride_uv = width.div_ceil(2) * 2;
+            Self {
+                width,
+                height,
+                y: vec![0; (width * height) as usize],
+                uv: vec![0; (stride_uv * height.div_ceil(2)) as usize],
+            }
+        }
+
+        fn pattern(width: u32, height: u32) -> Self {
+            let mut frame = Self::new(width, height);
+            fill_pattern(&mut frame.y, 0);
+            fill_pattern(&mut frame.uv, 85);
+            frame
+        }
+
+        fn stride_uv(&self) -> u32 {
+            self.width.div_ceil(2) * 2
+        }
+
+        fn rotate(&self, rotation: Rotation) -> Self {
+            let (width, height) = rotation.rotated_size(self.width, self.height);
+            let mut dst = Self::new(width, height);
+            let dst_stride_uv = dst.stride_uv();
+            nv12_rotate(
+                &self.y,
+                self.width,
+                &self.uv,
+                self.stride_uv(),
+                &mut dst.y,
+                width,
+                &mut dst.uv,
+                dst_stride_uv,
+                self.width,
+                self.height,
+                rotation,
+            );
+            dst
+        }
+
+        fn mirror(&self) -> Self {
+            let mut dst = Self::new(self.width, self.height);
+            let stride_uv = self.stride_uv();
+            nv12_mirror(
+                &self.y,
+                self.width,
+                &self.uv,
+                stride_uv,
+                &mut dst.y,
+                self.width,
+                &mut dst.uv,
+                stride_uv,
+                self.width,
+                self.height,
+            );
+            dst
+        }
+
+        fn crop(&self, x: u32, y: u32, width: u32, height: u32) -> Self {
+            let mut dst = Self::new(width, height);
+            let dst_stride_uv = dst.stride_uv();
+            nv12_crop(
+                &self.y,
+                self.width,
+                &self.uv,
+                self.stride_uv(),
+                self.width,
+                self.height,
+                x,
+                y,
+                &mut dst.y,
+                width,
+                &mut dst.uv,
+                dst_stride_uv,
+                width,
+                height,
+            );
+            dst
+        }
+
+        fn pad(&self, width: u32, height: u32, x: u32, y: u32, color: [u8; 3]) -> Self {
+            let mut dst = Self::new(width, height);
+            let dst_stride_uv = dst.stride_uv();
+            nv12_pad(
+                &self.y,
+                self.width,
+                &self.uv,
+                self.stride_uv(),
+                self.width,
+                self.height,
+                &mut dst.y,
+                width,
+                &mut dst.uv,
+                dst_stride_uv,
+                width,
+                height,
+                x,
+                y,
+                color,
+            );
+            dst
+        }
+
+        fn scale(&self, width: u32, height: u32, filter: ScaleFilter) -> Self {
+            let mut dst = Self::new(width, height);
+            let dst_stride_uv = dst.stride_uv();
+            nv12_scale(
+                &self.y,
+                self.width,
+                &self.uv,
+                self.stride_uv(),
+                self.width,
+                self.height,
+                &mut dst.y,
+                width,
+                &mut dst.uv,
+                dst_stride_uv,
+                width,
+                height,
+                filter,
+            );
+            dst
+        }
+
+        fn assert_eq(&self, other: &Self) {
+            assert_eq!((self.width, self.height), (other.width, other.height));
+            assert_eq!(self.y, other.y, "y plane");
+            assert_eq!(self.uv, other.uv, "uv plane");
+        }
+    }
+
+    fn fill_pattern(plane: &mut [u8], seed: u8) {
+        for (i, sample) in plane.iter_mut().enumerate() {
+            *sample = (i as u8).wrapping_mul(7).wrapping_add(seed);
+        }
+    }
+
+    const ROTATIONS: [Rotation; 4] =
+        [Rotation::Rotate0, Rotation::Rotate90, Rotation::Rotate180, Rotation::Rotate270];
+
+    /// Even and odd sizes, square or not
+    const SIZES: [(u32, u32); 6] = [(2, 2), (4, 2), (6, 10), (7, 5), (16, 9), (33, 17)];
+
+    fn inverse(rotation: Rotation) -> Rotation {
+        match rotation {
+            Rotation::Rotate0 => Rotation::Rotate0,
+            Rotation::Rotate90 => Rotation::Rotate270,
+            Rotation::Rotate180 => Rotation::Rotate180,
+            Rotation::Rotate270 => Rotation::Rotate90,
+        }
+    }
+
+    #[test]
+    fn i420_rotate_round_trips() {
+        for (width, height) in SIZES {
+            let frame = I420::pattern(width, height);
+            for rotation in ROTATIONS {
+                frame.rotate(rotation).rotate(inverse(rotation)).assert_eq(&frame);
+            }
+            let mut four_times = frame.rotate(Rotation::Rotate90);
+            for _ in 0..3 {
+                four_times = four_times.rotate(Rotation::Rotate90);
+            }
+            four_times.assert_eq(&frame);
+        }
+    }
+
+    #[test]
+    fn i420_rotate_matches_scalar() {
+        for (width, height) in SIZES {
+            let frame = I420::pattern(width, height);
+            for rotation in ROTATIONS {
+                let rotated = frame.rotate(rotation);
+                let mut expected = I420::new(rotated.width, rotated.height);
+                scalar::rotate_plane(
+                    &frame.y,
+                    width as usize,
+                    &mut expected.y,
+                    rotated.width as usize,
+                    width as usize,
+                    height as usize,
+                    1,
+                    rotation,
+                );
+                for (src, dst) in [(&frame.u, &mut expected.u), (&frame.v, &mut expected.v)] {
+                    scalar::rotate_plane(
+                        src,
+                        frame.stride_uv() as usize,
+                        dst,
+                        rotated.stride_uv() as usize,
+                        width.div_ceil(2) as usize,
+                        height.div_ceil(2) as usize,
+                        1,
+                        rotation,
+                    );
+                }
+                rotated.assert_eq(&expected);
+            }
+        }
+    }
+
+    #[test]
+    fn nv12_rotate_round_trips() {
+        for (width, height) in SIZES {
+            let frame = Nv12::pattern(width, height);
+            for rotation in ROTATIONS {
+                frame.rotate(rotation).rotate(inverse(rotation)).assert_eq(&frame);
+            }
+        }
+    }
+
+    #[test]
+    fn nv12_rotate_keeps_uv_pairs_together() {
+        let frame = Nv12::pattern(4, 2);
+        let rotated = frame.rotate(Rotation::Rotate90);
+        // 2x1 chroma samples become 1x2
+        assert_eq!(rotated.uv[0..2], frame.uv[0..2]);
+        assert_eq!(rotated.uv[2..4], frame.uv[2..4]);
+    }
+
+    #[test]
+    fn mirror_round_trips() {
+        for (width, height) in SIZES {
+            let frame = I420::pattern(width, height);
+            let mirrored = frame.mirror();
+            assert_eq!(mirrored.y[0], frame.y[width as usize - 1]);
+            mirrored.mirror().assert_eq(&frame);
+
+            let frame = Nv12::pattern(width, height);
+            let mirrored = frame.mirror();
+            let last_uv = (frame.width.div_ceil(2) as usize - 1) * 2;
+            assert_eq!(mirrored.uv[0..2], frame.uv[last_uv..last_uv + 2]);
+            mirrored.mirror().assert_eq(&frame);
+        }
+    }
+
+    #[test]
+    fn pad_then_crop_round_trips() {
+        for (width, height) in SIZES {
+            for (x, y) in [(0, 0), (2, 0), (0, 4), (6, 2)] {
+                let (dst_width, dst_height) = (width + x + 3, height + y + 2);
+
+                let frame = I420::pattern(width, height);
+                let padded = frame.pad(dst_width, dst_height, x, y, [16, 128, 128]);
+                assert_eq!(padded.y[0], if (x, y) == (0, 0) { frame.y[0] } else { 16 });
+                assert_eq!(*padded.y.last().unwrap(), 16);
+                padded.crop(x, y, width, height).assert_eq(&frame);
+
+                let frame = Nv12::pattern(width, height);
+                let padded = frame.pad(dst_width, dst_height, x, y, [16, 100, 200]);
+                assert_eq!(padded.uv[padded.uv.len() - 2..], [100, 200]);
+                padded.crop(x, y, width, height).assert_eq(&frame);
+            }
+        }
+    }
+
+    #[test]
+    fn crop_then_pad_keeps_the_region() {
+        let frame = I420::pattern(16, 12);
+        let cropped = frame.crop(4, 2, 8, 6);
+        for row in 0..6 {
+            let src = (row + 2) * 16 + 4;
+            assert_eq!(cropped.y[row * 8..row * 8 + 8], frame.y[src..src + 8]);
+        }
+        let padded = cropped.pad(16, 12, 4, 2, [0, 0, 0]);
+        padded.crop(4, 2, 8, 6).assert_eq(&cropped);
+
+        let frame = Nv12::pattern(16, 12);
+        let cropped = frame.crop(4, 2, 8, 6);
+        let src = 16 + 4;
+        assert_eq!(cropped.uv[0..8], frame.uv[src..src + 8]);
+        cropped.pad(16, 12, 4, 2, [0, 0, 0]).crop(4, 2, 8, 6).assert_eq(&cropped);
+    }
+
+    #[test]
+    #[should_panic]
+    fn crop_rejects_odd_origin() {
+        I420::pattern(8, 8).crop(1, 0, 4, 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn crop_rejects_out_of_bounds_region() {
+        I420::pattern(8, 8).crop(4, 4, 6, 4);
+    }
+
+    #[test]
+    fn scale_to_same_size_is_identity() {
+        for (width, height) in SIZES {
+            let frame = I420::pattern(width, height);
+            frame.scale(width, height, ScaleFilter::None).assert_eq(&frame);
+
+            let frame = Nv12::pattern(width, height);
+            frame.scale(width, height, ScaleFilter::None).assert_eq(&frame);
+        }
+    }
+
+    #[test]
+    fn scale_round_trips_a_flat_frame() {
+        let mut frame = I420::new(32, 18);
+        frame.y.fill(120);
+        frame.u.fill(60);
+        frame.v.fill(200);
+
+        for filter in
+            [ScaleFilter::None, ScaleFilter::Linear, ScaleFilter::Bilinear, ScaleFilter::Box]
+        {
+            let scaled = frame.scale(64, 36, filter).scale(32, 18, filter);
+            scaled.assert_eq(&frame);
+            let scaled = frame.scale(16, 10, filter).scale(32, 18, filter);
+            scaled.assert_eq(&frame);
+        }
+
+        let mut frame = Nv12::new(32, 18);
+        frame.y.fill(120);
+        for uv in frame.uv.chunks_mut(2) {
+            uv.copy_from_slice(&[60, 200]);
+        }
+        for filter in
+            [ScaleFilter::None, ScaleFilter::Linear, ScaleFilter::Bilinear, ScaleFilter::Box]
+        {
+            frame.scale(64, 36, filter).scale(32, 18, filter).assert_eq(&frame);
+            frame.scale(16, 10, filter).scale(32, 18, filter).assert_eq(&frame);
+        }
+    }
+
+    #[test]
+    fn letterbox_fits_and_centers() {
+        // 4:3 in 16:9, pillarbox
+        assert_eq!(letterbox_rect(640, 480, 1280, 720), (160, 0, 960, 720));
+        // 16:9 in 4:3, letterbox
+        assert_eq!(letterbox_rect(1280, 720, 640, 480), (0, 60, 640, 360));
+        // Same aspect ratio
+        assert_eq!(letterbox_rect(320, 180, 1280, 720), (0, 0, 1280, 720));
+        // Results are always even
+        let (x, y, width, height) = letterbox_rect(101, 57, 333, 111);
+        assert!(x % 2 == 0 && y % 2 == 0 && width % 2 == 0 && height % 2 == 0);
+        assert!(x + width <= 333 && y + height <= 111);
+        // Smallest destination, and odd sizes
+        assert_eq!(letterbox_rect(1920, 1080, 2, 2), (0, 0, 2, 2));
+        assert_eq!(letterbox_rect(1080, 1920, 3, 3), (0, 0, 2, 2));
+        let (_, _, width, height) = letterbox_rect(1, 1000, 7, 5);
+        assert_eq!((width, height), (2, 4));
+    }
+
+    #[test]
+    #[should_panic]
+    fn letterbox_rejects_tiny_destination() {
+        letterbox_rect(640, 480, 1, 480);
+    }
+}
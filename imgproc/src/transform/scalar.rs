@@ -0,0 +1,95 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Plane operations libyuv has no equivalent for (interleaved UV planes), also used as a
+//! reference in tests. `bpp` is the number of bytes per pixel, 1 for Y and 2 for UV.
+
+use super::Rotation;
+
+pub(crate) fn rotate_plane(
+    src: &[u8],
+    src_stride: usize,
+    dst: &mut [u8],
+    dst_stride: usize,
+    width: usize,
+    height: usize,
+    bpp: usize,
+    rotation: Rotation,
+) {
+    for y in 0..height {
+        for x in 0..width {
+            // Clockwise, like libyuv
+            let (dx, dy) = match rotation {
+                Rotation::Rotate0 => (x, y),
+                Rotation::Rotate90 => (height - 1 - y, x),
+                Rotation::Rotate180 => (width - 1 - x, height - 1 - y),
+                Rotation::Rotate270 => (y, width - 1 - x),
+            };
+            let s = y * src_stride + x * bpp;
+            let d = dy * dst_stride + dx * bpp;
+            dst[d..d + bpp].copy_from_slice(&src[s..s + bpp]);
+        }
+    }
+}
+
+pub(crate) fn mirror_plane(
+    src: &[u8],
+    src_stride: usize,
+    dst: &mut [u8],
+    dst_stride: usize,
+    width: usize,
+    height: usize,
+    bpp: usize,
+) {
+    for y in 0..height {
+        for x in 0..width {
+            let s = y * src_stride + x * bpp;
+            let d = y * dst_stride + (width - 1 - x) * bpp;
+            dst[d..d + bpp].copy_from_slice(&src[s..s + bpp]);
+        }
+    }
+}
+
+/// Fill `width` x `height` pixels with `pixel`, which is `bpp` bytes long.
+pub(crate) fn fill_plane(
+    dst: &mut [u8],
+    dst_stride: usize,
+    width: usize,
+    height: usize,
+    pixel: &[u8],
+) {
+    let bpp = pixel.len();
+    for y in 0..height {
+        let row = &mut dst[y * dst_stride..y * dst_stride + width * bpp];
+        for chunk in row.chunks_exact_mut(bpp) {
+            chunk.copy_from_slice(pixel);
+        }
+    }
+}
+
+pub(crate) fn copy_plane(
+    src: &[u8],
+    src_stride: usize,
+    dst: &mut [u8],
+    dst_stride: usize,
+    width: usize,
+    height: usize,
+    bpp: usize,
+) {
+    let row_len = width * bpp;
+    for y in 0..height {
+        dst[y * dst_stride..y * dst_stride + row_len]
+            .copy_from_slice(&src[y * src_stride..y * src_stride + row_len]);
+    }
+}
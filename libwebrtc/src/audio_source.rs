@@ -164,7 +164,7 @@ pub mod native {
 
     use super::*;
     use crate::imp::audio_source::NativeAudioSource as ImpAudioSource;
-    use crate::{audio_frame::AudioFrame, RtcError};
+    use crate::{audio_frame::AudioFrame, native::audio_processor::AudioProcessorChain, RtcError};
 
     #[derive(Clone)]
     pub struct NativeAudioSource {
@@ -191,6 +191,11 @@ pub mod native {
             self.handle.clear_buffer()
         }
 
+        /// Processors run on every captured frame, before it is buffered.
+        pub fn processors(&self) -> AudioProcessorChain {
+            self.handle.processors()
+        }
+
         pub async fn capture_frame(&self, frame: &AudioFrame<'_>) -> Result<(), RtcError> {
             self.handle.capture_frame(frame).await
         }
@@ -25,7 +25,10 @@ pub mod native {
     use livekit_runtime::Stream;
 
     use super::stream_imp;
-    use crate::{audio_frame::AudioFrame, audio_track::RtcAudioTrack};
+    use crate::{
+        audio_frame::AudioFrame, audio_track::RtcAudioTrack,
+        native::audio_processor::AudioProcessorChain,
+    };
 
     const DEFAULT_QUEUE_SIZE_FRAMES: usize = 10;
 
@@ -46,6 +49,12 @@ pub mod native {
         /// queue, or unbounded buffering, for only one of them can increase
         /// end-to-end latency for that stream and cause audio/video drift.
         pub queue_size_frames: Option<usize>,
+        /// Processors run on every decoded frame, on the WebRTC audio thread, before it is
+        /// queued. Processors keep per-stream state, so a chain must not be shared by streams.
+        ///
+        /// They run after the processors registered on the track with
+        /// [`RtcAudioTrack::playout_processors`], which apply to every stream of the track.
+        pub processors: AudioProcessorChain,
     }
 
     pub struct NativeAudioStream {
@@ -66,6 +75,7 @@ pub mod native {
                     sample_rate,
                     num_channels,
                     Some(DEFAULT_QUEUE_SIZE_FRAMES),
+                    AudioProcessorChain::default(),
                 ),
             }
         }
@@ -82,6 +92,7 @@ pub mod native {
                     sample_rate,
                     num_channels,
                     normalize_queue_size_frames(options.queue_size_frames),
+                    options.processors,
                 ),
             }
         }
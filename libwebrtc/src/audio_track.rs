@@ -19,6 +19,9 @@ use crate::{
     media_stream_track::{media_stream_track, RtcTrackState},
 };
 
+#[cfg(not(target_arch = "wasm32"))]
+use crate::native::audio_processor::AudioProcessorChainFactory;
+
 #[derive(Clone)]
 pub struct RtcAudioTrack {
     pub(crate) handle: imp_at::RtcAudioTrack,
@@ -26,6 +29,16 @@ pub struct RtcAudioTrack {
 
 impl RtcAudioTrack {
     media_stream_track!();
+
+    /// Factories of the processors run on every frame of the streams created from this track.
+    ///
+    /// Each [`NativeAudioStream`](crate::audio_stream::native::NativeAudioStream) builds its own
+    /// chain from the factories when it is created, so factories added later only apply to
+    /// streams created afterwards. Clones of the track share the factories.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn playout_processors(&self) -> AudioProcessorChainFactory {
+        self.handle.playout_processors()
+    }
 }
 
 impl Debug for RtcAudioTrack {
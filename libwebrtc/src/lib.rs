@@ -71,7 +71,8 @@ pub mod native {
     pub use webrtc_sys::webrtc::ffi::create_random_uuid;
 
     pub use crate::imp::{
-        apm, audio_mixer, audio_resampler, frame_cryptor, packet_trailer, yuv_helper,
+        apm, audio_mixer, audio_playout_buffer, audio_processor, audio_resampler, frame_cryptor,
+        frame_transformer, packet_trailer, video_codec, video_processor, yuv_helper,
     };
 }
 
@@ -0,0 +1,636 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Jitter-buffered, pull-based playout of an audio stream.
+//!
+//! [`AudioPlayoutBuffer`] turns the 10ms frames pushed by a
+//! [`NativeAudioStream`] into a pull API: the playout engine calls
+//! [`read`](AudioPlayoutBuffer::read) at its own clock with any number of samples, e.g. from a
+//! device callback.
+//!
+//! Received audio is kept at a target latency. Audio is resampled to the output rate with a
+//! variable-rate soxr resampler whose ratio is nudged, by at most
+//! [`max_drift_correction`](AudioPlayoutBufferOptions::max_drift_correction), to drain the buffer
+//! when it runs above the target and fill it when it runs below, absorbing the clock drift between
+//! the sender and the playout device. When the buffer runs dry the output fades to silence and
+//! the buffer primes again up to the target before resuming.
+
+use std::{
+    collections::VecDeque,
+    ffi::{c_void, CStr},
+    pin::Pin,
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use livekit_runtime::Stream;
+
+use crate::{audio_frame::AudioFrame, audio_stream::native::NativeAudioStream};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioPlayoutBufferOptions {
+    /// Sample rate of the audio returned by [`read`](AudioPlayoutBuffer::read).
+    pub sample_rate: u32,
+    /// Channels of the audio returned by [`read`](AudioPlayoutBuffer::read). The stream must be
+    /// created with the same number of channels.
+    pub num_channels: u32,
+    /// Latency the buffer primes to and steers towards.
+    pub target_latency: Duration,
+    /// Above this latency, the oldest audio is dropped to get back to the target.
+    pub max_latency: Duration,
+    /// Largest relative change of the playout rate used to correct drift, e.g. 0.005 plays at
+    /// most 0.5% faster or slower.
+    pub max_drift_correction: f64,
+}
+
+impl Default for AudioPlayoutBufferOptions {
+    fn default() -> Self {
+        Self {
+            sample_rate: 48000,
+            num_channels: 1,
+            target_latency: Duration::from_millis(60),
+            max_latency: Duration::from_millis(250),
+            // Keeps the pitch change inaudible
+            max_drift_correction: 0.005,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AudioPlayoutStats {
+    /// Reads that ran out of audio while playing.
+    pub underruns: u64,
+    /// Samples per channel filled with concealment instead of audio, including while priming.
+    pub concealed_samples: u64,
+    /// Samples per channel dropped because the buffer exceeded the max latency.
+    pub dropped_samples: u64,
+    /// Audio buffered ahead of the next read, including the resampler delay.
+    pub latency: Duration,
+    /// Current relative change of the playout rate, positive when playing faster to drain the
+    /// buffer.
+    pub drift_correction: f64,
+}
+
+/// Pulls audio from a stream at the caller's clock, see the [module docs](self).
+///
+/// Frames are received from the stream during [`read`](Self::read), so the stream queue must
+/// hold the audio delivered between two reads; the default queue of
+/// [`NativeAudioStream`] holds 100ms.
+pub struct AudioPlayoutBuffer<S = NativeAudioStream> {
+    stream: S,
+    options: AudioPlayoutBufferOptions,
+    stream_ended: bool,
+    /// Whether the resampler returned all of its audio after the stream ended.
+    flushed: bool,
+    /// Interleaved samples at `input_rate`.
+    input: VecDeque<i16>,
+    /// Rate of the received frames, 0 until the first frame.
+    input_rate: u32,
+    resampler: Option<VariableRateResampler>,
+    priming: bool,
+    /// Smoothed buffered duration in seconds, used to steer the playout rate.
+    level: f64,
+    /// Input frames owed to the resampler for the audio read so far.
+    input_credit: f64,
+    last_frame: Vec<i16>,
+    warned_channels: bool,
+    stats: AudioPlayoutStats,
+}
+
+impl<S> AudioPlayoutBuffer<S>
+where
+    S: Stream<Item = AudioFrame<'static>> + Unpin,
+{
+    pub fn new(stream: S, options: AudioPlayoutBufferOptions) -> Self {
+        assert!(options.sample_rate > 0 && options.num_channels > 0, "invalid output format");
+        let num_channels = options.num_channels as usize;
+        Self {
+            stream,
+            options,
+            stream_ended: false,
+            flushed: false,
+            input: VecDeque::new(),
+            input_rate: 0,
+            resampler: None,
+            priming: true,
+            level: 0.0,
+            input_credit: 0.0,
+            last_frame: vec![0; num_channels],
+            warned_channels: false,
+            stats: AudioPlayoutStats::default(),
+        }
+    }
+
+    /// Fills `output` with interleaved samples at the output rate and returns the number of
+    /// samples that hold received audio, the rest is concealment.
+    ///
+    /// # Panics
+    /// If the length of `output` is not a multiple of the number of channels.
+    pub fn read(&mut self, output: &mut [i16]) -> usize {
+        let num_channels = self.options.num_channels as usize;
+        assert!(
+            output.len().is_multiple_of(num_channels),
+            "output must hold whole frames of {} channels",
+            num_channels
+        );
+
+        self.receive();
+
+        let out_frames = output.len() / num_channels;
+        if out_frames == 0 {
+            return 0;
+        }
+
+        let buffered = self.buffered();
+        if buffered > self.options.max_latency.as_secs_f64() {
+            let excess = buffered - self.options.target_latency.as_secs_f64();
+            let frames = ((excess * self.input_rate as f64) as usize).min(self.buffered_frames());
+            self.input.drain(..frames * num_channels);
+            self.stats.dropped_samples += frames as u64;
+            self.level = self.buffered();
+        }
+
+        if self.priming {
+            // Once the stream ended, play the rest of the audio without waiting for the target
+            let primed =
+                self.stream_ended || self.buffered() >= self.options.target_latency.as_secs_f64();
+            if self.input_rate == 0 || self.is_finished() || !primed {
+                self.conceal(output);
+                self.update_latency();
+                return 0;
+            }
+            self.priming = false;
+            self.level = self.buffered();
+        }
+
+        let written = self.resample(output);
+        if written > 0 {
+            let end = written * num_channels;
+            self.last_frame.copy_from_slice(&output[end - num_channels..end]);
+        }
+        if written < out_frames {
+            if !self.stream_ended || !self.input.is_empty() {
+                self.stats.underruns += 1;
+            }
+            self.priming = true;
+            self.input_credit = 0.0;
+            self.conceal(&mut output[written * num_channels..]);
+        }
+
+        self.update_latency();
+        written * num_channels
+    }
+
+    pub fn stats(&self) -> AudioPlayoutStats {
+        self.stats.clone()
+    }
+
+    /// Audio buffered ahead of the next read.
+    pub fn latency(&self) -> Duration {
+        self.stats.latency
+    }
+
+    /// Whether the stream ended and all of its audio has been read, including the audio held by
+    /// the resampler.
+    pub fn is_finished(&self) -> bool {
+        self.stream_ended && self.input.is_empty() && (self.flushed || self.resampler.is_none())
+    }
+
+    pub fn stream(&self) -> &S {
+        &self.stream
+    }
+
+    pub fn into_stream(self) -> S {
+        self.stream
+    }
+
+    /// Moves the frames received by the stream into the buffer.
+    fn receive(&mut self) {
+        let mut cx = Context::from_waker(Waker::noop());
+        while !self.stream_ended {
+            match Pin::new(&mut self.stream).poll_next(&mut cx) {
+                Poll::Ready(Some(frame)) => self.push(frame),
+                Poll::Ready(None) => self.stream_ended = true,
+                Poll::Pending => break,
+            }
+        }
+    }
+
+    fn push(&mut self, frame: AudioFrame<'static>) {
+        if frame.num_channels != self.options.num_channels {
+            if !self.warned_channels {
+                log::warn!(
+                    "audio playout buffer: dropping frames with {} channels, expected {}",
+                    frame.num_channels,
+                    self.options.num_channels
+                );
+                self.warned_channels = true;
+            }
+            return;
+        }
+
+        if frame.sample_rate != self.input_rate {
+            let max_ratio = frame.sample_rate as f64 / self.options.sample_rate as f64
+                * (1.0 + self.options.max_drift_correction.abs())
+                * 1.001;
+            match VariableRateResampler::new(max_ratio, self.options.num_channels) {
+                Ok(resampler) => self.resampler = Some(resampler),
+                Err(err) => {
+                    log::error!("audio playout buffer: failed to create resampler: {}", err);
+                    return;
+                }
+            }
+            self.input.clear();
+            self.input_credit = 0.0;
+            self.input_rate = frame.sample_rate;
+            self.priming = true;
+        }
+
+        self.input.extend(frame.data.iter());
+    }
+
+    /// Resamples buffered audio into `output`, returns the number of frames written.
+    fn resample(&mut self, output: &mut [i16]) -> usize {
+        let num_channels = self.options.num_channels as usize;
+        let out_frames = output.len() / num_channels;
+        let read_duration = out_frames as f64 / self.options.sample_rate as f64;
+
+        // Smooth over the 10ms packet arrivals so they don't modulate the playout rate
+        let alpha = (read_duration / LEVEL_SMOOTHING.as_secs_f64()).min(1.0);
+        self.level += alpha * (self.buffered() - self.level);
+        self.stats.drift_correction = drift_correction(
+            self.level,
+            self.options.target_latency.as_secs_f64(),
+            self.options.max_drift_correction.abs(),
+        );
+
+        let Some(resampler) = self.resampler.as_mut() else {
+            return 0;
+        };
+        let ratio = self.input_rate as f64 / self.options.sample_rate as f64
+            * (1.0 + self.stats.drift_correction);
+        if let Err(err) = resampler.set_io_ratio(ratio, out_frames) {
+            log::warn!("audio playout buffer: failed to set the resampling ratio: {}", err);
+        }
+
+        // soxr accepts input at the ratio it was created with, only pass what the output needs
+        // so the excess stays here and is counted in the buffered level
+        self.input_credit += out_frames as f64 * ratio;
+        let mut written = 0;
+        while written < out_frames {
+            let output = &mut output[written * num_channels..];
+            let res = if self.stream_ended && self.input.is_empty() {
+                // No more input will come, drain the audio held in the resampler delay
+                resampler.flush(output).map(|produced| (0, produced))
+            } else {
+                let frames = (self.input_credit.floor() as usize).max(1);
+                let input = self.input.make_contiguous();
+                let input = &input[..(frames * num_channels).min(input.len())];
+                resampler.process(input, output)
+            };
+            match res {
+                Ok((0, 0)) => {
+                    self.flushed = self.stream_ended && self.input.is_empty();
+                    break;
+                }
+                Ok((consumed, produced)) => {
+                    self.input.drain(..consumed * num_channels);
+                    self.input_credit -= consumed as f64;
+                    written += produced;
+                }
+                Err(err) => {
+                    log::error!("audio playout buffer: failed to resample: {}", err);
+                    break;
+                }
+            }
+        }
+        written
+    }
+
+    /// Fades from the last played frame to silence over `output`.
+    fn conceal(&mut self, output: &mut [i16]) {
+        let num_channels = self.options.num_channels as usize;
+        let fade_frames = (self.options.sample_rate as usize * FADE_OUT_MS / 1000).max(1);
+        for (i, frame) in output.chunks_exact_mut(num_channels).enumerate() {
+            let gain = 1.0 - (i + 1) as f32 / fade_frames as f32;
+            for (sample, last) in frame.iter_mut().zip(&self.last_frame) {
+                *sample = (*last as f32 * gain.max(0.0)) as i16;
+            }
+        }
+        if !output.is_empty() {
+            self.last_frame.copy_from_slice(&output[output.len() - num_channels..]);
+        }
+        self.stats.concealed_samples += (output.len() / num_channels) as u64;
+    }
+
+    fn buffered_frames(&self) -> usize {
+        self.input.len() / self.options.num_channels as usize
+    }
+
+    /// Buffered duration in seconds, not including the resampler delay.
+    fn buffered(&self) -> f64 {
+        if self.input_rate == 0 {
+            return 0.0;
+        }
+        self.buffered_frames() as f64 / self.input_rate as f64
+    }
+
+    fn update_latency(&mut self) {
+        let delay = self.resampler.as_ref().map_or(0.0, |r| r.delay());
+        let latency = self.buffered() + delay / self.options.sample_rate as f64;
+        self.stats.latency = Duration::from_secs_f64(latency);
+    }
+}
+
+/// Time constant of the buffered level used to steer the playout rate.
+const LEVEL_SMOOTHING: Duration = Duration::from_millis(500);
+
+const FADE_OUT_MS: usize = 5;
+
+/// Relative playout rate change for the smoothed buffered `level`, proportional to its distance
+/// from the target and reaching `max` when the level is twice the target, or empty.
+fn drift_correction(level: f64, target: f64, max: f64) -> f64 {
+    if target <= 0.0 {
+        return 0.0;
+    }
+    ((level - target) / target).clamp(-1.0, 1.0) * max
+}
+
+/// soxr resampler in variable-rate mode, on interleaved `i16` samples.
+struct VariableRateResampler {
+    soxr: soxr_sys::soxr_t,
+    num_channels: usize,
+}
+
+unsafe impl Send for VariableRateResampler {}
+
+impl VariableRateResampler {
+    /// `max_io_ratio` bounds the input to output rate ratio passed to
+    /// [`set_io_ratio`](Self::set_io_ratio).
+    fn new(max_io_ratio: f64, num_channels: u32) -> Result<Self, String> {
+        let mut error: soxr_sys::soxr_error_t = std::ptr::null();
+        let soxr = unsafe {
+            let io_spec = soxr_sys::soxr_io_spec(
+                soxr_sys::soxr_datatype_t_SOXR_INT16_I,
+                soxr_sys::soxr_datatype_t_SOXR_INT16_I,
+            );
+            let quality_spec =
+                soxr_sys::soxr_quality_spec(soxr_sys::SOXR_20_BITQ as _, soxr_sys::SOXR_VR as _);
+            soxr_sys::soxr_create(
+                max_io_ratio,
+                1.0,
+                num_channels,
+                &mut error,
+                &io_spec,
+                &quality_spec,
+                std::ptr::null(),
+            )
+        };
+        check(error)?;
+        Ok(Self { soxr, num_channels: num_channels as usize })
+    }
+
+    /// Changes the ratio gradually over `slew_len` output samples.
+    fn set_io_ratio(&mut self, io_ratio: f64, slew_len: usize) -> Result<(), String> {
+        check(unsafe { soxr_sys::soxr_set_io_ratio(self.soxr, io_ratio, slew_len) })
+    }
+
+    /// Returns the number of input frames consumed and output frames produced.
+    fn process(&mut self, input: &[i16], output: &mut [i16]) -> Result<(usize, usize), String> {
+        let mut consumed = 0;
+        let mut produced = 0;
+        check(unsafe {
+            soxr_sys::soxr_process(
+                self.soxr,
+                input.as_ptr() as *const c_void,
+                input.len() / self.num_channels,
+                &mut consumed,
+                output.as_mut_ptr() as *mut c_void,
+                output.len() / self.num_channels,
+                &mut produced,
+            )
+        })?;
+        Ok((consumed, produced))
+    }
+
+    /// Signals the end of the input and returns the number of output frames produced from the
+    /// audio still held by the resampler, 0 once it is drained.
+    fn flush(&mut self, output: &mut [i16]) -> Result<usize, String> {
+        let mut produced = 0;
+        check(unsafe {
+            soxr_sys::soxr_process(
+                self.soxr,
+                std::ptr::null(),
+                0,
+                std::ptr::null_mut(),
+                output.as_mut_ptr() as *mut c_void,
+                output.len() / self.num_channels,
+                &mut produced,
+            )
+        })?;
+        Ok(produced)
+    }
+
+    /// Delay of the resampler, in output samples.
+    fn delay(&self) -> f64 {
+        unsafe { soxr_sys::soxr_delay(self.soxr) }
+    }
+}
+
+impl Drop for VariableRateResampler {
+    fn drop(&mut self) {
+        unsafe { soxr_sys::soxr_delete(self.soxr) };
+    }
+}
+
+fn check(error: soxr_sys::soxr_error_t) -> Result<(), String> {
+    if error.is_null() {
+        return Ok(());
+    }
+    Err(unsafe { CStr::from_ptr(error) }.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::VecDeque, sync::Arc};
+
+    use parking_lot::Mutex;
+
+    use super::*;
+
+    /// Stream of the frames pushed by the test.
+    #[derive(Clone, Default)]
+    struct TestStream {
+        frames: Arc<Mutex<VecDeque<AudioFrame<'static>>>>,
+        ended: Arc<Mutex<bool>>,
+    }
+
+    impl TestStream {
+        /// Pushes `ms` of a constant signal, in 10ms frames.
+        fn push(&self, sample_rate: u32, ms: u32, value: i16) {
+            let samples_per_channel = sample_rate / 100;
+            for _ in 0..ms / 10 {
+                self.frames.lock().push_back(AudioFrame {
+                    data: vec![value; samples_per_channel as usize].into(),
+                    sample_rate,
+                    num_channels: 1,
+                    samples_per_channel,
+                });
+            }
+        }
+
+        /// Ends the stream once the pushed frames are received.
+        fn end(&self) {
+            *self.ended.lock() = true;
+        }
+    }
+
+    impl Stream for TestStream {
+        type Item = AudioFrame<'static>;
+
+        fn poll_next(self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Option<Self::Item>> {
+            match self.frames.lock().pop_front() {
+                Some(frame) => Poll::Ready(Some(frame)),
+                None if *self.ended.lock() => Poll::Ready(None),
+                None => Poll::Pending,
+            }
+        }
+    }
+
+    fn buffer(stream: &TestStream) -> AudioPlayoutBuffer<TestStream> {
+        let options = AudioPlayoutBufferOptions {
+            target_latency: Duration::from_millis(40),
+            max_latency: Duration::from_millis(100),
+            ..Default::default()
+        };
+        AudioPlayoutBuffer::new(stream.clone(), options)
+    }
+
+    #[test]
+    fn primes_to_target_latency() {
+        let stream = TestStream::default();
+        let mut buffer = buffer(&stream);
+        let mut output = [1; 480];
+
+        stream.push(48000, 30, 1000);
+        assert_eq!(buffer.read(&mut output), 0);
+        assert!(output.iter().all(|s| *s == 0));
+
+        stream.push(48000, 10, 1000);
+        for _ in 0..3 {
+            assert_eq!(buffer.read(&mut output), 480);
+            stream.push(48000, 10, 1000);
+        }
+        assert!(output[400..].iter().all(|s| (*s - 1000).abs() < 10), "{:?}", &output[400..]);
+
+        let stats = buffer.stats();
+        assert_eq!(stats.underruns, 0);
+        assert_eq!(stats.concealed_samples, 480);
+        // 30ms left after the last read, some of it held by the resampler
+        assert!(stats.latency > Duration::from_millis(20), "{:?}", stats.latency);
+        assert!(stats.latency <= Duration::from_millis(40), "{:?}", stats.latency);
+    }
+
+    #[test]
+    fn conceals_underruns() {
+        let stream = TestStream::default();
+        let mut buffer = buffer(&stream);
+        let mut output = [0; 480];
+
+        stream.push(48000, 40, 1000);
+        while buffer.read(&mut output) == 480 {}
+        assert_eq!(buffer.stats().underruns, 1);
+        assert_eq!(output[479], 0);
+
+        // Primes again before resuming
+        stream.push(48000, 20, 1000);
+        assert_eq!(buffer.read(&mut output), 0);
+        assert_eq!(buffer.stats().underruns, 1);
+    }
+
+    #[test]
+    fn drops_audio_over_max_latency() {
+        let stream = TestStream::default();
+        let mut buffer = buffer(&stream);
+        let mut output = [0; 480];
+
+        stream.push(48000, 200, 1000);
+        assert_eq!(buffer.read(&mut output), 480);
+        let stats = buffer.stats();
+        assert_eq!(stats.dropped_samples, 48000 * 160 / 1000);
+        assert!(stats.latency <= Duration::from_millis(40), "{:?}", stats.latency);
+    }
+
+    #[test]
+    fn resamples_to_output_rate() {
+        let stream = TestStream::default();
+        let mut buffer = buffer(&stream);
+        // 10ms at 48kHz per read, fed with 10ms at 16kHz
+        let mut output = [0; 480];
+
+        stream.push(16000, 40, 1000);
+        for _ in 0..100 {
+            assert_eq!(buffer.read(&mut output), 480);
+            stream.push(16000, 10, 1000);
+        }
+        assert_eq!(buffer.stats().underruns, 0);
+    }
+
+    #[test]
+    fn plays_all_audio_once_ended() {
+        let stream = TestStream::default();
+        let mut buffer = buffer(&stream);
+        let mut output = [0; 480];
+
+        // Shorter than the target latency, and held partly by the resampler
+        stream.push(16000, 30, 1000);
+        stream.end();
+        let mut played = 0;
+        while !buffer.is_finished() {
+            played += buffer.read(&mut output);
+            assert!(played <= 48 * 40, "{}", played);
+        }
+        assert!(played.abs_diff(48 * 30) <= 48, "{}", played);
+        assert_eq!(buffer.stats().underruns, 0);
+        assert_eq!(buffer.read(&mut output), 0);
+    }
+
+    #[test]
+    fn compensates_drift() {
+        let stream = TestStream::default();
+        let mut buffer = buffer(&stream);
+        // The sender runs 0.4% faster than the playout device, 20s would build up 80ms
+        let mut output = [0; 478];
+
+        stream.push(48000, 40, 1000);
+        for _ in 0..2000 {
+            assert_eq!(buffer.read(&mut output), 478);
+            stream.push(48000, 10, 1000);
+        }
+
+        let stats = buffer.stats();
+        assert_eq!(stats.dropped_samples, 0);
+        assert_eq!(stats.underruns, 0);
+        assert!(stats.drift_correction > 0.0);
+    }
+
+    #[test]
+    fn steers_towards_target() {
+        assert_eq!(drift_correction(0.04, 0.04, 0.005), 0.0);
+        assert!(drift_correction(0.06, 0.04, 0.005) > 0.0);
+        assert!(drift_correction(0.02, 0.04, 0.005) < 0.0);
+        assert_eq!(drift_correction(1.0, 0.04, 0.005), 0.005);
+        assert_eq!(drift_correction(0.0, 0.04, 0.005), -0.005);
+    }
+}
@@ -0,0 +1,324 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Chains of in-place audio processors.
+//!
+//! An [`AudioProcessorChain`] runs its [`AudioProcessor`]s in order on every frame passed to
+//! [`NativeAudioSource::capture_frame`](crate::audio_source::native::NativeAudioSource::capture_frame),
+//! or on every frame delivered to a
+//! [`NativeAudioStream`](crate::audio_stream::native::NativeAudioStream) created with
+//! [`NativeAudioStreamOptions::processors`](crate::audio_stream::native::NativeAudioStreamOptions::processors).
+//!
+//! Chains are cheap to clone and clones share their processors, so a chain can be edited while
+//! audio flows through it. Processors keep per-stream state, so a chain runs on a single stream;
+//! the [`AudioProcessorChainFactory`] of a track, see
+//! [`RtcAudioTrack::playout_processors`](crate::audio_track::RtcAudioTrack::playout_processors),
+//! builds a fresh chain for each stream created from it.
+
+use std::{fmt::Debug, sync::Arc};
+
+use parking_lot::Mutex;
+
+use crate::{audio_frame::AudioFrame, native::apm::AudioProcessingModule};
+
+/// Processes audio frames in place.
+///
+/// Processors must keep the sample rate, channel count and length of the frame. They run on the
+/// thread delivering the audio, the WebRTC audio thread for playout streams, so they must not
+/// block.
+pub trait AudioProcessor: Send {
+    fn process(&mut self, frame: &mut AudioFrame<'_>);
+}
+
+/// An ordered list of [`AudioProcessor`]s, itself usable as a processor. A chain must not
+/// contain itself.
+#[derive(Clone, Default)]
+pub struct AudioProcessorChain {
+    processors: Arc<Mutex<Vec<Box<dyn AudioProcessor>>>>,
+}
+
+impl Debug for AudioProcessorChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AudioProcessorChain").field("len", &self.len()).finish()
+    }
+}
+
+impl AudioProcessorChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a processor, it runs after the ones already in the chain.
+    pub fn push(&self, processor: Box<dyn AudioProcessor>) {
+        self.processors.lock().push(processor);
+    }
+
+    /// Inserts a processor at `index`.
+    ///
+    /// # Panics
+    /// If `index > len`.
+    pub fn insert(&self, index: usize, processor: Box<dyn AudioProcessor>) {
+        self.processors.lock().insert(index, processor);
+    }
+
+    /// Removes the processor at `index`, if any.
+    pub fn remove(&self, index: usize) -> Option<Box<dyn AudioProcessor>> {
+        let mut processors = self.processors.lock();
+        (index < processors.len()).then(|| processors.remove(index))
+    }
+
+    pub fn clear(&self) {
+        self.processors.lock().clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.processors.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.processors.lock().is_empty()
+    }
+
+    /// Runs every processor of the chain on `frame`, in order.
+    pub fn process(&self, frame: &mut AudioFrame<'_>) {
+        for processor in self.processors.lock().iter_mut() {
+            processor.process(frame);
+        }
+    }
+}
+
+impl AudioProcessor for AudioProcessorChain {
+    fn process(&mut self, frame: &mut AudioFrame<'_>) {
+        AudioProcessorChain::process(self, frame)
+    }
+}
+
+/// Builds a new processor, called once for every stream the processor runs on.
+pub type AudioProcessorFactory = Arc<dyn Fn() -> Box<dyn AudioProcessor> + Send + Sync>;
+
+/// An ordered list of [`AudioProcessorFactory`]s, building an [`AudioProcessorChain`] per stream.
+///
+/// Clones share their factories. Editing the list only affects the chains built afterwards.
+#[derive(Clone, Default)]
+pub struct AudioProcessorChainFactory {
+    factories: Arc<Mutex<Vec<AudioProcessorFactory>>>,
+}
+
+impl Debug for AudioProcessorChainFactory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AudioProcessorChainFactory").field("len", &self.len()).finish()
+    }
+}
+
+impl AudioProcessorChainFactory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a factory, its processors run after the ones of the factories already listed.
+    pub fn push(&self, factory: AudioProcessorFactory) {
+        self.factories.lock().push(factory);
+    }
+
+    /// Inserts a factory at `index`.
+    ///
+    /// # Panics
+    /// If `index > len`.
+    pub fn insert(&self, index: usize, factory: AudioProcessorFactory) {
+        self.factories.lock().insert(index, factory);
+    }
+
+    /// Removes the factory at `index`, if any.
+    pub fn remove(&self, index: usize) -> Option<AudioProcessorFactory> {
+        let mut factories = self.factories.lock();
+        (index < factories.len()).then(|| factories.remove(index))
+    }
+
+    pub fn clear(&self) {
+        self.factories.lock().clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.factories.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.factories.lock().is_empty()
+    }
+
+    /// Builds a chain holding a new processor from every factory, in order.
+    pub fn build(&self) -> AudioProcessorChain {
+        let processors = self.factories.lock().iter().map(|factory| factory()).collect();
+        AudioProcessorChain { processors: Arc::new(Mutex::new(processors)) }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApmStream {
+    /// Near-end audio, e.g. the microphone capture path.
+    Capture,
+    /// Far-end audio, e.g. a playout stream, used as the echo canceller reference.
+    Render,
+}
+
+/// Runs an [`AudioProcessingModule`] as part of a chain.
+///
+/// The same module is usually shared by a [`Capture`](ApmStream::Capture) processor on the local
+/// track and a [`Render`](ApmStream::Render) processor on the remote tracks, so its echo canceller
+/// sees both ends. Frames must hold a multiple of 10ms of audio; other frames are left untouched.
+pub struct ApmProcessor {
+    apm: Arc<Mutex<AudioProcessingModule>>,
+    stream: ApmStream,
+}
+
+impl ApmProcessor {
+    pub fn new(apm: Arc<Mutex<AudioProcessingModule>>, stream: ApmStream) -> Self {
+        Self { apm, stream }
+    }
+
+    pub fn capture(apm: Arc<Mutex<AudioProcessingModule>>) -> Self {
+        Self::new(apm, ApmStream::Capture)
+    }
+
+    pub fn render(apm: Arc<Mutex<AudioProcessingModule>>) -> Self {
+        Self::new(apm, ApmStream::Render)
+    }
+}
+
+impl AudioProcessor for ApmProcessor {
+    fn process(&mut self, frame: &mut AudioFrame<'_>) {
+        let samples_per_10ms = (frame.sample_rate / 100 * frame.num_channels) as usize;
+        if samples_per_10ms == 0
+            || frame.data.is_empty()
+            || !frame.data.len().is_multiple_of(samples_per_10ms)
+        {
+            log::warn!("apm processor: frame is not a multiple of 10ms, skipping");
+            return;
+        }
+
+        let (sample_rate, num_channels) = (frame.sample_rate as i32, frame.num_channels as i32);
+        let data = frame.data.to_mut();
+        let mut apm = self.apm.lock();
+        let res = match self.stream {
+            ApmStream::Capture => apm.process_stream(data, sample_rate, num_channels),
+            ApmStream::Render => apm.process_reverse_stream(data, sample_rate, num_channels),
+        };
+        if let Err(err) = res {
+            log::warn!("apm processor: {}", err.message);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Add(i16);
+
+    impl AudioProcessor for Add {
+        fn process(&mut self, frame: &mut AudioFrame<'_>) {
+            frame.data.to_mut().iter_mut().for_each(|s| *s += self.0);
+        }
+    }
+
+    struct Double;
+
+    impl AudioProcessor for Double {
+        fn process(&mut self, frame: &mut AudioFrame<'_>) {
+            frame.data.to_mut().iter_mut().for_each(|s| *s *= 2);
+        }
+    }
+
+    fn run(chain: &AudioProcessorChain) -> Vec<i16> {
+        let data = [1i16, 2];
+        let mut frame = AudioFrame {
+            data: data.as_slice().into(),
+            sample_rate: 48_000,
+            num_channels: 1,
+            samples_per_channel: 2,
+        };
+        chain.process(&mut frame);
+        frame.data.into_owned()
+    }
+
+    #[test]
+    fn processors_run_in_order() {
+        let chain = AudioProcessorChain::new();
+        assert_eq!(run(&chain), vec![1, 2]);
+
+        chain.push(Box::new(Add(1)));
+        chain.push(Box::new(Double));
+        assert_eq!(run(&chain), vec![4, 6]);
+
+        chain.insert(0, Box::new(Double));
+        assert_eq!(run(&chain), vec![6, 10]);
+    }
+
+    #[test]
+    fn clones_share_processors() {
+        let chain = AudioProcessorChain::new();
+        let handle = chain.clone();
+        handle.push(Box::new(Add(1)));
+        handle.push(Box::new(Double));
+        assert_eq!(chain.len(), 2);
+
+        assert!(handle.remove(0).is_some());
+        assert!(handle.remove(1).is_none());
+        assert_eq!(run(&chain), vec![2, 4]);
+
+        handle.clear();
+        assert!(chain.is_empty());
+    }
+
+    #[test]
+    fn chains_nest() {
+        let inner = AudioProcessorChain::new();
+        inner.push(Box::new(Double));
+        let chain = AudioProcessorChain::new();
+        chain.push(Box::new(inner.clone()));
+        chain.push(Box::new(Add(1)));
+        assert_eq!(run(&chain), vec![3, 5]);
+
+        inner.push(Box::new(Double));
+        assert_eq!(run(&chain), vec![5, 9]);
+    }
+
+    struct Accumulate(i16);
+
+    impl AudioProcessor for Accumulate {
+        fn process(&mut self, frame: &mut AudioFrame<'_>) {
+            self.0 += 1;
+            frame.data.to_mut().iter_mut().for_each(|s| *s += self.0);
+        }
+    }
+
+    #[test]
+    fn factories_build_independent_chains() {
+        let factory = AudioProcessorChainFactory::new();
+        factory.push(Arc::new(|| Box::new(Accumulate(0)) as Box<dyn AudioProcessor>));
+        factory.push(Arc::new(|| Box::new(Double) as Box<dyn AudioProcessor>));
+
+        let first = factory.build();
+        assert_eq!(run(&first), vec![4, 6]);
+        assert_eq!(run(&first), vec![6, 8]);
+
+        let second = factory.build();
+        assert_eq!(second.len(), 2);
+        assert_eq!(run(&second), vec![4, 6]);
+
+        factory.remove(1);
+        assert_eq!(first.len(), 2);
+        assert_eq!(factory.build().len(), 1);
+    }
+}
@@ -16,7 +16,10 @@ use cxx::SharedPtr;
 use tokio::sync::oneshot;
 use webrtc_sys::audio_track as sys_at;
 
-use crate::{audio_frame::AudioFrame, audio_source::AudioSourceOptions, RtcError, RtcErrorType};
+use crate::{
+    audio_frame::AudioFrame, audio_source::AudioSourceOptions,
+    native::audio_processor::AudioProcessorChain, RtcError, RtcErrorType,
+};
 
 #[derive(Clone)]
 pub struct NativeAudioSource {
@@ -24,6 +27,7 @@ pub struct NativeAudioSource {
     sample_rate: u32,
     num_channels: u32,
     queue_size_samples: u32,
+    processors: AudioProcessorChain,
 }
 
 impl NativeAudioSource {
@@ -62,7 +66,13 @@ impl NativeAudioSource {
         );
 
         let queue_size_samples = (queue_size_ms * sample_rate * num_channels) / 1000;
-        Self { sys_handle, sample_rate, num_channels, queue_size_samples }
+        Self {
+            sys_handle,
+            sample_rate,
+            num_channels,
+            queue_size_samples,
+            processors: AudioProcessorChain::default(),
+        }
     }
 
     pub fn sys_handle(&self) -> SharedPtr<sys_at::ffi::AudioTrackSource> {
@@ -89,6 +99,10 @@ impl NativeAudioSource {
         self.sys_handle.clear_buffer();
     }
 
+    pub fn processors(&self) -> AudioProcessorChain {
+        self.processors.clone()
+    }
+
     pub async fn capture_frame(&self, frame: &AudioFrame<'_>) -> Result<(), RtcError> {
         if self.sample_rate != frame.sample_rate || self.num_channels != frame.num_channels {
             return Err(RtcError {
@@ -97,6 +111,16 @@ impl NativeAudioSource {
             });
         }
 
+        let processed;
+        let frame = if self.processors.is_empty() {
+            frame
+        } else {
+            let mut owned = frame.clone();
+            self.processors.process(&mut owned);
+            processed = owned;
+            &processed
+        };
+
         // Fast path: no buffering
         if self.queue_size_samples == 0 {
             // frame size must be 10ms for fast path
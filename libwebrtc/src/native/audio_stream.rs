@@ -28,7 +28,10 @@ use parking_lot::Mutex;
 use rtrb::{Consumer, Producer, PushError, RingBuffer};
 use webrtc_sys::audio_track as sys_at;
 
-use crate::{audio_frame::AudioFrame, audio_track::RtcAudioTrack};
+use crate::{
+    audio_frame::AudioFrame, audio_track::RtcAudioTrack,
+    native::audio_processor::AudioProcessorChain,
+};
 
 pub struct NativeAudioStream {
     native_sink: SharedPtr<sys_at::ffi::NativeAudioSink>,
@@ -42,9 +45,14 @@ impl NativeAudioStream {
         sample_rate: i32,
         num_channels: i32,
         queue_size_frames: Option<usize>,
+        processors: AudioProcessorChain,
     ) -> Self {
         let frame_queue = Arc::new(AudioFrameQueue::new(queue_size_frames));
-        let observer = Arc::new(AudioTrackObserver { frame_queue: frame_queue.clone() });
+        // The track's processors run first, then the ones given for this stream alone.
+        let chain = audio_track.handle.playout_processors().build();
+        chain.push(Box::new(processors));
+        let observer =
+            Arc::new(AudioTrackObserver { frame_queue: frame_queue.clone(), processors: chain });
         let native_sink = sys_at::ffi::new_native_audio_sink(
             Box::new(sys_at::AudioSinkWrapper::new(observer.clone())),
             sample_rate,
@@ -85,16 +93,19 @@ impl Stream for NativeAudioStream {
 
 pub struct AudioTrackObserver {
     frame_queue: Arc<AudioFrameQueue>,
+    processors: AudioProcessorChain,
 }
 
 impl sys_at::AudioSink for AudioTrackObserver {
     fn on_data(&self, data: &[i16], sample_rate: i32, nb_channels: usize, nb_frames: usize) {
-        self.frame_queue.push(AudioFrame {
+        let mut frame = AudioFrame {
             data: data.to_owned().into(),
             sample_rate: sample_rate as u32,
             num_channels: nb_channels as u32,
             samples_per_channel: nb_frames as u32,
-        });
+        };
+        self.processors.process(&mut frame);
+        self.frame_queue.push(frame);
     }
 }
 
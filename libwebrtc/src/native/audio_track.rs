@@ -16,18 +16,30 @@ use cxx::SharedPtr;
 use sys_at::ffi::audio_to_media;
 use webrtc_sys::audio_track as sys_at;
 
-use super::media_stream_track::impl_media_stream_track;
+use super::{
+    audio_processor::AudioProcessorChainFactory, media_stream_track::impl_media_stream_track,
+};
 use crate::media_stream_track::RtcTrackState;
 
 #[derive(Clone)]
 pub struct RtcAudioTrack {
     pub(crate) sys_handle: SharedPtr<sys_at::ffi::AudioTrack>,
+    playout_processors: AudioProcessorChainFactory,
 }
 
 impl RtcAudioTrack {
     impl_media_stream_track!(audio_to_media);
 
+    pub(crate) fn new(sys_handle: SharedPtr<sys_at::ffi::AudioTrack>) -> Self {
+        Self { sys_handle, playout_processors: AudioProcessorChainFactory::default() }
+    }
+
     pub fn sys_handle(&self) -> SharedPtr<sys_at::ffi::MediaStreamTrack> {
         audio_to_media(self.sys_handle.clone())
     }
+
+    /// Factories of the processors run on every `NativeAudioStream` created from this track.
+    pub fn playout_processors(&self) -> AudioProcessorChainFactory {
+        self.playout_processors.clone()
+    }
 }
@@ -19,8 +19,10 @@ use parking_lot::Mutex;
 use webrtc_sys::frame_cryptor::{self as sys_fc};
 
 use crate::{
-    native::packet_trailer::PacketTrailerHandler, peer_connection_factory::PeerConnectionFactory,
-    rtp_receiver::RtpReceiver, rtp_sender::RtpSender,
+    native::{frame_transformer::FrameTransformerHandler, packet_trailer::PacketTrailerHandler},
+    peer_connection_factory::PeerConnectionFactory,
+    rtp_receiver::RtpReceiver,
+    rtp_sender::RtpSender,
 };
 
 pub type OnStateChange = Box<dyn FnMut(String, EncryptionState) + Send + Sync>;
@@ -189,6 +191,11 @@ impl FrameCryptor {
     pub fn set_packet_trailer_handler(&self, handler: &PacketTrailerHandler) {
         self.sys_handle.set_packet_trailer_handler(handler.sys_handle());
     }
+
+    /// Chain an application frame transformer, which then sees unencrypted payloads.
+    pub fn set_frame_transformer(&self, handler: &FrameTransformerHandler) {
+        self.sys_handle.set_frame_transformer(handler.sys_handle());
+    }
 }
 
 #[derive(Clone)]
@@ -0,0 +1,250 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Application frame transformers (insertable streams).
+//!
+//! A [`FrameTransformer`] sees every encoded audio or video frame of an RTP sender before it is
+//! packetized, or of an RTP receiver before it is decoded, and may replace or drop its payload.
+//!
+//! When the stream is also encrypted or carries packet trailers, the application transformer
+//! always works on the plain payload: on the send side it runs before encryption and before the
+//! trailer is appended, on the receive side after both have been removed. Use
+//! [`FrameCryptor::set_frame_transformer`](crate::native::frame_cryptor::FrameCryptor::set_frame_transformer)
+//! or [`FrameTransformerHandler::set_packet_trailer_handler`] to build that chain.
+
+use std::sync::Arc;
+
+use cxx::SharedPtr;
+use parking_lot::Mutex;
+use webrtc_sys::frame_transformer::{self as sys_ft, ffi as sys_ft_ffi};
+
+use crate::{
+    native::packet_trailer::PacketTrailerHandler, peer_connection_factory::PeerConnectionFactory,
+    rtp_receiver::RtpReceiver, rtp_sender::RtpSender,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameKind {
+    Audio,
+    Video,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameDirection {
+    /// Encoded by the local encoder, about to be packetized.
+    Sender,
+    /// Depacketized, about to be decoded.
+    Receiver,
+}
+
+/// Metadata of an encoded frame going through a [`FrameTransformer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransformableFrameInfo {
+    pub kind: FrameKind,
+    pub direction: FrameDirection,
+    pub ssrc: u32,
+    pub rtp_timestamp: u32,
+    pub payload_type: u8,
+    /// e.g. `video/VP8` or `audio/opus`
+    pub mime_type: String,
+    /// Always false for audio frames.
+    pub is_key_frame: bool,
+    /// Capture time of the frame, in microseconds, when known.
+    pub capture_time_us: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrameTransformResult {
+    /// Forward the frame unchanged.
+    Forward,
+    /// Forward the frame with a new payload.
+    Replace(Vec<u8>),
+    /// Drop the frame.
+    Drop,
+}
+
+/// Inspects or modifies encoded frames.
+///
+/// Called synchronously on the WebRTC thread delivering the frame, so implementations must not
+/// block.
+pub trait FrameTransformer: Send + Sync {
+    fn transform(&self, info: &TransformableFrameInfo, payload: &[u8]) -> FrameTransformResult;
+}
+
+impl From<&sys_ft_ffi::TransformableFrameInfo> for TransformableFrameInfo {
+    fn from(value: &sys_ft_ffi::TransformableFrameInfo) -> Self {
+        Self {
+            kind: match value.kind {
+                sys_ft_ffi::TransformableFrameKind::Audio => FrameKind::Audio,
+                _ => FrameKind::Video,
+            },
+            direction: match value.direction {
+                sys_ft_ffi::TransformableFrameDirection::Sender => FrameDirection::Sender,
+                _ => FrameDirection::Receiver,
+            },
+            ssrc: value.ssrc,
+            rtp_timestamp: value.rtp_timestamp,
+            payload_type: value.payload_type,
+            mime_type: value.mime_type.clone(),
+            is_key_frame: value.is_key_frame,
+            capture_time_us: (value.capture_time_us >= 0).then_some(value.capture_time_us),
+        }
+    }
+}
+
+impl From<FrameTransformResult> for sys_ft_ffi::TransformedFrameData {
+    fn from(value: FrameTransformResult) -> Self {
+        let (action, data) = match value {
+            FrameTransformResult::Forward => {
+                (sys_ft_ffi::FrameTransformAction::Forward, Vec::new())
+            }
+            FrameTransformResult::Replace(data) => {
+                (sys_ft_ffi::FrameTransformAction::Replace, data)
+            }
+            FrameTransformResult::Drop => (sys_ft_ffi::FrameTransformAction::Drop, Vec::new()),
+        };
+        Self { action, data }
+    }
+}
+
+type TransformerSlot = Arc<Mutex<Option<Arc<dyn FrameTransformer>>>>;
+
+struct TransformerAdapter {
+    transformer: TransformerSlot,
+}
+
+impl sys_ft::FrameTransformer for TransformerAdapter {
+    fn transform(
+        &self,
+        info: &sys_ft_ffi::TransformableFrameInfo,
+        data: &[u8],
+    ) -> sys_ft_ffi::TransformedFrameData {
+        let transformer = self.transformer.lock().clone();
+        match transformer {
+            Some(transformer) => transformer.transform(&info.into(), data).into(),
+            None => FrameTransformResult::Forward.into(),
+        }
+    }
+}
+
+/// Runs an application [`FrameTransformer`] on an RTP sender or receiver.
+///
+/// The handler stays installed for the lifetime of the sender or receiver; replacing the
+/// transformer with [`set_transformer`](Self::set_transformer) does not rebuild the chain.
+#[derive(Clone)]
+pub struct FrameTransformerHandler {
+    sys_handle: SharedPtr<sys_ft_ffi::FrameTransformerHandler>,
+    transformer: TransformerSlot,
+}
+
+impl FrameTransformerHandler {
+    /// Replace the transformer, `None` forwards frames unchanged.
+    pub fn set_transformer(&self, transformer: Option<Arc<dyn FrameTransformer>>) {
+        *self.transformer.lock() = transformer;
+    }
+
+    /// Chain the packet trailer transformer, for streams without a frame cryptor.
+    pub fn set_packet_trailer_handler(&self, handler: &PacketTrailerHandler) {
+        self.sys_handle.set_packet_trailer_handler(handler.sys_handle());
+    }
+
+    pub(crate) fn sys_handle(&self) -> SharedPtr<sys_ft_ffi::FrameTransformerHandler> {
+        self.sys_handle.clone()
+    }
+}
+
+fn new_slot(
+    transformer: Option<Arc<dyn FrameTransformer>>,
+) -> (TransformerSlot, Box<sys_ft::FrameTransformerWrapper>) {
+    let slot: TransformerSlot = Arc::new(Mutex::new(transformer));
+    let adapter = Arc::new(TransformerAdapter { transformer: slot.clone() });
+    (slot, Box::new(sys_ft::FrameTransformerWrapper::new(adapter)))
+}
+
+/// Create a handler running `transformer` on the frames of `sender`, and install it.
+pub fn create_sender_handler(
+    peer_factory: &PeerConnectionFactory,
+    sender: &RtpSender,
+    transformer: Option<Arc<dyn FrameTransformer>>,
+) -> FrameTransformerHandler {
+    let (slot, wrapper) = new_slot(transformer);
+    FrameTransformerHandler {
+        sys_handle: sys_ft_ffi::new_frame_transformer_sender(
+            peer_factory.handle.sys_handle.clone(),
+            sender.handle.sys_handle.clone(),
+            wrapper,
+        ),
+        transformer: slot,
+    }
+}
+
+/// Create a handler running `transformer` on the frames of `receiver`, and install it.
+pub fn create_receiver_handler(
+    peer_factory: &PeerConnectionFactory,
+    receiver: &RtpReceiver,
+    transformer: Option<Arc<dyn FrameTransformer>>,
+) -> FrameTransformerHandler {
+    let (slot, wrapper) = new_slot(transformer);
+    FrameTransformerHandler {
+        sys_handle: sys_ft_ffi::new_frame_transformer_receiver(
+            peer_factory.handle.sys_handle.clone(),
+            receiver.handle.sys_handle.clone(),
+            wrapper,
+        ),
+        transformer: slot,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sys_ft::FrameTransformer as _;
+
+    use super::*;
+
+    struct Reverse;
+
+    impl FrameTransformer for Reverse {
+        fn transform(&self, info: &TransformableFrameInfo, payload: &[u8]) -> FrameTransformResult {
+            assert_eq!(info.capture_time_us, None);
+            FrameTransformResult::Replace(payload.iter().rev().copied().collect())
+        }
+    }
+
+    fn info() -> sys_ft_ffi::TransformableFrameInfo {
+        sys_ft_ffi::TransformableFrameInfo {
+            kind: sys_ft_ffi::TransformableFrameKind::Video,
+            direction: sys_ft_ffi::TransformableFrameDirection::Sender,
+            ssrc: 1,
+            rtp_timestamp: 90000,
+            payload_type: 96,
+            mime_type: "video/VP8".to_owned(),
+            is_key_frame: true,
+            capture_time_us: -1,
+        }
+    }
+
+    #[test]
+    fn swapping_the_transformer_takes_effect_immediately() {
+        let slot: TransformerSlot = Arc::new(Mutex::new(None));
+        let adapter = TransformerAdapter { transformer: slot.clone() };
+
+        let output = adapter.transform(&info(), &[1, 2, 3]);
+        assert_eq!(output.action, sys_ft_ffi::FrameTransformAction::Forward);
+
+        *slot.lock() = Some(Arc::new(Reverse));
+        let output = adapter.transform(&info(), &[1, 2, 3]);
+        assert_eq!(output.action, sys_ft_ffi::FrameTransformAction::Replace);
+        assert_eq!(output.data, vec![3, 2, 1]);
+    }
+}
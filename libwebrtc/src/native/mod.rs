@@ -16,6 +16,8 @@
 pub mod android;
 pub mod apm;
 pub mod audio_mixer;
+pub mod audio_playout_buffer;
+pub mod audio_processor;
 pub mod audio_resampler;
 pub mod audio_source;
 pub mod audio_stream;
@@ -24,6 +26,7 @@ pub mod data_channel;
 #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
 pub mod desktop_capturer;
 pub mod frame_cryptor;
+pub mod frame_transformer;
 pub mod ice_candidate;
 pub mod media_stream;
 pub mod media_stream_track;
@@ -35,7 +38,9 @@ pub mod rtp_receiver;
 pub mod rtp_sender;
 pub mod rtp_transceiver;
 pub mod session_description;
+pub mod video_codec;
 pub mod video_frame;
+pub mod video_processor;
 pub mod video_source;
 pub mod video_stream;
 pub mod video_track;
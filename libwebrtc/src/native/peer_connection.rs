@@ -33,13 +33,15 @@ use crate::{
     media_stream::MediaStream,
     media_stream_track::MediaStreamTrack,
     peer_connection::{
-        AnswerOptions, IceCandidateError, IceConnectionState, IceGatheringState, OfferOptions,
-        OnConnectionChange, OnDataChannel, OnIceCandidate, OnIceCandidateError,
-        OnIceConnectionChange, OnIceGatheringChange, OnNegotiationNeeded, OnSignalingChange,
-        OnTrack, PeerConnectionState, SignalingState, TrackEvent,
+        AnswerOptions, CandidatePairChangeEvent, IceCandidateError, IceConnectionState,
+        IceGatheringState, OfferOptions, OnConnectionChange, OnDataChannel, OnIceCandidate,
+        OnIceCandidateError, OnIceConnectionChange, OnIceGatheringChange,
+        OnIceSelectedCandidatePairChanged, OnNegotiationNeeded, OnSignalingChange, OnTrack,
+        PeerConnectionState, SignalingState, TrackEvent,
     },
     peer_connection_factory::{
-        ContinualGatheringPolicy, IceServer, IceTransportsType, RtcConfiguration,
+        ContinualGatheringPolicy, IceServer, IceTransportsType, NetworkType, RtcConfiguration,
+        TcpCandidatePolicy,
     },
     rtp_receiver::RtpReceiver,
     rtp_sender::RtpSender,
@@ -160,12 +162,61 @@ impl From<IceTransportsType> for sys_pc::ffi::IceTransportsType {
     }
 }
 
+impl From<TcpCandidatePolicy> for sys_pc::ffi::TcpCandidatePolicy {
+    fn from(value: TcpCandidatePolicy) -> Self {
+        match value {
+            TcpCandidatePolicy::Enabled => sys_pc::ffi::TcpCandidatePolicy::Enabled,
+            TcpCandidatePolicy::Disabled => sys_pc::ffi::TcpCandidatePolicy::Disabled,
+        }
+    }
+}
+
+/// `rtc::AdapterType` bits matched by the port allocator's network ignore mask.
+fn adapter_type_mask(network_type: NetworkType) -> u32 {
+    const ETHERNET: u32 = 1 << 0;
+    const WIFI: u32 = 1 << 1;
+    const CELLULAR: u32 = 1 << 2;
+    const VPN: u32 = 1 << 3;
+    const LOOPBACK: u32 = 1 << 4;
+    // 2G, 3G, 4G and 5G are reported as distinct adapter types
+    const CELLULAR_GENERATIONS: u32 = (1 << 6) | (1 << 7) | (1 << 8) | (1 << 9);
+
+    match network_type {
+        NetworkType::Ethernet => ETHERNET,
+        NetworkType::Wifi => WIFI,
+        NetworkType::Cellular => CELLULAR | CELLULAR_GENERATIONS,
+        NetworkType::Vpn => VPN,
+        NetworkType::Loopback => LOOPBACK,
+    }
+}
+
 impl From<RtcConfiguration> for sys_pc::ffi::RtcConfiguration {
     fn from(value: RtcConfiguration) -> Self {
+        let policy = value.relay_transport_policy;
+        let ice_servers = value
+            .ice_servers
+            .into_iter()
+            .filter_map(|mut server| {
+                server.urls.retain(|url| policy.allows(url));
+                (!server.urls.is_empty()).then(|| server.into())
+            })
+            .collect();
+        let (min_port, max_port) =
+            value.port_range.map_or((0, 0), |range| (*range.start(), *range.end()));
+
         Self {
-            ice_servers: value.ice_servers.into_iter().map(Into::into).collect(),
+            ice_servers,
             continual_gathering_policy: value.continual_gathering_policy.into(),
             ice_transport_type: value.ice_transport_type.into(),
+            min_port,
+            max_port,
+            network_ignore_mask: value
+                .excluded_network_types
+                .into_iter()
+                .fold(adapter_type_mask(NetworkType::Loopback), |mask, network_type| {
+                    mask | adapter_type_mask(network_type)
+                }),
+            tcp_candidate_policy: value.tcp_candidate_policy.into(),
         }
     }
 }
@@ -482,6 +533,13 @@ impl PeerConnection {
         *self.observer.ice_gathering_change_handler.lock() = f;
     }
 
+    pub fn on_ice_selected_candidate_pair_changed(
+        &self,
+        f: Option<OnIceSelectedCandidatePairChanged>,
+    ) {
+        *self.observer.ice_selected_candidate_pair_handler.lock() = f;
+    }
+
     pub fn on_negotiation_needed(&self, f: Option<OnNegotiationNeeded>) {
         *self.observer.negotiation_needed_handler.lock() = f;
     }
@@ -503,6 +561,7 @@ pub struct PeerObserver {
     pub ice_candidate_error_handler: Mutex<Option<OnIceCandidateError>>,
     pub ice_connection_change_handler: Mutex<Option<OnIceConnectionChange>>,
     pub ice_gathering_change_handler: Mutex<Option<OnIceGatheringChange>>,
+    pub ice_selected_candidate_pair_handler: Mutex<Option<OnIceSelectedCandidatePairChanged>>,
     pub negotiation_needed_handler: Mutex<Option<OnNegotiationNeeded>>,
     pub signaling_change_handler: Mutex<Option<OnSignalingChange>>,
     pub track_handler: Mutex<Option<OnTrack>>,
@@ -582,8 +641,15 @@ impl sys_pcf::PeerConnectionObserver for PeerObserver {
 
     fn on_ice_selected_candidate_pair_changed(
         &self,
-        _event: sys_pcf::ffi::CandidatePairChangeEvent,
+        event: sys_pcf::ffi::CandidatePairChangeEvent,
     ) {
+        if let Some(f) = self.ice_selected_candidate_pair_handler.lock().as_mut() {
+            f(CandidatePairChangeEvent {
+                reason: event.reason,
+                last_data_received_ms: event.last_data_received_ms,
+                estimated_disconnected_time_ms: event.estimated_disconnected_time_ms,
+            });
+        }
     }
 
     fn on_add_track(
@@ -17,12 +17,21 @@ use std::sync::Arc;
 use cxx::{SharedPtr, UniquePtr};
 use lazy_static::lazy_static;
 use parking_lot::Mutex;
-use webrtc_sys::{peer_connection_factory as sys_pcf, rtc_error as sys_err, webrtc as sys_rtc};
+use webrtc_sys::{
+    peer_connection_factory as sys_pcf, rtc_error as sys_err, video_codec as sys_vc,
+    webrtc as sys_rtc,
+};
 
 use crate::{
     audio_source::native::NativeAudioSource,
     audio_track::RtcAudioTrack,
-    imp::{audio_track as imp_at, peer_connection as imp_pc, video_track as imp_vt},
+    imp::{
+        audio_track as imp_at, peer_connection as imp_pc,
+        video_codec::{
+            DecoderFactoryAdapter, EncoderFactoryAdapter, VideoDecoderFactory, VideoEncoderFactory,
+        },
+        video_track as imp_vt,
+    },
     peer_connection::PeerConnection,
     peer_connection_factory::RtcConfiguration,
     rtp_parameters::RtpCapabilities,
@@ -99,11 +108,9 @@ impl PeerConnectionFactory {
 
     pub fn create_audio_track(&self, label: &str, source: NativeAudioSource) -> RtcAudioTrack {
         RtcAudioTrack {
-            handle: imp_at::RtcAudioTrack {
-                sys_handle: self
-                    .sys_handle
-                    .create_audio_track(label.to_string(), source.handle.sys_handle()),
-            },
+            handle: imp_at::RtcAudioTrack::new(
+                self.sys_handle.create_audio_track(label.to_string(), source.handle.sys_handle()),
+            ),
         }
     }
 
@@ -113,9 +120,9 @@ impl PeerConnectionFactory {
     /// The track will capture audio from the selected recording device.
     pub fn create_device_audio_track(&self, label: &str) -> RtcAudioTrack {
         RtcAudioTrack {
-            handle: imp_at::RtcAudioTrack {
-                sys_handle: self.sys_handle.create_device_audio_track(label.to_string()),
-            },
+            handle: imp_at::RtcAudioTrack::new(
+                self.sys_handle.create_device_audio_track(label.to_string()),
+            ),
         }
     }
 
@@ -331,6 +338,22 @@ impl PeerConnectionFactory {
     pub fn is_platform_adm_active(&self) -> bool {
         self.sys_handle.audio_device().is_platform_adm_active()
     }
+
+    pub fn register_video_encoder_factory(&self, factory: Arc<dyn VideoEncoderFactory>) {
+        let adapter = Arc::new(EncoderFactoryAdapter(factory));
+        sys_vc::ffi::register_video_encoder_factory(
+            &self.sys_handle,
+            Box::new(sys_vc::VideoEncoderFactoryWrapper::new(adapter)),
+        );
+    }
+
+    pub fn register_video_decoder_factory(&self, factory: Arc<dyn VideoDecoderFactory>) {
+        let adapter = Arc::new(DecoderFactoryAdapter(factory));
+        sys_vc::ffi::register_video_decoder_factory(
+            &self.sys_handle,
+            Box::new(sys_vc::VideoDecoderFactoryWrapper::new(adapter)),
+        );
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,628 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Video encoders and decoders implemented in Rust.
+//!
+//! Register a [`VideoEncoderFactory`] or [`VideoDecoderFactory`] with
+//! [`PeerConnectionFactoryExt`](crate::peer_connection_factory::native::PeerConnectionFactoryExt)
+//! to make its formats available to every peer connection created afterwards. Registered
+//! factories are queried before the built-in ones, so a format they support replaces the
+//! built-in implementation. Pre-encoded tracks keep using the passthrough encoder.
+//!
+//! A panic in [`VideoEncoder::encode`] or [`VideoDecoder::decode`] is logged and reported to
+//! WebRTC as a codec error, unless the binary is built with `panic = "abort"`.
+
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    panic::{self, AssertUnwindSafe},
+    sync::Arc,
+};
+
+use cxx::UniquePtr;
+use thiserror::Error;
+use webrtc_sys::{video_codec as sys_vc, video_frame as vf_sys, video_track as vt_sys};
+
+use super::video_frame::new_video_frame_buffer;
+use crate::video_frame::{BoxVideoFrame, EncodedFrameType, EncodedVideoCodec, VideoFrame};
+
+#[derive(Debug, Error)]
+#[error("video codec error: {0}")]
+pub struct VideoCodecError(pub String);
+
+/// A codec name with its SDP `fmtp` parameters, e.g. `H264` with `profile-level-id`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SdpVideoFormat {
+    pub name: String,
+    pub parameters: BTreeMap<String, String>,
+}
+
+impl SdpVideoFormat {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), parameters: BTreeMap::new() }
+    }
+
+    pub fn with_parameter(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.parameters.insert(key.into(), value.into());
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VideoEncoderSettings {
+    pub codec: EncodedVideoCodec,
+    pub width: u32,
+    pub height: u32,
+    pub start_bitrate_bps: u32,
+    pub max_bitrate_bps: u32,
+    pub max_framerate: u32,
+    pub number_of_cores: i32,
+    /// Maximum size of an RTP payload, useful for encoders that produce slices.
+    pub max_payload_size: usize,
+}
+
+/// Target rates computed by WebRTC's bandwidth estimation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateControlParameters {
+    pub bitrate_bps: u32,
+    pub framerate_fps: f64,
+}
+
+/// One encoded access unit.
+///
+/// H.264 and H.265 payloads are Annex B, AV1 payloads are a temporal unit of OBUs.
+#[derive(Debug, Clone)]
+pub struct EncodedImage {
+    pub data: Vec<u8>,
+    pub frame_type: EncodedFrameType,
+    /// Encoded resolution, 0 to use the resolution of the input frame.
+    pub width: u32,
+    pub height: u32,
+    pub qp: Option<i32>,
+}
+
+#[derive(Debug, Clone)]
+pub struct VideoDecoderSettings {
+    pub codec: EncodedVideoCodec,
+    pub max_width: u32,
+    pub max_height: u32,
+    pub number_of_cores: i32,
+}
+
+/// One received access unit, in the same bitstream format as [`EncodedImage`].
+#[derive(Debug, Clone)]
+pub struct EncodedFrame<'a> {
+    pub data: &'a [u8],
+    pub frame_type: EncodedFrameType,
+    pub rtp_timestamp: u32,
+}
+
+pub trait VideoEncoder: Send {
+    fn implementation_name(&self) -> String;
+
+    fn init(&mut self, settings: &VideoEncoderSettings) -> Result<(), VideoCodecError>;
+
+    /// Returns `Ok(None)` when the frame is dropped, e.g. by rate control.
+    fn encode(
+        &mut self,
+        frame: &BoxVideoFrame,
+        keyframe_requested: bool,
+    ) -> Result<Option<EncodedImage>, VideoCodecError>;
+
+    fn set_rates(&mut self, rates: RateControlParameters);
+
+    fn release(&mut self) {}
+}
+
+pub trait VideoEncoderFactory: Send + Sync {
+    fn supported_formats(&self) -> Vec<SdpVideoFormat>;
+
+    /// Returns `None` to fall back to the built-in encoders.
+    fn create_encoder(&self, format: &SdpVideoFormat) -> Option<Box<dyn VideoEncoder>>;
+}
+
+pub trait VideoDecoder: Send {
+    fn implementation_name(&self) -> String;
+
+    fn configure(&mut self, settings: &VideoDecoderSettings) -> Result<(), VideoCodecError>;
+
+    /// Returns `Ok(None)` when the frame was consumed without producing a picture.
+    fn decode(
+        &mut self,
+        frame: &EncodedFrame<'_>,
+    ) -> Result<Option<BoxVideoFrame>, VideoCodecError>;
+
+    fn release(&mut self) {}
+}
+
+pub trait VideoDecoderFactory: Send + Sync {
+    fn supported_formats(&self) -> Vec<SdpVideoFormat>;
+
+    /// Returns `None` to fall back to the built-in decoders.
+    fn create_decoder(&self, format: &SdpVideoFormat) -> Option<Box<dyn VideoDecoder>>;
+}
+
+impl From<&SdpVideoFormat> for sys_vc::ffi::SdpVideoFormat {
+    fn from(value: &SdpVideoFormat) -> Self {
+        Self {
+            name: value.name.clone(),
+            parameters: value
+                .parameters
+                .iter()
+                .map(|(key, value)| sys_vc::ffi::SdpParameter {
+                    key: key.clone(),
+                    value: value.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<&sys_vc::ffi::SdpVideoFormat> for SdpVideoFormat {
+    fn from(value: &sys_vc::ffi::SdpVideoFormat) -> Self {
+        Self {
+            name: value.name.clone(),
+            parameters: value
+                .parameters
+                .iter()
+                .map(|param| (param.key.clone(), param.value.clone()))
+                .collect(),
+        }
+    }
+}
+
+/// Returns `None` for a codec the C++ side added that isn't known here.
+fn encoded_video_codec(value: vt_sys::ffi::EncodedVideoCodec) -> Option<EncodedVideoCodec> {
+    match value {
+        vt_sys::ffi::EncodedVideoCodec::H264 => Some(EncodedVideoCodec::H264),
+        vt_sys::ffi::EncodedVideoCodec::H265 => Some(EncodedVideoCodec::H265),
+        vt_sys::ffi::EncodedVideoCodec::VP8 => Some(EncodedVideoCodec::VP8),
+        vt_sys::ffi::EncodedVideoCodec::VP9 => Some(EncodedVideoCodec::VP9),
+        vt_sys::ffi::EncodedVideoCodec::AV1 => Some(EncodedVideoCodec::AV1),
+        _ => None,
+    }
+}
+
+impl From<vt_sys::ffi::EncodedFrameType> for EncodedFrameType {
+    fn from(value: vt_sys::ffi::EncodedFrameType) -> Self {
+        match value {
+            vt_sys::ffi::EncodedFrameType::Key => Self::Key,
+            _ => Self::Delta,
+        }
+    }
+}
+
+pub(crate) struct EncoderFactoryAdapter(pub Arc<dyn VideoEncoderFactory>);
+
+impl sys_vc::VideoEncoderFactory for EncoderFactoryAdapter {
+    fn supported_formats(&self) -> Vec<sys_vc::ffi::SdpVideoFormat> {
+        self.0.supported_formats().iter().map(Into::into).collect()
+    }
+
+    fn create_encoder(
+        &self,
+        format: &sys_vc::ffi::SdpVideoFormat,
+    ) -> Option<Box<dyn sys_vc::VideoEncoder>> {
+        let encoder = self.0.create_encoder(&format.into())?;
+        Some(Box::new(EncoderAdapter { encoder }))
+    }
+}
+
+struct EncoderAdapter {
+    encoder: Box<dyn VideoEncoder>,
+}
+
+impl sys_vc::VideoEncoder for EncoderAdapter {
+    fn implementation_name(&self) -> String {
+        self.encoder.implementation_name()
+    }
+
+    fn init_encode(&mut self, settings: &sys_vc::ffi::VideoEncoderSettings) -> bool {
+        let Some(codec) = encoded_video_codec(settings.codec) else {
+            log::error!(
+                "{}: unsupported codec {:?}",
+                self.encoder.implementation_name(),
+                settings.codec
+            );
+            return false;
+        };
+        let settings = VideoEncoderSettings {
+            codec,
+            width: settings.width,
+            height: settings.height,
+            start_bitrate_bps: settings.start_bitrate_bps,
+            max_bitrate_bps: settings.max_bitrate_bps,
+            max_framerate: settings.max_framerate,
+            number_of_cores: settings.number_of_cores,
+            max_payload_size: settings.max_payload_size as usize,
+        };
+        match self.encoder.init(&settings) {
+            Ok(()) => true,
+            Err(err) => {
+                log::error!("failed to initialize {}: {}", self.encoder.implementation_name(), err);
+                false
+            }
+        }
+    }
+
+    fn encode(
+        &mut self,
+        frame: &vf_sys::ffi::VideoFrame,
+        keyframe_requested: bool,
+    ) -> sys_vc::ffi::EncodedImageData {
+        let frame: BoxVideoFrame = VideoFrame {
+            rotation: frame.rotation().into(),
+            timestamp_us: frame.timestamp_us(),
+            frame_metadata: None,
+            buffer: new_video_frame_buffer(unsafe { frame.video_frame_buffer() }),
+        };
+
+        // A panic must not unwind into the WebRTC encoder thread
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            self.encoder.encode(&frame, keyframe_requested)
+        }));
+        let (status, image) = match result {
+            Ok(Ok(Some(image))) => (sys_vc::ffi::VideoCodecStatus::Ok, Some(image)),
+            Ok(Ok(None)) => (sys_vc::ffi::VideoCodecStatus::NoOutput, None),
+            Ok(Err(err)) => {
+                log::error!("{} failed to encode: {}", self.encoder.implementation_name(), err);
+                (sys_vc::ffi::VideoCodecStatus::Error, None)
+            }
+            Err(payload) => {
+                log::error!(
+                    "{} panicked while encoding: {}",
+                    self.encoder.implementation_name(),
+                    panic_message(payload.as_ref())
+                );
+                (sys_vc::ffi::VideoCodecStatus::Error, None)
+            }
+        };
+        let image = image.unwrap_or(EncodedImage {
+            data: Vec::new(),
+            frame_type: EncodedFrameType::Delta,
+            width: 0,
+            height: 0,
+            qp: None,
+        });
+
+        sys_vc::ffi::EncodedImageData {
+            status,
+            frame_type: image.frame_type.into(),
+            width: image.width,
+            height: image.height,
+            qp: image.qp.unwrap_or(-1),
+            data: image.data,
+        }
+    }
+
+    fn set_rates(&mut self, bitrate_bps: u32, framerate_fps: f64) {
+        self.encoder.set_rates(RateControlParameters { bitrate_bps, framerate_fps });
+    }
+
+    fn release(&mut self) {
+        self.encoder.release();
+    }
+}
+
+pub(crate) struct DecoderFactoryAdapter(pub Arc<dyn VideoDecoderFactory>);
+
+impl sys_vc::VideoDecoderFactory for DecoderFactoryAdapter {
+    fn supported_formats(&self) -> Vec<sys_vc::ffi::SdpVideoFormat> {
+        self.0.supported_formats().iter().map(Into::into).collect()
+    }
+
+    fn create_decoder(
+        &self,
+        format: &sys_vc::ffi::SdpVideoFormat,
+    ) -> Option<Box<dyn sys_vc::VideoDecoder>> {
+        let decoder = self.0.create_decoder(&format.into())?;
+        Some(Box::new(DecoderAdapter { decoder }))
+    }
+}
+
+struct DecoderAdapter {
+    decoder: Box<dyn VideoDecoder>,
+}
+
+impl sys_vc::VideoDecoder for DecoderAdapter {
+    fn implementation_name(&self) -> String {
+        self.decoder.implementation_name()
+    }
+
+    fn configure(&mut self, settings: &sys_vc::ffi::VideoDecoderSettings) -> bool {
+        let Some(codec) = encoded_video_codec(settings.codec) else {
+            log::error!(
+                "{}: unsupported codec {:?}",
+                self.decoder.implementation_name(),
+                settings.codec
+            );
+            return false;
+        };
+        let settings = VideoDecoderSettings {
+            codec,
+            max_width: settings.max_width,
+            max_height: settings.max_height,
+            number_of_cores: settings.number_of_cores,
+        };
+        match self.decoder.configure(&settings) {
+            Ok(()) => true,
+            Err(err) => {
+                log::error!("failed to configure {}: {}", self.decoder.implementation_name(), err);
+                false
+            }
+        }
+    }
+
+    fn decode(
+        &mut self,
+        data: &[u8],
+        frame_type: vt_sys::ffi::EncodedFrameType,
+        rtp_timestamp: u32,
+    ) -> sys_vc::ffi::DecodedImageData {
+        let frame = EncodedFrame { data, frame_type: frame_type.into(), rtp_timestamp };
+        // A panic must not unwind into the WebRTC decoder thread
+        match panic::catch_unwind(AssertUnwindSafe(|| self.decoder.decode(&frame))) {
+            Ok(Ok(Some(frame))) => {
+                let mut builder = vf_sys::ffi::new_video_frame_builder();
+                builder.pin_mut().set_rotation(frame.rotation.into());
+                builder.pin_mut().set_video_frame_buffer(frame.buffer.as_ref().sys_handle());
+                builder.pin_mut().set_timestamp_us(frame.timestamp_us);
+                sys_vc::ffi::DecodedImageData {
+                    status: sys_vc::ffi::VideoCodecStatus::Ok,
+                    frame: builder.pin_mut().build(),
+                }
+            }
+            Ok(Ok(None)) => sys_vc::ffi::DecodedImageData {
+                status: sys_vc::ffi::VideoCodecStatus::NoOutput,
+                frame: UniquePtr::null(),
+            },
+            Ok(Err(err)) => {
+                log::error!("{} failed to decode: {}", self.decoder.implementation_name(), err);
+                sys_vc::ffi::DecodedImageData {
+                    status: sys_vc::ffi::VideoCodecStatus::Error,
+                    frame: UniquePtr::null(),
+                }
+            }
+            Err(payload) => {
+                log::error!(
+                    "{} panicked while decoding: {}",
+                    self.decoder.implementation_name(),
+                    panic_message(payload.as_ref())
+                );
+                sys_vc::ffi::DecodedImageData {
+                    status: sys_vc::ffi::VideoCodecStatus::Error,
+                    frame: UniquePtr::null(),
+                }
+            }
+        }
+    }
+
+    fn release(&mut self) {
+        self.decoder.release();
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown panic"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use sys_vc::VideoDecoderFactory as _;
+
+    use super::*;
+    use crate::video_frame::{BoxVideoBuffer, I420Buffer, VideoRotation};
+
+    /// Encodes each frame as its index and resolution, decoded back to a black frame.
+    #[derive(Default)]
+    struct CountingCodec {
+        encoded: Arc<AtomicU32>,
+        decoded: Arc<AtomicU32>,
+    }
+
+    struct CountingEncoder(Arc<AtomicU32>);
+
+    impl VideoEncoder for CountingEncoder {
+        fn implementation_name(&self) -> String {
+            "counting".to_owned()
+        }
+
+        fn init(&mut self, settings: &VideoEncoderSettings) -> Result<(), VideoCodecError> {
+            assert_eq!(settings.codec, EncodedVideoCodec::VP8);
+            Ok(())
+        }
+
+        fn encode(
+            &mut self,
+            frame: &BoxVideoFrame,
+            keyframe_requested: bool,
+        ) -> Result<Option<EncodedImage>, VideoCodecError> {
+            let index = self.0.fetch_add(1, Ordering::SeqCst);
+            let (width, height) = (frame.buffer.width(), frame.buffer.height());
+            let data = [index, width, height].iter().flat_map(|v| v.to_le_bytes()).collect();
+            let frame_type =
+                if keyframe_requested { EncodedFrameType::Key } else { EncodedFrameType::Delta };
+            Ok(Some(EncodedImage { data, frame_type, width, height, qp: None }))
+        }
+
+        fn set_rates(&mut self, _rates: RateControlParameters) {}
+    }
+
+    struct CountingDecoder(Arc<AtomicU32>);
+
+    impl VideoDecoder for CountingDecoder {
+        fn implementation_name(&self) -> String {
+            "counting".to_owned()
+        }
+
+        fn configure(&mut self, _settings: &VideoDecoderSettings) -> Result<(), VideoCodecError> {
+            Ok(())
+        }
+
+        fn decode(
+            &mut self,
+            frame: &EncodedFrame<'_>,
+        ) -> Result<Option<BoxVideoFrame>, VideoCodecError> {
+            let field = |i: usize| {
+                frame
+                    .data
+                    .get(i * 4..i * 4 + 4)
+                    .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+                    .ok_or_else(|| VideoCodecError("truncated frame".to_owned()))
+            };
+            let index = field(0)?;
+            assert_eq!(index, self.0.fetch_add(1, Ordering::SeqCst));
+            Ok(Some(VideoFrame {
+                rotation: VideoRotation::VideoRotation0,
+                timestamp_us: i64::from(index),
+                frame_metadata: None,
+                buffer: Box::new(I420Buffer::new_black(field(1)?, field(2)?)),
+            }))
+        }
+    }
+
+    impl VideoEncoderFactory for CountingCodec {
+        fn supported_formats(&self) -> Vec<SdpVideoFormat> {
+            vec![SdpVideoFormat::new("VP8")]
+        }
+
+        fn create_encoder(&self, _format: &SdpVideoFormat) -> Option<Box<dyn VideoEncoder>> {
+            Some(Box::new(CountingEncoder(self.encoded.clone())))
+        }
+    }
+
+    impl VideoDecoderFactory for CountingCodec {
+        fn supported_formats(&self) -> Vec<SdpVideoFormat> {
+            vec![SdpVideoFormat::new("VP8")]
+        }
+
+        fn create_decoder(&self, _format: &SdpVideoFormat) -> Option<Box<dyn VideoDecoder>> {
+            Some(Box::new(CountingDecoder(self.decoded.clone())))
+        }
+    }
+
+    #[test]
+    fn frames_round_trip_through_the_adapters() {
+        let codec = Arc::new(CountingCodec::default());
+        let format = sys_vc::ffi::SdpVideoFormat::from(&SdpVideoFormat::new("VP8"));
+        let mut encoder = sys_vc::VideoEncoderFactory::create_encoder(
+            &EncoderFactoryAdapter(codec.clone()),
+            &format,
+        )
+        .unwrap();
+        let mut decoder = DecoderFactoryAdapter(codec.clone()).create_decoder(&format).unwrap();
+
+        assert!(encoder.init_encode(&sys_vc::ffi::VideoEncoderSettings {
+            codec: vt_sys::ffi::EncodedVideoCodec::VP8,
+            width: 320,
+            height: 240,
+            start_bitrate_bps: 300_000,
+            max_bitrate_bps: 1_000_000,
+            max_framerate: 30,
+            number_of_cores: 1,
+            max_payload_size: 1200,
+        }));
+        assert!(decoder.configure(&sys_vc::ffi::VideoDecoderSettings {
+            codec: vt_sys::ffi::EncodedVideoCodec::VP8,
+            max_width: 320,
+            max_height: 240,
+            number_of_cores: 1,
+        }));
+
+        let buffer: BoxVideoBuffer = Box::new(I420Buffer::new_black(320, 240));
+        for index in 0..5 {
+            let mut builder = vf_sys::ffi::new_video_frame_builder();
+            builder.pin_mut().set_video_frame_buffer(buffer.as_ref().sys_handle());
+            let frame = builder.pin_mut().build();
+
+            let image = encoder.encode(&frame, index == 0);
+            assert_eq!(image.status, sys_vc::ffi::VideoCodecStatus::Ok);
+            let frame_type = if index == 0 {
+                vt_sys::ffi::EncodedFrameType::Key
+            } else {
+                vt_sys::ffi::EncodedFrameType::Delta
+            };
+            assert_eq!(image.frame_type, frame_type);
+
+            let decoded = decoder.decode(&image.data, image.frame_type, 90 * index);
+            assert_eq!(decoded.status, sys_vc::ffi::VideoCodecStatus::Ok);
+            assert_eq!((decoded.frame.width(), decoded.frame.height()), (320, 240));
+        }
+        assert_eq!(codec.encoded.load(Ordering::SeqCst), 5);
+        assert_eq!(codec.decoded.load(Ordering::SeqCst), 5);
+
+        // A truncated bitstream is reported as a decoding error
+        let decoded = decoder.decode(&[0, 1], vt_sys::ffi::EncodedFrameType::Delta, 0);
+        assert_eq!(decoded.status, sys_vc::ffi::VideoCodecStatus::Error);
+        assert!(decoded.frame.is_null());
+    }
+
+    struct PanickingEncoder;
+
+    impl VideoEncoder for PanickingEncoder {
+        fn implementation_name(&self) -> String {
+            "panicking".to_owned()
+        }
+
+        fn init(&mut self, _settings: &VideoEncoderSettings) -> Result<(), VideoCodecError> {
+            Ok(())
+        }
+
+        fn encode(
+            &mut self,
+            _frame: &BoxVideoFrame,
+            _keyframe_requested: bool,
+        ) -> Result<Option<EncodedImage>, VideoCodecError> {
+            panic!("encoder asked to panic")
+        }
+
+        fn set_rates(&mut self, _rates: RateControlParameters) {}
+    }
+
+    #[test]
+    fn encoder_panics_are_reported_as_errors() {
+        let mut encoder = EncoderAdapter { encoder: Box::new(PanickingEncoder) };
+        let buffer: BoxVideoBuffer = Box::new(I420Buffer::new_black(320, 240));
+        let mut builder = vf_sys::ffi::new_video_frame_builder();
+        builder.pin_mut().set_video_frame_buffer(buffer.as_ref().sys_handle());
+        let frame = builder.pin_mut().build();
+
+        // The encoder keeps reporting errors instead of aborting the process
+        for _ in 0..2 {
+            let image = sys_vc::VideoEncoder::encode(&mut encoder, &frame, true);
+            assert_eq!(image.status, sys_vc::ffi::VideoCodecStatus::Error);
+            assert!(image.data.is_empty());
+        }
+    }
+
+    #[test]
+    fn sdp_format_round_trips_through_sys() {
+        let format = SdpVideoFormat::new("H264")
+            .with_parameter("profile-level-id", "42e01f")
+            .with_parameter("packetization-mode", "1");
+
+        let sys_format = sys_vc::ffi::SdpVideoFormat::from(&format);
+        assert_eq!(sys_format.name, "H264");
+        assert_eq!(sys_format.parameters[0].key, "packetization-mode");
+        assert_eq!(SdpVideoFormat::from(&sys_format), format);
+    }
+}
@@ -239,6 +239,13 @@ impl I420Buffer {
         unsafe { &*recursive_cast!(&*self.sys_handle, i420_to_yuv8, yuv8_to_yuv, yuv_to_vfb) }
     }
 
+    /// A buffer with its own copy of the pixels.
+    pub(crate) fn copy(&self) -> vf::I420Buffer {
+        vf::I420Buffer {
+            handle: I420Buffer { sys_handle: vfb_sys::ffi::copy_i420_buffer(&self.sys_handle) },
+        }
+    }
+
     pub fn width(&self) -> u32 {
         unsafe {
             let ptr = recursive_cast!(&*self.sys_handle, i420_to_yuv8, yuv8_to_yuv, yuv_to_vfb);
@@ -0,0 +1,375 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Pre-encode video processors.
+//!
+//! A [`VideoProcessor`] installed on a
+//! [`NativeVideoSource`](crate::video_source::native::NativeVideoSource) sees every raw frame
+//! passed to `capture_frame` before it reaches the encoders, and may modify, replace or drop it.
+//! Pre-encoded and DMA-buffer captures bypass the processor.
+//!
+//! Processing runs synchronously on the thread calling `capture_frame`. Each frame gets a time
+//! budget: when the processor overruns it, the overrun is carried over and the next frames are
+//! dropped without being processed until it has been paid back, so the average cost per captured
+//! frame stays within the budget. Frames are dropped rather than forwarded unprocessed so that a
+//! masking processor never leaks the content it is meant to hide.
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use parking_lot::Mutex;
+
+use crate::video_frame::{I420Buffer, VideoBuffer, VideoFrame};
+
+/// Inspects or modifies raw frames before they are encoded.
+///
+/// The processor owns the frame it is given and may write to its buffer in place, the caller's
+/// buffer is never modified. Captured I420 buffers are copied for this, which costs one frame
+/// copy per captured frame while a processor is installed. Other formats are converted to a new
+/// I420 buffer anyway, and no copy is made when no processor is installed.
+pub trait VideoProcessor: Send {
+    /// Returns the frame to encode, or `None` to drop it.
+    fn process(&mut self, frame: VideoFrame<I420Buffer>) -> Option<VideoFrame<I420Buffer>>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VideoProcessorOptions {
+    /// Time the processor may spend on a single frame, `Duration::ZERO` disables the budget.
+    pub budget: Duration,
+}
+
+impl Default for VideoProcessorOptions {
+    fn default() -> Self {
+        // One frame interval at 30 fps
+        Self { budget: Duration::from_millis(33) }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VideoProcessorStats {
+    /// Frames handed to the processor.
+    pub frames_processed: u64,
+    /// Frames the processor returned `None` for.
+    pub frames_dropped_by_processor: u64,
+    /// Frames dropped without being processed to pay back budget overruns.
+    pub frames_dropped_over_budget: u64,
+    /// Processed frames that took longer than the budget.
+    pub frames_over_budget: u64,
+    pub last_processing_time: Duration,
+    pub max_processing_time: Duration,
+}
+
+/// Tracks the time a processor spent over its budget.
+#[derive(Debug)]
+struct ProcessingBudget {
+    budget: Duration,
+    overrun: Duration,
+}
+
+impl ProcessingBudget {
+    fn new(budget: Duration) -> Self {
+        Self { budget, overrun: Duration::ZERO }
+    }
+
+    /// Whether the next frame may be processed, a refused frame pays back one budget.
+    fn admit(&mut self) -> bool {
+        if self.overrun.is_zero() {
+            return true;
+        }
+        self.overrun = self.overrun.saturating_sub(self.budget);
+        false
+    }
+
+    /// Records the time spent on a frame, returns whether it went over budget.
+    fn record(&mut self, elapsed: Duration) -> bool {
+        if self.budget.is_zero() || elapsed <= self.budget {
+            return false;
+        }
+        self.overrun += elapsed - self.budget;
+        true
+    }
+}
+
+/// A [`VideoProcessor`] with its budget and stats, as installed on a source.
+pub(crate) struct VideoProcessorRunner {
+    processor: Box<dyn VideoProcessor>,
+    budget: ProcessingBudget,
+    stats: VideoProcessorStats,
+}
+
+impl VideoProcessorRunner {
+    pub(crate) fn new(processor: Box<dyn VideoProcessor>, options: VideoProcessorOptions) -> Self {
+        Self {
+            processor,
+            budget: ProcessingBudget::new(options.budget),
+            stats: VideoProcessorStats::default(),
+        }
+    }
+
+    pub(crate) fn stats(&self) -> VideoProcessorStats {
+        self.stats.clone()
+    }
+
+    pub(crate) fn run(&mut self, frame: VideoFrame<I420Buffer>) -> Option<VideoFrame<I420Buffer>> {
+        if !self.budget.admit() {
+            self.stats.frames_dropped_over_budget += 1;
+            return None;
+        }
+
+        let start = Instant::now();
+        let frame = self.processor.process(frame);
+        let elapsed = start.elapsed();
+
+        self.stats.frames_processed += 1;
+        self.stats.last_processing_time = elapsed;
+        self.stats.max_processing_time = self.stats.max_processing_time.max(elapsed);
+        if self.budget.record(elapsed) {
+            self.stats.frames_over_budget += 1;
+            log::debug!("video processor went over budget: {:?}", elapsed);
+        }
+        if frame.is_none() {
+            self.stats.frames_dropped_by_processor += 1;
+        }
+        frame
+    }
+}
+
+/// A region of the frame, in coordinates normalized to `0.0..=1.0`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MaskRegion {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaskStyle {
+    /// Fill the region with a solid color.
+    Fill { y: u8, u: u8, v: u8 },
+    /// Replace the region with blocks of its average color, `block_size` luma pixels wide.
+    Pixelate { block_size: u32 },
+}
+
+impl Default for MaskStyle {
+    fn default() -> Self {
+        Self::Fill { y: 16, u: 128, v: 128 }
+    }
+}
+
+/// CPU-only reference processor hiding regions of the frame.
+///
+/// Clones share their regions, keep one to update them while the processor is installed.
+#[derive(Debug, Clone, Default)]
+pub struct PrivacyMaskProcessor {
+    regions: Arc<Mutex<Vec<MaskRegion>>>,
+    style: MaskStyle,
+}
+
+impl PrivacyMaskProcessor {
+    pub fn new(regions: Vec<MaskRegion>, style: MaskStyle) -> Self {
+        Self { regions: Arc::new(Mutex::new(regions)), style }
+    }
+
+    pub fn set_regions(&self, regions: Vec<MaskRegion>) {
+        *self.regions.lock() = regions;
+    }
+
+    pub fn regions(&self) -> Vec<MaskRegion> {
+        self.regions.lock().clone()
+    }
+}
+
+impl VideoProcessor for PrivacyMaskProcessor {
+    fn process(&mut self, mut frame: VideoFrame<I420Buffer>) -> Option<VideoFrame<I420Buffer>> {
+        let regions = self.regions.lock().clone();
+        if regions.is_empty() {
+            return Some(frame);
+        }
+
+        let buffer = &mut frame.buffer;
+        let (width, height) = (buffer.width(), buffer.height());
+        let (stride_y, stride_u, stride_v) = buffer.strides();
+        let (data_y, data_u, data_v) = buffer.data_mut();
+
+        for region in regions {
+            let Some(luma) = PixelRect::from_region(&region, width, height) else {
+                continue;
+            };
+            let chroma = luma.half();
+            match self.style {
+                MaskStyle::Fill { y, u, v } => {
+                    fill_rect(data_y, stride_y, &luma, y);
+                    fill_rect(data_u, stride_u, &chroma, u);
+                    fill_rect(data_v, stride_v, &chroma, v);
+                }
+                MaskStyle::Pixelate { block_size } => {
+                    let block_size = block_size.max(2);
+                    pixelate_rect(data_y, stride_y, &luma, block_size);
+                    pixelate_rect(data_u, stride_u, &chroma, block_size / 2);
+                    pixelate_rect(data_v, stride_v, &chroma, block_size / 2);
+                }
+            }
+        }
+
+        Some(frame)
+    }
+}
+
+/// A rectangle in pixels, `x` and `y` inclusive, `right` and `bottom` exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PixelRect {
+    x: u32,
+    y: u32,
+    right: u32,
+    bottom: u32,
+}
+
+impl PixelRect {
+    /// Maps a normalized region onto a `width`x`height` luma plane, growing it to even bounds
+    /// so it covers whole chroma samples. Returns `None` when it is outside of the frame.
+    fn from_region(region: &MaskRegion, width: u32, height: u32) -> Option<Self> {
+        let to_px = |v: f32, size: u32| (v.clamp(0.0, 1.0) * size as f32) as u32;
+        let to_px_ceil = |v: f32, size: u32| (v.clamp(0.0, 1.0) * size as f32).ceil() as u32;
+
+        let x = to_px(region.x, width) & !1;
+        let y = to_px(region.y, height) & !1;
+        let right = to_px_ceil(region.x + region.width, width).next_multiple_of(2).min(width);
+        let bottom = to_px_ceil(region.y + region.height, height).next_multiple_of(2).min(height);
+        (x < right && y < bottom).then_some(Self { x, y, right, bottom })
+    }
+
+    /// The same rectangle on a 2x2 subsampled plane.
+    fn half(&self) -> Self {
+        Self {
+            x: self.x / 2,
+            y: self.y / 2,
+            right: self.right.div_ceil(2),
+            bottom: self.bottom.div_ceil(2),
+        }
+    }
+}
+
+fn fill_rect(plane: &mut [u8], stride: u32, rect: &PixelRect, value: u8) {
+    for row in rect.y..rect.bottom {
+        let start = (row * stride) as usize;
+        plane[start + rect.x as usize..start + rect.right as usize].fill(value);
+    }
+}
+
+fn pixelate_rect(plane: &mut [u8], stride: u32, rect: &PixelRect, block_size: u32) {
+    let block_size = block_size.max(1);
+    for block_y in (rect.y..rect.bottom).step_by(block_size as usize) {
+        for block_x in (rect.x..rect.right).step_by(block_size as usize) {
+            let block = PixelRect {
+                x: block_x,
+                y: block_y,
+                right: (block_x + block_size).min(rect.right),
+                bottom: (block_y + block_size).min(rect.bottom),
+            };
+
+            let mut sum = 0u32;
+            for row in block.y..block.bottom {
+                let start = (row * stride) as usize;
+                sum += plane[start + block.x as usize..start + block.right as usize]
+                    .iter()
+                    .map(|v| *v as u32)
+                    .sum::<u32>();
+            }
+            let count = (block.right - block.x) * (block.bottom - block.y);
+            fill_rect(plane, stride, &block, ((sum + count / 2) / count) as u8);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget_drops_frames_to_pay_back_overruns() {
+        let mut budget = ProcessingBudget::new(Duration::from_millis(10));
+        assert!(budget.admit());
+        assert!(!budget.record(Duration::from_millis(10)));
+        assert!(budget.admit());
+
+        // 15ms over a 10ms budget, paid back by two dropped frames
+        assert!(budget.record(Duration::from_millis(25)));
+        assert!(!budget.admit());
+        assert!(!budget.admit());
+        assert!(budget.admit());
+    }
+
+    #[test]
+    fn zero_budget_never_drops() {
+        let mut budget = ProcessingBudget::new(Duration::ZERO);
+        assert!(!budget.record(Duration::from_secs(1)));
+        assert!(budget.admit());
+    }
+
+    #[test]
+    fn region_is_aligned_to_chroma_samples() {
+        let region = MaskRegion { x: 0.1, y: 0.25, width: 0.25, height: 0.5 };
+        let rect = PixelRect::from_region(&region, 30, 20).unwrap();
+        assert_eq!(rect, PixelRect { x: 2, y: 4, right: 12, bottom: 16 });
+        assert_eq!(rect.half(), PixelRect { x: 1, y: 2, right: 6, bottom: 8 });
+
+        let clamped = MaskRegion { x: 0.9, y: -1.0, width: 1.0, height: 3.0 };
+        assert_eq!(
+            PixelRect::from_region(&clamped, 31, 20),
+            Some(PixelRect { x: 26, y: 0, right: 31, bottom: 20 })
+        );
+
+        let outside = MaskRegion { x: 1.0, y: 0.0, width: 0.5, height: 1.0 };
+        assert_eq!(PixelRect::from_region(&outside, 30, 20), None);
+    }
+
+    #[test]
+    fn fill_only_touches_the_rect() {
+        let stride = 6;
+        let mut plane = vec![1u8; 6 * 4];
+        fill_rect(&mut plane, stride, &PixelRect { x: 2, y: 1, right: 4, bottom: 3 }, 9);
+
+        #[rustfmt::skip]
+        let expected = vec![
+            1, 1, 1, 1, 1, 1,
+            1, 1, 9, 9, 1, 1,
+            1, 1, 9, 9, 1, 1,
+            1, 1, 1, 1, 1, 1,
+        ];
+        assert_eq!(plane, expected);
+    }
+
+    #[test]
+    fn pixelate_averages_blocks() {
+        let stride = 5;
+        #[rustfmt::skip]
+        let mut plane = vec![
+            0, 2, 10, 20, 7,
+            4, 6, 30, 40, 7,
+            1, 2, 50, 60, 7,
+        ];
+        pixelate_rect(&mut plane, stride, &PixelRect { x: 0, y: 0, right: 4, bottom: 3 }, 2);
+
+        #[rustfmt::skip]
+        let expected = vec![
+            3, 3, 25, 25, 7,
+            3, 3, 25, 25, 7,
+            2, 2, 55, 55, 7,
+        ];
+        assert_eq!(plane, expected);
+    }
+}
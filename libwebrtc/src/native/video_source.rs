@@ -22,13 +22,19 @@ use std::{
 
 use cxx::SharedPtr;
 use livekit_runtime::interval;
+use parking_lot::Mutex;
 use webrtc_sys::{video_frame as vf_sys, video_frame::ffi::VideoRotation, video_track as vt_sys};
 
 #[cfg(target_os = "linux")]
 use crate::video_frame::FrameMetadata;
 use crate::{
-    native::packet_trailer::PacketTrailerHandler,
-    video_frame::{EncodedVideoFrame, I420Buffer, VideoBuffer, VideoFrame},
+    native::{
+        packet_trailer::PacketTrailerHandler,
+        video_processor::{
+            VideoProcessor, VideoProcessorOptions, VideoProcessorRunner, VideoProcessorStats,
+        },
+    },
+    video_frame::{EncodedVideoFrame, I420Buffer, VideoBuffer, VideoBufferType, VideoFrame},
     video_source::{EncodedRateControl, VideoResolution},
 };
 
@@ -48,6 +54,7 @@ impl From<VideoResolution> for vt_sys::ffi::VideoResolution {
 pub struct NativeVideoSource {
     sys_handle: SharedPtr<vt_sys::ffi::VideoTrackSource>,
     captured_frames: Arc<AtomicUsize>,
+    processor: Arc<Mutex<Option<Arc<Mutex<VideoProcessorRunner>>>>>,
 }
 
 impl NativeVideoSource {
@@ -76,6 +83,7 @@ impl NativeVideoSource {
                 is_screencast,
             ),
             captured_frames: Arc::new(AtomicUsize::new(0)),
+            processor: Default::default(),
         };
 
         if raw_keepalive {
@@ -125,6 +133,36 @@ impl NativeVideoSource {
     }
 
     pub fn capture_frame<T: AsRef<dyn VideoBuffer>>(&self, frame: &VideoFrame<T>) {
+        // Dropped frames count as captured too, they must not bring back the keepalive
+        self.captured_frames.fetch_add(1, Ordering::Relaxed);
+
+        // The processor runs outside of the source lock, so replacing it doesn't wait for the
+        // frame being processed
+        let processor = self.processor.lock().clone();
+        let processed = processor.map(|processor| {
+            // `to_i420` shares the pixels of an I420 buffer, which the processor must not write
+            // to. Other formats are converted into a new buffer already.
+            let captured = frame.buffer.as_ref();
+            let buffer = match captured.buffer_type() {
+                VideoBufferType::I420 => captured.to_i420().handle.copy(),
+                _ => captured.to_i420(),
+            };
+            processor.lock().run(VideoFrame {
+                rotation: frame.rotation,
+                timestamp_us: frame.timestamp_us,
+                frame_metadata: frame.frame_metadata.clone(),
+                buffer,
+            })
+        });
+
+        match processed {
+            Some(Some(frame)) => self.on_captured_frame(&frame),
+            Some(None) => {}
+            None => self.on_captured_frame(frame),
+        }
+    }
+
+    fn on_captured_frame<T: AsRef<dyn VideoBuffer>>(&self, frame: &VideoFrame<T>) {
         let mut builder = vf_sys::ffi::new_video_frame_builder();
         builder.pin_mut().set_rotation(frame.rotation.into());
         builder.pin_mut().set_video_frame_buffer(frame.buffer.as_ref().sys_handle());
@@ -147,8 +185,6 @@ impl NativeVideoSource {
             None => (false, 0, 0, Vec::new()),
         };
 
-        self.captured_frames.fetch_add(1, Ordering::Relaxed);
-
         self.sys_handle.on_captured_frame(
             &builder.pin_mut().build(),
             &vt_sys::ffi::FrameMetadata {
@@ -286,7 +322,86 @@ impl NativeVideoSource {
         self.sys_handle.set_packet_trailer_handler(handler.sys_handle());
     }
 
+    /// Install a processor running on every frame passed to `capture_frame`, `None` removes it.
+    pub fn set_processor(
+        &self,
+        processor: Option<Box<dyn VideoProcessor>>,
+        options: VideoProcessorOptions,
+    ) {
+        *self.processor.lock() = processor
+            .map(|processor| Arc::new(Mutex::new(VideoProcessorRunner::new(processor, options))));
+    }
+
+    /// Stats of the installed processor, reset when it is replaced.
+    pub fn processor_stats(&self) -> Option<VideoProcessorStats> {
+        let processor = self.processor.lock().clone();
+        processor.map(|processor| processor.lock().stats())
+    }
+
     pub fn video_resolution(&self) -> VideoResolution {
         self.sys_handle.video_resolution().into()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::video_frame::{NV12Buffer, VideoRotation};
+
+    /// Paints the luma plane white, and removes itself from `source` if set.
+    struct Paint {
+        source: Option<NativeVideoSource>,
+    }
+
+    impl VideoProcessor for Paint {
+        fn process(&mut self, mut frame: VideoFrame<I420Buffer>) -> Option<VideoFrame<I420Buffer>> {
+            frame.buffer.data_mut().0.fill(235);
+            if let Some(source) = self.source.take() {
+                source.set_processor(None, VideoProcessorOptions::default());
+            }
+            Some(frame)
+        }
+    }
+
+    fn capture(source: &NativeVideoSource, buffer: impl AsRef<dyn VideoBuffer>) {
+        source.capture_frame(&VideoFrame {
+            rotation: VideoRotation::VideoRotation0,
+            timestamp_us: 1,
+            frame_metadata: None,
+            buffer,
+        });
+    }
+
+    #[test]
+    fn processor_does_not_write_to_the_captured_buffer() {
+        let source = NativeVideoSource::new_encoded(VideoResolution { width: 16, height: 16 });
+        source.set_processor(Some(Box::new(Paint { source: None })), Default::default());
+
+        let buffer = I420Buffer::new_black(16, 16);
+        capture(&source, &buffer);
+        assert!(buffer.data().0.iter().all(|y| *y == 0));
+        assert_eq!(source.processor_stats().unwrap().frames_processed, 1);
+    }
+
+    #[test]
+    fn processor_converts_other_formats() {
+        let source = NativeVideoSource::new_encoded(VideoResolution { width: 16, height: 16 });
+        source.set_processor(Some(Box::new(Paint { source: None })), Default::default());
+
+        let mut buffer = NV12Buffer::new(16, 16);
+        buffer.data_mut().0.fill(16);
+        capture(&source, &buffer);
+        assert!(buffer.data().0.iter().all(|y| *y == 16));
+        assert_eq!(source.processor_stats().unwrap().frames_processed, 1);
+    }
+
+    #[test]
+    fn processor_may_replace_itself() {
+        let source = NativeVideoSource::new_encoded(VideoResolution { width: 16, height: 16 });
+        let processor = Paint { source: Some(source.clone()) };
+        source.set_processor(Some(Box::new(processor)), Default::default());
+
+        capture(&source, &I420Buffer::new_black(16, 16));
+        assert!(source.processor_stats().is_none());
+    }
+}
@@ -87,6 +87,15 @@ pub struct IceCandidateError {
     pub error_text: String,
 }
 
+/// ICE switched the candidate pair carrying the media, use
+/// [`PeerConnection::get_stats`] to inspect the new pair.
+#[derive(Debug, Clone)]
+pub struct CandidatePairChangeEvent {
+    pub reason: String,
+    pub last_data_received_ms: i64,
+    pub estimated_disconnected_time_ms: i64,
+}
+
 #[derive(Debug, Clone)]
 pub struct TrackEvent {
     pub receiver: RtpReceiver,
@@ -101,6 +110,7 @@ pub type OnIceCandidate = Box<dyn FnMut(IceCandidate) + Send + Sync>;
 pub type OnIceCandidateError = Box<dyn FnMut(IceCandidateError) + Send + Sync>;
 pub type OnIceConnectionChange = Box<dyn FnMut(IceConnectionState) + Send + Sync>;
 pub type OnIceGatheringChange = Box<dyn FnMut(IceGatheringState) + Send + Sync>;
+pub type OnIceSelectedCandidatePairChanged = Box<dyn FnMut(CandidatePairChangeEvent) + Send + Sync>;
 pub type OnNegotiationNeeded = Box<dyn FnMut(u32) + Send + Sync>;
 pub type OnSignalingChange = Box<dyn FnMut(SignalingState) + Send + Sync>;
 pub type OnTrack = Box<dyn FnMut(TrackEvent) + Send + Sync>;
@@ -249,6 +259,13 @@ impl PeerConnection {
         self.handle.on_ice_gathering_state_change(f)
     }
 
+    pub fn on_ice_selected_candidate_pair_changed(
+        &self,
+        f: Option<OnIceSelectedCandidatePairChanged>,
+    ) {
+        self.handle.on_ice_selected_candidate_pair_changed(f)
+    }
+
     pub fn on_negotiation_needed(&self, f: Option<OnNegotiationNeeded>) {
         self.handle.on_negotiation_needed(f)
     }
@@ -291,6 +308,7 @@ mod tests {
             }],
             continual_gathering_policy: ContinualGatheringPolicy::GatherOnce,
             ice_transport_type: IceTransportsType::All,
+            ..Default::default()
         };
 
         let bob = factory.create_peer_connection(config.clone()).unwrap();
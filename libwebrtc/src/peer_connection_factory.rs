@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::fmt::Debug;
+use std::{fmt::Debug, ops::RangeInclusive};
 
 use crate::{
     imp::peer_connection_factory as imp_pcf, peer_connection::PeerConnection,
@@ -39,11 +39,70 @@ pub enum IceTransportsType {
     All,
 }
 
+/// Whether host candidates are also gathered over TCP.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TcpCandidatePolicy {
+    Enabled,
+    Disabled,
+}
+
+/// Transports allowed to reach the TURN servers.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RelayTransportPolicy {
+    /// Use every `turn:` and `turns:` URL.
+    All,
+    /// Only TURN over TCP (`turn:...?transport=tcp`) or TLS (`turns:`).
+    Tcp,
+    /// Only TURN over TLS (`turns:`).
+    Tls,
+}
+
+impl RelayTransportPolicy {
+    /// Whether an ICE server URL may be used under this policy. STUN URLs are always kept.
+    pub fn allows(&self, url: &str) -> bool {
+        let url = url.trim().to_ascii_lowercase();
+        if url.starts_with("turns:") {
+            return true;
+        }
+        if !url.starts_with("turn:") {
+            return true;
+        }
+        match self {
+            Self::All => true,
+            Self::Tcp => url
+                .split_once('?')
+                .is_some_and(|(_, query)| query.split('&').any(|p| p == "transport=tcp")),
+            Self::Tls => false,
+        }
+    }
+}
+
+/// Kind of network interface, used to keep ICE off some of them.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum NetworkType {
+    Ethernet,
+    Wifi,
+    Cellular,
+    Vpn,
+    Loopback,
+}
+
 #[derive(Debug, Clone)]
 pub struct RtcConfiguration {
     pub ice_servers: Vec<IceServer>,
     pub continual_gathering_policy: ContinualGatheringPolicy,
     pub ice_transport_type: IceTransportsType,
+    /// Local UDP/TCP ports candidates are allocated from. `None` lets the OS pick.
+    pub port_range: Option<RangeInclusive<u16>>,
+    /// Interfaces ICE never gathers candidates on. Loopback is always excluded.
+    ///
+    /// Only applied when the peer connection is created, it is ignored by
+    /// [`PeerConnection::set_configuration`](crate::peer_connection::PeerConnection::set_configuration).
+    ///
+    pub excluded_network_types: Vec<NetworkType>,
+    pub tcp_candidate_policy: TcpCandidatePolicy,
+    /// Filters the TURN URLs of `ice_servers`, e.g. to only relay over TLS on port 443.
+    pub relay_transport_policy: RelayTransportPolicy,
 }
 
 impl Default for RtcConfiguration {
@@ -52,6 +111,10 @@ impl Default for RtcConfiguration {
             ice_servers: vec![],
             continual_gathering_policy: ContinualGatheringPolicy::GatherContinually,
             ice_transport_type: IceTransportsType::All,
+            port_range: None,
+            excluded_network_types: vec![],
+            tcp_candidate_policy: TcpCandidatePolicy::Enabled,
+            relay_transport_policy: RelayTransportPolicy::All,
         }
     }
 }
@@ -92,7 +155,23 @@ impl PeerConnectionFactory {
 
 #[cfg(all(test, not(target_arch = "wasm32")))]
 mod tests {
-    use super::PeerConnectionFactory;
+    use super::{PeerConnectionFactory, RelayTransportPolicy};
+
+    #[test]
+    fn relay_transport_policy_filters_turn_urls() {
+        let urls = [
+            "stun:stun.example.com:3478",
+            "turn:turn.example.com:3478?transport=udp",
+            "turn:turn.example.com:3478?transport=tcp",
+            "turns:turn.example.com:443?transport=tcp",
+        ];
+        let allowed =
+            |policy: RelayTransportPolicy| urls.iter().filter(|url| policy.allows(url)).count();
+        assert_eq!(allowed(RelayTransportPolicy::All), 4);
+        assert_eq!(allowed(RelayTransportPolicy::Tcp), 3);
+        assert_eq!(allowed(RelayTransportPolicy::Tls), 2);
+        assert!(!RelayTransportPolicy::Tcp.allows("turn:turn.example.com:3478"));
+    }
 
     #[test]
     fn zero_playout_delay_factory_uses_force_playout_delay_field_trial() {
@@ -106,10 +185,15 @@ mod tests {
 }
 
 pub mod native {
+    use std::sync::Arc;
+
     use super::PeerConnectionFactory;
     use crate::{
-        audio_source::native::NativeAudioSource, audio_track::RtcAudioTrack,
-        video_source::native::NativeVideoSource, video_track::RtcVideoTrack,
+        audio_source::native::NativeAudioSource,
+        audio_track::RtcAudioTrack,
+        native::video_codec::{VideoDecoderFactory, VideoEncoderFactory},
+        video_source::native::NativeVideoSource,
+        video_track::RtcVideoTrack,
     };
 
     pub trait PeerConnectionFactoryExt {
@@ -177,6 +261,13 @@ pub mod native {
         fn release_platform_adm(&self);
         fn platform_adm_ref_count(&self) -> i32;
         fn is_platform_adm_active(&self) -> bool;
+
+        /// Register encoders implemented in Rust, preferred over the built-in ones for the
+        /// formats they support. Only peer connections created afterwards are affected.
+        fn register_video_encoder_factory(&self, factory: Arc<dyn VideoEncoderFactory>);
+        /// Register decoders implemented in Rust, preferred over the built-in ones for the
+        /// formats they support. Only peer connections created afterwards are affected.
+        fn register_video_decoder_factory(&self, factory: Arc<dyn VideoDecoderFactory>);
     }
 
     impl PeerConnectionFactoryExt for PeerConnectionFactory {
@@ -319,5 +410,13 @@ pub mod native {
         fn is_platform_adm_active(&self) -> bool {
             self.handle.is_platform_adm_active()
         }
+
+        fn register_video_encoder_factory(&self, factory: Arc<dyn VideoEncoderFactory>) {
+            self.handle.register_video_encoder_factory(factory)
+        }
+
+        fn register_video_decoder_factory(&self, factory: Arc<dyn VideoDecoderFactory>) {
+            self.handle.register_video_decoder_factory(factory)
+        }
     }
 }
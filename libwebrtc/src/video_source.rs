@@ -57,7 +57,10 @@ pub mod native {
     use std::fmt::{Debug, Formatter};
 
     use super::*;
-    use crate::native::packet_trailer::PacketTrailerHandler;
+    use crate::native::{
+        packet_trailer::PacketTrailerHandler,
+        video_processor::{VideoProcessor, VideoProcessorOptions, VideoProcessorStats},
+    };
     #[cfg(target_os = "linux")]
     use crate::video_frame::FrameMetadata;
     use crate::video_frame::{EncodedVideoFrame, VideoBuffer, VideoFrame};
@@ -159,6 +162,23 @@ pub mod native {
             self.handle.set_packet_trailer_handler(handler)
         }
 
+        /// Install a [`VideoProcessor`] running on every frame passed to
+        /// [`capture_frame`](Self::capture_frame) before it is encoded, `None` removes it.
+        ///
+        /// Pre-encoded and DMA-buffer captures bypass the processor.
+        pub fn set_processor(
+            &self,
+            processor: Option<Box<dyn VideoProcessor>>,
+            options: VideoProcessorOptions,
+        ) {
+            self.handle.set_processor(processor, options)
+        }
+
+        /// Stats of the installed processor, reset when it is replaced.
+        pub fn processor_stats(&self) -> Option<VideoProcessorStats> {
+            self.handle.processor_stats()
+        }
+
         pub fn video_resolution(&self) -> VideoResolution {
             self.handle.video_resolution()
         }
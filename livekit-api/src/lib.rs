@@ -38,6 +38,10 @@ mod region;
 #[cfg(feature = "webhooks")]
 pub mod webhooks;
 
+// Proxy/TLS settings shared by the signalling client and the server-API services.
+#[cfg(any(feature = "signal-client", feature = "services-tokio", feature = "services-async"))]
+pub use livekit_net::{ClientIdentity, ProxyOptions, TransportOptions};
+
 #[allow(dead_code)]
 pub(crate) fn get_env_keys() -> Result<(String, String), std::env::VarError> {
     let api_key = std::env::var("LIVEKIT_API_KEY")?;
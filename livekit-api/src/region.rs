@@ -70,6 +70,9 @@ struct CachedRegions {
     /// Effective lifetime of this entry: the server's `Cache-Control: max-age`
     /// when present, otherwise [`RegionCache::DEFAULT_TTL`].
     ttl: Duration,
+    /// Whether `urls` is ordered by measured latency rather than as served.
+    #[cfg(feature = "signal-client")]
+    ranked: bool,
 }
 
 /// Outcome of a [`RegionCache::get`] lookup.
@@ -118,17 +121,48 @@ impl RegionCache {
     /// when the header is absent.
     pub(crate) fn insert(&self, host: String, urls: Vec<String>, max_age: Option<Duration>) {
         let ttl = max_age.unwrap_or(self.default_ttl);
-        self.entries
-            .lock()
-            .unwrap()
-            .insert(host, CachedRegions { urls, fetched_at: Instant::now(), ttl });
+        self.entries.lock().unwrap().insert(
+            host,
+            CachedRegions {
+                urls,
+                fetched_at: Instant::now(),
+                ttl,
+                #[cfg(feature = "signal-client")]
+                ranked: false,
+            },
+        );
+    }
+
+    /// Reorders the cached list for `host` to `ranked_urls`, best first, keeping
+    /// the entry's age. URLs not already cached are ignored; a miss is a no-op.
+    // Only the signaling consumer probes region latency.
+    #[cfg(feature = "signal-client")]
+    pub(crate) fn rank(&self, host: &str, ranked_urls: &[String]) {
+        let mut entries = self.entries.lock().unwrap();
+        if let Some(entry) = entries.get_mut(host) {
+            let mut urls: Vec<String> =
+                ranked_urls.iter().filter(|u| entry.urls.contains(u)).cloned().collect();
+            urls.extend(entry.urls.iter().filter(|u| !ranked_urls.contains(u)).cloned());
+            entry.urls = urls;
+            entry.ranked = true;
+        }
+    }
+
+    /// The best region URL for `host` if its entry has been [ranked](Self::rank).
+    ///
+    /// Stale entries still count: region URLs rarely change, and a caller that
+    /// fails to connect to the preferred region falls back to the regular lookup.
+    #[cfg(feature = "signal-client")]
+    pub(crate) fn preferred(&self, host: &str) -> Option<String> {
+        let entries = self.entries.lock().unwrap();
+        entries.get(host).filter(|e| e.ranked).and_then(|e| e.urls.first().cloned())
     }
 
     /// Removes `failed_url` from the cached list for `host` so it is not handed
     /// out again. If that empties the list, the entry is dropped entirely,
     /// forcing a re-fetch on the next lookup.
     // Only the signaling consumer prunes individual failed regions.
-    #[allow(dead_code)]
+    #[cfg(feature = "signal-client")]
     pub(crate) fn mark_failed(&self, host: &str, failed_url: &str) {
         let mut entries = self.entries.lock().unwrap();
         if let Some(entry) = entries.get_mut(host) {
@@ -201,6 +235,8 @@ mod tests {
                     urls: stale_urls.clone(),
                     fetched_at: past,
                     ttl: RegionCache::DEFAULT_TTL,
+                    #[cfg(feature = "signal-client")]
+                    ranked: false,
                 },
             );
             assert!(
@@ -225,6 +261,7 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "signal-client")]
     #[test]
     fn region_cache_mark_failed_prunes_then_drops() {
         let cache = RegionCache::new(RegionCache::DEFAULT_TTL);
@@ -248,6 +285,38 @@ mod tests {
         cache.mark_failed("unknown.livekit.cloud", &r1);
     }
 
+    #[cfg(feature = "signal-client")]
+    #[test]
+    fn region_cache_rank_reorders_and_prefers() {
+        let cache = RegionCache::new(RegionCache::DEFAULT_TTL);
+        let host = "rank.livekit.cloud";
+        let r1 = "wss://r1.livekit.cloud".to_string();
+        let r2 = "wss://r2.livekit.cloud".to_string();
+        let r3 = "wss://r3.livekit.cloud".to_string();
+        cache.insert(host.to_string(), vec![r1.clone(), r2.clone(), r3.clone()], None);
+        assert_eq!(cache.preferred(host), None, "served order is not a preference");
+
+        // Unknown URLs are dropped; cached URLs missing from the ranking go last.
+        cache.rank(host, &[r3.clone(), "wss://unknown.livekit.cloud".into(), r1.clone()]);
+        assert!(
+            matches!(cache.get(host), Cached::Fresh(u) if u == vec![r3.clone(), r1.clone(), r2.clone()]),
+            "ranking reorders the cached list"
+        );
+        assert_eq!(cache.preferred(host), Some(r3.clone()));
+
+        // A failed preferred region hands over to the next best.
+        cache.mark_failed(host, &r3);
+        assert_eq!(cache.preferred(host), Some(r1));
+
+        // A re-fetch replaces the ranking with the served order.
+        cache.insert(host.to_string(), vec![r2, r3], None);
+        assert_eq!(cache.preferred(host), None);
+
+        // Ranking an unknown host is a no-op.
+        cache.rank("unknown.livekit.cloud", &[]);
+        assert!(matches!(cache.get("unknown.livekit.cloud"), Cached::Miss));
+    }
+
     #[test]
     fn region_cache_invalidate_and_clear() {
         let cache = RegionCache::new(RegionCache::DEFAULT_TTL);
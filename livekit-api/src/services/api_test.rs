@@ -36,6 +36,7 @@ use super::sip::CreateSIPParticipantOptions;
 use super::twirp_client::{ServerError, ServerResult, TwirpClient};
 use super::{LiveKitApi, ServiceError, SipCallError, LIVEKIT_PACKAGE};
 use crate::access_token::{AccessToken, VideoGrants};
+use crate::{ProxyOptions, TransportOptions};
 
 fn base_url() -> String {
     std::env::var("LK_TEST_SERVER_URL").unwrap_or_else(|_| "http://127.0.0.1:9999".to_owned())
@@ -214,6 +215,20 @@ fn api(mock: Option<&str>) -> LiveKitApi {
     }
 }
 
+#[test]
+fn transport_options() {
+    let proxied = TransportOptions {
+        proxy: Some(ProxyOptions::new("socks5h://127.0.0.1:1080").with_credentials("user", "pw")),
+        ..Default::default()
+    };
+    assert!(api(None).with_transport(&proxied).is_ok());
+
+    let invalid =
+        TransportOptions { proxy: Some(ProxyOptions::new("not a url")), ..Default::default() };
+    let err = api(None).with_transport(&invalid).expect_err("invalid proxy URL");
+    assert!(matches!(err, ServiceError::Transport(_)), "{err:?}");
+}
+
 #[tokio::test]
 async fn room_smoke() {
     let base = base_url();
@@ -129,6 +129,11 @@ impl EgressClient {
         Self { base, client: TwirpClient::with_client(host, LIVEKIT_PACKAGE, None, client) }
     }
 
+    pub(crate) fn with_http_client(mut self, client: crate::http_client::Client) -> Self {
+        self.client = self.client.with_http_client(client);
+        self
+    }
+
     #[cfg(test)]
     pub(crate) fn with_default_headers(mut self, headers: http::HeaderMap) -> Self {
         self.client = self.client.with_default_headers(headers);
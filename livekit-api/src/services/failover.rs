@@ -24,6 +24,7 @@ use std::{sync::OnceLock, time::Duration};
 use http::header::{HeaderMap, CONTENT_LENGTH, CONTENT_TYPE};
 use url::Url;
 
+use crate::http_client;
 use crate::region::{is_cloud_host, parse_max_age, Cached, RegionCache, RegionsResponse};
 
 /// Total attempts (the original request plus fallback regions) and the base
@@ -130,7 +131,11 @@ fn region_cache() -> &'static RegionCache {
 /// cached list when available, otherwise an empty list (the caller then stops
 /// failing over). Forwards the caller's headers so a valid token — and any test
 /// directives — reach the discovery endpoint.
-pub(crate) async fn region_urls(base: &Url, headers: &HeaderMap) -> Vec<String> {
+pub(crate) async fn region_urls(
+    client: &http_client::Client,
+    base: &Url,
+    headers: &HeaderMap,
+) -> Vec<String> {
     let key = host_key(base);
     let cache = region_cache();
 
@@ -140,7 +145,7 @@ pub(crate) async fn region_urls(base: &Url, headers: &HeaderMap) -> Vec<String>
         Cached::Miss => None,
     };
 
-    match fetch(base, headers).await {
+    match fetch(client, base, headers).await {
         Ok((urls, max_age)) => {
             // A zero max-age (e.g. `Cache-Control: max-age=0`) means "do not
             // cache"; skip the insert so the next call re-fetches rather than
@@ -177,11 +182,15 @@ fn max_age_from_cache_control(value: Option<&str>) -> Option<Duration> {
 }
 
 #[cfg(feature = "services-tokio")]
-async fn fetch(base: &Url, headers: &HeaderMap) -> Result<(Vec<String>, Option<Duration>), ()> {
+async fn fetch(
+    client: &http_client::Client,
+    base: &Url,
+    headers: &HeaderMap,
+) -> Result<(Vec<String>, Option<Duration>), ()> {
     let mut url = base.clone();
     url.set_path("/settings/regions");
 
-    let resp = reqwest::Client::new()
+    let resp = client
         .get(url)
         .headers(forward_headers(headers))
         .timeout(DISCOVERY_TIMEOUT)
@@ -199,7 +208,11 @@ async fn fetch(base: &Url, headers: &HeaderMap) -> Result<(Vec<String>, Option<D
 }
 
 #[cfg(all(feature = "services-async", not(feature = "services-tokio")))]
-async fn fetch(base: &Url, headers: &HeaderMap) -> Result<(Vec<String>, Option<Duration>), ()> {
+async fn fetch(
+    client: &http_client::Client,
+    base: &Url,
+    headers: &HeaderMap,
+) -> Result<(Vec<String>, Option<Duration>), ()> {
     use isahc::config::Configurable;
     use isahc::AsyncReadResponseExt;
 
@@ -213,7 +226,7 @@ async fn fetch(base: &Url, headers: &HeaderMap) -> Result<(Vec<String>, Option<D
         builder = builder.header(name.as_str(), value.as_bytes());
     }
     let request = builder.body(()).map_err(|_| ())?;
-    let mut resp = isahc::send_async(request).await.map_err(|_| ())?;
+    let mut resp = client.inner().send_async(request).await.map_err(|_| ())?;
     if !resp.status().is_success() {
         return Err(());
     }
@@ -14,6 +14,8 @@
 
 use std::time::Duration;
 
+use livekit_net::TransportOptions;
+
 use super::agent_dispatch::AgentDispatchClient;
 use super::connector::ConnectorClient;
 use super::egress::EgressClient;
@@ -104,6 +106,19 @@ impl LiveKitApi {
         self
     }
 
+    /// Routes every service through a proxy and/or custom TLS settings, replacing the shared
+    /// HTTP client (region discovery for failover included).
+    pub fn with_transport(mut self, options: &TransportOptions) -> ServiceResult<Self> {
+        let http = http_client::client_with(options)?;
+        self.room = self.room.with_http_client(http.clone());
+        self.egress = self.egress.with_http_client(http.clone());
+        self.ingress = self.ingress.with_http_client(http.clone());
+        self.sip = self.sip.with_http_client(http.clone());
+        self.agent_dispatch = self.agent_dispatch.with_http_client(http.clone());
+        self.connector = self.connector.with_http_client(http);
+        Ok(self)
+    }
+
     /// Injects a mock-control header on every service (see the mock test server's
     /// X-Lk-Mock protocol). Test-only, since the service methods don't expose
     /// per-call headers.
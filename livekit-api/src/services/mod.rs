@@ -56,6 +56,8 @@ pub enum ServiceError {
     AccessToken(#[from] AccessTokenError),
     #[error("server error: {0}")]
     Twirp(#[from] ServerError),
+    #[error("invalid transport options: {0}")]
+    Transport(#[from] livekit_net::TransportError),
 }
 
 pub type ServiceResult<T> = Result<T, ServiceError>;
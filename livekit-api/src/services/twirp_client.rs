@@ -161,6 +161,12 @@ impl TwirpClient {
         }
     }
 
+    /// Swaps in another HTTP client, e.g. one built from transport options.
+    pub(crate) fn with_http_client(mut self, client: http_client::Client) -> Self {
+        self.client = client;
+        self
+    }
+
     #[cfg(test)]
     pub(crate) fn with_default_headers(mut self, headers: HeaderMap) -> Self {
         self.default_headers = headers;
@@ -306,7 +312,9 @@ impl TwirpClient {
     ) -> Option<Url> {
         let region_urls = match region_urls {
             Some(urls) => urls,
-            None => region_urls.insert(failover::region_urls(original, forward).await),
+            None => {
+                region_urls.insert(failover::region_urls(&self.client, original, forward).await)
+            }
         };
         failover::pick_next(region_urls, attempted)
     }
@@ -34,15 +34,17 @@ use thiserror::Error;
 use tokio::sync::{mpsc, Mutex as AsyncMutex, RwLock as AsyncRwLock};
 
 use crate::signal_client::signal_stream::SignalStream;
-use livekit_net::HttpClientExt;
+use livekit_net::{HttpClientExt, TransportOptions};
 
+pub mod recording;
 mod region_url_provider;
 mod signal_stream;
 
 #[cfg(test)]
 pub(crate) mod test_transport;
 
-pub use region_url_provider::RegionUrlProvider;
+pub use recording::SignalRecorder;
+pub use region_url_provider::{RegionLatency, RegionUrlProvider};
 
 pub type SignalEmitter = mpsc::UnboundedSender<SignalEvent>;
 pub type SignalEvents = mpsc::UnboundedReceiver<SignalEvent>;
@@ -51,6 +53,7 @@ pub type SignalResult<T> = Result<T, SignalError>;
 pub const JOIN_RESPONSE_TIMEOUT: Duration = Duration::from_secs(5);
 pub const SIGNAL_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
 const REGION_FETCH_TIMEOUT: Duration = Duration::from_secs(3);
+const REGION_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
 const VALIDATE_TIMEOUT: Duration = Duration::from_secs(3);
 pub const PROTOCOL_VERSION: u32 = 17;
 
@@ -169,6 +172,34 @@ pub struct SignalOptions {
     /// [`CLIENT_PROTOCOL_DATA_STREAM_RPC`] instead of [`CLIENT_PROTOCOL_DATA_STREAM_V2`].
     #[doc(hidden)]
     pub use_legacy_data_streams: bool,
+    /// Records signal traffic of every connection made with these options.
+    pub recorder: Option<SignalRecorder>,
+    /// Proxy and TLS settings for the signalling WebSocket and its HTTP requests
+    /// (validation, region discovery).
+    pub transport: TransportOptions,
+    /// WebSocket client for connections made with these options, instead of the
+    /// process-wide one. Takes precedence over [`Self::transport`].
+    pub ws_client: Option<SignalWsClient>,
+}
+
+/// A WebSocket client scoped to the connections of one [`SignalOptions`].
+///
+/// Unlike [`livekit_net::set_ws_client`], which registers a single client for the
+/// whole process, this lets each connection use its own client, e.g. a
+/// [`ReplayWsClient`](recording::ReplayWsClient) per test.
+#[derive(Clone)]
+pub struct SignalWsClient(pub Arc<dyn livekit_net::WsClient>);
+
+impl SignalWsClient {
+    pub fn new(client: impl livekit_net::WsClient + 'static) -> Self {
+        Self(Arc::new(client))
+    }
+}
+
+impl Debug for SignalWsClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SignalWsClient").finish_non_exhaustive()
+    }
 }
 
 impl Default for SignalOptions {
@@ -180,6 +211,9 @@ impl Default for SignalOptions {
             single_peer_connection: false,
             connect_timeout: SIGNAL_CONNECT_TIMEOUT,
             use_legacy_data_streams: false,
+            recorder: None,
+            transport: TransportOptions::default(),
+            ws_client: None,
         }
     }
 }
@@ -236,6 +270,26 @@ impl SignalClient {
             (Self { inner, emitter, handle: Mutex::new(Some(signal_task)) }, join_response, events)
         };
 
+        // Regions ranked ahead of time (see `RegionUrlProvider::rank_region_urls`)
+        // skip the round trip through the project URL. On failure, the regular
+        // path below still runs.
+        if let Some(region_url) = RegionUrlProvider::preferred_region_url(url) {
+            log::debug!("connecting to preferred region: {}", region_url);
+            match SignalInner::connect(&region_url, token, options.clone(), publisher_offer.clone())
+                .await
+            {
+                Ok((inner, join_response, stream_events)) => {
+                    return Ok(handle_success(inner, join_response, stream_events))
+                }
+                Err(err) => {
+                    log::warn!("preferred region {} failed: {}", region_url, err);
+                    if is_connectivity_error(&err) {
+                        RegionUrlProvider::mark_failed(url, &region_url);
+                    }
+                }
+            }
+        }
+
         match SignalInner::connect(url, token, options.clone(), publisher_offer.clone()).await {
             Ok((inner, join_response, stream_events)) => {
                 Ok(handle_success(inner, join_response, stream_events))
@@ -253,7 +307,13 @@ impl SignalClient {
                 // that must NOT be fatal: log a warning and fall back to the
                 // original connection error rather than masking it with the
                 // fetch error.
-                let urls = match RegionUrlProvider::fetch_region_urls(url, token).await {
+                let urls = match RegionUrlProvider::fetch_region_urls(
+                    url,
+                    token,
+                    &options.transport,
+                )
+                .await
+                {
                     Ok(urls) => urls,
                     Err(region_err) => {
                         log::warn!(
@@ -284,7 +344,9 @@ impl SignalClient {
                         Err(region_conn_err) => {
                             // This region is unreachable; drop it from the cache
                             // so the next attempt doesn't hand it out again.
-                            RegionUrlProvider::mark_failed(url, region_url);
+                            if is_connectivity_error(&region_conn_err) {
+                                RegionUrlProvider::mark_failed(url, region_url);
+                            }
                             last_err = region_conn_err;
                         }
                     }
@@ -405,7 +467,7 @@ impl SignalInner {
             get_livekit_url(url, &options, use_v1_path, false, None, "", publisher_offer.as_ref())?;
         // Try to connect to the SignalClient
         let (stream, mut events, single_pc_mode_active) =
-            match SignalStream::connect(lk_url.clone(), token, options.connect_timeout).await {
+            match SignalStream::connect(lk_url.clone(), token, &options).await {
                 Ok((new_stream, stream_events)) => {
                     log::debug!(
                         "signal connection successful: path={}, single_pc_mode={}",
@@ -435,26 +497,20 @@ impl SignalInner {
                         let lk_url_v0 =
                             get_livekit_url(url, &options, false, false, None, "", None)?;
                         log::warn!("v1 path not found (404), falling back to v0 path");
-                        match SignalStream::connect(
-                            lk_url_v0.clone(),
-                            token,
-                            options.connect_timeout,
-                        )
-                        .await
-                        {
+                        match SignalStream::connect(lk_url_v0.clone(), token, &options).await {
                             Ok((new_stream, stream_events)) => (new_stream, stream_events, false),
                             Err(err) => {
                                 log::error!("v0 fallback also failed: {:?}", err);
                                 if let SignalError::TokenFormat = err {
                                     return Err(err);
                                 }
-                                Self::validate(lk_url_v0, token).await?;
+                                Self::validate(lk_url_v0, token, &options.transport).await?;
                                 return Err(err);
                             }
                         }
                     } else {
                         // Connection failed, try to retrieve more information
-                        Self::validate(lk_url, token).await?;
+                        Self::validate(lk_url, token, &options.transport).await?;
                         return Err(err);
                     }
                 }
@@ -488,9 +544,13 @@ impl SignalInner {
     /// what the original error was, masking the real cause (e.g. a 503 from a
     /// saturated node becomes a fabricated 401 to the caller). See
     /// https://github.com/livekit/rust-sdks/issues/1042.
-    async fn validate(ws_url: url::Url, token: &str) -> SignalResult<()> {
+    async fn validate(
+        ws_url: url::Url,
+        token: &str,
+        transport: &TransportOptions,
+    ) -> SignalResult<()> {
         let validate_url = get_validate_url(ws_url);
-        let http = require_http_client()?;
+        let http = require_http_client(transport)?;
         let headers = bearer_headers(token);
 
         let validate_fut = async {
@@ -569,7 +629,7 @@ impl SignalInner {
 
         let result = async {
             let (new_stream, mut events) =
-                SignalStream::connect(lk_url, &token, self.options.connect_timeout).await?;
+                SignalStream::connect(lk_url, &token, &self.options).await?;
             let reconnect_response = get_reconnect_response(&mut events).await?;
             SignalResult::Ok((new_stream, reconnect_response, events))
         }
@@ -969,17 +1029,39 @@ pub(super) fn bearer_headers(token: &str) -> Vec<livekit_net::Header> {
     vec![livekit_net::Header { name: "Authorization".into(), value: format!("Bearer {token}") }]
 }
 
-/// Resolve the registered WebSocket client, or a permanent
+/// Resolve the WebSocket client for `options`, or a permanent
 /// [`SignalError::TransportNotConfigured`] if none has been set. Centralises the
 /// lookup so callers share one error rather than each inventing a string.
-pub(super) fn require_ws_client() -> SignalResult<Arc<dyn livekit_net::WsClient>> {
-    livekit_net::ws_client().ok_or(SignalError::TransportNotConfigured)
+pub(super) fn require_ws_client(
+    options: &SignalOptions,
+) -> SignalResult<Arc<dyn livekit_net::WsClient>> {
+    if let Some(client) = &options.ws_client {
+        return Ok(client.0.clone());
+    }
+    livekit_net::ws_client_with(&options.transport)?.ok_or(SignalError::TransportNotConfigured)
+}
+
+/// Whether `err` means the server could not be reached or failed, as opposed to
+/// the client being rejected (e.g. a 401/403 for an invalid token), which every
+/// region would repeat. Only the former should drop a region from the cache.
+fn is_connectivity_error(err: &SignalError) -> bool {
+    match err {
+        SignalError::Connection(_)
+        | SignalError::Timeout(_)
+        | SignalError::Closed
+        | SignalError::SendError
+        | SignalError::Server(..) => true,
+        SignalError::Handshake { status } => status.is_server_error(),
+        _ => false,
+    }
 }
 
 /// Resolve the registered HTTP client, or a permanent
 /// [`SignalError::TransportNotConfigured`] if none has been set.
-pub(super) fn require_http_client() -> SignalResult<Arc<dyn livekit_net::HttpClient>> {
-    livekit_net::http_client().ok_or(SignalError::TransportNotConfigured)
+pub(super) fn require_http_client(
+    transport: &TransportOptions,
+) -> SignalResult<Arc<dyn livekit_net::HttpClient>> {
+    livekit_net::http_client_with(transport)?.ok_or(SignalError::TransportNotConfigured)
 }
 
 /// Verify `token` can be encoded as an HTTP `Authorization` header value. A token
@@ -1409,6 +1491,7 @@ mod tests {
         let (urls, _max_age) = region_url_provider::fetch_from_endpoint(
             "https://mock.livekit.cloud/settings/regions",
             "test-tok",
+            &TransportOptions::default(),
         )
         .await
         .expect("fetch_from_endpoint should succeed via mock");
@@ -1427,7 +1510,7 @@ mod tests {
         install_mock_transport();
 
         let ws_url = url::Url::parse("ws://mock.livekit.cloud/rtc").unwrap();
-        let result = SignalInner::validate(ws_url, "test-tok").await;
+        let result = SignalInner::validate(ws_url, "test-tok", &TransportOptions::default()).await;
         assert!(
             result.is_ok(),
             "validate() must forward Bearer token; mock returned 401 without it — got: {:?}",
@@ -1456,7 +1539,7 @@ mod tests {
         install_mock_transport();
 
         let ws_url = url::Url::parse("wss://connrefused.livekit.cloud/rtc").unwrap();
-        let result = SignalInner::validate(ws_url, "test-tok").await;
+        let result = SignalInner::validate(ws_url, "test-tok", &TransportOptions::default()).await;
         assert!(
             result.is_ok(),
             "validate() must swallow its own transport error so the caller's \
@@ -1473,13 +1556,12 @@ mod tests {
         use crate::signal_client::test_transport::install_mock_transport;
         install_mock_transport();
 
-        let (_stream, mut events) = SignalStream::connect(
-            url::Url::parse("ws://mock/rtc").unwrap(),
-            "tok",
-            Duration::from_secs(1),
-        )
-        .await
-        .unwrap();
+        let options =
+            SignalOptions { connect_timeout: Duration::from_secs(1), ..Default::default() };
+        let (_stream, mut events) =
+            SignalStream::connect(url::Url::parse("ws://mock/rtc").unwrap(), "tok", &options)
+                .await
+                .unwrap();
         let msg = events.recv().await.expect("expected a frame");
         assert!(matches!(*msg, proto::signal_response::Message::PongResp(_)));
     }
@@ -1495,7 +1577,12 @@ mod tests {
         install_mock_transport();
 
         let endpoint = "http://mock.test/connrefused/settings/regions";
-        let result = region_url_provider::fetch_from_endpoint(endpoint, "test-tok").await;
+        let result = region_url_provider::fetch_from_endpoint(
+            endpoint,
+            "test-tok",
+            &TransportOptions::default(),
+        )
+        .await;
 
         let err = result.expect_err("expected Err from connrefused endpoint");
         let SignalError::RegionError(msg) = err else {
@@ -1508,6 +1595,66 @@ mod tests {
         );
     }
 
+    /// Ranking probes every region and caches the order, after which the fastest
+    /// region is preferred for the host. The mock serves one region and answers
+    /// its (unauthenticated) probe with a 401, which still counts as reachable.
+    #[cfg(feature = "signal-client-tokio")]
+    #[tokio::test]
+    async fn rank_region_urls_via_mock_transport() {
+        use crate::signal_client::test_transport::install_mock_transport;
+        install_mock_transport();
+
+        let url = "wss://rank-mock.livekit.cloud";
+        assert_eq!(RegionUrlProvider::preferred_region_url(url), None);
+
+        let latencies =
+            RegionUrlProvider::rank_region_urls(url, "test-tok", &TransportOptions::default())
+                .await
+                .unwrap();
+        assert_eq!(latencies.len(), 1);
+        assert_eq!(latencies[0].url, "wss://us-mock.livekit.cloud");
+        assert!(latencies[0].rtt.is_some(), "probe should reach the mock");
+        assert_eq!(
+            RegionUrlProvider::preferred_region_url(url).as_deref(),
+            Some("wss://us-mock.livekit.cloud")
+        );
+
+        // Non-cloud hosts only probe the URL itself.
+        let latencies = RegionUrlProvider::rank_region_urls(
+            "ws://localhost:7880",
+            "test-tok",
+            &Default::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(latencies.len(), 1);
+        assert_eq!(latencies[0].url, "ws://localhost:7880");
+        assert_eq!(RegionUrlProvider::preferred_region_url("ws://localhost:7880"), None);
+    }
+
+    /// Auth rejections reach the region fine, so they must not prune it from the
+    /// cache; unreachable or failing servers must.
+    #[test]
+    fn only_connectivity_errors_fail_a_region() {
+        let handshake =
+            |status: u16| SignalError::Handshake { status: StatusCode::from_u16(status).unwrap() };
+        assert!(is_connectivity_error(&SignalError::Connection("refused".into())));
+        assert!(is_connectivity_error(&SignalError::Timeout("join".into())));
+        assert!(is_connectivity_error(&SignalError::Server(
+            StatusCode::SERVICE_UNAVAILABLE,
+            String::new()
+        )));
+        assert!(is_connectivity_error(&handshake(502)));
+
+        assert!(!is_connectivity_error(&SignalError::Client(
+            StatusCode::UNAUTHORIZED,
+            String::new()
+        )));
+        assert!(!is_connectivity_error(&SignalError::Client(StatusCode::FORBIDDEN, String::new())));
+        assert!(!is_connectivity_error(&handshake(401)));
+        assert!(!is_connectivity_error(&handshake(403)));
+    }
+
     /// A non-JSON region body yields a descriptive `RegionError`. The mock
     /// returns a 200 with a non-JSON body for URLs marked `badjson`.
     #[cfg(feature = "signal-client-tokio")]
@@ -1517,7 +1664,12 @@ mod tests {
         install_mock_transport();
 
         let endpoint = "http://mock.test/badjson";
-        let result = region_url_provider::fetch_from_endpoint(endpoint, "test-tok").await;
+        let result = region_url_provider::fetch_from_endpoint(
+            endpoint,
+            "test-tok",
+            &TransportOptions::default(),
+        )
+        .await;
 
         let err = result.expect_err("expected Err from badjson endpoint");
         assert!(
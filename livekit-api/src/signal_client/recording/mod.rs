@@ -0,0 +1,435 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Recording of signal and data channel traffic, and its replay.
+//!
+//! A [`SignalRecorder`] set in [`SignalOptions::recorder`](super::SignalOptions::recorder)
+//! writes every signal request and response, the outcome of every signal
+//! connection attempt, and the header of every data packet to a compact file.
+//! [`ReplayWsClient`] feeds such a recording back to a client.
+//!
+//! Recordings contain the signaling payloads as sent on the wire, including
+//! SDPs, participant metadata and refreshed tokens; treat them as sensitive.
+//!
+//! # Format
+//!
+//! The file starts with the magic `LKSR` and a version byte, followed by
+//! records of `kind: u8`, `timestamp: varint` (microseconds since the
+//! recording started, from a monotonic clock), `length: varint` and the
+//! payload.
+
+use std::{
+    fmt::Debug,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use livekit_protocol as proto;
+use parking_lot::Mutex;
+use prost::Message;
+
+mod replay;
+
+pub use replay::{ReplayOptions, ReplayWsClient};
+
+const MAGIC: &[u8; 4] = b"LKSR";
+const VERSION: u8 = 1;
+
+/// The kind of a [`Record`], which determines how its payload is encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum RecordKind {
+    /// A signal connection was opened. The payload is the URL without its query.
+    Connect = 1,
+    /// A signal connection attempt failed. The payload is the error, see
+    /// [`Record::connect_error`].
+    ConnectFailed = 2,
+    /// An encoded [`proto::SignalRequest`] sent to the server.
+    SignalRequest = 3,
+    /// An encoded [`proto::SignalResponse`] received from the server.
+    SignalResponse = 4,
+    /// An encoded [`proto::DataPacket`] sent on a data channel, without its value.
+    DataPacketSent = 5,
+    /// An encoded [`proto::DataPacket`] received on a data channel, without its value.
+    DataPacketReceived = 6,
+}
+
+impl TryFrom<u8> for RecordKind {
+    type Error = io::Error;
+
+    fn try_from(value: u8) -> io::Result<Self> {
+        Ok(match value {
+            1 => Self::Connect,
+            2 => Self::ConnectFailed,
+            3 => Self::SignalRequest,
+            4 => Self::SignalResponse,
+            5 => Self::DataPacketSent,
+            6 => Self::DataPacketReceived,
+            _ => return Err(invalid_data(format!("unknown record kind {value}"))),
+        })
+    }
+}
+
+/// One entry of a recording.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub kind: RecordKind,
+    /// Time since the recording started.
+    pub timestamp: Duration,
+    pub payload: Vec<u8>,
+}
+
+impl Record {
+    pub fn signal_request(&self) -> Option<proto::SignalRequest> {
+        (self.kind == RecordKind::SignalRequest)
+            .then(|| proto::SignalRequest::decode(self.payload.as_slice()).ok())?
+    }
+
+    pub fn signal_response(&self) -> Option<proto::SignalResponse> {
+        (self.kind == RecordKind::SignalResponse)
+            .then(|| proto::SignalResponse::decode(self.payload.as_slice()).ok())?
+    }
+
+    /// Header of a sent or received data packet.
+    pub fn data_packet(&self) -> Option<proto::DataPacket> {
+        matches!(self.kind, RecordKind::DataPacketSent | RecordKind::DataPacketReceived)
+            .then(|| proto::DataPacket::decode(self.payload.as_slice()).ok())?
+    }
+
+    /// Transport error of a failed connection attempt.
+    pub fn connect_error(&self) -> Option<livekit_net::TransportError> {
+        use livekit_net::TransportError;
+
+        if self.kind != RecordKind::ConnectFailed {
+            return None;
+        }
+        let error = String::from_utf8_lossy(&self.payload);
+        let (tag, detail) = error.split_once(':').unwrap_or((&error, ""));
+        Some(match tag {
+            "timeout" => TransportError::Timeout,
+            "closed" => TransportError::Closed,
+            "http" => detail
+                .parse()
+                .map(|status| TransportError::Http { status })
+                .unwrap_or_else(|_| TransportError::Other(error.to_string())),
+            "connection" => TransportError::Connection(detail.to_owned()),
+            _ => TransportError::Other(detail.to_owned()),
+        })
+    }
+}
+
+fn encode_connect_error(error: &livekit_net::TransportError) -> String {
+    use livekit_net::TransportError;
+
+    match error {
+        TransportError::Timeout => "timeout".into(),
+        TransportError::Closed => "closed".into(),
+        TransportError::Http { status } => format!("http:{status}"),
+        TransportError::Connection(message) => format!("connection:{message}"),
+        TransportError::Other(message) => format!("other:{message}"),
+    }
+}
+
+/// Writes signal and data channel traffic to a recording.
+///
+/// Cheap to clone; clones append to the same recording. Records are written by a
+/// dedicated thread, so recording never blocks on file IO. The first write error
+/// stops the recording and is logged, it is never surfaced to the client.
+#[derive(Clone)]
+pub struct SignalRecorder {
+    inner: Arc<RecorderInner>,
+}
+
+struct RecorderInner {
+    /// Held while timestamping a record so records are queued in timestamp order.
+    commands: Mutex<mpsc::Sender<WriterCommand>>,
+    start: Instant,
+    failed: Arc<AtomicBool>,
+}
+
+enum WriterCommand {
+    Record(Vec<u8>),
+    Flush(mpsc::SyncSender<io::Result<()>>),
+}
+
+impl Debug for SignalRecorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SignalRecorder").finish_non_exhaustive()
+    }
+}
+
+impl SignalRecorder {
+    /// Creates a recording file at `path`, replacing any existing file.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?))
+    }
+
+    /// Starts a recording written to `writer`.
+    pub fn new(mut writer: impl Write + Send + 'static) -> io::Result<Self> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        let (commands, commands_rx) = mpsc::channel();
+        let failed = Arc::new(AtomicBool::new(false));
+        let thread_failed = failed.clone();
+        std::thread::Builder::new()
+            .name("signal-recorder".into())
+            .spawn(move || write_thread(writer, commands_rx, &thread_failed))?;
+        let inner = RecorderInner { commands: Mutex::new(commands), start: Instant::now(), failed };
+        Ok(Self { inner: Arc::new(inner) })
+    }
+
+    /// Records the outcome of a signal connection attempt to `url`.
+    pub fn record_connect(&self, url: &url::Url, result: Result<(), &livekit_net::TransportError>) {
+        match result {
+            Ok(()) => self.record(RecordKind::Connect, livekit_net::redact_url(url).as_bytes()),
+            Err(error) => {
+                self.record(RecordKind::ConnectFailed, encode_connect_error(error).as_bytes())
+            }
+        }
+    }
+
+    /// Records a data packet, keeping its header but not its value.
+    ///
+    /// The value is detached from `packet` while the header is encoded and restored
+    /// afterwards, so the payload is never copied.
+    pub fn record_data_packet(&self, packet: &mut proto::DataPacket, received: bool) {
+        let value = packet.value.take();
+        let header = packet.encode_to_vec();
+        packet.value = value;
+        let kind =
+            if received { RecordKind::DataPacketReceived } else { RecordKind::DataPacketSent };
+        self.record(kind, &header);
+    }
+
+    /// Appends a raw record.
+    pub fn record(&self, kind: RecordKind, payload: &[u8]) {
+        if self.inner.failed.load(Ordering::Relaxed) {
+            return;
+        }
+        let commands = self.inner.commands.lock();
+        let timestamp = self.inner.start.elapsed().as_micros() as u64;
+
+        let mut frame = Vec::with_capacity(payload.len() + 11);
+        frame.push(kind as u8);
+        prost::encoding::encode_varint(timestamp, &mut frame);
+        prost::encoding::encode_varint(payload.len() as u64, &mut frame);
+        frame.extend_from_slice(payload);
+        _ = commands.send(WriterCommand::Record(frame));
+    }
+
+    /// Blocks until every record appended so far has been written and flushed.
+    pub fn flush(&self) -> io::Result<()> {
+        let (result_tx, result_rx) = mpsc::sync_channel(1);
+        self.inner
+            .commands
+            .lock()
+            .send(WriterCommand::Flush(result_tx))
+            .map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?;
+        result_rx.recv().map_err(|_| io::Error::from(io::ErrorKind::BrokenPipe))?
+    }
+}
+
+/// Writes queued records until every [`SignalRecorder`] clone is dropped.
+fn write_thread(
+    mut writer: impl Write,
+    commands: mpsc::Receiver<WriterCommand>,
+    failed: &AtomicBool,
+) {
+    for command in commands {
+        match command {
+            WriterCommand::Record(frame) => {
+                if failed.load(Ordering::Relaxed) {
+                    continue;
+                }
+                if let Err(err) = writer.write_all(&frame) {
+                    log::error!("failed to write signal recording, recording stopped: {:?}", err);
+                    failed.store(true, Ordering::Relaxed);
+                }
+            }
+            WriterCommand::Flush(result_tx) => _ = result_tx.send(writer.flush()),
+        }
+    }
+    if let Err(err) = writer.flush() {
+        log::error!("failed to flush signal recording: {:?}", err);
+    }
+}
+
+/// Reads the records of a recording in order.
+pub struct RecordingReader<R> {
+    reader: R,
+}
+
+impl RecordingReader<BufReader<File>> {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> RecordingReader<R> {
+    /// Checks the recording header and returns a reader positioned on the first record.
+    pub fn new(mut reader: R) -> io::Result<Self> {
+        let mut header = [0u8; 5];
+        reader.read_exact(&mut header)?;
+        if &header[..4] != MAGIC {
+            return Err(invalid_data("not a signal recording".into()));
+        }
+        if header[4] != VERSION {
+            return Err(invalid_data(format!("unsupported recording version {}", header[4])));
+        }
+        Ok(Self { reader })
+    }
+
+    fn read_record(&mut self) -> io::Result<Option<Record>> {
+        let mut kind = [0u8; 1];
+        if self.reader.read(&mut kind)? == 0 {
+            return Ok(None);
+        }
+        let kind = RecordKind::try_from(kind[0])?;
+        let timestamp = Duration::from_micros(read_varint(&mut self.reader)?);
+        let len = read_varint(&mut self.reader)? as usize;
+        let mut payload = vec![0u8; len];
+        self.reader.read_exact(&mut payload)?;
+        Ok(Some(Record { kind, timestamp, payload }))
+    }
+}
+
+impl<R: Read> Iterator for RecordingReader<R> {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+fn read_varint(reader: &mut impl Read) -> io::Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0u8; 1];
+        reader.read_exact(&mut byte)?;
+        value |= u64::from(byte[0] & 0x7f) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(invalid_data("varint overflow".into()))
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use livekit_net::TransportError;
+
+    /// A writer whose contents stay readable after the recorder took ownership.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn read_all(recorder: &SignalRecorder, buffer: &SharedBuffer) -> Vec<Record> {
+        recorder.flush().unwrap();
+        let data = buffer.0.lock().clone();
+        RecordingReader::new(data.as_slice()).unwrap().collect::<io::Result<_>>().unwrap()
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let buffer = SharedBuffer::default();
+        let recorder = SignalRecorder::new(buffer.clone()).unwrap();
+        let url = url::Url::parse("wss://example.livekit.cloud/rtc?access_token=secret").unwrap();
+        let request =
+            proto::SignalRequest { message: Some(proto::signal_request::Message::Ping(42)) };
+        let response =
+            proto::SignalResponse { message: Some(proto::signal_response::Message::Pong(42)) };
+
+        recorder.record_connect(&url, Ok(()));
+        recorder.record(RecordKind::SignalRequest, &request.encode_to_vec());
+        recorder.record(RecordKind::SignalResponse, &response.encode_to_vec());
+
+        let records = read_all(&recorder, &buffer);
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].kind, RecordKind::Connect);
+        assert_eq!(records[0].payload, b"wss://example.livekit.cloud/rtc");
+        assert_eq!(records[1].signal_request(), Some(request));
+        assert_eq!(records[2].signal_response(), Some(response));
+        assert!(records.windows(2).all(|w| w[0].timestamp <= w[1].timestamp));
+    }
+
+    #[test]
+    fn test_data_packet_header_only() {
+        let buffer = SharedBuffer::default();
+        let recorder = SignalRecorder::new(buffer.clone()).unwrap();
+        let mut packet = proto::DataPacket {
+            participant_identity: "alice".into(),
+            destination_identities: vec!["bob".into()],
+            sequence: 7,
+            value: Some(proto::data_packet::Value::User(proto::UserPacket {
+                payload: b"secret".to_vec(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+
+        recorder.record_data_packet(&mut packet, true);
+        assert!(packet.value.is_some(), "the packet value must be restored");
+
+        let records = read_all(&recorder, &buffer);
+        assert_eq!(records[0].kind, RecordKind::DataPacketReceived);
+        let header = records[0].data_packet().unwrap();
+        assert_eq!(header.sequence, 7);
+        assert_eq!(header.destination_identities, vec!["bob".to_string()]);
+        assert!(header.value.is_none());
+    }
+
+    #[test]
+    fn test_connect_error_roundtrip() {
+        let buffer = SharedBuffer::default();
+        let recorder = SignalRecorder::new(buffer.clone()).unwrap();
+        let url = url::Url::parse("wss://example.livekit.cloud/rtc/v1").unwrap();
+
+        recorder.record_connect(&url, Err(&TransportError::Http { status: 404 }));
+        recorder.record_connect(&url, Err(&TransportError::Connection("refused".into())));
+
+        let records = read_all(&recorder, &buffer);
+        assert!(matches!(records[0].connect_error(), Some(TransportError::Http { status: 404 })));
+        assert!(matches!(
+            records[1].connect_error(),
+            Some(TransportError::Connection(message)) if message == "refused"
+        ));
+    }
+
+    #[test]
+    fn test_rejects_foreign_file() {
+        assert!(RecordingReader::new(&b"MCAP0"[..]).is_err());
+        assert!(RecordingReader::new(&b"LKSR\x09"[..]).is_err());
+    }
+}
@@ -0,0 +1,312 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{collections::VecDeque, io, path::Path, time::Duration};
+
+use livekit_net::{Header, TransportError, WsClient, WsConnectResult, WsConnection};
+use livekit_runtime::Instant;
+use parking_lot::Mutex;
+use tokio::sync::{watch, Mutex as AsyncMutex};
+
+use super::{Record, RecordKind, RecordingReader};
+
+/// Options for [`ReplayWsClient::with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct ReplayOptions {
+    /// Deliver responses at their recorded offset from the connection start,
+    /// instead of as soon as they are due.
+    pub realtime: bool,
+}
+
+/// One recorded signal connection attempt.
+enum Session {
+    Failed(TransportError),
+    Connected(VecDeque<ReplayFrame>),
+}
+
+struct ReplayFrame {
+    /// Number of requests the client had sent when the response was received.
+    requests_before: usize,
+    /// Offset of the response from the connection start.
+    offset: Duration,
+    frame: Vec<u8>,
+}
+
+/// A [`WsClient`] that replays a recording made with
+/// [`SignalRecorder`](super::SignalRecorder).
+///
+/// Each `connect` plays the next recorded connection attempt: failed attempts
+/// fail with the recorded error, and successful ones deliver the recorded
+/// signal responses. A response is only delivered once the client has sent as
+/// many requests as it had when the response was recorded, which keeps the
+/// replay deterministic regardless of timing. Requests are otherwise not
+/// compared with the recording, and data packet records are ignored.
+///
+/// Pass it in [`SignalOptions::ws_client`](crate::signal_client::SignalOptions::ws_client)
+/// to replay a single connection's recording, so several replays can run in one
+/// process.
+pub struct ReplayWsClient {
+    sessions: Mutex<VecDeque<Session>>,
+    options: ReplayOptions,
+}
+
+impl ReplayWsClient {
+    pub fn new(records: impl IntoIterator<Item = Record>) -> Self {
+        Self::with_options(records, ReplayOptions::default())
+    }
+
+    pub fn with_options(records: impl IntoIterator<Item = Record>, options: ReplayOptions) -> Self {
+        let mut sessions = VecDeque::new();
+        let mut current: Option<(Duration, usize, VecDeque<ReplayFrame>)> = None;
+
+        fn finish(
+            sessions: &mut VecDeque<Session>,
+            current: &mut Option<(Duration, usize, VecDeque<ReplayFrame>)>,
+        ) {
+            if let Some((_, _, frames)) = current.take() {
+                sessions.push_back(Session::Connected(frames));
+            }
+        }
+
+        for record in records {
+            match record.kind {
+                RecordKind::Connect => {
+                    finish(&mut sessions, &mut current);
+                    current = Some((record.timestamp, 0, VecDeque::new()));
+                }
+                RecordKind::ConnectFailed => {
+                    finish(&mut sessions, &mut current);
+                    let error = record.connect_error().unwrap_or(TransportError::Closed);
+                    sessions.push_back(Session::Failed(error));
+                }
+                RecordKind::SignalRequest => {
+                    if let Some((_, requests, _)) = current.as_mut() {
+                        *requests += 1;
+                    }
+                }
+                RecordKind::SignalResponse => {
+                    if let Some((start, requests, frames)) = current.as_mut() {
+                        frames.push_back(ReplayFrame {
+                            requests_before: *requests,
+                            offset: record.timestamp.saturating_sub(*start),
+                            frame: record.payload,
+                        });
+                    }
+                }
+                RecordKind::DataPacketSent | RecordKind::DataPacketReceived => {}
+            }
+        }
+        finish(&mut sessions, &mut current);
+
+        Self { sessions: Mutex::new(sessions), options }
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let records = RecordingReader::open(path)?.collect::<io::Result<Vec<_>>>()?;
+        Ok(Self::new(records))
+    }
+
+    /// Number of recorded connection attempts not replayed yet.
+    pub fn remaining_connections(&self) -> usize {
+        self.sessions.lock().len()
+    }
+}
+
+#[async_trait::async_trait]
+impl WsClient for ReplayWsClient {
+    async fn connect(
+        &self,
+        _url: String,
+        _headers: Vec<Header>,
+        _timeout_ms: u64,
+    ) -> Result<WsConnectResult, TransportError> {
+        let session = self.sessions.lock().pop_front().ok_or_else(|| {
+            TransportError::Connection("recording has no more signal connections".into())
+        })?;
+        match session {
+            Session::Failed(error) => Err(error),
+            Session::Connected(frames) => Ok(WsConnectResult {
+                connection: std::sync::Arc::new(ReplayConnection::new(
+                    frames,
+                    self.options.realtime,
+                )),
+            }),
+        }
+    }
+}
+
+struct ReplayConnection {
+    frames: AsyncMutex<VecDeque<ReplayFrame>>,
+    sent: watch::Sender<usize>,
+    closed: watch::Sender<bool>,
+    start: Instant,
+    realtime: bool,
+}
+
+impl ReplayConnection {
+    fn new(frames: VecDeque<ReplayFrame>, realtime: bool) -> Self {
+        Self {
+            frames: AsyncMutex::new(frames),
+            sent: watch::channel(0).0,
+            closed: watch::channel(false).0,
+            start: Instant::now(),
+            realtime,
+        }
+    }
+
+    async fn next_frame(&self) -> Option<Vec<u8>> {
+        let mut frames = self.frames.lock().await;
+        let (requests_before, offset) = {
+            let next = frames.front()?;
+            (next.requests_before, next.offset)
+        };
+
+        let _ = self.sent.subscribe().wait_for(|sent| *sent >= requests_before).await;
+        if self.realtime {
+            livekit_runtime::sleep((self.start + offset).saturating_duration_since(Instant::now()))
+                .await;
+        }
+        frames.pop_front().map(|frame| frame.frame)
+    }
+}
+
+#[async_trait::async_trait]
+impl WsConnection for ReplayConnection {
+    async fn send(&self, _frame: Vec<u8>) -> Result<(), TransportError> {
+        if *self.closed.borrow() {
+            return Err(TransportError::Closed);
+        }
+        self.sent.send_modify(|sent| *sent += 1);
+        Ok(())
+    }
+
+    /// Once the recording is exhausted the connection stays open, like an idle
+    /// server, until it is closed.
+    async fn recv(&self) -> Result<Option<Vec<u8>>, TransportError> {
+        let mut closed = self.closed.subscribe();
+        if *closed.borrow() {
+            return Ok(None);
+        }
+        tokio::select! {
+            Some(frame) = self.next_frame() => Ok(Some(frame)),
+            _ = closed.wait_for(|closed| *closed) => Ok(None),
+        }
+    }
+
+    async fn close(&self) {
+        self.closed.send_replace(true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use livekit_protocol as proto;
+    use prost::Message;
+
+    fn record(kind: RecordKind, timestamp_ms: u64, payload: Vec<u8>) -> Record {
+        Record { kind, timestamp: Duration::from_millis(timestamp_ms), payload }
+    }
+
+    fn response(message: proto::signal_response::Message) -> Vec<u8> {
+        proto::SignalResponse { message: Some(message) }.encode_to_vec()
+    }
+
+    #[tokio::test]
+    async fn test_replays_connection_attempts_in_order() {
+        let client = ReplayWsClient::new([
+            record(RecordKind::ConnectFailed, 0, b"http:404".to_vec()),
+            record(RecordKind::Connect, 10, b"ws://localhost/rtc".to_vec()),
+            record(
+                RecordKind::SignalResponse,
+                20,
+                response(proto::signal_response::Message::Pong(1)),
+            ),
+        ]);
+        assert_eq!(client.remaining_connections(), 2);
+
+        let first = client.connect("ws://localhost/rtc/v1".into(), vec![], 1000).await;
+        assert!(matches!(first, Err(TransportError::Http { status: 404 })));
+
+        let conn = client.connect("ws://localhost/rtc".into(), vec![], 1000).await.unwrap();
+        let frame = conn.connection.recv().await.unwrap().unwrap();
+        assert_eq!(frame, response(proto::signal_response::Message::Pong(1)));
+
+        let exhausted = client.connect("ws://localhost/rtc".into(), vec![], 1000).await;
+        assert!(matches!(exhausted, Err(TransportError::Connection(_))));
+    }
+
+    #[tokio::test]
+    async fn test_response_waits_for_requests() {
+        let client = ReplayWsClient::new([
+            record(RecordKind::Connect, 0, vec![]),
+            record(
+                RecordKind::SignalResponse,
+                1,
+                response(proto::signal_response::Message::Pong(1)),
+            ),
+            record(RecordKind::SignalRequest, 2, vec![]),
+            record(RecordKind::DataPacketSent, 3, vec![]),
+            record(
+                RecordKind::SignalResponse,
+                4,
+                response(proto::signal_response::Message::Pong(2)),
+            ),
+        ]);
+        let conn = client.connect(String::new(), vec![], 1000).await.unwrap().connection;
+
+        assert!(conn.recv().await.unwrap().is_some());
+        let pending = tokio::time::timeout(Duration::from_millis(50), conn.recv()).await;
+        assert!(pending.is_err(), "second response must wait for the request");
+
+        conn.send(vec![]).await.unwrap();
+        let frame = conn.recv().await.unwrap().unwrap();
+        assert_eq!(frame, response(proto::signal_response::Message::Pong(2)));
+    }
+
+    #[tokio::test]
+    async fn test_close_ends_recv() {
+        let client = ReplayWsClient::new([record(RecordKind::Connect, 0, vec![])]);
+        let conn = client.connect(String::new(), vec![], 1000).await.unwrap().connection;
+
+        conn.close().await;
+        assert_eq!(conn.recv().await.unwrap(), None);
+        assert!(matches!(conn.send(vec![]).await, Err(TransportError::Closed)));
+    }
+
+    #[tokio::test]
+    async fn test_clients_are_scoped_per_connection() {
+        use crate::signal_client::{require_ws_client, SignalOptions, SignalWsClient};
+
+        let options_for = |pong| {
+            let client = ReplayWsClient::new([
+                record(RecordKind::Connect, 0, vec![]),
+                record(
+                    RecordKind::SignalResponse,
+                    1,
+                    response(proto::signal_response::Message::Pong(pong)),
+                ),
+            ]);
+            SignalOptions { ws_client: Some(SignalWsClient::new(client)), ..Default::default() }
+        };
+        let (first, second) = (options_for(1), options_for(2));
+
+        for (options, pong) in [(&first, 1), (&second, 2)] {
+            let client = require_ws_client(options).unwrap();
+            let conn = client.connect(String::new(), vec![], 1000).await.unwrap().connection;
+            let frame = conn.recv().await.unwrap().unwrap();
+            assert_eq!(frame, response(proto::signal_response::Message::Pong(pong)));
+        }
+    }
+}
@@ -16,7 +16,7 @@ use std::{
     collections::HashMap,
     error::Error as StdError,
     sync::{Arc, OnceLock},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use parking_lot::Mutex;
@@ -24,8 +24,8 @@ use tokio::sync::Mutex as AsyncMutex;
 
 use crate::region::{is_cloud_host, parse_max_age, Cached, RegionCache, RegionsResponse};
 
-use super::{SignalError, SignalResult, REGION_FETCH_TIMEOUT};
-use livekit_net::HttpClientExt;
+use super::{SignalError, SignalResult, REGION_FETCH_TIMEOUT, REGION_PROBE_TIMEOUT};
+use livekit_net::{HttpClientExt, TransportOptions};
 
 /// Process-wide region cache for the signaling path. Persisting it here (rather
 /// than on a per-connection object) means it survives across reconnect attempts
@@ -76,6 +76,14 @@ fn error_with_chain(err: &dyn StdError) -> String {
         .join(": ")
 }
 
+/// Round-trip time measured to a region by [`RegionUrlProvider::rank_region_urls`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegionLatency {
+    pub url: String,
+    /// `None` if the region did not respond within the probe timeout.
+    pub rtt: Option<Duration>,
+}
+
 pub struct RegionUrlProvider;
 
 impl RegionUrlProvider {
@@ -88,7 +96,11 @@ impl RegionUrlProvider {
     /// rather than surfacing the error. Concurrent calls for the same host are
     /// de-duplicated: only one fetch runs at a time and the rest reuse its
     /// result.
-    pub async fn fetch_region_urls(url: &str, token: &str) -> SignalResult<Vec<String>> {
+    pub async fn fetch_region_urls(
+        url: &str,
+        token: &str,
+        transport: &TransportOptions,
+    ) -> SignalResult<Vec<String>> {
         let host = region_host(url)?;
         // Non-cloud (direct / self-hosted) hosts have no regions.
         if !is_cloud_host(&host) {
@@ -115,7 +127,7 @@ impl RegionUrlProvider {
         }
 
         let endpoint = region_endpoint(url)?;
-        match fetch_from_endpoint(&endpoint, token).await {
+        match fetch_from_endpoint(&endpoint, token, transport).await {
             Ok((urls, max_age)) => {
                 cache.insert(host, urls.clone(), max_age);
                 Ok(urls)
@@ -134,6 +146,53 @@ impl RegionUrlProvider {
         }
     }
 
+    /// Measures the round trip to each region of `url`'s host and caches the
+    /// regions ordered by it, fastest first and unreachable ones last.
+    ///
+    /// Regions are fetched as in [`Self::fetch_region_urls`] and probed
+    /// concurrently with a plain HTTP request. Once ranked, connecting to the
+    /// host tries [`Self::preferred_region_url`] first and falls back in the
+    /// ranked order. Non-cloud hosts have no regions, so only `url` itself is
+    /// probed and nothing is cached.
+    ///
+    pub async fn rank_region_urls(
+        url: &str,
+        token: &str,
+        transport: &TransportOptions,
+    ) -> SignalResult<Vec<RegionLatency>> {
+        let host = region_host(url)?;
+        let urls = match is_cloud_host(&host) {
+            true => Self::fetch_region_urls(url, token, transport).await?,
+            false => vec![url.to_string()],
+        };
+
+        let probes: Vec<_> = urls
+            .into_iter()
+            .map(|url| {
+                let probe = livekit_runtime::spawn(probe_rtt(url.clone(), transport.clone()));
+                (url, probe)
+            })
+            .collect();
+        let mut latencies = Vec::with_capacity(probes.len());
+        for (url, probe) in probes {
+            latencies.push(RegionLatency { url, rtt: probe.await });
+        }
+        // Stable, so unreachable regions keep their served order at the end.
+        latencies.sort_by_key(|l| l.rtt.unwrap_or(Duration::MAX));
+
+        if is_cloud_host(&host) {
+            let ranked: Vec<String> = latencies.iter().map(|l| l.url.clone()).collect();
+            region_cache().rank(&host, &ranked);
+        }
+        Ok(latencies)
+    }
+
+    /// The fastest region of `url`'s host, if it has been ranked with
+    /// [`Self::rank_region_urls`].
+    pub fn preferred_region_url(url: &str) -> Option<String> {
+        region_host(url).ok().and_then(|host| region_cache().preferred(&host))
+    }
+
     /// Reports that `failed_url` (a region URL previously returned for `url`'s
     /// host) could not be connected to, dropping it from the cache so it is not
     /// handed out again. When the host's last region URL is dropped the whole
@@ -167,8 +226,9 @@ impl RegionUrlProvider {
 pub(crate) async fn fetch_from_endpoint(
     endpoint_url: &str,
     token: &str,
+    transport: &TransportOptions,
 ) -> SignalResult<(Vec<String>, Option<Duration>)> {
-    let http = super::require_http_client()?;
+    let http = super::require_http_client(transport)?;
     let headers = super::bearer_headers(token);
     let endpoint_url = endpoint_url.to_string();
 
@@ -203,18 +263,42 @@ pub(crate) async fn fetch_from_endpoint(
         .map_err(|_| SignalError::RegionError("region fetch timed out".into()))?
 }
 
-fn region_endpoint(url: &str) -> SignalResult<String> {
+/// Measures the round trip of a request to the root of `url`'s server. Any HTTP
+/// response counts, since only reachability and latency matter here.
+async fn probe_rtt(url: String, transport: TransportOptions) -> Option<Duration> {
+    let http = super::require_http_client(&transport).ok()?;
+    let probe_url = http_endpoint(&url, "/").ok()?;
+    let started = Instant::now();
+    match livekit_runtime::timeout(REGION_PROBE_TIMEOUT, http.get(probe_url, vec![])).await {
+        Ok(Ok(_)) => Some(started.elapsed()),
+        Ok(Err(err)) => {
+            log::debug!("region probe to {url} failed: {}", error_with_chain(&err));
+            None
+        }
+        Err(_) => {
+            log::debug!("region probe to {url} timed out");
+            None
+        }
+    }
+}
+
+/// Maps a signalling URL onto the HTTP(S) URL of `path` on the same server.
+fn http_endpoint(url: &str, path: &str) -> SignalResult<String> {
     let mut url = url::Url::parse(url).map_err(|err| SignalError::UrlParse(err.to_string()))?;
     match url.scheme() {
         "wss" => url.set_scheme("https").unwrap(),
         "ws" => url.set_scheme("http").unwrap(),
         _ => (),
     }
-    url.set_path("/settings/regions");
+    url.set_path(path);
 
     Ok(url.to_string())
 }
 
+fn region_endpoint(url: &str) -> SignalResult<String> {
+    http_endpoint(url, "/settings/regions")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -400,8 +484,12 @@ mod tests {
 
     #[tokio::test]
     async fn test_fetch_non_cloud_url_returns_empty() {
-        let result =
-            RegionUrlProvider::fetch_region_urls("wss://localhost:7880", "fake-token").await;
+        let result = RegionUrlProvider::fetch_region_urls(
+            "wss://localhost:7880",
+            "fake-token",
+            &Default::default(),
+        )
+        .await;
         assert_eq!(result.unwrap(), Vec::<String>::new());
     }
 }
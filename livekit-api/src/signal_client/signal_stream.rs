@@ -15,11 +15,14 @@
 use livekit_protocol as proto;
 use livekit_runtime::JoinHandle;
 use prost::Message as ProtoMessage;
-use std::{sync::Arc, time::Duration};
+use std::sync::Arc;
 
 use tokio::sync::{mpsc, oneshot};
 
-use super::{SignalError, SignalResult};
+use super::{
+    recording::{RecordKind, SignalRecorder},
+    SignalError, SignalOptions, SignalResult,
+};
 
 #[derive(Debug)]
 enum InternalMessage {
@@ -48,7 +51,7 @@ impl SignalStream {
     pub async fn connect(
         url: url::Url,
         token: &str,
-        connect_timeout: Duration,
+        options: &SignalOptions,
     ) -> SignalResult<(Self, mpsc::UnboundedReceiver<Box<proto::signal_response::Message>>)> {
         log::info!("connecting to {}", livekit_net::redact_url(&url));
 
@@ -57,27 +60,40 @@ impl SignalStream {
         // failure that the caller would pointlessly retry.
         super::check_token_format(token)?;
 
-        let transport = super::require_ws_client()?;
+        let transport = super::require_ws_client(options)?;
 
         let headers = super::bearer_headers(token);
+        let connect_timeout = options.connect_timeout;
+        let recorder = options.recorder.clone();
 
         // Delegate the connect deadline to the transport via `timeout_ms`, but keep
         // an outer Rust-side timeout as a backstop: a foreign/host transport that
         // ignores or mishandles `timeout_ms` must not be able to hang connect (and
         // thus the engine's reconnect loop) forever.
-        let conn = livekit_runtime::timeout(
+        let result = livekit_runtime::timeout(
             connect_timeout,
             transport.connect(url.to_string(), headers, connect_timeout.as_millis() as u64),
         )
-        .await
-        .map_err(|_| SignalError::Timeout("signal connection timed out".into()))??
-        .connection;
+        .await;
+
+        if let Some(recorder) = &recorder {
+            match &result {
+                Ok(Ok(_)) => recorder.record_connect(&url, Ok(())),
+                Ok(Err(err)) => recorder.record_connect(&url, Err(err)),
+                Err(_) => recorder.record_connect(&url, Err(&livekit_net::TransportError::Timeout)),
+            }
+        }
+
+        let conn = result
+            .map_err(|_| SignalError::Timeout("signal connection timed out".into()))??
+            .connection;
 
         let (emitter, events) = mpsc::unbounded_channel();
         let (internal_tx, internal_rx) = mpsc::channel::<InternalMessage>(8);
-        let write_handle = livekit_runtime::spawn(Self::write_task(internal_rx, conn.clone()));
+        let write_handle =
+            livekit_runtime::spawn(Self::write_task(internal_rx, conn.clone(), recorder.clone()));
         let read_handle =
-            livekit_runtime::spawn(Self::read_task(internal_tx.clone(), conn, emitter));
+            livekit_runtime::spawn(Self::read_task(internal_tx.clone(), conn, emitter, recorder));
 
         Ok((Self { internal_tx, read_handle, write_handle }, events))
     }
@@ -106,11 +122,15 @@ impl SignalStream {
     async fn write_task(
         mut internal_rx: mpsc::Receiver<InternalMessage>,
         conn: Arc<dyn livekit_net::WsConnection>,
+        recorder: Option<SignalRecorder>,
     ) {
         while let Some(msg) = internal_rx.recv().await {
             match msg {
                 InternalMessage::Signal { signal, response_chn } => {
                     let data = proto::SignalRequest { message: Some(signal) }.encode_to_vec();
+                    if let Some(recorder) = &recorder {
+                        recorder.record(RecordKind::SignalRequest, &data);
+                    }
 
                     if let Err(err) = conn.send(data).await {
                         // A send failure is a broken/closed socket, not a timeout —
@@ -135,10 +155,14 @@ impl SignalStream {
         internal_tx: mpsc::Sender<InternalMessage>,
         conn: Arc<dyn livekit_net::WsConnection>,
         emitter: mpsc::UnboundedSender<Box<proto::signal_response::Message>>,
+        recorder: Option<SignalRecorder>,
     ) {
         loop {
             match conn.recv().await {
                 Ok(Some(bytes)) => {
+                    if let Some(recorder) = &recorder {
+                        recorder.record(RecordKind::SignalResponse, &bytes);
+                    }
                     match proto::SignalResponse::decode(bytes.as_slice()) {
                         Ok(res) => {
                             if let Some(msg) = res.message {
@@ -0,0 +1,83 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sample plugin applying a fixed gain.
+//!
+//! Options: `{"gain": 0.5}`. `{"panic": true}` makes the session panic on the first frame, to
+//! exercise the host's error handling. Loading fails without a token, and
+//! `gain_unload_count` reports how many times the library was unloaded.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use livekit_audio_plugin::{
+    export_audio_filter, AudioFilter, FilterError, LoadOptions, SessionConfig,
+};
+use serde::Deserialize;
+
+static UNLOADS: AtomicU32 = AtomicU32::new(0);
+
+#[no_mangle]
+pub extern "C" fn gain_unload_count() -> u32 {
+    UNLOADS.load(Ordering::SeqCst)
+}
+
+#[derive(Deserialize)]
+#[serde(default)]
+struct GainOptions {
+    gain: f32,
+    panic: bool,
+}
+
+impl Default for GainOptions {
+    fn default() -> Self {
+        Self { gain: 1.0, panic: false }
+    }
+}
+
+struct Gain {
+    options: GainOptions,
+}
+
+impl AudioFilter for Gain {
+    fn on_load(options: &LoadOptions) -> Result<(), FilterError> {
+        if options.token.is_empty() {
+            return Err(FilterError::new("missing token"));
+        }
+        Ok(())
+    }
+
+    fn on_unload() {
+        UNLOADS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn create(config: SessionConfig) -> Result<Self, FilterError> {
+        let options = match config.options.as_str() {
+            "" => GainOptions::default(),
+            options => serde_json::from_str(options)
+                .map_err(|e| FilterError::new(format!("invalid options: {e}")))?,
+        };
+        Ok(Self { options })
+    }
+
+    fn process_i16(&mut self, input: &[i16], output: &mut [i16]) {
+        if self.options.panic {
+            panic!("gain filter asked to panic");
+        }
+        for (dst, src) in output.iter_mut().zip(input) {
+            *dst = (*src as f32 * self.options.gain).clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+        }
+    }
+}
+
+export_audio_filter!(Gain);
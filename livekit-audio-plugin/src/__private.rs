@@ -0,0 +1,270 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of the entry points generated by [`export_audio_filter!`](crate::export_audio_filter).
+//!
+//! Every function takes the raw arguments of its C entry point, the host is trusted to pass
+//! valid pointers and to never call a session concurrently.
+
+#![allow(clippy::missing_safety_doc)]
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    ffi::{c_char, c_void, CStr, CString},
+    panic::{catch_unwind, AssertUnwindSafe},
+};
+
+use serde::de::DeserializeOwned;
+
+use crate::{
+    abi::{capability, error_kind, ABI_VERSION},
+    AudioFilter, FilterError, LoadOptions, SessionConfig,
+};
+
+struct PluginError {
+    kind: i32,
+    message: CString,
+}
+
+impl PluginError {
+    fn new(kind: i32, message: String) -> Self {
+        let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+        Self { kind, message }
+    }
+
+    fn failed(err: FilterError) -> Self {
+        Self::new(error_kind::FAILED, err.0)
+    }
+
+    fn panic(payload: Box<dyn Any + Send>) -> Self {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "unknown panic".to_owned()
+        };
+        Self::new(error_kind::PANIC, message)
+    }
+}
+
+thread_local! {
+    /// Error of the last call not made on a session, per thread so the message returned by
+    /// [`last_error`] can't be freed by a call made on another thread.
+    static GLOBAL_ERROR: RefCell<Option<PluginError>> = const { RefCell::new(None) };
+}
+
+fn set_global_error(error: Option<PluginError>) {
+    GLOBAL_ERROR.with(|global| *global.borrow_mut() = error);
+}
+
+struct Session<T> {
+    filter: T,
+    error: Option<PluginError>,
+    poisoned: bool,
+}
+
+impl<T> Session<T> {
+    /// Runs `f` on the filter, catching panics. Returns false when the session is poisoned.
+    fn run(&mut self, f: impl FnOnce(&mut T)) -> bool {
+        if self.poisoned {
+            self.error = Some(PluginError::new(
+                error_kind::PANIC,
+                "session poisoned by an earlier panic".to_owned(),
+            ));
+            return false;
+        }
+
+        self.error = None;
+        if let Err(payload) = catch_unwind(AssertUnwindSafe(|| f(&mut self.filter))) {
+            self.error = Some(PluginError::panic(payload));
+            self.poisoned = true;
+            return false;
+        }
+        true
+    }
+}
+
+unsafe fn session<'a, T>(session: *const c_void) -> Option<&'a mut Session<T>> {
+    (session as *mut Session<T>).as_mut()
+}
+
+unsafe fn to_string(s: *const c_char) -> String {
+    if s.is_null() {
+        return String::new();
+    }
+    CStr::from_ptr(s).to_string_lossy().into_owned()
+}
+
+unsafe fn from_json<D: DeserializeOwned + Default>(s: *const c_char) -> D {
+    serde_json::from_str(&to_string(s)).unwrap_or_default()
+}
+
+unsafe fn slice<'a, S>(data: *const S, len: usize) -> &'a [S] {
+    if data.is_null() || len == 0 {
+        return &[];
+    }
+    std::slice::from_raw_parts(data, len)
+}
+
+unsafe fn slice_mut<'a, S>(data: *mut S, len: usize) -> &'a mut [S] {
+    if data.is_null() || len == 0 {
+        return &mut [];
+    }
+    std::slice::from_raw_parts_mut(data, len)
+}
+
+pub unsafe fn negotiate<T: AudioFilter>(host_version: u32, capabilities: *mut u32) -> u32 {
+    if !capabilities.is_null() {
+        let mut caps = capability::FLOAT
+            | capability::STREAM_INFO
+            | capability::TOKEN_REFRESH
+            | capability::ERRORS
+            | capability::UNLOAD;
+        if T::SEPARATE_RATES {
+            caps |= capability::SEPARATE_RATES;
+        }
+        *capabilities = caps;
+    }
+    // The legacy entry points are always exported, so any older host is fine
+    host_version.min(ABI_VERSION)
+}
+
+pub unsafe fn last_error<T: AudioFilter>(session: *const c_void, kind: *mut i32) -> *const c_char {
+    let (error_kind, message) = match self::session::<T>(session) {
+        Some(session) => match &session.error {
+            Some(error) => (error.kind, error.message.as_ptr()),
+            None => return std::ptr::null(),
+        },
+        // The message stays alive until the next global call on this thread replaces it
+        None => match GLOBAL_ERROR.with(|global| {
+            global.borrow().as_ref().map(|error| (error.kind, error.message.as_ptr()))
+        }) {
+            Some(error) => error,
+            None => return std::ptr::null(),
+        },
+    };
+    if !kind.is_null() {
+        *kind = error_kind;
+    }
+    message
+}
+
+pub unsafe fn on_load<T: AudioFilter>(options: *const c_char) -> i32 {
+    let options: LoadOptions = from_json(options);
+    let (code, error) = match catch_unwind(|| T::on_load(&options)) {
+        Ok(Ok(())) => (0, None),
+        Ok(Err(err)) => (-1, Some(PluginError::failed(err))),
+        Err(payload) => (-2, Some(PluginError::panic(payload))),
+    };
+    set_global_error(error);
+    code
+}
+
+pub fn on_unload<T: AudioFilter>() {
+    set_global_error(catch_unwind(T::on_unload).err().map(PluginError::panic));
+}
+
+pub unsafe fn update_token<T: AudioFilter>(url: *const c_char, token: *const c_char) {
+    let (url, token) = (to_string(url), to_string(token));
+    set_global_error(catch_unwind(|| T::update_token(&url, &token)).err().map(PluginError::panic));
+}
+
+pub unsafe fn create<T: AudioFilter>(
+    input_sample_rate: u32,
+    output_sample_rate: u32,
+    options: *const c_char,
+    stream_info: *const c_char,
+) -> *mut c_void {
+    if input_sample_rate != output_sample_rate && !T::SEPARATE_RATES {
+        set_global_error(Some(PluginError::failed(FilterError::new(
+            "the filter does not support different input and output sample rates",
+        ))));
+        return std::ptr::null_mut();
+    }
+
+    let config = SessionConfig {
+        input_sample_rate,
+        output_sample_rate,
+        options: to_string(options),
+        stream_info: from_json(stream_info),
+    };
+    let (session, error) = match catch_unwind(|| T::create(config)) {
+        Ok(Ok(filter)) => {
+            let session = Session { filter, error: None, poisoned: false };
+            (Box::into_raw(Box::new(session)) as *mut c_void, None)
+        }
+        Ok(Err(err)) => (std::ptr::null_mut(), Some(PluginError::failed(err))),
+        Err(payload) => (std::ptr::null_mut(), Some(PluginError::panic(payload))),
+    };
+    set_global_error(error);
+    session
+}
+
+pub unsafe fn destroy<T: AudioFilter>(session: *const c_void) {
+    if session.is_null() {
+        return;
+    }
+    let session = Box::from_raw(session as *mut Session<T>);
+    // Dropping a filter that panicked may panic again, it must not unwind into the host
+    let _ = catch_unwind(AssertUnwindSafe(move || drop(session)));
+}
+
+pub unsafe fn process_i16<T: AudioFilter>(
+    session: *const c_void,
+    in_num_samples: usize,
+    input: *const i16,
+    out_num_samples: usize,
+    output: *mut i16,
+) {
+    let output = slice_mut(output, out_num_samples);
+    let Some(session) = self::session::<T>(session) else {
+        output.fill(0);
+        return;
+    };
+    let input = slice(input, in_num_samples);
+    if !session.run(|filter| filter.process_i16(input, output)) {
+        output.fill(0);
+    }
+}
+
+pub unsafe fn process_f32<T: AudioFilter>(
+    session: *const c_void,
+    in_num_samples: usize,
+    input: *const f32,
+    out_num_samples: usize,
+    output: *mut f32,
+) {
+    let output = slice_mut(output, out_num_samples);
+    let Some(session) = self::session::<T>(session) else {
+        output.fill(0.0);
+        return;
+    };
+    let input = slice(input, in_num_samples);
+    if !session.run(|filter| filter.process_f32(input, output)) {
+        output.fill(0.0);
+    }
+}
+
+pub unsafe fn update_stream_info<T: AudioFilter>(
+    session: *const c_void,
+    stream_info: *const c_char,
+) {
+    let Some(session) = self::session::<T>(session) else {
+        return;
+    };
+    let info = from_json(stream_info);
+    session.run(|filter| filter.update_stream_info(info));
+}
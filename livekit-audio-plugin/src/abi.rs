@@ -0,0 +1,58 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Constants of the audio filter plugin C ABI, shared by plugins and the host.
+//!
+//! The `livekit` host keeps its own copy of these values, they must not change once released.
+//!
+//! Version 1 plugins export `audio_filter_create` and the `process` functions, version 2 adds the
+//! `_v2` functions taking separate input and output rates. Version 3 plugins also export:
+//!
+//! ```c
+//! // Returns the ABI version the plugin speaks with this host, 0 when it can't.
+//! uint32_t audio_filter_negotiate(uint32_t host_version, uint32_t* capabilities);
+//! // Error of the last call on `session`, or of the last global call made on the calling thread
+//! // when it is NULL. Returns NULL when that call succeeded. The message is valid until the next
+//! // call on `session`, or the next global call on the calling thread.
+//! const char* audio_filter_last_error(const void* session, int32_t* kind);
+//! // Called before the library is unloaded.
+//! void audio_filter_on_unload(void);
+//! ```
+
+/// Latest ABI version.
+pub const ABI_VERSION: u32 = 3;
+
+/// Bits of the `capabilities` reported by `audio_filter_negotiate`.
+pub mod capability {
+    /// Sessions may use different input and output sample rates.
+    pub const SEPARATE_RATES: u32 = 1 << 0;
+    /// `audio_filter_process_float` is implemented.
+    pub const FLOAT: u32 = 1 << 1;
+    /// `audio_filter_update_stream_info` is implemented.
+    pub const STREAM_INFO: u32 = 1 << 2;
+    /// `audio_filter_update_token` is implemented.
+    pub const TOKEN_REFRESH: u32 = 1 << 3;
+    /// `audio_filter_last_error` reports errors and panics.
+    pub const ERRORS: u32 = 1 << 4;
+    /// `audio_filter_on_unload` is implemented.
+    pub const UNLOAD: u32 = 1 << 5;
+}
+
+/// Kinds of errors returned by `audio_filter_last_error`.
+pub mod error_kind {
+    /// The call failed, e.g. a session couldn't be created.
+    pub const FAILED: i32 = 1;
+    /// The plugin panicked. A session that panicked outputs silence from then on.
+    pub const PANIC: i32 = 2;
+}
@@ -0,0 +1,261 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Safe Rust SDK for LiveKit audio filter plugins.
+//!
+//! Implement [`AudioFilter`] and export it with [`export_audio_filter!`] from a `cdylib` crate,
+//! the library can then be loaded with `livekit::AudioFilterPlugin`:
+//!
+//! ```
+//! use livekit_audio_plugin::{export_audio_filter, AudioFilter, FilterError, SessionConfig};
+//!
+//! struct Mute;
+//!
+//! impl AudioFilter for Mute {
+//!     fn create(_config: SessionConfig) -> Result<Self, FilterError> {
+//!         Ok(Mute)
+//!     }
+//!
+//!     fn process_i16(&mut self, _input: &[i16], output: &mut [i16]) {
+//!         output.fill(0);
+//!     }
+//! }
+//!
+//! export_audio_filter!(Mute);
+//! ```
+
+use serde::Deserialize;
+
+pub mod abi;
+
+#[doc(hidden)]
+pub mod __private;
+
+/// Error returned by an [`AudioFilter`], reported to the host.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{0}")]
+pub struct FilterError(pub String);
+
+impl FilterError {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self(message.into())
+    }
+}
+
+/// Options passed to [`AudioFilter::on_load`] when a room connects.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct LoadOptions {
+    pub url: String,
+    pub token: String,
+}
+
+/// The stream a session filters.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StreamInfo {
+    pub url: String,
+    pub room_id: String,
+    pub room_name: String,
+    pub participant_identity: String,
+    pub participant_id: String,
+    pub track_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionConfig {
+    pub input_sample_rate: u32,
+    /// Equal to `input_sample_rate` unless [`AudioFilter::SEPARATE_RATES`] is set.
+    pub output_sample_rate: u32,
+    /// Options given by the application when creating the stream, usually JSON.
+    pub options: String,
+    pub stream_info: StreamInfo,
+}
+
+/// An audio filter session.
+///
+/// Panics are caught at the plugin boundary and reported to the host. A session that panicked is
+/// not used again and outputs silence until it is destroyed.
+pub trait AudioFilter: Send + Sized + 'static {
+    /// Whether sessions can run with different input and output sample rates.
+    const SEPARATE_RATES: bool = false;
+
+    /// Called every time a room connects, before sessions are created for it.
+    fn on_load(_options: &LoadOptions) -> Result<(), FilterError> {
+        Ok(())
+    }
+
+    /// Called when the room token is refreshed.
+    fn update_token(_url: &str, _token: &str) {}
+
+    /// Called before the host unloads the library.
+    fn on_unload() {}
+
+    fn create(config: SessionConfig) -> Result<Self, FilterError>;
+
+    /// Filters interleaved samples. Both slices cover the same duration, at the input and output
+    /// rates of the session.
+    fn process_i16(&mut self, input: &[i16], output: &mut [i16]);
+
+    /// Like [`process_i16`](Self::process_i16) with samples in `-1.0..=1.0`. Converts to `i16`
+    /// unless overridden.
+    fn process_f32(&mut self, input: &[f32], output: &mut [f32]) {
+        let input: Vec<i16> = input.iter().map(|s| (s.clamp(-1.0, 1.0) * 32767.0) as i16).collect();
+        let mut out = vec![0i16; output.len()];
+        self.process_i16(&input, &mut out);
+        for (dst, src) in output.iter_mut().zip(out) {
+            *dst = src as f32 / 32767.0;
+        }
+    }
+
+    fn update_stream_info(&mut self, _info: StreamInfo) {}
+}
+
+/// Exports the C entry points of an [`AudioFilter`] implementation.
+///
+/// Must be invoked once, in a `cdylib` crate.
+#[macro_export]
+macro_rules! export_audio_filter {
+    ($filter:ty) => {
+        const _: () = {
+            use ::std::ffi::{c_char, c_void};
+            use $crate::__private as private;
+
+            #[no_mangle]
+            pub unsafe extern "C" fn audio_filter_negotiate(
+                host_version: u32,
+                capabilities: *mut u32,
+            ) -> u32 {
+                private::negotiate::<$filter>(host_version, capabilities)
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn audio_filter_last_error(
+                session: *const c_void,
+                kind: *mut i32,
+            ) -> *const c_char {
+                private::last_error::<$filter>(session, kind)
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn audio_filter_on_load(options: *const c_char) -> i32 {
+                private::on_load::<$filter>(options)
+            }
+
+            #[no_mangle]
+            pub extern "C" fn audio_filter_on_unload() {
+                private::on_unload::<$filter>()
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn audio_filter_update_token(
+                url: *const c_char,
+                token: *const c_char,
+            ) {
+                private::update_token::<$filter>(url, token)
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn audio_filter_create(
+                sample_rate: u32,
+                options: *const c_char,
+                stream_info: *const c_char,
+            ) -> *mut c_void {
+                private::create::<$filter>(sample_rate, sample_rate, options, stream_info)
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn audio_filter_create_v2(
+                input_sample_rate: u32,
+                output_sample_rate: u32,
+                options: *const c_char,
+                stream_info: *const c_char,
+            ) -> *mut c_void {
+                private::create::<$filter>(
+                    input_sample_rate,
+                    output_sample_rate,
+                    options,
+                    stream_info,
+                )
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn audio_filter_destroy(session: *const c_void) {
+                private::destroy::<$filter>(session)
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn audio_filter_process_int16(
+                session: *const c_void,
+                num_samples: usize,
+                input: *const i16,
+                output: *mut i16,
+            ) {
+                private::process_i16::<$filter>(session, num_samples, input, num_samples, output)
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn audio_filter_process_int16_v2(
+                session: *const c_void,
+                in_num_samples: usize,
+                input: *const i16,
+                out_num_samples: usize,
+                output: *mut i16,
+            ) {
+                private::process_i16::<$filter>(
+                    session,
+                    in_num_samples,
+                    input,
+                    out_num_samples,
+                    output,
+                )
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn audio_filter_process_float(
+                session: *const c_void,
+                num_samples: usize,
+                input: *const f32,
+                output: *mut f32,
+            ) {
+                private::process_f32::<$filter>(session, num_samples, input, num_samples, output)
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn audio_filter_process_float_v2(
+                session: *const c_void,
+                in_num_samples: usize,
+                input: *const f32,
+                out_num_samples: usize,
+                output: *mut f32,
+            ) {
+                private::process_f32::<$filter>(
+                    session,
+                    in_num_samples,
+                    input,
+                    out_num_samples,
+                    output,
+                )
+            }
+
+            #[no_mangle]
+            pub unsafe extern "C" fn audio_filter_update_stream_info(
+                session: *const c_void,
+                stream_info: *const c_char,
+            ) {
+                private::update_stream_info::<$filter>(session, stream_info)
+            }
+        };
+    };
+}
@@ -0,0 +1,167 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Loads the `gain` example plugin and drives it through its C entry points, the way
+//! `livekit::AudioFilterPlugin` does.
+
+use std::{
+    ffi::{c_char, c_void, CStr, CString},
+    path::PathBuf,
+    process::Command,
+    sync::{Mutex, MutexGuard},
+};
+
+use libloading::{Library, Symbol};
+use livekit_audio_plugin::abi::{capability, error_kind, ABI_VERSION};
+
+type NegotiateFn = unsafe extern "C" fn(u32, *mut u32) -> u32;
+type LastErrorFn = unsafe extern "C" fn(*const c_void, *mut i32) -> *const c_char;
+type OnLoadFn = unsafe extern "C" fn(*const c_char) -> i32;
+type CreateV2Fn = unsafe extern "C" fn(u32, u32, *const c_char, *const c_char) -> *mut c_void;
+type DestroyFn = unsafe extern "C" fn(*const c_void);
+type ProcessI16Fn = unsafe extern "C" fn(*const c_void, usize, *const i16, *mut i16);
+type OnUnloadFn = unsafe extern "C" fn();
+
+/// `cargo test` builds the examples next to the test binaries, build it when it is missing
+/// (e.g. when running a single test target).
+fn plugin_path() -> PathBuf {
+    let file_name = format!("{}gain{}", std::env::consts::DLL_PREFIX, std::env::consts::DLL_SUFFIX);
+    let exe = std::env::current_exe().unwrap();
+    let examples = exe.parent().unwrap().parent().unwrap().join("examples");
+    let path = examples.join(&file_name);
+    if path.exists() {
+        return path;
+    }
+
+    // A separate target dir, the outer cargo still holds the lock on ours
+    let target_dir = exe.parent().unwrap().parent().unwrap().join("sample-plugin");
+    let status = Command::new(env!("CARGO"))
+        .args(["build", "--example", "gain", "--manifest-path"])
+        .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .status()
+        .expect("failed to build the sample plugin");
+    assert!(status.success());
+    target_dir.join("debug").join("examples").join(file_name)
+}
+
+/// Tests share the loaded library, and so its global error.
+static PLUGIN_LOCK: Mutex<()> = Mutex::new(());
+
+struct Plugin {
+    lib: Library,
+    _guard: MutexGuard<'static, ()>,
+}
+
+impl Plugin {
+    fn load() -> Self {
+        let guard = PLUGIN_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        Self { lib: unsafe { Library::new(plugin_path()) }.unwrap(), _guard: guard }
+    }
+
+    fn symbol<T>(&self, name: &str) -> Symbol<'_, T> {
+        unsafe { self.lib.get(name.as_bytes()) }.unwrap()
+    }
+
+    fn last_error(&self, session: *const c_void) -> Option<(i32, String)> {
+        let last_error = self.symbol::<LastErrorFn>("audio_filter_last_error");
+        let mut kind = 0;
+        let message = unsafe { last_error(session, &mut kind) };
+        (!message.is_null())
+            .then(|| (kind, unsafe { CStr::from_ptr(message) }.to_string_lossy().into_owned()))
+    }
+
+    fn create(&self, input_rate: u32, output_rate: u32, options: &str) -> *mut c_void {
+        let create = self.symbol::<CreateV2Fn>("audio_filter_create_v2");
+        let options = CString::new(options).unwrap();
+        let stream_info = CString::new(r#"{"roomName":"room","trackId":"TR_test"}"#).unwrap();
+        unsafe { create(input_rate, output_rate, options.as_ptr(), stream_info.as_ptr()) }
+    }
+
+    fn process(&self, session: *const c_void, input: &[i16]) -> Vec<i16> {
+        let process = self.symbol::<ProcessI16Fn>("audio_filter_process_int16");
+        let mut output = vec![-1; input.len()];
+        unsafe { process(session, input.len(), input.as_ptr(), output.as_mut_ptr()) };
+        output
+    }
+
+    fn destroy(&self, session: *const c_void) {
+        let destroy = self.symbol::<DestroyFn>("audio_filter_destroy");
+        unsafe { destroy(session) };
+    }
+}
+
+#[test]
+fn sample_plugin_lifecycle() {
+    let plugin = Plugin::load();
+
+    let negotiate = plugin.symbol::<NegotiateFn>("audio_filter_negotiate");
+    let mut caps = 0;
+    assert_eq!(unsafe { negotiate(ABI_VERSION, &mut caps) }, ABI_VERSION);
+    assert_eq!(unsafe { negotiate(2, std::ptr::null_mut()) }, 2);
+    assert_ne!(caps & capability::ERRORS, 0);
+    assert_ne!(caps & capability::UNLOAD, 0);
+    assert_eq!(caps & capability::SEPARATE_RATES, 0);
+
+    let on_load = plugin.symbol::<OnLoadFn>("audio_filter_on_load");
+    let options = CString::new(r#"{"url":"wss://example","token":"token"}"#).unwrap();
+    assert_eq!(unsafe { on_load(options.as_ptr()) }, 0);
+    assert_eq!(plugin.last_error(std::ptr::null()), None);
+
+    let session = plugin.create(48000, 48000, r#"{"gain":2.0}"#);
+    assert!(!session.is_null());
+    assert_eq!(plugin.process(session, &[1, -2, 20000]), vec![2, -4, i16::MAX]);
+    assert_eq!(plugin.last_error(session), None);
+    plugin.destroy(session);
+
+    let on_unload = plugin.symbol::<OnUnloadFn>("audio_filter_on_unload");
+    unsafe { on_unload() };
+}
+
+#[test]
+fn sample_plugin_reports_errors() {
+    let plugin = Plugin::load();
+
+    assert!(plugin.create(48000, 16000, "").is_null());
+    let (kind, message) = plugin.last_error(std::ptr::null()).unwrap();
+    assert_eq!(kind, error_kind::FAILED);
+    assert!(message.contains("sample rates"), "{message}");
+
+    assert!(plugin.create(48000, 48000, "not json").is_null());
+    let (kind, message) = plugin.last_error(std::ptr::null()).unwrap();
+    assert_eq!(kind, error_kind::FAILED);
+    assert!(message.starts_with("invalid options"), "{message}");
+}
+
+#[test]
+fn sample_plugin_catches_panics() {
+    let plugin = Plugin::load();
+
+    let session = plugin.create(48000, 48000, r#"{"panic":true}"#);
+    assert!(!session.is_null());
+
+    assert_eq!(plugin.process(session, &[1, 2, 3]), vec![0, 0, 0]);
+    let (kind, message) = plugin.last_error(session).unwrap();
+    assert_eq!(kind, error_kind::PANIC);
+    assert_eq!(message, "gain filter asked to panic");
+
+    // Poisoned, the filter is not called again
+    assert_eq!(plugin.process(session, &[1, 2, 3]), vec![0, 0, 0]);
+    let (kind, message) = plugin.last_error(session).unwrap();
+    assert_eq!(kind, error_kind::PANIC);
+    assert!(message.contains("poisoned"), "{message}");
+
+    plugin.destroy(session);
+}
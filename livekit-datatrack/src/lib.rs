@@ -20,6 +20,15 @@ mod track;
 /// Schema and frame encoding metadata for typed tracks.
 mod schema;
 
+/// Opt-in validation of frames against schema definitions.
+mod validation;
+
+/// Delivery statistics for local and remote tracks.
+mod stats;
+
+/// Typed publishing and subscribing over serde and prost codecs.
+mod typed;
+
 /// Local track publication.
 mod local;
 
@@ -43,7 +52,10 @@ mod error;
 
 /// Public APIs re-exported by client SDKs.
 pub mod api {
-    pub use crate::{error::*, frame::*, local::*, remote::*, schema::*, track::*};
+    pub use crate::{
+        error::*, frame::*, local::*, remote::*, schema::*, stats::*, track::*, typed::*,
+        validation::*,
+    };
 }
 
 /// Internal APIs used within client SDKs to power data tracks functionality.
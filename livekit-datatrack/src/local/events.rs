@@ -31,6 +31,19 @@ pub enum InputEvent {
     UnpublishRequest(UnpublishRequest),
     SfuPublishResponse(SfuPublishResponse),
     SfuUnpublishResponse(SfuUnpublishResponse),
+    /// NACK has been received from a subscriber.
+    ///
+    /// This is the payload of a [`remote::OutputEvent::NackAvailable`](crate::backend::remote::OutputEvent::NackAvailable)
+    /// event produced by the subscriber, delivered over the reliable data channel.
+    ///
+    NackReceived(Bytes),
+    /// Packets from an [`OutputEvent::PacketsAvailable`] event were discarded by the
+    /// transport before being sent.
+    ///
+    /// Packets must all belong to the same frame. This is only used for reporting
+    /// [statistics](crate::api::LocalDataTrackStats::send_queue_drops).
+    ///
+    PacketsDropped(Vec<Bytes>),
     /// Republish all tracks.
     ///
     /// This must be sent after a full reconnect in order for existing publications
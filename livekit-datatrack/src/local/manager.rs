@@ -15,20 +15,26 @@
 use super::{
     events::*,
     pipeline::{Pipeline, PipelineOptions},
+    retransmit::RetransmitBuffer,
     LocalTrackInner,
 };
 use crate::{
-    api::{DataTrackFrame, DataTrackInfo, DataTrackOptions, InternalError, PublishError},
+    api::{
+        DataTrackFrame, DataTrackInfo, DataTrackOptions, DataTrackReliability, FrameValidator,
+        InternalError, PublishError,
+    },
     e2ee::EncryptionProvider,
     local::LocalDataTrack,
-    packet::{self, Handle},
+    packet::{self, Handle, Nack, Packet},
+    stats::LocalCounters,
 };
 use anyhow::{anyhow, Context};
+use bytes::Bytes;
 use futures_core::Stream;
 use std::{
     collections::HashMap,
     pin::Pin,
-    sync::Arc,
+    sync::{atomic::Ordering, Arc},
     task::{Context as TaskContext, Poll},
     time::Duration,
 };
@@ -99,6 +105,8 @@ impl Manager {
                 InputEvent::SfuUnpublishResponse(event) => {
                     self.on_sfu_unpublish_response(event).await
                 }
+                InputEvent::NackReceived(bytes) => self.on_nack_received(bytes),
+                InputEvent::PacketsDropped(packets) => self.on_packets_dropped(packets),
                 InputEvent::RepublishTracks => self.on_republish_tracks().await,
                 InputEvent::Shutdown => break,
             }
@@ -129,7 +137,9 @@ impl Manager {
         }
 
         let (result_tx, result_rx) = oneshot::channel();
-        self.descriptors.insert(handle, Descriptor::Pending(result_tx));
+        let reliability = event.options.reliability;
+        let validator = event.options.validator;
+        self.descriptors.insert(handle, Descriptor::Pending { result_tx, reliability, validator });
 
         livekit_runtime::spawn(Self::forward_publish_result(
             handle,
@@ -207,12 +217,14 @@ impl Manager {
             return;
         };
         match descriptor {
-            Descriptor::Pending(result_tx) => {
+            Descriptor::Pending { result_tx, reliability, validator } => {
                 // SFU accepted initial publication request
                 if result_tx.is_closed() {
                     return;
                 }
-                let result = event.result.map(|track_info| self.create_local_track(track_info));
+                let result = event
+                    .result
+                    .map(|track_info| self.create_local_track(track_info, reliability, validator));
                 _ = result_tx.send(result);
                 return;
             }
@@ -237,22 +249,37 @@ impl Manager {
         }
     }
 
-    fn create_local_track(&mut self, info: DataTrackInfo) -> LocalDataTrack {
+    fn create_local_track(
+        &mut self,
+        info: DataTrackInfo,
+        reliability: DataTrackReliability,
+        validator: Option<Arc<dyn FrameValidator>>,
+    ) -> LocalDataTrack {
         let info = Arc::new(info);
         let encryption_provider =
             if info.uses_e2ee() { self.encryption_provider.as_ref().map(Arc::clone) } else { None };
 
-        let pipeline_opts = PipelineOptions { info: info.clone(), encryption_provider };
+        let pipeline_opts =
+            PipelineOptions { info: info.clone(), encryption_provider, reliability };
         let pipeline = Pipeline::new(pipeline_opts);
 
         let (frame_tx, frame_rx) = mpsc::channel(Self::FRAME_BUFFER_COUNT);
         let (state_tx, state_rx) = watch::channel(PublishState::Published);
+        let (nack_tx, nack_rx) = mpsc::channel(Self::NACK_BUFFER_COUNT);
+
+        let retransmit_buffer = (reliability == DataTrackReliability::ReliableOrdered)
+            .then(|| RetransmitBuffer::new(Self::RETRANSMIT_BUFFER_PACKETS));
+        let counters = Arc::new(LocalCounters::default());
 
         let track_task = TrackTask {
             info: info.clone(),
+            reliability,
             pipeline,
+            retransmit_buffer,
             state_rx,
             frame_rx,
+            nack_rx,
+            counters: counters.clone(),
             event_in_tx: self.event_in_tx.clone(),
             event_out_tx: self.event_out_tx.clone(),
         };
@@ -260,10 +287,16 @@ impl Manager {
 
         self.descriptors.insert(
             info.pub_handle,
-            Descriptor::Active { info: info.clone(), state_tx: state_tx.clone(), task_handle },
+            Descriptor::Active {
+                info: info.clone(),
+                state_tx: state_tx.clone(),
+                nack_tx,
+                counters: counters.clone(),
+                task_handle,
+            },
         );
 
-        let inner = LocalTrackInner { frame_tx, state_tx };
+        let inner = LocalTrackInner { frame_tx, state_tx, validator, counters };
         LocalDataTrack::new(info, inner)
     }
 
@@ -271,6 +304,40 @@ impl Manager {
         self.remove_descriptor(event.handle);
     }
 
+    fn on_nack_received(&mut self, bytes: Bytes) {
+        let nack = match Nack::deserialize(bytes) {
+            Ok(nack) => nack,
+            Err(err) => {
+                log::error!("Failed to deserialize NACK: {}", err);
+                return;
+            }
+        };
+        let Some(Descriptor::Active { nack_tx, .. }) = self.descriptors.get(&nack.track_handle)
+        else {
+            log::debug!("NACK for inactive track {}", nack.track_handle);
+            return;
+        };
+        _ = nack_tx
+            .try_send(nack.sequences)
+            .inspect_err(|err| log::debug!("Cannot send NACK to track task: {}", err));
+    }
+
+    fn on_packets_dropped(&mut self, packets: Vec<Bytes>) {
+        // All packets belong to the same frame, so the first identifies the track.
+        let Some(first) = packets.into_iter().next() else { return };
+        let handle = match Packet::deserialize(first) {
+            Ok(packet) => packet.header.track_handle,
+            Err(err) => {
+                log::error!("Failed to deserialize dropped packet: {}", err);
+                return;
+            }
+        };
+        let Some(Descriptor::Active { counters, .. }) = self.descriptors.get(&handle) else {
+            return;
+        };
+        counters.send_queue_drops.fetch_add(1, Ordering::Relaxed);
+    }
+
     fn remove_descriptor(&mut self, handle: Handle) {
         let Some(descriptor) = self.descriptors.remove(&handle) else {
             return;
@@ -287,7 +354,7 @@ impl Manager {
         let descriptors = std::mem::take(&mut self.descriptors);
         for (handle, descriptor) in descriptors {
             match descriptor {
-                Descriptor::Pending(result_tx) => {
+                Descriptor::Pending { result_tx, .. } => {
                     // TODO: support republish for pending publications
                     _ = result_tx.send(Err(PublishError::Disconnected));
                 }
@@ -311,7 +378,7 @@ impl Manager {
     async fn shutdown(self) {
         for (_, descriptor) in self.descriptors {
             match descriptor {
-                Descriptor::Pending(result_tx) => {
+                Descriptor::Pending { result_tx, .. } => {
                     _ = result_tx.send(Err(PublishError::Disconnected))
                 }
                 Descriptor::Active { state_tx, task_handle, .. } => {
@@ -327,14 +394,25 @@ impl Manager {
 
     /// Maximum number of input and output events to buffer.
     const EVENT_BUFFER_COUNT: usize = 16;
+
+    /// Maximum number of incoming NACKs to buffer per track.
+    const NACK_BUFFER_COUNT: usize = 16;
+
+    /// Number of sent packets retained per track for retransmission.
+    const RETRANSMIT_BUFFER_PACKETS: usize = 256;
 }
 
 /// Task for an individual published data track.
 struct TrackTask {
     info: Arc<DataTrackInfo>,
+    reliability: DataTrackReliability,
     pipeline: Pipeline,
+    /// Present only for reliable tracks.
+    retransmit_buffer: Option<RetransmitBuffer>,
     state_rx: watch::Receiver<PublishState>,
     frame_rx: mpsc::Receiver<DataTrackFrame>,
+    nack_rx: mpsc::Receiver<Vec<u16>>,
+    counters: Arc<LocalCounters>,
     event_in_tx: mpsc::Sender<InputEvent>,
     event_out_tx: mpsc::Sender<OutputEvent>,
 }
@@ -350,13 +428,27 @@ impl TrackTask {
                 _ = self.state_rx.changed() => {
                     state = *self.state_rx.borrow();
                 }
-                Some(frame) = self.frame_rx.recv() => {
+                Some(mut frame) = self.frame_rx.recv() => {
                     if state == PublishState::Republishing {
                         // Drop frames while republishing.
+                        self.counters.frames_dropped.fetch_add(1, Ordering::Relaxed);
                         continue;
                     }
+                    if self.reliability == DataTrackReliability::LatestOnly {
+                        // Skip any frames already superseded by a newer one.
+                        while let Ok(newer) = self.frame_rx.try_recv() {
+                            self.counters.frames_dropped.fetch_add(1, Ordering::Relaxed);
+                            frame = newer;
+                        }
+                    }
                     self.process_and_send(frame);
                 }
+                Some(sequences) = self.nack_rx.recv() => {
+                    if state == PublishState::Republishing {
+                        continue;
+                    }
+                    self.retransmit(sequences);
+                }
             }
         }
 
@@ -372,13 +464,55 @@ impl TrackTask {
             .process_frame(frame)
             .inspect_err(|err| log::debug!("Process failed: {}", err))
         else {
+            self.counters.frames_dropped.fetch_add(1, Ordering::Relaxed);
             return;
         };
-        let packets: Vec<_> = packets.into_iter().map(|packet| packet.serialize()).collect();
-        _ = self
-            .event_out_tx
+        let packet_count = packets.len() as u64;
+        let byte_count: usize = packets.iter().map(|packet| packet.payload.len()).sum();
+        let packets: Vec<_> = packets
+            .into_iter()
+            .map(|packet| {
+                let sequence = packet.header.sequence;
+                let serialized = packet.serialize();
+                if let Some(buffer) = &mut self.retransmit_buffer {
+                    buffer.push(sequence, serialized.clone()); // Cheap clone
+                }
+                serialized
+            })
+            .collect();
+        if !self.send(packets) {
+            self.counters.frames_dropped.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        self.counters.frames_sent.fetch_add(1, Ordering::Relaxed);
+        self.counters.packets_sent.fetch_add(packet_count, Ordering::Relaxed);
+        self.counters.bytes_sent.fetch_add(byte_count as u64, Ordering::Relaxed);
+    }
+
+    /// Resend previously sent packets reported lost by a subscriber.
+    fn retransmit(&mut self, sequences: Vec<u16>) {
+        let Some(buffer) = &self.retransmit_buffer else {
+            log::debug!("Ignoring NACK for track without reliable delivery");
+            return;
+        };
+        let packets: Vec<_> =
+            sequences.into_iter().filter_map(|sequence| buffer.get(sequence)).collect();
+        if packets.is_empty() {
+            return;
+        }
+        log::trace!("Retransmitting {} packet(s)", packets.len());
+        let packet_count = packets.len() as u64;
+        if self.send(packets) {
+            self.counters.packets_retransmitted.fetch_add(packet_count, Ordering::Relaxed);
+        }
+    }
+
+    /// Hands packets to the transport, returning whether they were accepted.
+    fn send(&self, packets: Vec<Bytes>) -> bool {
+        self.event_out_tx
             .try_send(packets.into())
-            .inspect_err(|err| log::debug!("Cannot send packets to transport: {}", err));
+            .inspect_err(|err| log::debug!("Cannot send packets to transport: {}", err))
+            .is_ok()
     }
 }
 
@@ -389,14 +523,21 @@ enum Descriptor {
     /// The associated channel is used to send a result to the user,
     /// either the local track or a publish error.
     ///
-    Pending(oneshot::Sender<Result<LocalDataTrack, PublishError>>),
+    Pending {
+        result_tx: oneshot::Sender<Result<LocalDataTrack, PublishError>>,
+        reliability: DataTrackReliability,
+        validator: Option<Arc<dyn FrameValidator>>,
+    },
     /// Publication is active.
     ///
-    /// The associated channel is used to end the track task.
+    /// The associated channels are used to end the track task and to forward
+    /// NACKs received from subscribers.
     ///
     Active {
         info: Arc<DataTrackInfo>,
         state_tx: watch::Sender<PublishState>,
+        nack_tx: mpsc::Sender<Vec<u16>>,
+        counters: Arc<LocalCounters>,
         task_handle: livekit_runtime::JoinHandle<()>,
     },
 }
@@ -493,9 +634,8 @@ impl ManagerInput {
 mod tests {
     use super::*;
     use crate::{
-        api::DataTrackSid,
+        api::{DataTrackSid, FrameValidationError, PushFrameErrorReason},
         e2ee::{EncryptedPayload, EncryptionError, EncryptionProvider},
-        packet::Packet,
         utils::testing::expect_event,
     };
     use bytes::Bytes;
@@ -687,6 +827,87 @@ mod tests {
         assert!(packet.header.extensions.e2ee.is_some());
     }
 
+    #[tokio::test]
+    async fn test_publish_with_validator() {
+        #[derive(Debug)]
+        struct MaxLengthValidator;
+
+        impl FrameValidator for MaxLengthValidator {
+            fn validate(&self, payload: &[u8]) -> Result<(), FrameValidationError> {
+                if payload.len() > 2 {
+                    return Err(FrameValidationError::new("too long"));
+                }
+                Ok(())
+            }
+        }
+
+        let options = ManagerOptions { encryption_provider: None };
+        let (manager, input, mut output) = Manager::new(options);
+        livekit_runtime::spawn(manager.run());
+
+        let (result_tx, result_rx) = oneshot::channel();
+        let options =
+            DataTrackOptions::new("validated").with_validator(Arc::new(MaxLengthValidator));
+        input.send(PublishRequest { options, result_tx }.into()).unwrap();
+
+        let event = expect_event!(output, OutputEvent::SfuPublishRequest);
+        let mut info: DataTrackInfo = Faker.fake();
+        info.pub_handle = event.handle;
+        info.uses_e2ee = false;
+        let event = SfuPublishResponse { handle: event.handle, result: Ok(info) };
+        input.send(event.into()).unwrap();
+
+        let track = result_rx.await.unwrap().unwrap();
+
+        let err = track.try_push(vec![1, 2, 3].into()).unwrap_err();
+        assert!(matches!(err.reason(), PushFrameErrorReason::ValidationFailed));
+        assert_eq!(err.into_frame().payload.as_ref(), &[1, 2, 3]);
+
+        track.try_push(vec![1, 2].into()).unwrap();
+        let packets = expect_event!(output, OutputEvent::PacketsAvailable);
+        let packet = Packet::deserialize(packets.into_iter().next().unwrap()).unwrap();
+        assert_eq!(packet.payload.as_ref(), &[1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_stats() {
+        let options = ManagerOptions { encryption_provider: None };
+        let (manager, input, mut output) = Manager::new(options);
+        livekit_runtime::spawn(manager.run());
+
+        let (result_tx, result_rx) = oneshot::channel();
+        let options = DataTrackOptions::new("stats");
+        input.send(PublishRequest { options, result_tx }.into()).unwrap();
+
+        let event = expect_event!(output, OutputEvent::SfuPublishRequest);
+        let mut info: DataTrackInfo = Faker.fake();
+        info.pub_handle = event.handle;
+        info.uses_e2ee = false;
+        let event = SfuPublishResponse { handle: event.handle, result: Ok(info) };
+        input.send(event.into()).unwrap();
+
+        let track = result_rx.await.unwrap().unwrap();
+
+        track.try_push(vec![0xFA; 20_000].into()).unwrap();
+        let packets = expect_event!(output, OutputEvent::PacketsAvailable);
+        assert_eq!(packets.len(), 2);
+
+        input.send(InputEvent::PacketsDropped(packets)).unwrap();
+        // Events are handled in order, so the drop is counted once the query is answered.
+        input.query_tracks().await;
+
+        let stats = track.stats();
+        assert_eq!(stats.frames_pushed, 1);
+        assert_eq!(stats.frames_sent, 1);
+        assert_eq!(stats.packets_sent, 2);
+        assert_eq!(stats.bytes_sent, 20_000);
+        assert_eq!(stats.send_queue_drops, 1);
+
+        track.unpublish();
+        assert!(track.try_push(vec![1].into()).is_err());
+        assert_eq!(track.stats().frames_rejected, 1);
+    }
+
     #[tokio::test]
     async fn test_republish_tracks() {
         let options = ManagerOptions { encryption_provider: None };
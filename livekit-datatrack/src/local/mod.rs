@@ -13,11 +13,20 @@
 // limitations under the License.
 
 use crate::{
-    api::{DataTrack, DataTrackFrame, DataTrackInfo, DataTrackSchemaError, InternalError},
+    api::{
+        DataTrack, DataTrackFrame, DataTrackInfo, DataTrackReliability, DataTrackSchemaError,
+        InternalError,
+    },
     schema::{DataTrackFrameEncoding, DataTrackSchemaId},
+    stats::{LocalCounters, LocalDataTrackStats},
     track::DataTrackInner,
+    validation::FrameValidator,
+};
+use std::{
+    fmt,
+    marker::PhantomData,
+    sync::{atomic::Ordering, Arc},
 };
-use std::{fmt, marker::PhantomData, sync::Arc};
 use thiserror::Error;
 use tokio::sync::{mpsc, watch};
 
@@ -27,6 +36,7 @@ pub(crate) mod proto;
 
 mod packetizer;
 mod pipeline;
+mod retransmit;
 
 /// Data track published by the local participant.
 pub type LocalDataTrack = DataTrack<Local>;
@@ -80,8 +90,18 @@ impl DataTrack<Local> {
     /// - The track has been unpublished by the local participant or SFU
     /// - The room is no longer connected
     /// - Frames are being pushed too fast
+    /// - The frame was rejected by the track's [validator](DataTrackOptions::with_validator)
     ///
     pub fn try_push(&self, frame: DataTrackFrame) -> Result<(), PushFrameError> {
+        let counters = &self.inner().counters;
+        let result = self.try_push_inner(frame);
+        let counter =
+            if result.is_ok() { &counters.frames_pushed } else { &counters.frames_rejected };
+        counter.fetch_add(1, Ordering::Relaxed);
+        result
+    }
+
+    fn try_push_inner(&self, frame: DataTrackFrame) -> Result<(), PushFrameError> {
         match self.inner().publish_state() {
             manager::PublishState::Republishing => {
                 return Err(PushFrameError::new(frame, PushFrameErrorReason::QueueFull))?
@@ -91,12 +111,23 @@ impl DataTrack<Local> {
             }
             manager::PublishState::Published => {}
         }
+        if let Some(validator) = &self.inner().validator {
+            if let Err(err) = validator.validate(&frame.payload) {
+                log::debug!("Frame rejected: {}", err);
+                return Err(PushFrameError::new(frame, PushFrameErrorReason::ValidationFailed));
+            }
+        }
         self.inner()
             .frame_tx
             .try_send(frame)
             .map_err(|err| PushFrameError::new(err.into_inner(), PushFrameErrorReason::QueueFull))
     }
 
+    /// Returns delivery statistics for the track.
+    pub fn stats(&self) -> LocalDataTrackStats {
+        self.inner().counters.snapshot()
+    }
+
     /// Unpublishes the track.
     pub fn unpublish(&self) {
         self.inner().local_unpublish();
@@ -107,6 +138,8 @@ impl DataTrack<Local> {
 pub(crate) struct LocalTrackInner {
     pub frame_tx: mpsc::Sender<DataTrackFrame>,
     pub state_tx: watch::Sender<manager::PublishState>,
+    pub validator: Option<Arc<dyn FrameValidator>>,
+    pub counters: Arc<LocalCounters>,
 }
 
 impl LocalTrackInner {
@@ -156,6 +189,8 @@ pub struct DataTrackOptions {
     pub(crate) name: String,
     pub(crate) schema: Option<DataTrackSchemaId>,
     pub(crate) frame_encoding: Option<DataTrackFrameEncoding>,
+    pub(crate) reliability: DataTrackReliability,
+    pub(crate) validator: Option<Arc<dyn FrameValidator>>,
 }
 
 impl DataTrackOptions {
@@ -168,7 +203,13 @@ impl DataTrackOptions {
     /// - Must be unique per publisher
     ///
     pub fn new(name: impl Into<String>) -> Self {
-        Self { name: name.into(), schema: None, frame_encoding: None }
+        Self {
+            name: name.into(),
+            schema: None,
+            frame_encoding: None,
+            reliability: DataTrackReliability::default(),
+            validator: None,
+        }
     }
 
     /// Sets the schema associated with frames sent on the track.
@@ -180,6 +221,26 @@ impl DataTrackOptions {
     pub fn with_frame_encoding(self, encoding: DataTrackFrameEncoding) -> Self {
         Self { frame_encoding: Some(encoding), ..self }
     }
+
+    /// Sets the delivery guarantee for frames sent on the track.
+    ///
+    /// Defaults to [`DataTrackReliability::BestEffort`].
+    ///
+    pub fn with_reliability(self, reliability: DataTrackReliability) -> Self {
+        Self { reliability, ..self }
+    }
+
+    /// Sets a validator to check frames against before they are pushed.
+    ///
+    /// Frames which fail validation are rejected by [`LocalDataTrack::try_push`] with
+    /// [`PushFrameErrorReason::ValidationFailed`]. See [`validator_for_schema`] to create
+    /// a validator from the definition of the track's schema.
+    ///
+    /// [`validator_for_schema`]: crate::api::validator_for_schema
+    ///
+    pub fn with_validator(self, validator: Arc<dyn FrameValidator>) -> Self {
+        Self { validator: Some(validator), ..self }
+    }
 }
 
 impl From<String> for DataTrackOptions {
@@ -273,6 +334,8 @@ pub enum PushFrameErrorReason {
     TrackUnpublished,
     /// Frame was dropped due to the pipeline queue being full.
     QueueFull,
+    /// Frame does not conform to the track's schema.
+    ValidationFailed,
 }
 
 impl fmt::Display for PushFrameErrorReason {
@@ -280,6 +343,7 @@ impl fmt::Display for PushFrameErrorReason {
         match self {
             Self::TrackUnpublished => write!(f, "track unpublished"),
             Self::QueueFull => write!(f, "queue full"),
+            Self::ValidationFailed => write!(f, "validation failed"),
         }
     }
 }
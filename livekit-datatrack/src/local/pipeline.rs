@@ -14,10 +14,10 @@
 
 use super::packetizer::{Packetizer, PacketizerFrame};
 use crate::{
-    api::{DataTrackFrame, DataTrackInfo},
+    api::{DataTrackFrame, DataTrackInfo, DataTrackReliability},
     e2ee::{EncryptionError, EncryptionProvider},
     local::packetizer::PacketizerError,
-    packet::{self, Extensions, Packet, UserTimestampExt},
+    packet::{self, Extensions, Packet, ReliabilityExt, UserTimestampExt},
 };
 use from_variants::FromVariants;
 use std::sync::Arc;
@@ -26,11 +26,13 @@ use thiserror::Error;
 pub(super) struct PipelineOptions {
     pub info: Arc<DataTrackInfo>,
     pub encryption_provider: Option<Arc<dyn EncryptionProvider>>,
+    pub reliability: DataTrackReliability,
 }
 
 /// Pipeline for an individual published data track.
 pub(super) struct Pipeline {
     encryption_provider: Option<Arc<dyn EncryptionProvider>>,
+    reliability: Option<ReliabilityExt>,
     packetizer: Packetizer,
 }
 
@@ -47,11 +49,18 @@ impl Pipeline {
     pub fn new(options: PipelineOptions) -> Self {
         debug_assert_eq!(options.info.uses_e2ee, options.encryption_provider.is_some());
         let packetizer = Packetizer::new(options.info.pub_handle, Self::TRANSPORT_MTU);
-        Self { encryption_provider: options.encryption_provider, packetizer }
+        let reliability = match options.reliability {
+            DataTrackReliability::BestEffort => None,
+            DataTrackReliability::LatestOnly => Some(ReliabilityExt::LatestOnly),
+            DataTrackReliability::ReliableOrdered => Some(ReliabilityExt::ReliableOrdered),
+        };
+        Self { encryption_provider: options.encryption_provider, reliability, packetizer }
     }
 
     pub fn process_frame(&mut self, frame: DataTrackFrame) -> Result<Vec<Packet>, PipelineError> {
-        let frame = self.encrypt_if_needed(frame.into())?;
+        let mut frame: PacketizerFrame = frame.into();
+        frame.extensions.reliability = self.reliability;
+        let frame = self.encrypt_if_needed(frame)?;
         let packets = self.packetizer.packetize(frame)?;
         Ok(packets)
     }
@@ -84,6 +93,7 @@ impl From<DataTrackFrame> for PacketizerFrame {
             extensions: Extensions {
                 user_timestamp: frame.user_timestamp.map(UserTimestampExt),
                 e2ee: None,
+                reliability: None,
             },
         }
     }
@@ -100,7 +110,11 @@ mod tests {
         let mut info: DataTrackInfo = Faker.fake();
         info.uses_e2ee = false;
 
-        let options = PipelineOptions { info: info.into(), encryption_provider: None };
+        let options = PipelineOptions {
+            info: info.into(),
+            encryption_provider: None,
+            reliability: DataTrackReliability::BestEffort,
+        };
         let mut pipeline = Pipeline::new(options);
 
         let repeated_byte: u8 = Faker.fake();
@@ -114,8 +128,28 @@ mod tests {
 
         for packet in packets {
             assert!(packet.header.extensions.e2ee.is_none());
+            assert!(packet.header.extensions.reliability.is_none());
             assert!(!packet.payload.is_empty());
             assert!(packet.payload.iter().all(|byte| *byte == repeated_byte));
         }
     }
+
+    #[test]
+    fn test_process_frame_reliable() {
+        let mut info: DataTrackInfo = Faker.fake();
+        info.uses_e2ee = false;
+
+        let options = PipelineOptions {
+            info: info.into(),
+            encryption_provider: None,
+            reliability: DataTrackReliability::ReliableOrdered,
+        };
+        let mut pipeline = Pipeline::new(options);
+
+        let frame = DataTrackFrame::new(vec![0xFA; 20_000]);
+        let packets = pipeline.process_frame(frame).unwrap();
+        assert!(packets.iter().all(|packet| {
+            packet.header.extensions.reliability == Some(ReliabilityExt::ReliableOrdered)
+        }));
+    }
 }
@@ -0,0 +1,111 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use bytes::Bytes;
+use std::collections::VecDeque;
+
+/// Retains recently sent packets so they can be retransmitted when a subscriber
+/// reports them as lost.
+///
+/// Packets must be pushed in sequence order; once the buffer reaches capacity, the
+/// oldest packet is discarded to make room for the newest.
+///
+#[derive(Debug)]
+pub(super) struct RetransmitBuffer {
+    /// Serialized packets, contiguous by sequence number starting from the front.
+    packets: VecDeque<(u16, Bytes)>,
+    capacity: usize,
+}
+
+impl RetransmitBuffer {
+    /// Creates a buffer holding up to `capacity` packets.
+    pub fn new(capacity: usize) -> Self {
+        debug_assert!(capacity >= 1);
+        Self { packets: VecDeque::with_capacity(capacity), capacity }
+    }
+
+    /// Retains a serialized packet with the given sequence number.
+    pub fn push(&mut self, sequence: u16, packet: Bytes) {
+        if let Some((last, _)) = self.packets.back() {
+            if sequence != last.wrapping_add(1) {
+                // Discontinuity, older packets can no longer be located by offset.
+                self.packets.clear();
+            }
+        }
+        if self.packets.len() >= self.capacity {
+            self.packets.pop_front();
+        }
+        self.packets.push_back((sequence, packet));
+    }
+
+    /// Returns the serialized packet with the given sequence number if still retained.
+    pub fn get(&self, sequence: u16) -> Option<Bytes> {
+        let (first, _) = self.packets.front()?;
+        let offset = sequence.wrapping_sub(*first) as usize;
+        let (found, packet) = self.packets.get(offset)?;
+        debug_assert_eq!(*found, sequence);
+        Some(packet.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet(sequence: u16) -> Bytes {
+        Bytes::from(sequence.to_be_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_get_retained() {
+        let mut buffer = RetransmitBuffer::new(4);
+        for sequence in 10..14 {
+            buffer.push(sequence, packet(sequence));
+        }
+        for sequence in 10..14 {
+            assert_eq!(buffer.get(sequence), Some(packet(sequence)));
+        }
+        assert!(buffer.get(9).is_none());
+        assert!(buffer.get(14).is_none());
+    }
+
+    #[test]
+    fn test_evicts_oldest() {
+        let mut buffer = RetransmitBuffer::new(2);
+        for sequence in 0..3 {
+            buffer.push(sequence, packet(sequence));
+        }
+        assert!(buffer.get(0).is_none());
+        assert_eq!(buffer.get(2), Some(packet(2)));
+    }
+
+    #[test]
+    fn test_sequence_wraparound() {
+        let mut buffer = RetransmitBuffer::new(4);
+        for sequence in [u16::MAX - 1, u16::MAX, 0, 1] {
+            buffer.push(sequence, packet(sequence));
+        }
+        assert_eq!(buffer.get(u16::MAX), Some(packet(u16::MAX)));
+        assert_eq!(buffer.get(1), Some(packet(1)));
+    }
+
+    #[test]
+    fn test_discontinuity_clears() {
+        let mut buffer = RetransmitBuffer::new(4);
+        buffer.push(0, packet(0));
+        buffer.push(5, packet(5));
+        assert!(buffer.get(0).is_none());
+        assert_eq!(buffer.get(5), Some(packet(5)));
+    }
+}
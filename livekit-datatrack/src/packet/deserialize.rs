@@ -14,7 +14,7 @@
 
 use super::{
     consts::*, E2eeExt, ExtensionTag, Extensions, FrameMarker, Handle, HandleError, Header, Packet,
-    Timestamp, UserTimestampExt,
+    ReliabilityExt, Timestamp, UserTimestampExt,
 };
 use bytes::{Buf, Bytes};
 use thiserror::Error;
@@ -125,6 +125,10 @@ impl Extensions {
                 UserTimestampExt::TAG if len >= UserTimestampExt::LEN => {
                     extensions.user_timestamp = deserialize_ext!(UserTimestampExt, raw, len);
                 }
+                ReliabilityExt::TAG if len >= ReliabilityExt::LEN => {
+                    // Unknown modes are treated as best-effort.
+                    extensions.reliability = deserialize_ext!(ReliabilityExt, raw, len).flatten();
+                }
                 _ => {
                     // Skip over unknown or length-mismatched extensions (forward compatible).
                     if raw.remaining() < len {
@@ -146,6 +150,16 @@ impl UserTimestampExt {
     }
 }
 
+impl ReliabilityExt {
+    fn deserialize(raw: [u8; Self::LEN]) -> Option<Self> {
+        match raw[0] {
+            Self::LATEST_ONLY => Some(Self::LatestOnly),
+            Self::RELIABLE_ORDERED => Some(Self::ReliableOrdered),
+            _ => None,
+        }
+    }
+}
+
 impl E2eeExt {
     fn deserialize(raw: [u8; Self::LEN]) -> Self {
         let key_index = raw[0];
@@ -159,7 +173,7 @@ impl E2eeExt {
 mod tests {
     use super::*;
     use bytes::{BufMut, BytesMut};
-    use test_case::test_matrix;
+    use test_case::{test_case, test_matrix};
 
     /// Returns the simplest valid packet to use in test.
     fn valid_packet() -> BytesMut {
@@ -274,6 +288,23 @@ mod tests {
         );
     }
 
+    #[test_case(1, Some(ReliabilityExt::LatestOnly) ; "latest_only")]
+    #[test_case(2, Some(ReliabilityExt::ReliableOrdered) ; "reliable_ordered")]
+    #[test_case(9, None ; "unknown_mode")]
+    fn test_ext_reliability(mode: u8, expected: Option<ReliabilityExt>) {
+        let mut raw = valid_packet();
+        raw[0] |= 1 << EXT_FLAG_SHIFT; // Extension flag
+        raw.put_u16(1); // Extension words
+
+        raw.put_u8(3); // ID 3
+        raw.put_u8(1); // Length
+        raw.put_u8(mode);
+        raw.put_bytes(0, 3); // Padding
+
+        let packet = Packet::deserialize(raw.freeze()).unwrap();
+        assert_eq!(packet.header.extensions.reliability, expected);
+    }
+
     #[test]
     fn test_ext_forward_compat_longer_length() {
         let mut raw = valid_packet();
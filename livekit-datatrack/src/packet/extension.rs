@@ -19,6 +19,7 @@ use core::fmt;
 pub struct Extensions {
     pub user_timestamp: Option<UserTimestampExt>,
     pub e2ee: Option<E2eeExt>,
+    pub reliability: Option<ReliabilityExt>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -39,6 +40,19 @@ impl fmt::Debug for E2eeExt {
     }
 }
 
+/// Delivery mode requested by the publisher.
+///
+/// Absent for best-effort tracks, which is the default.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "__fuzz"), derive(fake::Dummy))]
+pub enum ReliabilityExt {
+    /// Only the most recent frame is of interest; stale frames may be discarded.
+    LatestOnly,
+    /// Lost packets should be requested for retransmission and delivered in order.
+    ReliableOrdered,
+}
+
 pub(super) type ExtensionTag = u8;
 
 impl UserTimestampExt {
@@ -50,3 +64,11 @@ impl E2eeExt {
     pub(super) const TAG: ExtensionTag = 1;
     pub(super) const LEN: usize = 13;
 }
+
+impl ReliabilityExt {
+    pub(super) const TAG: ExtensionTag = 3;
+    pub(super) const LEN: usize = 1;
+
+    pub(super) const LATEST_ONLY: u8 = 1;
+    pub(super) const RELIABLE_ORDERED: u8 = 2;
+}
@@ -18,11 +18,13 @@ use core::fmt;
 mod deserialize;
 mod extension;
 mod handle;
+mod nack;
 mod serialize;
 mod time;
 
 pub use extension::*;
 pub use handle::*;
+pub use nack::*;
 pub use time::*;
 
 #[derive(Clone)]
@@ -0,0 +1,82 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{deserialize::DeserializeError, Handle};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+/// Request from a subscriber for the publisher to retransmit lost packets.
+///
+/// Only sent for tracks published with [`ReliabilityExt::ReliableOrdered`](super::ReliabilityExt).
+/// Unlike packets, NACKs are delivered out-of-band over the reliable data channel rather
+/// than on the track itself.
+///
+/// Wire format: the publisher handle of the track (16 bits) followed by each
+/// missing sequence number (16 bits each).
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nack {
+    /// Publisher handle of the track the packets were sent on.
+    pub track_handle: Handle,
+    /// Sequence numbers of the packets to retransmit.
+    pub sequences: Vec<u16>,
+}
+
+impl Nack {
+    /// Serialize the NACK into a new buffer.
+    pub fn serialize(self) -> Bytes {
+        let mut buf = BytesMut::with_capacity(2 + 2 * self.sequences.len());
+        buf.put_u16(self.track_handle.into());
+        for sequence in self.sequences {
+            buf.put_u16(sequence);
+        }
+        buf.freeze()
+    }
+
+    pub fn deserialize(mut raw: Bytes) -> Result<Self, DeserializeError> {
+        if raw.remaining() < 2 || raw.remaining() & 1 != 0 {
+            Err(DeserializeError::TooShort)?
+        }
+        let track_handle: Handle = raw.get_u16().try_into()?;
+        let mut sequences = Vec::with_capacity(raw.remaining() / 2);
+        while raw.has_remaining() {
+            sequences.push(raw.get_u16());
+        }
+        Ok(Self { track_handle, sequences })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fake::{Fake, Faker};
+
+    #[test]
+    fn test_roundtrip() {
+        let nack = Nack { track_handle: Faker.fake(), sequences: vec![0, 1, 0xFFFF, 42] };
+        let deserialized = Nack::deserialize(nack.clone().serialize()).unwrap();
+        assert_eq!(deserialized, nack);
+    }
+
+    #[test]
+    fn test_odd_length() {
+        let raw = Bytes::from_static(&[0x00, 0x01, 0x00]);
+        assert!(matches!(Nack::deserialize(raw), Err(DeserializeError::TooShort)));
+    }
+
+    #[test]
+    fn test_invalid_handle() {
+        let raw = Bytes::from_static(&[0x00, 0x00, 0x00, 0x01]);
+        assert!(matches!(Nack::deserialize(raw), Err(DeserializeError::InvalidHandle(_))));
+    }
+}
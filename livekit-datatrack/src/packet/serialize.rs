@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::{consts::*, E2eeExt, Extensions, FrameMarker, Header, Packet, UserTimestampExt};
+use super::{
+    consts::*, E2eeExt, Extensions, FrameMarker, Header, Packet, ReliabilityExt, UserTimestampExt,
+};
 use bytes::{BufMut, Bytes, BytesMut};
 use thiserror::Error;
 
@@ -138,6 +140,9 @@ impl Extensions {
         if self.user_timestamp.is_some() {
             len += EXT_MARKER_LEN + UserTimestampExt::LEN;
         }
+        if self.reliability.is_some() {
+            len += EXT_MARKER_LEN + ReliabilityExt::LEN;
+        }
         len
     }
 
@@ -148,6 +153,9 @@ impl Extensions {
         if let Some(user_timestamp) = self.user_timestamp {
             user_timestamp.serialize_into(buf);
         }
+        if let Some(reliability) = self.reliability {
+            reliability.serialize_into(buf);
+        }
     }
 }
 
@@ -168,6 +176,17 @@ impl UserTimestampExt {
     }
 }
 
+impl ReliabilityExt {
+    fn serialize_into(self, buf: &mut impl BufMut) {
+        buf.put_u8(Self::TAG);
+        buf.put_u8(Self::LEN as u8);
+        buf.put_u8(match self {
+            Self::LatestOnly => Self::LATEST_ONLY,
+            Self::ReliableOrdered => Self::RELIABLE_ORDERED,
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::packet::{
@@ -187,6 +206,7 @@ mod tests {
                 extensions: Extensions {
                     user_timestamp: UserTimestampExt(0x4411221111118811).into(),
                     e2ee: E2eeExt { key_index: 0xFA, iv: [0x3C; 12] }.into(),
+                    reliability: None,
                 },
             },
             payload: vec![0xFA; 1024].into(),
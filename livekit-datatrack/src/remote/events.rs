@@ -50,6 +50,7 @@ pub enum OutputEvent {
     SfuUpdateSubscription(SfuUpdateSubscription),
     TrackPublished(TrackPublished),
     TrackUnpublished(TrackUnpublished),
+    NackAvailable(NackAvailable),
 }
 
 // MARK: - Input events
@@ -157,3 +158,16 @@ pub struct TrackUnpublished {
     /// SID of the track that was unpublished.
     pub sid: DataTrackSid,
 }
+
+/// NACK is ready to be sent to the publisher of a track.
+///
+/// Deliver the payload to the publisher over the reliable data channel, where it is
+/// handled as a [`local::InputEvent::NackReceived`](crate::backend::local::InputEvent::NackReceived) event.
+///
+#[derive(Debug)]
+pub struct NackAvailable {
+    /// Identity of the participant who published the track.
+    pub publisher_identity: String,
+    /// Serialized NACK.
+    pub payload: Bytes,
+}
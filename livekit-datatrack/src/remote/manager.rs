@@ -14,7 +14,7 @@
 
 use super::{
     events::*,
-    pipeline::{Pipeline, PipelineOptions},
+    pipeline::{Pipeline, PipelineOptions, PipelineOutput},
     RemoteDataTrack, RemoteTrackInner,
 };
 use crate::{
@@ -23,7 +23,8 @@ use crate::{
         RemoteDataTrackPipelineOptions,
     },
     e2ee::DecryptionProvider,
-    packet::{Handle, Packet},
+    packet::{Handle, Nack, Packet},
+    stats::RemoteCounters,
 };
 use anyhow::{anyhow, Context};
 use bytes::Bytes;
@@ -36,6 +37,7 @@ use std::{
         Arc,
     },
     task::{Context as TaskContext, Poll},
+    time::Duration,
 };
 use tokio::sync::{broadcast, mpsc, oneshot, watch};
 use tokio_stream::{wrappers::ReceiverStream, Stream};
@@ -222,13 +224,16 @@ impl Manager {
             max_partial_frames: Arc::new(AtomicUsize::new(
                 RemoteDataTrackPipelineOptions::default().max_partial_frames(),
             )),
+            counters: Arc::default(),
         };
+        let counters = descriptor.counters.clone();
         self.descriptors.insert(sid, descriptor);
 
         let inner = RemoteTrackInner {
             published_rx,
             event_in_tx: self.event_in_tx.downgrade(), // TODO: wrap
             publisher_identity,
+            counters,
         };
         let track = RemoteDataTrack::new(info, inner);
         _ = self.event_out_tx.send(TrackPublished { track }.into()).await;
@@ -356,16 +361,19 @@ impl Manager {
             publisher_identity: descriptor.publisher_identity.clone(),
             decryption_provider,
             max_partial_frames: descriptor.max_partial_frames.clone(),
+            counters: descriptor.counters.clone(),
         };
         let pipeline = Pipeline::new(pipeline_opts);
 
         let track_task = TrackTask {
             info: descriptor.info.clone(),
+            publisher_identity: descriptor.publisher_identity.clone(),
             pipeline,
             published_rx: descriptor.published_tx.subscribe(),
             packet_rx,
             frame_tx: frame_tx.clone(),
             event_in_tx: self.event_in_tx.clone(),
+            event_out_tx: self.event_out_tx.clone(),
         };
         let task_handle = livekit_runtime::spawn(track_task.run());
 
@@ -452,6 +460,7 @@ struct Descriptor {
     published_tx: watch::Sender<bool>,
     subscription: SubscriptionState,
     max_partial_frames: Arc<AtomicUsize>,
+    counters: Arc<RemoteCounters>,
 }
 
 #[derive(Debug)]
@@ -477,17 +486,22 @@ enum SubscriptionState {
 /// Task for an individual data track with an active subscription.
 struct TrackTask {
     info: Arc<DataTrackInfo>,
+    publisher_identity: Arc<str>,
     pipeline: Pipeline,
     published_rx: watch::Receiver<bool>,
     packet_rx: mpsc::Receiver<Packet>,
     frame_tx: broadcast::Sender<DataTrackFrame>,
     event_in_tx: mpsc::Sender<InputEvent>,
+    event_out_tx: mpsc::Sender<OutputEvent>,
 }
 
 impl TrackTask {
     async fn run(mut self) {
         log::debug!("Track task started: name={}", self.info.name);
 
+        let mut retransmit_interval = livekit_runtime::interval(Self::RETRANSMIT_POLL_INTERVAL);
+        retransmit_interval.set_missed_tick_behavior(livekit_runtime::MissedTickBehavior::Delay);
+
         let mut is_published = *self.published_rx.borrow();
         while is_published {
             tokio::select! {
@@ -501,7 +515,12 @@ impl TrackTask {
                     break;  // No more subscribers
                 },
                 Some(packet) = self.packet_rx.recv() => {
-                    self.receive(packet);
+                    let output = self.pipeline.process_packet(packet);
+                    self.handle_output(output);
+                },
+                _ = retransmit_interval.tick(), if self.pipeline.is_awaiting_retransmit() => {
+                    let output = self.pipeline.poll_retransmit();
+                    self.handle_output(output);
                 },
                 else => break
             }
@@ -510,13 +529,32 @@ impl TrackTask {
         log::debug!("Track task ended: name={}", self.info.name);
     }
 
-    fn receive(&mut self, packet: Packet) {
-        let Some(frame) = self.pipeline.process_packet(packet) else { return };
+    fn handle_output(&mut self, output: PipelineOutput) {
+        for frame in output.frames {
+            _ = self
+                .frame_tx
+                .send(frame)
+                .inspect_err(|err| log::debug!("Cannot send frame to subscribers: {}", err));
+        }
+        if !output.nack.is_empty() {
+            self.send_nack(output.nack);
+        }
+    }
+
+    fn send_nack(&self, sequences: Vec<u16>) {
+        let nack = Nack { track_handle: self.info.pub_handle, sequences };
+        let event = NackAvailable {
+            publisher_identity: self.publisher_identity.to_string(),
+            payload: nack.serialize(),
+        };
         _ = self
-            .frame_tx
-            .send(frame)
-            .inspect_err(|err| log::debug!("Cannot send frame to subscribers: {}", err));
+            .event_out_tx
+            .try_send(event.into())
+            .inspect_err(|err| log::debug!("Cannot send NACK: {}", err));
     }
+
+    /// How often to check for missing packets while awaiting retransmission.
+    const RETRANSMIT_POLL_INTERVAL: Duration = Duration::from_millis(10);
 }
 
 /// Channel for sending [`InputEvent`]s to [`Manager`].
@@ -613,11 +651,12 @@ mod tests {
 
         let pipeline_opts = PipelineOptions {
             info: info.clone(),
-            publisher_identity,
+            publisher_identity: publisher_identity.clone(),
             decryption_provider: None,
             max_partial_frames: Arc::new(AtomicUsize::new(
                 RemoteDataTrackPipelineOptions::default().max_partial_frames(),
             )),
+            counters: Arc::default(),
         };
         let pipeline = Pipeline::new(pipeline_opts);
 
@@ -625,9 +664,18 @@ mod tests {
         let (_packet_tx, packet_rx) = mpsc::channel(4);
         let (frame_tx, frame_rx) = broadcast::channel(4);
         let (event_in_tx, mut event_in_rx) = mpsc::channel(4);
+        let (event_out_tx, _event_out_rx) = mpsc::channel(4);
 
-        let task =
-            TrackTask { info: info, pipeline, published_rx, packet_rx, frame_tx, event_in_tx };
+        let task = TrackTask {
+            info,
+            publisher_identity,
+            pipeline,
+            published_rx,
+            packet_rx,
+            frame_tx,
+            event_in_tx,
+            event_out_tx,
+        };
         let task_handle = livekit_runtime::spawn(task.run());
 
         let trigger_shutdown = async {
@@ -1491,6 +1539,7 @@ mod tests {
             packet.header.frame_number = frame_number;
             packet.header.sequence = sequence;
             packet.header.extensions.e2ee = None;
+            packet.header.extensions.reliability = None;
             packet.payload = Bytes::copy_from_slice(payload);
             input.send(InputEvent::PacketReceived(packet.serialize())).unwrap();
         };
@@ -12,7 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::api::{DataTrack, DataTrackFrame, DataTrackInfo, DataTrackInner, InternalError};
+use crate::{
+    api::{
+        DataTrack, DataTrackFrame, DataTrackInfo, DataTrackInner, FrameValidator, InternalError,
+        RemoteDataTrackStats,
+    },
+    stats::RemoteCounters,
+};
 use events::{InputEvent, SetPipelineOptions, SubscribeRequest};
 use livekit_runtime::timeout;
 use std::{
@@ -32,6 +38,7 @@ pub(crate) mod proto;
 
 mod depacketizer;
 mod pipeline;
+mod reorder;
 
 /// Data track published by a remote participant.
 pub type RemoteDataTrack = DataTrack<Remote>;
@@ -93,6 +100,7 @@ impl DataTrack<Remote> {
         &self,
         options: DataTrackSubscribeOptions,
     ) -> Result<DataTrackStream, DataTrackSubscribeError> {
+        let validator = options.validator.clone();
         let (result_tx, result_rx) = oneshot::channel();
         let subscribe_event = SubscribeRequest { sid: self.info.sid(), options, result_tx };
         self.inner()
@@ -109,7 +117,12 @@ impl DataTrack<Remote> {
             .map_err(|_| DataTrackSubscribeError::Timeout)?
             .map_err(|_| DataTrackSubscribeError::Disconnected)??;
 
-        Ok(DataTrackStream { inner: BroadcastStream::new(frame_rx) })
+        Ok(DataTrackStream {
+            inner: BroadcastStream::new(frame_rx),
+            counters: self.inner().counters.clone(),
+            validator,
+            frames_rejected: 0,
+        })
     }
 
     /// Identity of the participant who published the track.
@@ -117,6 +130,14 @@ impl DataTrack<Remote> {
         &self.inner().publisher_identity
     }
 
+    /// Returns delivery statistics for the track.
+    ///
+    /// Statistics are only collected while the track has at least one subscription.
+    ///
+    pub fn stats(&self) -> RemoteDataTrackStats {
+        self.inner().counters.snapshot()
+    }
+
     /// Configures options for the pipeline handling incoming packets for this track.
     ///
     /// These options apply to all current and future subscriptions of this track, and may be
@@ -134,6 +155,40 @@ impl DataTrack<Remote> {
 /// A stream of [`DataTrackFrame`]s received from a [`RemoteDataTrack`].
 pub struct DataTrackStream {
     inner: BroadcastStream<DataTrackFrame>,
+    counters: Arc<RemoteCounters>,
+    validator: Option<Arc<dyn FrameValidator>>,
+    frames_rejected: u64,
+}
+
+impl DataTrackStream {
+    /// Returns loss and recovery counts for frames received on the track.
+    ///
+    /// Counts are cumulative for the track and shared by all of its subscriptions. They are
+    /// a subset of the track's [statistics](RemoteDataTrack::stats).
+    ///
+    pub fn delivery_counts(&self) -> DataTrackDeliveryCounts {
+        self.counters.snapshot().into()
+    }
+
+    /// Returns the number of frames discarded by this subscription's
+    /// [validator](DataTrackSubscribeOptions::with_validator).
+    pub fn frames_rejected(&self) -> u64 {
+        self.frames_rejected
+    }
+}
+
+#[cfg(test)]
+impl DataTrackStream {
+    pub(crate) fn from_receiver(
+        frame_rx: tokio::sync::broadcast::Receiver<DataTrackFrame>,
+    ) -> Self {
+        Self {
+            inner: BroadcastStream::new(frame_rx),
+            counters: Arc::default(),
+            validator: None,
+            frames_rejected: 0,
+        }
+    }
 }
 
 impl Stream for DataTrackStream {
@@ -143,7 +198,16 @@ impl Stream for DataTrackStream {
         let this = self.get_mut();
         loop {
             match Pin::new(&mut this.inner).poll_next(cx) {
-                Poll::Ready(Some(Ok(frame))) => return Poll::Ready(Some(frame)),
+                Poll::Ready(Some(Ok(frame))) => {
+                    if let Some(validator) = &this.validator {
+                        if let Err(err) = validator.validate(&frame.payload) {
+                            log::debug!("Frame rejected: {}", err);
+                            this.frames_rejected += 1;
+                            continue;
+                        }
+                    }
+                    return Poll::Ready(Some(frame));
+                }
                 Poll::Ready(Some(Err(_))) => continue,
                 Poll::Ready(None) => return Poll::Ready(None),
                 Poll::Pending => return Poll::Pending,
@@ -157,6 +221,7 @@ pub(crate) struct RemoteTrackInner {
     publisher_identity: Arc<str>,
     published_rx: watch::Receiver<bool>,
     event_in_tx: mpsc::WeakSender<InputEvent>,
+    counters: Arc<RemoteCounters>,
 }
 
 impl RemoteTrackInner {
@@ -170,6 +235,42 @@ impl RemoteTrackInner {
     }
 }
 
+/// Loss and recovery counts for frames received on a [`RemoteDataTrack`].
+///
+/// Obtain with [`DataTrackStream::delivery_counts`].
+///
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct DataTrackDeliveryCounts {
+    /// Number of frames which could not be delivered.
+    ///
+    /// This includes frames with missing packets and, for
+    /// [`DataTrackReliability::LatestOnly`](crate::api::DataTrackReliability::LatestOnly)
+    /// tracks, frames which arrived after a newer one.
+    ///
+    pub frames_dropped: u64,
+    /// Number of packets which were never received, even after requesting retransmission.
+    ///
+    /// Only tracked for [`DataTrackReliability::ReliableOrdered`](crate::api::DataTrackReliability::ReliableOrdered) tracks.
+    ///
+    pub packets_lost: u64,
+    /// Number of packets received following a retransmission request.
+    ///
+    /// Only tracked for [`DataTrackReliability::ReliableOrdered`](crate::api::DataTrackReliability::ReliableOrdered) tracks.
+    ///
+    pub packets_recovered: u64,
+}
+
+impl From<RemoteDataTrackStats> for DataTrackDeliveryCounts {
+    fn from(stats: RemoteDataTrackStats) -> Self {
+        Self {
+            frames_dropped: stats.frames_dropped,
+            packets_lost: stats.packets_lost,
+            packets_recovered: stats.packets_recovered,
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 #[cfg_attr(feature = "uniffi", derive(uniffi::Error))]
 #[cfg_attr(feature = "uniffi", uniffi(flat_error))]
@@ -201,6 +302,7 @@ pub enum DataTrackSubscribeError {
 #[derive(Debug, Clone)]
 pub struct DataTrackSubscribeOptions {
     buffer_size: usize,
+    pub(crate) validator: Option<Arc<dyn FrameValidator>>,
 }
 
 impl DataTrackSubscribeOptions {
@@ -209,7 +311,7 @@ impl DataTrackSubscribeOptions {
     /// Equivalent to [`Self::default`].
     ///
     pub fn new() -> Self {
-        Self { buffer_size: 16 }
+        Self { buffer_size: 16, validator: None }
     }
 
     /// Returns the maximum number of received frames buffered internally.
@@ -232,6 +334,17 @@ impl DataTrackSubscribeOptions {
         self.buffer_size = frames;
         self
     }
+
+    /// Sets a validator to check received frames against.
+    ///
+    /// Frames which fail validation are not yielded by the [`DataTrackStream`]; the number
+    /// discarded is reported by [`DataTrackStream::frames_rejected`]. Unlike the buffer size,
+    /// the validator applies only to the subscription created with these options.
+    ///
+    pub fn with_validator(mut self, validator: Arc<dyn FrameValidator>) -> Self {
+        self.validator = Some(validator);
+        self
+    }
 }
 
 impl Default for DataTrackSubscribeOptions {
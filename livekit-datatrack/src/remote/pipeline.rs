@@ -12,15 +12,24 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::depacketizer::{Depacketizer, DepacketizerFrame, DepacketizerPushOptions};
+use super::{
+    depacketizer::{
+        Depacketizer, DepacketizerDropReason, DepacketizerFrame, DepacketizerPushOptions,
+    },
+    reorder::{ReorderBuffer, ReorderOutput},
+};
 use crate::{
     api::{DataTrackFrame, DataTrackInfo},
     e2ee::{DecryptionProvider, EncryptedPayload},
-    packet::Packet,
+    packet::{FrameMarker, Packet, ReliabilityExt},
+    stats::RemoteCounters,
 };
-use std::sync::{
-    atomic::{AtomicUsize, Ordering},
-    Arc,
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Instant,
 };
 
 /// Options for creating a [`Pipeline`].
@@ -29,6 +38,7 @@ pub(super) struct PipelineOptions {
     pub publisher_identity: Arc<str>,
     pub decryption_provider: Option<Arc<dyn DecryptionProvider>>,
     pub max_partial_frames: Arc<AtomicUsize>,
+    pub counters: Arc<RemoteCounters>,
 }
 
 /// Pipeline for an individual data track subscription.
@@ -36,9 +46,25 @@ pub(super) struct Pipeline {
     publisher_identity: Arc<str>,
     e2ee_provider: Option<Arc<dyn DecryptionProvider>>,
     max_partial_frames: Arc<AtomicUsize>,
+    counters: Arc<RemoteCounters>,
+    reorder_buffer: ReorderBuffer,
+    /// Frame number of the last delivered frame, used to discard stale frames
+    /// on latest-only tracks.
+    last_frame_number: Option<u16>,
+    /// Highest sequence number received, used to detect out-of-order packets.
+    highest_sequence: Option<u16>,
     depacketizer: Depacketizer,
 }
 
+/// Result from processing incoming packets with a [`Pipeline`].
+#[derive(Debug, Default)]
+pub(super) struct PipelineOutput {
+    /// Frames ready to be delivered to subscribers, in order.
+    pub frames: Vec<DataTrackFrame>,
+    /// Sequence numbers to request for retransmission from the publisher.
+    pub nack: Vec<u16>,
+}
+
 impl Pipeline {
     /// Creates a new pipeline with the given options.
     pub fn new(options: PipelineOptions) -> Self {
@@ -48,16 +74,88 @@ impl Pipeline {
             publisher_identity: options.publisher_identity,
             e2ee_provider: options.decryption_provider,
             max_partial_frames: options.max_partial_frames,
+            counters: options.counters,
+            reorder_buffer: ReorderBuffer::new(),
+            last_frame_number: None,
+            highest_sequence: None,
             depacketizer,
         }
     }
 
-    pub fn process_packet(&mut self, packet: Packet) -> Option<DataTrackFrame> {
+    pub fn process_packet(&mut self, packet: Packet) -> PipelineOutput {
+        self.record_received(&packet);
+        if packet.header.extensions.reliability != Some(ReliabilityExt::ReliableOrdered) {
+            let mut output = PipelineOutput::default();
+            output.frames.extend(self.process_in_order(packet));
+            return output;
+        }
+        let reordered = self.reorder_buffer.push(packet, Instant::now());
+        self.process_reordered(reordered)
+    }
+
+    fn record_received(&mut self, packet: &Packet) {
+        self.counters.packets_received.fetch_add(1, Ordering::Relaxed);
+        let sequence = packet.header.sequence;
+        match self.highest_sequence {
+            Some(highest) if (sequence.wrapping_sub(highest) as i16) < 0 => {
+                self.counters.packets_out_of_order.fetch_add(1, Ordering::Relaxed);
+            }
+            _ => self.highest_sequence = Some(sequence),
+        }
+    }
+
+    /// Whether or not the pipeline is waiting on retransmission of missing packets.
+    ///
+    /// While waiting, [`Self::poll_retransmit`] must be called periodically.
+    ///
+    pub fn is_awaiting_retransmit(&self) -> bool {
+        self.reorder_buffer.is_waiting()
+    }
+
+    /// Request retransmission of missing packets, or give up on them if they
+    /// could not be recovered in time.
+    pub fn poll_retransmit(&mut self) -> PipelineOutput {
+        let reordered = self.reorder_buffer.poll(Instant::now());
+        self.process_reordered(reordered)
+    }
+
+    fn process_reordered(&mut self, reordered: ReorderOutput) -> PipelineOutput {
+        self.counters.packets_lost.fetch_add(reordered.lost, Ordering::Relaxed);
+        self.counters.packets_recovered.fetch_add(reordered.recovered, Ordering::Relaxed);
+        let frames = reordered
+            .packets
+            .into_iter()
+            .filter_map(|packet| self.process_in_order(packet))
+            .collect();
+        PipelineOutput { frames, nack: reordered.nack }
+    }
+
+    fn process_in_order(&mut self, packet: Packet) -> Option<DataTrackFrame> {
+        let frame_number = packet.header.frame_number;
+        if packet.header.extensions.reliability == Some(ReliabilityExt::LatestOnly)
+            && self.is_stale(&packet)
+        {
+            if matches!(packet.header.marker, FrameMarker::Start | FrameMarker::Single) {
+                self.counters.frames_dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            return None;
+        }
         let frame = self.depacketize(packet)?;
         let frame = self.decrypt_if_needed(frame)?;
+        self.last_frame_number = Some(frame_number);
+        self.counters.frames_received.fetch_add(1, Ordering::Relaxed);
+        if let Some(user_timestamp) = frame.extensions.user_timestamp {
+            self.counters.record_latency(user_timestamp.0);
+        }
         Some(frame.into())
     }
 
+    /// Whether a newer frame than the one the packet belongs to has already been delivered.
+    fn is_stale(&self, packet: &Packet) -> bool {
+        let Some(last_frame_number) = self.last_frame_number else { return false };
+        (packet.header.frame_number.wrapping_sub(last_frame_number) as i16) <= 0
+    }
+
     /// Depacketize the given frame, log if a drop occurs.
     fn depacketize(&mut self, packet: Packet) -> Option<DepacketizerFrame> {
         let push_options = DepacketizerPushOptions {
@@ -65,7 +163,16 @@ impl Pipeline {
         };
         let result = self.depacketizer.push(packet, push_options);
         if let Some(drop) = result.drop_error {
-            // In a future version, use this to maintain drop statistics.
+            self.counters.frames_dropped.fetch_add(1, Ordering::Relaxed);
+            let reason_counter = match drop.reason {
+                DepacketizerDropReason::Interrupted { .. } => Some(&self.counters.frames_evicted),
+                DepacketizerDropReason::Incomplete { .. } => Some(&self.counters.frames_incomplete),
+                DepacketizerDropReason::BufferFull => Some(&self.counters.frames_oversized),
+                DepacketizerDropReason::UnknownFrame => None,
+            };
+            if let Some(counter) = reason_counter {
+                counter.fetch_add(1, Ordering::Relaxed);
+            }
             log::debug!("{}", drop);
         };
         result.frame
@@ -105,13 +212,13 @@ impl From<DepacketizerFrame> for DataTrackFrame {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::packet::{FrameMarker, Header};
+    use crate::{
+        api::DataTrackStream,
+        packet::{Header, UserTimestampExt},
+    };
     use fake::{Fake, Faker};
 
-    #[test]
-    fn test_process_frame() {
-        const PAYLOAD_LEN: usize = 1024;
-
+    fn pipeline() -> Pipeline {
         let mut info: DataTrackInfo = Faker.fake();
         info.uses_e2ee = false;
 
@@ -122,16 +229,130 @@ mod tests {
             publisher_identity,
             decryption_provider: None,
             max_partial_frames: Arc::new(AtomicUsize::new(1)),
+            counters: Default::default(),
         };
-        let mut pipeline = Pipeline::new(options);
+        Pipeline::new(options)
+    }
 
+    fn single_packet(reliability: Option<ReliabilityExt>) -> Packet {
         let mut header: Header = Faker.fake();
         header.marker = FrameMarker::Single;
         header.extensions.e2ee = None;
+        header.extensions.reliability = reliability;
+        Packet { header, payload: vec![Faker.fake(); 16].into() }
+    }
+
+    #[test]
+    fn test_process_frame() {
+        const PAYLOAD_LEN: usize = 1024;
+
+        let mut pipeline = pipeline();
+
+        let mut header: Header = Faker.fake();
+        header.marker = FrameMarker::Single;
+        header.extensions.e2ee = None;
+        header.extensions.reliability = None;
 
         let frame = Packet { header, payload: vec![Faker.fake(); PAYLOAD_LEN].into() };
 
-        let frame = pipeline.process_packet(frame).expect("Should return a frame");
+        let frame = pipeline.process_packet(frame).frames.pop().expect("Should return a frame");
         assert_eq!(frame.payload.len(), PAYLOAD_LEN);
     }
+
+    #[test]
+    fn test_latest_only_discards_stale() {
+        let mut pipeline = pipeline();
+
+        let mut newer = single_packet(Some(ReliabilityExt::LatestOnly));
+        newer.header.frame_number = 10;
+        let mut older = newer.clone();
+        older.header.frame_number = 9;
+
+        assert_eq!(pipeline.process_packet(newer).frames.len(), 1);
+        assert!(pipeline.process_packet(older).frames.is_empty());
+        assert_eq!(pipeline.counters.snapshot().frames_dropped, 1);
+    }
+
+    #[test]
+    fn test_delivery_counts_follow_track_stats() {
+        let mut pipeline = pipeline();
+        let (_frame_tx, frame_rx) = tokio::sync::broadcast::channel(1);
+        let mut stream = DataTrackStream::from_receiver(frame_rx);
+        stream.counters = pipeline.counters.clone();
+
+        let mut newer = single_packet(Some(ReliabilityExt::LatestOnly));
+        newer.header.frame_number = 10;
+        let mut older = newer.clone();
+        older.header.frame_number = 9;
+        pipeline.process_packet(newer);
+        pipeline.process_packet(older);
+
+        let counts = stream.delivery_counts();
+        assert_eq!(counts.frames_dropped, 1);
+        assert_eq!(counts, pipeline.counters.snapshot().into());
+    }
+
+    #[test]
+    fn test_reliable_ordered_holds_back_until_gap_filled() {
+        let mut pipeline = pipeline();
+
+        let mut packets: Vec<_> = (0..3u16)
+            .map(|index| {
+                let mut packet = single_packet(Some(ReliabilityExt::ReliableOrdered));
+                packet.header.sequence = index;
+                packet.header.frame_number = index;
+                packet
+            })
+            .collect();
+        let third = packets.pop().unwrap();
+        let second = packets.pop().unwrap();
+        let first = packets.pop().unwrap();
+
+        assert_eq!(pipeline.process_packet(first).frames.len(), 1);
+        assert!(pipeline.process_packet(third).frames.is_empty());
+        assert!(pipeline.is_awaiting_retransmit());
+        assert_eq!(pipeline.process_packet(second).frames.len(), 2);
+        assert!(!pipeline.is_awaiting_retransmit());
+
+        let stats = pipeline.counters.snapshot();
+        assert_eq!(stats.packets_received, 3);
+        assert_eq!(stats.packets_out_of_order, 1);
+        assert_eq!(stats.frames_received, 3);
+    }
+
+    #[test]
+    fn test_stats_evicted_frame() {
+        let mut pipeline = pipeline();
+
+        let mut start = single_packet(None);
+        start.header.marker = FrameMarker::Start;
+        start.header.frame_number = 1;
+        start.header.sequence = 1;
+        let mut interrupting = single_packet(None);
+        interrupting.header.frame_number = 2;
+        interrupting.header.sequence = 2;
+
+        assert!(pipeline.process_packet(start).frames.is_empty());
+        assert_eq!(pipeline.process_packet(interrupting).frames.len(), 1);
+
+        let stats = pipeline.counters.snapshot();
+        assert_eq!(stats.frames_dropped, 1);
+        assert_eq!(stats.frames_evicted, 1);
+        assert_eq!(stats.frames_received, 1);
+    }
+
+    #[test]
+    fn test_stats_latency_from_user_timestamp() {
+        let mut pipeline = pipeline();
+
+        let mut packet = single_packet(None);
+        let sent_at = std::time::SystemTime::now() - std::time::Duration::from_millis(250);
+        let sent_at_ms = sent_at.duration_since(std::time::UNIX_EPOCH).unwrap().as_millis();
+        packet.header.extensions.user_timestamp = Some(UserTimestampExt(sent_at_ms as u64));
+        pipeline.process_packet(packet);
+
+        let latency = pipeline.counters.snapshot().latency.expect("Should record latency");
+        assert_eq!(latency.samples, 1);
+        assert!(latency.p50 >= std::time::Duration::from_millis(250));
+    }
 }
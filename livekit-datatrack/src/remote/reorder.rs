@@ -0,0 +1,341 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::packet::Packet;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// Restores sequence order for packets sent with reliable delivery and determines
+/// which missing packets to request for retransmission.
+///
+/// Packets are released strictly in sequence order. When a gap is detected, later
+/// packets are held back until the missing ones arrive or retransmission is abandoned,
+/// in which case the missing packets are counted as lost and delivery resumes.
+///
+#[derive(Debug, Default)]
+pub(super) struct ReorderBuffer {
+    /// Sequence number of the next packet to release, unknown until the first packet.
+    next_sequence: Option<u16>,
+    /// Packets received ahead of a gap, keyed by sequence number.
+    pending: HashMap<u16, Packet>,
+    /// Packets detected as missing, keyed by sequence number.
+    ///
+    /// Invariant: every sequence number between `next_sequence` and the highest pending
+    /// sequence number is either in `pending` or `missing`.
+    ///
+    missing: HashMap<u16, MissingPacket>,
+}
+
+#[derive(Debug)]
+struct MissingPacket {
+    detected_at: Instant,
+    last_nack: Option<Instant>,
+    nack_count: u8,
+}
+
+/// Result from a call to [`ReorderBuffer::push`] or [`ReorderBuffer::poll`].
+#[derive(Debug, Default)]
+pub(super) struct ReorderOutput {
+    /// Packets ready to be depacketized, in sequence order.
+    pub packets: Vec<Packet>,
+    /// Sequence numbers to request for retransmission.
+    pub nack: Vec<u16>,
+    /// Number of packets abandoned as lost.
+    pub lost: u64,
+    /// Number of packets received following a retransmission request.
+    pub recovered: u64,
+}
+
+impl ReorderBuffer {
+    /// Maximum distance ahead of the next expected packet that will be buffered.
+    ///
+    /// Matches the number of packets retained by the publisher for retransmission.
+    ///
+    const MAX_PENDING_PACKETS: u16 = 256;
+
+    /// How long to wait for a missing packet to arrive out of order before requesting it.
+    const REORDER_DELAY: Duration = Duration::from_millis(10);
+
+    /// How long to wait for a retransmission before requesting it again.
+    const NACK_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+    /// Number of retransmission requests sent before a packet is abandoned.
+    const MAX_NACK_ATTEMPTS: u8 = 5;
+
+    /// Creates a new reorder buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether or not packets are being held back waiting for missing packets.
+    pub fn is_waiting(&self) -> bool {
+        !self.missing.is_empty()
+    }
+
+    /// Push a received packet into the buffer.
+    pub fn push(&mut self, packet: Packet, now: Instant) -> ReorderOutput {
+        let mut output = ReorderOutput::default();
+        let sequence = packet.header.sequence;
+
+        let Some(next) = self.next_sequence else {
+            self.next_sequence = Some(sequence.wrapping_add(1));
+            output.packets.push(packet);
+            return output;
+        };
+        let offset = sequence.wrapping_sub(next) as i16;
+        if offset < 0 || self.pending.contains_key(&sequence) {
+            // Already released, abandoned, or a duplicate.
+            return output;
+        }
+        if let Some(missing) = self.missing.remove(&sequence) {
+            if missing.nack_count > 0 {
+                output.recovered += 1;
+            }
+        }
+
+        // Too far ahead to wait for the gap to be filled, abandon the oldest packets.
+        while sequence.wrapping_sub(self.next_sequence.unwrap()) >= Self::MAX_PENDING_PACKETS {
+            self.skip(&mut output);
+        }
+
+        let next = self.next_sequence.unwrap();
+        if sequence != next {
+            let mut gap_sequence = next;
+            while gap_sequence != sequence {
+                if !self.pending.contains_key(&gap_sequence) {
+                    self.missing.entry(gap_sequence).or_insert(MissingPacket {
+                        detected_at: now,
+                        last_nack: None,
+                        nack_count: 0,
+                    });
+                }
+                gap_sequence = gap_sequence.wrapping_add(1);
+            }
+            self.pending.insert(sequence, packet);
+            return output;
+        }
+
+        output.packets.push(packet);
+        self.next_sequence = Some(next.wrapping_add(1));
+        self.release_contiguous(&mut output);
+        output
+    }
+
+    /// Determine which missing packets to request, and abandon those which
+    /// could not be recovered.
+    ///
+    /// Call periodically while [`Self::is_waiting`].
+    ///
+    pub fn poll(&mut self, now: Instant) -> ReorderOutput {
+        let mut output = ReorderOutput::default();
+
+        while let Some(next) = self.next_sequence {
+            let Some(missing) = self.missing.get(&next) else { break };
+            if !missing.is_abandoned(now) {
+                break;
+            }
+            self.skip(&mut output);
+        }
+
+        for (sequence, missing) in self.missing.iter_mut() {
+            if missing.is_nack_due(now) {
+                missing.last_nack = Some(now);
+                missing.nack_count += 1;
+                output.nack.push(*sequence);
+            }
+        }
+        if let Some(next) = self.next_sequence {
+            output.nack.sort_by_key(|sequence| sequence.wrapping_sub(next));
+        }
+        output
+    }
+
+    /// Abandon the next expected packet, releasing any packets it was holding back.
+    fn skip(&mut self, output: &mut ReorderOutput) {
+        let Some(next) = self.next_sequence else { return };
+        if let Some(packet) = self.pending.remove(&next) {
+            output.packets.push(packet);
+        } else {
+            self.missing.remove(&next);
+            output.lost += 1;
+        }
+        self.next_sequence = Some(next.wrapping_add(1));
+        self.release_contiguous(output);
+    }
+
+    fn release_contiguous(&mut self, output: &mut ReorderOutput) {
+        let Some(mut next) = self.next_sequence else { return };
+        while let Some(packet) = self.pending.remove(&next) {
+            output.packets.push(packet);
+            next = next.wrapping_add(1);
+        }
+        self.next_sequence = Some(next);
+    }
+}
+
+impl MissingPacket {
+    fn is_nack_due(&self, now: Instant) -> bool {
+        match self.last_nack {
+            None => now.duration_since(self.detected_at) >= ReorderBuffer::REORDER_DELAY,
+            Some(last_nack) => {
+                self.nack_count < ReorderBuffer::MAX_NACK_ATTEMPTS
+                    && now.duration_since(last_nack) >= ReorderBuffer::NACK_RETRY_INTERVAL
+            }
+        }
+    }
+
+    fn is_abandoned(&self, now: Instant) -> bool {
+        let Some(last_nack) = self.last_nack else { return false };
+        self.nack_count >= ReorderBuffer::MAX_NACK_ATTEMPTS
+            && now.duration_since(last_nack) >= ReorderBuffer::NACK_RETRY_INTERVAL
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fake::{Fake, Faker};
+
+    fn packet(sequence: u16) -> Packet {
+        let mut packet: Packet = Faker.fake();
+        packet.header.sequence = sequence;
+        packet
+    }
+
+    fn sequences(packets: &[Packet]) -> Vec<u16> {
+        packets.iter().map(|packet| packet.header.sequence).collect()
+    }
+
+    #[test]
+    fn test_in_order() {
+        let mut buffer = ReorderBuffer::new();
+        let now = Instant::now();
+        for sequence in 10..20 {
+            let output = buffer.push(packet(sequence), now);
+            assert_eq!(sequences(&output.packets), vec![sequence]);
+        }
+        assert!(!buffer.is_waiting());
+    }
+
+    #[test]
+    fn test_reorders() {
+        let mut buffer = ReorderBuffer::new();
+        let now = Instant::now();
+
+        buffer.push(packet(0), now);
+        assert!(buffer.push(packet(2), now).packets.is_empty());
+        assert!(buffer.push(packet(3), now).packets.is_empty());
+        assert!(buffer.is_waiting());
+
+        let output = buffer.push(packet(1), now);
+        assert_eq!(sequences(&output.packets), vec![1, 2, 3]);
+        assert_eq!(output.recovered, 0, "Arrived before NACK was sent");
+        assert!(!buffer.is_waiting());
+    }
+
+    #[test]
+    fn test_nack_and_recover() {
+        let mut buffer = ReorderBuffer::new();
+        let start = Instant::now();
+
+        buffer.push(packet(0), start);
+        buffer.push(packet(3), start);
+
+        assert!(buffer.poll(start).nack.is_empty(), "Should wait for reordering");
+
+        let now = start + ReorderBuffer::REORDER_DELAY;
+        assert_eq!(buffer.poll(now).nack, vec![1, 2]);
+        assert!(buffer.poll(now).nack.is_empty(), "Should not repeat before retry interval");
+
+        let output = buffer.push(packet(2), now);
+        assert!(output.packets.is_empty());
+        assert_eq!(output.recovered, 1);
+
+        let now = now + ReorderBuffer::NACK_RETRY_INTERVAL;
+        assert_eq!(buffer.poll(now).nack, vec![1]);
+
+        let output = buffer.push(packet(1), now);
+        assert_eq!(sequences(&output.packets), vec![1, 2, 3]);
+        assert_eq!(output.recovered, 1);
+    }
+
+    #[test]
+    fn test_abandon_after_max_attempts() {
+        let mut buffer = ReorderBuffer::new();
+        let mut now = Instant::now();
+
+        buffer.push(packet(0), now);
+        buffer.push(packet(2), now);
+
+        now += ReorderBuffer::REORDER_DELAY;
+        for _ in 0..ReorderBuffer::MAX_NACK_ATTEMPTS {
+            let output = buffer.poll(now);
+            assert_eq!(output.nack, vec![1]);
+            assert!(output.packets.is_empty());
+            now += ReorderBuffer::NACK_RETRY_INTERVAL;
+        }
+
+        let output = buffer.poll(now);
+        assert!(output.nack.is_empty());
+        assert_eq!(output.lost, 1);
+        assert_eq!(sequences(&output.packets), vec![2]);
+        assert!(!buffer.is_waiting());
+
+        // Late arrival of the abandoned packet is ignored.
+        assert!(buffer.push(packet(1), now).packets.is_empty());
+    }
+
+    #[test]
+    fn test_gap_beyond_window() {
+        let mut buffer = ReorderBuffer::new();
+        let now = Instant::now();
+
+        buffer.push(packet(0), now);
+        buffer.push(packet(2), now);
+
+        let far = 1 + ReorderBuffer::MAX_PENDING_PACKETS;
+        let output = buffer.push(packet(far), now);
+        assert_eq!(sequences(&output.packets), vec![2]);
+        assert_eq!(output.lost, 1);
+
+        // Remaining gap up to the new packet is still recoverable.
+        assert!(buffer.is_waiting());
+    }
+
+    #[test]
+    fn test_sequence_wraparound() {
+        let mut buffer = ReorderBuffer::new();
+        let now = Instant::now();
+
+        buffer.push(packet(u16::MAX - 1), now);
+        assert!(buffer.push(packet(0), now).packets.is_empty());
+
+        let output = buffer.push(packet(u16::MAX), now);
+        assert_eq!(sequences(&output.packets), vec![u16::MAX, 0]);
+    }
+
+    #[test]
+    fn test_duplicate_ignored() {
+        let mut buffer = ReorderBuffer::new();
+        let now = Instant::now();
+
+        buffer.push(packet(5), now);
+        assert!(buffer.push(packet(5), now).packets.is_empty());
+
+        buffer.push(packet(7), now);
+        assert!(buffer.push(packet(7), now).packets.is_empty());
+    }
+}
@@ -0,0 +1,269 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Statistics for a [`LocalDataTrack`](crate::api::LocalDataTrack).
+///
+/// Obtain with [`LocalDataTrack::stats`](crate::api::LocalDataTrack::stats). All counts
+/// are cumulative for the lifetime of the track.
+///
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct LocalDataTrackStats {
+    /// Number of frames accepted by `try_push`.
+    pub frames_pushed: u64,
+    /// Number of frames rejected by `try_push`.
+    ///
+    /// This includes frames rejected because the queue was full and frames
+    /// which failed validation.
+    ///
+    pub frames_rejected: u64,
+    /// Number of accepted frames which were never handed to the transport.
+    ///
+    /// Frames are dropped while the track is being republished, when
+    /// superseded on [`DataTrackReliability::LatestOnly`](crate::api::DataTrackReliability::LatestOnly)
+    /// tracks, or when packetization or encryption fails.
+    ///
+    pub frames_dropped: u64,
+    /// Number of frames handed to the transport.
+    pub frames_sent: u64,
+    /// Number of frames handed to the transport, but discarded from its send
+    /// queue in favor of a newer frame before being sent.
+    pub send_queue_drops: u64,
+    /// Number of packets handed to the transport, excluding retransmissions.
+    pub packets_sent: u64,
+    /// Number of packets resent in response to a subscriber's retransmission request.
+    pub packets_retransmitted: u64,
+    /// Number of payload bytes handed to the transport, excluding retransmissions.
+    pub bytes_sent: u64,
+}
+
+/// Statistics for a [`RemoteDataTrack`](crate::api::RemoteDataTrack).
+///
+/// Obtain with [`RemoteDataTrack::stats`](crate::api::RemoteDataTrack::stats). Counts are
+/// cumulative for the track and shared by all of its subscriptions.
+///
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct RemoteDataTrackStats {
+    /// Number of packets received.
+    pub packets_received: u64,
+    /// Number of packets received with a lower sequence number than one received before it.
+    pub packets_out_of_order: u64,
+    /// Number of packets which were never received, even after requesting retransmission.
+    pub packets_lost: u64,
+    /// Number of packets received following a retransmission request.
+    pub packets_recovered: u64,
+    /// Number of frames reassembled and delivered to subscribers.
+    pub frames_received: u64,
+    /// Number of frames which could not be delivered, for any reason.
+    pub frames_dropped: u64,
+    /// Number of partial frames evicted by the start of a newer frame.
+    ///
+    /// The number of partial frames assembled concurrently can be raised with
+    /// [`RemoteDataTrackPipelineOptions::with_max_partial_frames`](crate::api::RemoteDataTrackPipelineOptions::with_max_partial_frames).
+    ///
+    pub frames_evicted: u64,
+    /// Number of frames with missing packets when the final packet arrived.
+    pub frames_incomplete: u64,
+    /// Number of frames dropped for spanning more packets than can be buffered.
+    pub frames_oversized: u64,
+    /// End-to-end latency of recently received frames.
+    ///
+    /// Measured from the frame's user timestamp, so only present when the publisher
+    /// attaches one with [`DataTrackFrame::with_user_timestamp_now`](crate::api::DataTrackFrame::with_user_timestamp_now).
+    /// Accuracy depends on the publisher and subscriber clocks being synchronized.
+    ///
+    pub latency: Option<DataTrackLatencyStats>,
+}
+
+/// Latency percentiles over a rolling window of recently received frames.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct DataTrackLatencyStats {
+    /// Number of frames in the window.
+    pub samples: u32,
+    /// Median latency.
+    pub p50: Duration,
+    /// 90th percentile latency.
+    pub p90: Duration,
+    /// 99th percentile latency.
+    pub p99: Duration,
+    /// Highest latency in the window.
+    pub max: Duration,
+}
+
+/// Shared counters backing [`LocalDataTrackStats`], updated by the track and its task.
+#[derive(Debug, Default)]
+pub(crate) struct LocalCounters {
+    pub frames_pushed: AtomicU64,
+    pub frames_rejected: AtomicU64,
+    pub frames_dropped: AtomicU64,
+    pub frames_sent: AtomicU64,
+    pub send_queue_drops: AtomicU64,
+    pub packets_sent: AtomicU64,
+    pub packets_retransmitted: AtomicU64,
+    pub bytes_sent: AtomicU64,
+}
+
+impl LocalCounters {
+    pub fn snapshot(&self) -> LocalDataTrackStats {
+        LocalDataTrackStats {
+            frames_pushed: self.frames_pushed.load(Ordering::Relaxed),
+            frames_rejected: self.frames_rejected.load(Ordering::Relaxed),
+            frames_dropped: self.frames_dropped.load(Ordering::Relaxed),
+            frames_sent: self.frames_sent.load(Ordering::Relaxed),
+            send_queue_drops: self.send_queue_drops.load(Ordering::Relaxed),
+            packets_sent: self.packets_sent.load(Ordering::Relaxed),
+            packets_retransmitted: self.packets_retransmitted.load(Ordering::Relaxed),
+            bytes_sent: self.bytes_sent.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Shared counters backing [`RemoteDataTrackStats`], updated by the track's pipeline.
+#[derive(Debug, Default)]
+pub(crate) struct RemoteCounters {
+    pub packets_received: AtomicU64,
+    pub packets_out_of_order: AtomicU64,
+    pub packets_lost: AtomicU64,
+    pub packets_recovered: AtomicU64,
+    pub frames_received: AtomicU64,
+    pub frames_dropped: AtomicU64,
+    pub frames_evicted: AtomicU64,
+    pub frames_incomplete: AtomicU64,
+    pub frames_oversized: AtomicU64,
+    pub latency: Mutex<LatencyWindow>,
+}
+
+impl RemoteCounters {
+    pub fn snapshot(&self) -> RemoteDataTrackStats {
+        RemoteDataTrackStats {
+            packets_received: self.packets_received.load(Ordering::Relaxed),
+            packets_out_of_order: self.packets_out_of_order.load(Ordering::Relaxed),
+            packets_lost: self.packets_lost.load(Ordering::Relaxed),
+            packets_recovered: self.packets_recovered.load(Ordering::Relaxed),
+            frames_received: self.frames_received.load(Ordering::Relaxed),
+            frames_dropped: self.frames_dropped.load(Ordering::Relaxed),
+            frames_evicted: self.frames_evicted.load(Ordering::Relaxed),
+            frames_incomplete: self.frames_incomplete.load(Ordering::Relaxed),
+            frames_oversized: self.frames_oversized.load(Ordering::Relaxed),
+            latency: self.latency.lock().unwrap().percentiles(),
+        }
+    }
+
+    /// Records the latency of a received frame from its user timestamp.
+    pub fn record_latency(&self, user_timestamp: u64) {
+        let Some(latency) = latency_since(user_timestamp) else { return };
+        self.latency.lock().unwrap().push(latency);
+    }
+}
+
+/// Rolling window of the most recent latency samples.
+#[derive(Debug, Default)]
+pub(crate) struct LatencyWindow {
+    samples: VecDeque<Duration>,
+}
+
+impl LatencyWindow {
+    /// Maximum number of samples retained.
+    const CAPACITY: usize = 512;
+
+    pub fn push(&mut self, sample: Duration) {
+        if self.samples.len() >= Self::CAPACITY {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    /// Computes percentiles over the window, or none if it is empty.
+    pub fn percentiles(&self) -> Option<DataTrackLatencyStats> {
+        if self.samples.is_empty() {
+            return None;
+        }
+        let mut sorted: Vec<_> = self.samples.iter().copied().collect();
+        sorted.sort_unstable();
+        // Nearest-rank method.
+        let percentile = |p: usize| sorted[(sorted.len() * p).div_ceil(100).max(1) - 1];
+        Some(DataTrackLatencyStats {
+            samples: sorted.len() as u32,
+            p50: percentile(50),
+            p90: percentile(90),
+            p99: percentile(99),
+            max: *sorted.last().unwrap(),
+        })
+    }
+}
+
+/// Time elapsed since the given UNIX timestamp in milliseconds.
+///
+/// Returns none if the timestamp is in the future (e.g., due to clock skew).
+///
+fn latency_since(timestamp_ms: u64) -> Option<Duration> {
+    let sent_at = UNIX_EPOCH.checked_add(Duration::from_millis(timestamp_ms))?;
+    SystemTime::now().duration_since(sent_at).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_window_empty() {
+        assert!(LatencyWindow::default().percentiles().is_none());
+    }
+
+    #[test]
+    fn test_latency_window_percentiles() {
+        let mut window = LatencyWindow::default();
+        for ms in (1..=100).rev() {
+            window.push(Duration::from_millis(ms));
+        }
+        let stats = window.percentiles().unwrap();
+        assert_eq!(stats.samples, 100);
+        assert_eq!(stats.p50, Duration::from_millis(50));
+        assert_eq!(stats.p90, Duration::from_millis(90));
+        assert_eq!(stats.p99, Duration::from_millis(99));
+        assert_eq!(stats.max, Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_latency_window_rolls_over() {
+        let mut window = LatencyWindow::default();
+        for _ in 0..LatencyWindow::CAPACITY {
+            window.push(Duration::from_secs(10));
+        }
+        for _ in 0..LatencyWindow::CAPACITY {
+            window.push(Duration::from_millis(5));
+        }
+        let stats = window.percentiles().unwrap();
+        assert_eq!(stats.samples as usize, LatencyWindow::CAPACITY);
+        assert_eq!(stats.max, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn test_latency_since_future_timestamp() {
+        let future = SystemTime::now() + Duration::from_secs(60);
+        let future_ms = future.duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
+        assert!(latency_since(future_ms).is_none());
+    }
+}
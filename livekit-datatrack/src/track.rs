@@ -117,6 +117,36 @@ impl DataTrackInfo {
     }
 }
 
+/// Delivery guarantee for frames sent on a data track.
+///
+/// Set by the publisher with [`DataTrackOptions::with_reliability`](crate::api::DataTrackOptions::with_reliability)
+/// and carried on each packet, so subscribers apply the matching behavior automatically.
+///
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum DataTrackReliability {
+    /// Frames are delivered as they arrive; lost or incomplete frames are dropped.
+    ///
+    /// This is the lowest-overhead mode and suits high-frequency data such as sensor readings.
+    ///
+    #[default]
+    BestEffort,
+    /// Only the most recent frame is of interest.
+    ///
+    /// The publisher skips frames which are superseded before they can be sent, and
+    /// subscribers discard frames that arrive after a newer one has been delivered.
+    ///
+    LatestOnly,
+    /// Frames are delivered completely and in the order they were pushed.
+    ///
+    /// Subscribers detect lost packets from gaps in sequence numbers and request their
+    /// retransmission, holding back later frames until the gap is filled. If a packet cannot
+    /// be recovered within the retransmit window, the affected frame is dropped and delivery
+    /// resumes with the next one.
+    ///
+    ReliableOrdered,
+}
+
 /// SFU-assigned identifier uniquely identifying a data track.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub struct DataTrackSid(String);
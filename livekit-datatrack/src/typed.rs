@@ -0,0 +1,605 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    api::{
+        DataTrack, DataTrackFrame, DataTrackOptions, DataTrackStream, DataTrackSubscribeError,
+        LocalDataTrack, PushFrameError, Remote,
+    },
+    schema::{DataTrackFrameEncoding, DataTrackSchemaId},
+};
+use bytes::Bytes;
+use std::{
+    fmt,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use thiserror::Error;
+use tokio_stream::Stream;
+
+/// Encodes and decodes values of type `T` as frame payloads.
+///
+/// Implemented by [`SerdeCodec`] (with the `serde` feature) for types implementing
+/// `serde::Serialize`, `serde::de::DeserializeOwned` and [`SerdeSchema`], and by
+/// [`ProstCodec`] (with the `prost` feature) for types implementing `prost::Message`
+/// and `prost::Name`.
+///
+pub trait FrameCodec<T> {
+    /// Frame encoding used when none is specified explicitly.
+    fn default_encoding() -> DataTrackFrameEncoding;
+
+    /// Returns whether values can be encoded and decoded using the given frame encoding.
+    fn supports(encoding: &DataTrackFrameEncoding) -> bool;
+
+    /// Returns the schema ID describing `T` in the given frame encoding, if any.
+    fn schema_id(encoding: &DataTrackFrameEncoding) -> Option<DataTrackSchemaId>;
+
+    /// Encodes a value as a frame payload.
+    fn encode(value: &T, encoding: &DataTrackFrameEncoding) -> Result<Bytes, TypedFrameError>;
+
+    /// Decodes a value from a frame payload.
+    fn decode(payload: &[u8], encoding: &DataTrackFrameEncoding) -> Result<T, TypedFrameError>;
+}
+
+/// Codec for types implementing `serde::Serialize`, `serde::de::DeserializeOwned` and
+/// [`SerdeSchema`].
+///
+/// Supports [`Json`], [`Msgpack`], and [`Cbor`] frame encodings, defaulting to JSON.
+/// For JSON, the schema ID is named [`SerdeSchema::SCHEMA_NAME`] with a [`JsonSchema`]
+/// encoding.
+///
+/// [`Json`]: DataTrackFrameEncoding::Json
+/// [`Msgpack`]: DataTrackFrameEncoding::Msgpack
+/// [`Cbor`]: DataTrackFrameEncoding::Cbor
+/// [`JsonSchema`]: crate::api::DataTrackSchemaEncoding::JsonSchema
+///
+#[derive(Debug, Clone, Copy)]
+pub struct SerdeCodec;
+
+/// Names the schema describing a type encoded with [`SerdeCodec`].
+///
+/// The name is part of the track's [`DataTrackSchemaId`] and must match on both ends, so
+/// it is declared explicitly rather than derived from the Rust type path.
+///
+/// # Examples
+///
+/// ```
+/// # use livekit_datatrack::api::SerdeSchema;
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Pose { x: f64, y: f64 }
+///
+/// impl SerdeSchema for Pose {
+///     const SCHEMA_NAME: &'static str = "example.Pose";
+/// }
+/// ```
+///
+#[cfg(feature = "serde")]
+pub trait SerdeSchema {
+    /// Name of the schema, typically a fully-qualified, versioned identifier.
+    const SCHEMA_NAME: &'static str;
+}
+
+/// Codec for types implementing `prost::Message` and `prost::Name`.
+///
+/// Supports the [`Protobuf`] frame encoding. The schema ID is named after the message's
+/// fully-qualified Protobuf name.
+///
+/// [`Protobuf`]: DataTrackFrameEncoding::Protobuf
+///
+#[derive(Debug, Clone, Copy)]
+pub struct ProstCodec;
+
+/// A decoded value along with the metadata of the frame it was received in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypedDataTrackFrame<T> {
+    /// The decoded value.
+    pub value: T,
+    /// The frame's user timestamp, if one is associated.
+    pub user_timestamp: Option<u64>,
+}
+
+/// An error that can occur when encoding or decoding a typed frame.
+#[derive(Debug, Error)]
+pub enum TypedFrameError {
+    /// The track does not declare a frame encoding.
+    #[error("Track has no frame encoding")]
+    MissingEncoding,
+
+    /// The codec does not support the track's frame encoding.
+    #[error("Frame encoding {0:?} not supported by codec")]
+    UnsupportedEncoding(DataTrackFrameEncoding),
+
+    /// The value could not be encoded.
+    #[error("Failed to encode frame: {0}")]
+    Encode(String),
+
+    /// The payload could not be decoded.
+    #[error("Failed to decode frame: {0}")]
+    Decode(String),
+}
+
+/// Typed frame could not be pushed to a data track.
+#[derive(Debug, Error)]
+pub enum TypedPushFrameError {
+    /// The value could not be encoded.
+    #[error(transparent)]
+    Encode(TypedFrameError),
+
+    /// The encoded frame could not be pushed.
+    #[error(transparent)]
+    Push(#[from] PushFrameError),
+}
+
+/// A received frame could not be decoded.
+///
+/// Yielded by [`TypedDataTrackStream`] in place of the value, the raw frame can
+/// be obtained with [`Self::into_frame`] for inspection.
+///
+#[derive(Debug, Error)]
+#[error("{error}")]
+pub struct FrameDecodeError {
+    frame: DataTrackFrame,
+    error: TypedFrameError,
+}
+
+impl FrameDecodeError {
+    /// Returns the reason the frame could not be decoded.
+    pub fn error(&self) -> &TypedFrameError {
+        &self.error
+    }
+
+    /// Consumes the error and returns the frame that couldn't be decoded.
+    pub fn into_frame(self) -> DataTrackFrame {
+        self.frame
+    }
+}
+
+/// Local data track publishing values of type `T`, encoded with codec `C`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use livekit_datatrack::api::*;
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Pose { x: f64, y: f64 }
+///
+/// # #[cfg(feature = "serde")]
+/// impl SerdeSchema for Pose {
+///     const SCHEMA_NAME: &'static str = "example.Pose";
+/// }
+///
+/// # #[cfg(feature = "serde")]
+/// # fn example(published: LocalDataTrack) -> Result<(), Box<dyn std::error::Error>> {
+/// // Publish with these options, they carry the codec's frame encoding and the schema ID of
+/// // `Pose`, e.g. with `LocalParticipant::publish_data_track`
+/// let options = TypedLocalDataTrack::<Pose>::options("pose");
+/// # let _ = options;
+///
+/// let track = TypedLocalDataTrack::<Pose>::new(published)?;
+/// track.try_push(&Pose { x: 1.0, y: 2.0 })?;
+/// # Ok(())
+/// # }
+/// ```
+///
+pub struct TypedLocalDataTrack<T, C = SerdeCodec> {
+    track: LocalDataTrack,
+    encoding: DataTrackFrameEncoding,
+    _type: PhantomData<fn() -> (T, C)>,
+}
+
+impl<T, C: FrameCodec<T>> TypedLocalDataTrack<T, C> {
+    /// Creates publish options using the codec's default frame encoding.
+    pub fn options(name: impl Into<String>) -> DataTrackOptions {
+        Self::options_with_encoding(name, C::default_encoding())
+    }
+
+    /// Creates publish options using the given frame encoding.
+    ///
+    /// The schema ID is set from the codec's [`FrameCodec::schema_id`], if it
+    /// provides one for the encoding.
+    ///
+    pub fn options_with_encoding(
+        name: impl Into<String>,
+        encoding: DataTrackFrameEncoding,
+    ) -> DataTrackOptions {
+        let schema = C::schema_id(&encoding);
+        let mut options = DataTrackOptions::new(name).with_frame_encoding(encoding);
+        if let Some(schema) = schema {
+            options = options.with_schema(schema);
+        }
+        options
+    }
+
+    /// Wraps a published track, using its declared frame encoding.
+    ///
+    /// Fails if the track has no frame encoding or it is not supported by the codec.
+    ///
+    pub fn new(track: LocalDataTrack) -> Result<Self, TypedFrameError> {
+        let encoding = supported_encoding::<T, C>(track.info().frame_encoding())?;
+        Ok(Self { track, encoding, _type: PhantomData })
+    }
+
+    /// Encodes a value and tries pushing it to subscribers of the track.
+    ///
+    /// See [`LocalDataTrack::try_push`] for details on push failures.
+    ///
+    pub fn try_push(&self, value: &T) -> Result<(), TypedPushFrameError> {
+        let payload = C::encode(value, &self.encoding).map_err(TypedPushFrameError::Encode)?;
+        Ok(self.track.try_push(payload.into())?)
+    }
+
+    /// Encodes a value and tries pushing it with the given user timestamp.
+    pub fn try_push_with_user_timestamp(
+        &self,
+        value: &T,
+        user_timestamp: u64,
+    ) -> Result<(), TypedPushFrameError> {
+        let payload = C::encode(value, &self.encoding).map_err(TypedPushFrameError::Encode)?;
+        let frame = DataTrackFrame::new(payload).with_user_timestamp(user_timestamp);
+        Ok(self.track.try_push(frame)?)
+    }
+
+    /// Returns the underlying track.
+    pub fn track(&self) -> &LocalDataTrack {
+        &self.track
+    }
+
+    /// Consumes the wrapper and returns the underlying track.
+    pub fn into_inner(self) -> LocalDataTrack {
+        self.track
+    }
+}
+
+impl<T, C> fmt::Debug for TypedLocalDataTrack<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedLocalDataTrack")
+            .field("track", &self.track)
+            .field("encoding", &self.encoding)
+            .finish()
+    }
+}
+
+impl DataTrack<Remote> {
+    /// Subscribes to the data track, decoding frames as values of type `T` with codec `C`.
+    ///
+    /// Frames are decoded according to the track's declared frame encoding. If the track
+    /// declares no encoding or one the codec does not support, every frame is yielded as
+    /// a [`FrameDecodeError`].
+    ///
+    pub async fn subscribe_typed<T, C: FrameCodec<T>>(
+        &self,
+    ) -> Result<TypedDataTrackStream<T, C>, DataTrackSubscribeError> {
+        let stream = self.subscribe().await?;
+        Ok(TypedDataTrackStream::new(stream, self.info().frame_encoding().cloned()))
+    }
+}
+
+/// A stream of values decoded from frames received on a [`RemoteDataTrack`].
+///
+/// Each item is either a decoded [`TypedDataTrackFrame`] or a [`FrameDecodeError`]
+/// holding the frame which could not be decoded; decoding failures do not end the stream.
+///
+/// [`RemoteDataTrack`]: crate::api::RemoteDataTrack
+///
+pub struct TypedDataTrackStream<T, C = SerdeCodec> {
+    inner: DataTrackStream,
+    encoding: Result<DataTrackFrameEncoding, Option<DataTrackFrameEncoding>>,
+    _type: PhantomData<fn() -> (T, C)>,
+}
+
+impl<T, C: FrameCodec<T>> TypedDataTrackStream<T, C> {
+    /// Wraps a stream of frames encoded with the given frame encoding.
+    pub fn new(inner: DataTrackStream, encoding: Option<DataTrackFrameEncoding>) -> Self {
+        let encoding = match encoding {
+            Some(encoding) if C::supports(&encoding) => Ok(encoding),
+            encoding => Err(encoding),
+        };
+        Self { inner, encoding, _type: PhantomData }
+    }
+
+    /// Returns the underlying stream of raw frames.
+    pub fn into_inner(self) -> DataTrackStream {
+        self.inner
+    }
+
+    fn decode(&self, frame: DataTrackFrame) -> Result<TypedDataTrackFrame<T>, FrameDecodeError> {
+        let result = match &self.encoding {
+            Ok(encoding) => C::decode(&frame.payload, encoding),
+            Err(None) => Err(TypedFrameError::MissingEncoding),
+            Err(Some(encoding)) => Err(TypedFrameError::UnsupportedEncoding(encoding.clone())),
+        };
+        match result {
+            Ok(value) => Ok(TypedDataTrackFrame { value, user_timestamp: frame.user_timestamp }),
+            Err(error) => Err(FrameDecodeError { frame, error }),
+        }
+    }
+}
+
+impl<T, C: FrameCodec<T>> Stream for TypedDataTrackStream<T, C> {
+    type Item = Result<TypedDataTrackFrame<T>, FrameDecodeError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        match Pin::new(&mut this.inner).poll_next(cx) {
+            Poll::Ready(Some(frame)) => Poll::Ready(Some(this.decode(frame))),
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+fn supported_encoding<T, C: FrameCodec<T>>(
+    encoding: Option<&DataTrackFrameEncoding>,
+) -> Result<DataTrackFrameEncoding, TypedFrameError> {
+    match encoding {
+        Some(encoding) if C::supports(encoding) => Ok(encoding.clone()),
+        Some(encoding) => Err(TypedFrameError::UnsupportedEncoding(encoding.clone())),
+        None => Err(TypedFrameError::MissingEncoding),
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize + serde::de::DeserializeOwned + SerdeSchema> FrameCodec<T> for SerdeCodec {
+    fn default_encoding() -> DataTrackFrameEncoding {
+        DataTrackFrameEncoding::Json
+    }
+
+    fn supports(encoding: &DataTrackFrameEncoding) -> bool {
+        matches!(
+            encoding,
+            DataTrackFrameEncoding::Json
+                | DataTrackFrameEncoding::Msgpack
+                | DataTrackFrameEncoding::Cbor
+        )
+    }
+
+    fn schema_id(encoding: &DataTrackFrameEncoding) -> Option<DataTrackSchemaId> {
+        use crate::schema::DataTrackSchemaEncoding;
+        // Only JSON may be described by a schema; MessagePack and CBOR are self-describing.
+        (*encoding == DataTrackFrameEncoding::Json)
+            .then(|| DataTrackSchemaId::new(T::SCHEMA_NAME, DataTrackSchemaEncoding::JsonSchema))
+    }
+
+    fn encode(value: &T, encoding: &DataTrackFrameEncoding) -> Result<Bytes, TypedFrameError> {
+        let encode_err = |err: &dyn fmt::Display| TypedFrameError::Encode(err.to_string());
+        let payload = match encoding {
+            DataTrackFrameEncoding::Json => {
+                serde_json::to_vec(value).map_err(|e| encode_err(&e))?
+            }
+            DataTrackFrameEncoding::Msgpack => {
+                rmp_serde::to_vec_named(value).map_err(|e| encode_err(&e))?
+            }
+            DataTrackFrameEncoding::Cbor => {
+                let mut payload = Vec::new();
+                ciborium::into_writer(value, &mut payload).map_err(|e| encode_err(&e))?;
+                payload
+            }
+            encoding => return Err(TypedFrameError::UnsupportedEncoding(encoding.clone())),
+        };
+        Ok(payload.into())
+    }
+
+    fn decode(payload: &[u8], encoding: &DataTrackFrameEncoding) -> Result<T, TypedFrameError> {
+        let decode_err = |err: &dyn fmt::Display| TypedFrameError::Decode(err.to_string());
+        match encoding {
+            DataTrackFrameEncoding::Json => {
+                serde_json::from_slice(payload).map_err(|e| decode_err(&e))
+            }
+            DataTrackFrameEncoding::Msgpack => {
+                rmp_serde::from_slice(payload).map_err(|e| decode_err(&e))
+            }
+            DataTrackFrameEncoding::Cbor => {
+                ciborium::from_reader(payload).map_err(|e| decode_err(&e))
+            }
+            encoding => Err(TypedFrameError::UnsupportedEncoding(encoding.clone())),
+        }
+    }
+}
+
+#[cfg(feature = "prost")]
+impl<T: prost::Message + prost::Name + Default> FrameCodec<T> for ProstCodec {
+    fn default_encoding() -> DataTrackFrameEncoding {
+        DataTrackFrameEncoding::Protobuf
+    }
+
+    fn supports(encoding: &DataTrackFrameEncoding) -> bool {
+        *encoding == DataTrackFrameEncoding::Protobuf
+    }
+
+    fn schema_id(encoding: &DataTrackFrameEncoding) -> Option<DataTrackSchemaId> {
+        use crate::schema::DataTrackSchemaEncoding;
+        <Self as FrameCodec<T>>::supports(encoding)
+            .then(|| DataTrackSchemaId::new(T::full_name(), DataTrackSchemaEncoding::Protobuf))
+    }
+
+    fn encode(value: &T, encoding: &DataTrackFrameEncoding) -> Result<Bytes, TypedFrameError> {
+        if !<Self as FrameCodec<T>>::supports(encoding) {
+            return Err(TypedFrameError::UnsupportedEncoding(encoding.clone()));
+        }
+        Ok(value.encode_to_vec().into())
+    }
+
+    fn decode(payload: &[u8], encoding: &DataTrackFrameEncoding) -> Result<T, TypedFrameError> {
+        if !<Self as FrameCodec<T>>::supports(encoding) {
+            return Err(TypedFrameError::UnsupportedEncoding(encoding.clone()));
+        }
+        T::decode(payload).map_err(|err| TypedFrameError::Decode(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+    use tokio::sync::broadcast;
+
+    /// Codec treating payloads as UTF-8 strings, for testing without optional features.
+    struct Utf8Codec;
+
+    impl FrameCodec<String> for Utf8Codec {
+        fn default_encoding() -> DataTrackFrameEncoding {
+            DataTrackFrameEncoding::Custom("utf8".into())
+        }
+
+        fn supports(encoding: &DataTrackFrameEncoding) -> bool {
+            *encoding == Self::default_encoding()
+        }
+
+        fn schema_id(_encoding: &DataTrackFrameEncoding) -> Option<DataTrackSchemaId> {
+            None
+        }
+
+        fn encode(value: &String, _: &DataTrackFrameEncoding) -> Result<Bytes, TypedFrameError> {
+            Ok(Bytes::copy_from_slice(value.as_bytes()))
+        }
+
+        fn decode(payload: &[u8], _: &DataTrackFrameEncoding) -> Result<String, TypedFrameError> {
+            String::from_utf8(payload.to_vec())
+                .map_err(|err| TypedFrameError::Decode(err.to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_yields_decode_errors() {
+        let (frame_tx, frame_rx) = broadcast::channel(4);
+        let stream = DataTrackStream::from_receiver(frame_rx);
+        let mut stream = TypedDataTrackStream::<String, Utf8Codec>::new(
+            stream,
+            Some(Utf8Codec::default_encoding()),
+        );
+
+        frame_tx.send(DataTrackFrame::new(b"hello".as_slice()).with_user_timestamp(7)).unwrap();
+        frame_tx.send(DataTrackFrame::new(vec![0xFF, 0xFE])).unwrap();
+        frame_tx.send(DataTrackFrame::new(b"world".as_slice())).unwrap();
+        drop(frame_tx);
+
+        let first = stream.next().await.unwrap().unwrap();
+        assert_eq!(
+            first,
+            TypedDataTrackFrame { value: "hello".to_string(), user_timestamp: Some(7) }
+        );
+
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert!(matches!(err.error(), TypedFrameError::Decode(_)));
+        assert_eq!(err.into_frame().payload().as_ref(), &[0xFF, 0xFE]);
+
+        assert_eq!(stream.next().await.unwrap().unwrap().value, "world");
+        assert!(stream.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stream_unsupported_encoding() {
+        let (frame_tx, frame_rx) = broadcast::channel(4);
+        let stream = DataTrackStream::from_receiver(frame_rx);
+        let mut stream = TypedDataTrackStream::<String, Utf8Codec>::new(
+            stream,
+            Some(DataTrackFrameEncoding::Json),
+        );
+
+        frame_tx.send(DataTrackFrame::new(b"hello".as_slice())).unwrap();
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert!(matches!(
+            err.error(),
+            TypedFrameError::UnsupportedEncoding(DataTrackFrameEncoding::Json)
+        ));
+    }
+
+    #[test]
+    fn test_options_without_schema() {
+        let options = TypedLocalDataTrack::<String, Utf8Codec>::options("text");
+        assert_eq!(options.frame_encoding, Some(Utf8Codec::default_encoding()));
+        assert!(options.schema.is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    mod serde_codec {
+        use super::*;
+        use crate::api::DataTrackSchemaEncoding;
+        use test_case::test_case;
+
+        #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+        struct Pose {
+            x: f64,
+            frame_id: String,
+        }
+
+        impl SerdeSchema for Pose {
+            const SCHEMA_NAME: &'static str = "example.Pose";
+        }
+
+        #[test_case(DataTrackFrameEncoding::Json)]
+        #[test_case(DataTrackFrameEncoding::Msgpack)]
+        #[test_case(DataTrackFrameEncoding::Cbor)]
+        fn test_roundtrip(encoding: DataTrackFrameEncoding) {
+            let pose = Pose { x: 1.5, frame_id: "map".into() };
+            let payload = SerdeCodec::encode(&pose, &encoding).unwrap();
+            let decoded: Pose = SerdeCodec::decode(&payload, &encoding).unwrap();
+            assert_eq!(decoded, pose);
+        }
+
+        #[test]
+        fn test_decode_error() {
+            let result: Result<Pose, _> = SerdeCodec::decode(b"{}", &DataTrackFrameEncoding::Json);
+            assert!(matches!(result, Err(TypedFrameError::Decode(_))));
+        }
+
+        #[test]
+        fn test_schema_id() {
+            let options = TypedLocalDataTrack::<Pose>::options("pose");
+            let schema = options.schema.unwrap();
+            assert_eq!(schema.name(), "example.Pose");
+            assert_eq!(schema.encoding(), &DataTrackSchemaEncoding::JsonSchema);
+
+            let options = TypedLocalDataTrack::<Pose>::options_with_encoding(
+                "pose",
+                DataTrackFrameEncoding::Cbor,
+            );
+            assert!(options.schema.is_none());
+        }
+    }
+
+    #[cfg(feature = "prost")]
+    mod prost_codec {
+        use super::*;
+        use crate::api::DataTrackSchemaEncoding;
+
+        #[derive(Clone, PartialEq, prost::Message)]
+        struct Pose {
+            #[prost(double, tag = "1")]
+            x: f64,
+        }
+
+        impl prost::Name for Pose {
+            const NAME: &'static str = "Pose";
+            const PACKAGE: &'static str = "example";
+        }
+
+        #[test]
+        fn test_roundtrip() {
+            let encoding = DataTrackFrameEncoding::Protobuf;
+            let payload = ProstCodec::encode(&Pose { x: 1.5 }, &encoding).unwrap();
+            let decoded: Pose = ProstCodec::decode(&payload, &encoding).unwrap();
+            assert_eq!(decoded, Pose { x: 1.5 });
+        }
+
+        #[test]
+        fn test_schema_id() {
+            let options = TypedLocalDataTrack::<Pose, ProstCodec>::options("pose");
+            let schema = options.schema.unwrap();
+            assert_eq!(schema.name(), "example.Pose");
+            assert_eq!(schema.encoding(), &DataTrackSchemaEncoding::Protobuf);
+        }
+    }
+}
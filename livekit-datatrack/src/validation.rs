@@ -0,0 +1,356 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::schema::{DataTrackSchemaEncoding, DataTrackSchemaId};
+use std::{fmt, sync::Arc};
+use thiserror::Error;
+
+/// Checks frame payloads against a schema definition.
+///
+/// Validation is opt-in. Attach a validator to a publication with
+/// [`DataTrackOptions::with_validator`] to reject non-conforming frames on push, or to
+/// a subscription with [`DataTrackSubscribeOptions::with_validator`] to discard them
+/// on receive.
+///
+/// Validators for well-known schema encodings can be created with [`validator_for_schema`]
+/// when the corresponding feature is enabled. Implement this trait to validate frames
+/// against other formats.
+///
+/// [`DataTrackOptions::with_validator`]: crate::api::DataTrackOptions::with_validator
+/// [`DataTrackSubscribeOptions::with_validator`]: crate::api::DataTrackSubscribeOptions::with_validator
+///
+pub trait FrameValidator: Send + Sync + fmt::Debug {
+    /// Returns an error if the payload does not conform to the schema.
+    fn validate(&self, payload: &[u8]) -> Result<(), FrameValidationError>;
+}
+
+/// Frame payload does not conform to the schema it was validated against.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+#[error("Frame does not conform to schema: {reason}")]
+pub struct FrameValidationError {
+    reason: String,
+}
+
+impl FrameValidationError {
+    /// Creates an error with a human-readable reason.
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self { reason: reason.into() }
+    }
+
+    /// Returns the reason the frame was rejected.
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+/// An error that can occur when creating a validator from a schema definition.
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Error))]
+#[cfg_attr(feature = "uniffi", uniffi(flat_error))]
+pub enum SchemaDefinitionError {
+    /// No validator is available for the schema encoding.
+    ///
+    /// Validation of JSON Schema and Protobuf definitions requires the `json-schema`
+    /// and `protobuf-schema` features respectively.
+    ///
+    #[error("No validator available for schema encoding {0:?}")]
+    UnsupportedEncoding(DataTrackSchemaEncoding),
+
+    /// The schema definition could not be parsed or compiled.
+    #[error("Invalid schema definition: {0}")]
+    Invalid(String),
+
+    /// The schema definition does not contain the message type to validate against.
+    #[error("Message type '{0}' not found in schema definition")]
+    MessageNotFound(String),
+}
+
+/// Creates a validator from a schema definition.
+///
+/// The definition is the same string shared with [`define_schema`]; how it is interpreted
+/// depends on the encoding of the schema ID:
+///
+/// - [`JsonSchema`]: a JSON Schema document. Frames must be UTF-8 JSON conforming to it.
+/// - [`Protobuf`]: a `.proto` file. Frames must decode as the message whose fully-qualified
+///   name matches the schema name, or the only message in the file if none match.
+///
+/// An empty definition is rejected as [`Invalid`](SchemaDefinitionError::Invalid) regardless
+/// of the encoding.
+///
+/// [`define_schema`]: https://docs.rs/livekit/latest/livekit/prelude/struct.LocalParticipant.html#method.define_schema
+/// [`JsonSchema`]: DataTrackSchemaEncoding::JsonSchema
+/// [`Protobuf`]: DataTrackSchemaEncoding::Protobuf
+///
+pub fn validator_for_schema(
+    schema: &DataTrackSchemaId,
+    definition: &str,
+) -> Result<Arc<dyn FrameValidator>, SchemaDefinitionError> {
+    if definition.trim().is_empty() {
+        Err(SchemaDefinitionError::Invalid("definition is empty".into()))?
+    }
+    match schema.encoding() {
+        #[cfg(feature = "json-schema")]
+        DataTrackSchemaEncoding::JsonSchema => Ok(Arc::new(JsonSchemaValidator::new(definition)?)),
+        #[cfg(feature = "protobuf-schema")]
+        DataTrackSchemaEncoding::Protobuf => {
+            Ok(Arc::new(ProtobufValidator::from_proto(definition, Some(schema.name()))?))
+        }
+        encoding => Err(SchemaDefinitionError::UnsupportedEncoding(encoding.clone())),
+    }
+}
+
+#[cfg(feature = "json-schema")]
+pub use json::JsonSchemaValidator;
+
+#[cfg(feature = "json-schema")]
+mod json {
+    use super::{FrameValidationError, FrameValidator, SchemaDefinitionError};
+    use std::fmt;
+
+    /// Validates JSON frames against a JSON Schema document.
+    pub struct JsonSchemaValidator {
+        validator: jsonschema::Validator,
+    }
+
+    impl JsonSchemaValidator {
+        /// Compiles a validator from a JSON Schema document.
+        pub fn new(definition: &str) -> Result<Self, SchemaDefinitionError> {
+            let schema: serde_json::Value = serde_json::from_str(definition)
+                .map_err(|err| SchemaDefinitionError::Invalid(err.to_string()))?;
+            let validator = jsonschema::validator_for(&schema)
+                .map_err(|err| SchemaDefinitionError::Invalid(err.to_string()))?;
+            Ok(Self { validator })
+        }
+    }
+
+    impl FrameValidator for JsonSchemaValidator {
+        fn validate(&self, payload: &[u8]) -> Result<(), FrameValidationError> {
+            let instance: serde_json::Value = serde_json::from_slice(payload)
+                .map_err(|err| FrameValidationError::new(format!("malformed JSON: {err}")))?;
+            self.validator
+                .validate(&instance)
+                .map_err(|err| FrameValidationError::new(err.to_string()))
+        }
+    }
+
+    impl fmt::Debug for JsonSchemaValidator {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("JsonSchemaValidator").finish_non_exhaustive()
+        }
+    }
+}
+
+#[cfg(feature = "protobuf-schema")]
+pub use protobuf::ProtobufValidator;
+
+#[cfg(feature = "protobuf-schema")]
+mod protobuf {
+    use super::{FrameValidationError, FrameValidator, SchemaDefinitionError};
+    use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor};
+    use protox::file::{ChainFileResolver, File, FileResolver, GoogleFileResolver};
+
+    /// Validates Protobuf frames by decoding them as a given message type.
+    #[derive(Debug)]
+    pub struct ProtobufValidator {
+        message: MessageDescriptor,
+    }
+
+    impl ProtobufValidator {
+        /// Compiles a validator from the source of a `.proto` file.
+        ///
+        /// Imports of the well-known `google/protobuf/*.proto` types are supported; other
+        /// imports are not. If `message_name` is `None` or does not match any message,
+        /// the file must define exactly one top-level message.
+        ///
+        pub fn from_proto(
+            definition: &str,
+            message_name: Option<&str>,
+        ) -> Result<Self, SchemaDefinitionError> {
+            let mut resolver = ChainFileResolver::new();
+            resolver.add(SourceFileResolver { source: definition.to_string() });
+            resolver.add(GoogleFileResolver::new());
+
+            let mut compiler = protox::Compiler::with_file_resolver(resolver);
+            compiler
+                .open_file(SourceFileResolver::FILE_NAME)
+                .map_err(|err| SchemaDefinitionError::Invalid(err.to_string()))?;
+            Self::from_descriptor_pool(&compiler.descriptor_pool(), message_name)
+        }
+
+        /// Creates a validator from an encoded `FileDescriptorSet`.
+        ///
+        /// Use this when the schema has already been compiled, for example with `protoc
+        /// --descriptor_set_out`.
+        ///
+        pub fn from_file_descriptor_set(
+            bytes: &[u8],
+            message_name: Option<&str>,
+        ) -> Result<Self, SchemaDefinitionError> {
+            let pool = DescriptorPool::decode(bytes)
+                .map_err(|err| SchemaDefinitionError::Invalid(err.to_string()))?;
+            Self::from_descriptor_pool(&pool, message_name)
+        }
+
+        fn from_descriptor_pool(
+            pool: &DescriptorPool,
+            message_name: Option<&str>,
+        ) -> Result<Self, SchemaDefinitionError> {
+            if let Some(message) = message_name.and_then(|name| pool.get_message_by_name(name)) {
+                return Ok(Self { message });
+            }
+            // Fall back to the only message defined outside of the well-known types.
+            let mut candidates = pool
+                .files()
+                .filter(|file| file.package_name() != "google.protobuf")
+                .flat_map(|file| file.messages().collect::<Vec<_>>());
+            match (candidates.next(), candidates.next()) {
+                (Some(message), None) => Ok(Self { message }),
+                _ => Err(SchemaDefinitionError::MessageNotFound(
+                    message_name.unwrap_or_default().to_string(),
+                )),
+            }
+        }
+
+        /// Returns the fully-qualified name of the message type frames are decoded as.
+        pub fn message_name(&self) -> &str {
+            self.message.full_name()
+        }
+    }
+
+    impl FrameValidator for ProtobufValidator {
+        fn validate(&self, payload: &[u8]) -> Result<(), FrameValidationError> {
+            DynamicMessage::decode(self.message.clone(), payload)
+                .map(|_| ())
+                .map_err(|err| FrameValidationError::new(err.to_string()))
+        }
+    }
+
+    /// Resolves the in-memory schema definition as a single `.proto` file.
+    struct SourceFileResolver {
+        source: String,
+    }
+
+    impl SourceFileResolver {
+        const FILE_NAME: &'static str = "schema.proto";
+    }
+
+    impl FileResolver for SourceFileResolver {
+        fn open_file(&self, name: &str) -> Result<File, protox::Error> {
+            if name != Self::FILE_NAME {
+                return Err(protox::Error::file_not_found(name));
+            }
+            File::from_source(name, &self.source)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validator_for_unsupported_encoding() {
+        let schema = DataTrackSchemaId::new("pose", DataTrackSchemaEncoding::Ros2Msg);
+        let result = validator_for_schema(&schema, "float64 x");
+        assert!(matches!(result, Err(SchemaDefinitionError::UnsupportedEncoding(_))));
+    }
+
+    #[test]
+    fn test_validator_for_empty_definition() {
+        let schema = DataTrackSchemaId::new("pose", DataTrackSchemaEncoding::Ros2Msg);
+        let result = validator_for_schema(&schema, " \n");
+        assert!(matches!(result, Err(SchemaDefinitionError::Invalid(_))));
+    }
+
+    #[cfg(feature = "json-schema")]
+    mod json {
+        use super::*;
+
+        const DEFINITION: &str = r#"{
+            "type": "object",
+            "properties": { "x": { "type": "number" } },
+            "required": ["x"]
+        }"#;
+
+        fn validator() -> Arc<dyn FrameValidator> {
+            let schema = DataTrackSchemaId::new("pose", DataTrackSchemaEncoding::JsonSchema);
+            validator_for_schema(&schema, DEFINITION).unwrap()
+        }
+
+        #[test]
+        fn test_conforming_frame() {
+            assert_eq!(validator().validate(br#"{"x": 1.5}"#), Ok(()));
+        }
+
+        #[test]
+        fn test_non_conforming_frame() {
+            assert!(validator().validate(br#"{"x": "one"}"#).is_err());
+            assert!(validator().validate(br#"{}"#).is_err());
+        }
+
+        #[test]
+        fn test_malformed_frame() {
+            assert!(validator().validate(&[0xFF, 0x00]).is_err());
+        }
+
+        #[test]
+        fn test_invalid_definition() {
+            let result = JsonSchemaValidator::new("{ not json");
+            assert!(matches!(result, Err(SchemaDefinitionError::Invalid(_))));
+        }
+    }
+
+    #[cfg(feature = "protobuf-schema")]
+    mod protobuf {
+        use super::*;
+
+        const DEFINITION: &str = r#"
+            syntax = "proto3";
+            package example;
+            message Pose {
+                double x = 1;
+                string frame_id = 2;
+            }
+        "#;
+
+        #[test]
+        fn test_conforming_frame() {
+            let validator = ProtobufValidator::from_proto(DEFINITION, None).unwrap();
+            assert_eq!(validator.message_name(), "example.Pose");
+
+            // Field 1 (fixed64) followed by field 2 (length-delimited)
+            let mut frame = vec![0x09];
+            frame.extend_from_slice(&1.5f64.to_le_bytes());
+            frame.extend_from_slice(&[0x12, 0x03, b'm', b'a', b'p']);
+            assert_eq!(validator.validate(&frame), Ok(()));
+        }
+
+        #[test]
+        fn test_non_conforming_frame() {
+            let validator = ProtobufValidator::from_proto(DEFINITION, None).unwrap();
+            // Field 1 declared with length-delimited wire type
+            assert!(validator.validate(&[0x0A, 0x08, 0x00]).is_err());
+        }
+
+        #[test]
+        fn test_message_selected_by_schema_name() {
+            let definition = format!("{DEFINITION}\nmessage Twist {{ double z = 1; }}");
+            let schema = DataTrackSchemaId::new("example.Twist", DataTrackSchemaEncoding::Protobuf);
+            assert!(validator_for_schema(&schema, &definition).is_ok());
+
+            let result = ProtobufValidator::from_proto(&definition, None);
+            assert!(matches!(result, Err(SchemaDefinitionError::MessageNotFound(_))));
+        }
+    }
+}
@@ -15,24 +15,57 @@
 use crate::proto;
 use livekit::{
     data_track::{
-        DataTrackFrame, DataTrackFrameEncoding, DataTrackInfo, DataTrackOptions,
-        DataTrackSchemaEncoding, DataTrackSchemaId, DataTrackSubscribeError, PublishError,
-        PushFrameError, PushFrameErrorReason, RemoteDataTrackPipelineOptions,
+        DataTrackFrame, DataTrackFrameEncoding, DataTrackInfo, DataTrackLatencyStats,
+        DataTrackOptions, DataTrackReliability, DataTrackSchemaEncoding, DataTrackSchemaId,
+        DataTrackSubscribeError, FrameValidator, LocalDataTrackStats, PublishError, PushFrameError,
+        PushFrameErrorReason, RemoteDataTrackPipelineOptions, RemoteDataTrackStats,
+        SchemaDefinitionError,
     },
     prelude::DataTrackSubscribeOptions,
 };
+use std::sync::Arc;
 
-impl From<proto::DataTrackOptions> for DataTrackOptions {
-    fn from(options: proto::DataTrackOptions) -> Self {
+impl TryFrom<proto::DataTrackOptions> for DataTrackOptions {
+    type Error = SchemaDefinitionError;
+
+    fn try_from(options: proto::DataTrackOptions) -> Result<Self, Self::Error> {
         let frame_encoding = options.frame_encoding.map(Into::into);
+        let schema: Option<DataTrackSchemaId> = options.schema.map(Into::into);
         let mut result = Self::new(options.name);
-        if let Some(schema) = options.schema {
-            result = result.with_schema(schema.into());
+        if let Some(definition) = options.validation_definition {
+            result = result.with_validator(validator(schema.as_ref(), &definition)?);
+        }
+        if let Some(schema) = schema {
+            result = result.with_schema(schema);
         }
         if let Some(frame_encoding) = frame_encoding {
             result = result.with_frame_encoding(frame_encoding);
         }
-        result
+        if options.reliability.is_some() {
+            result = result.with_reliability(options.reliability().into());
+        }
+        Ok(result)
+    }
+}
+
+/// Creates a validator for the given definition, which requires the track to have a schema.
+pub fn validator(
+    schema: Option<&DataTrackSchemaId>,
+    definition: &str,
+) -> Result<Arc<dyn FrameValidator>, SchemaDefinitionError> {
+    let schema = schema.ok_or_else(|| {
+        SchemaDefinitionError::Invalid("validation requires the track to have a schema".into())
+    })?;
+    livekit::data_track::validator_for_schema(schema, definition)
+}
+
+impl From<proto::DataTrackReliability> for DataTrackReliability {
+    fn from(reliability: proto::DataTrackReliability) -> Self {
+        match reliability {
+            proto::DataTrackReliability::BestEffort => Self::BestEffort,
+            proto::DataTrackReliability::LatestOnly => Self::LatestOnly,
+            proto::DataTrackReliability::ReliableOrdered => Self::ReliableOrdered,
+        }
     }
 }
 
@@ -74,6 +107,51 @@ impl From<proto::RemoteDataTrackPipelineOptions> for RemoteDataTrackPipelineOpti
     }
 }
 
+impl From<LocalDataTrackStats> for proto::LocalDataTrackStats {
+    fn from(stats: LocalDataTrackStats) -> Self {
+        Self {
+            frames_pushed: stats.frames_pushed,
+            frames_rejected: stats.frames_rejected,
+            frames_dropped: stats.frames_dropped,
+            frames_sent: stats.frames_sent,
+            send_queue_drops: stats.send_queue_drops,
+            packets_sent: stats.packets_sent,
+            packets_retransmitted: stats.packets_retransmitted,
+            bytes_sent: stats.bytes_sent,
+        }
+    }
+}
+
+impl From<RemoteDataTrackStats> for proto::RemoteDataTrackStats {
+    fn from(stats: RemoteDataTrackStats) -> Self {
+        Self {
+            packets_received: stats.packets_received,
+            packets_out_of_order: stats.packets_out_of_order,
+            packets_lost: stats.packets_lost,
+            packets_recovered: stats.packets_recovered,
+            frames_received: stats.frames_received,
+            frames_dropped: stats.frames_dropped,
+            frames_evicted: stats.frames_evicted,
+            frames_incomplete: stats.frames_incomplete,
+            frames_oversized: stats.frames_oversized,
+            latency: stats.latency.map(Into::into),
+        }
+    }
+}
+
+impl From<DataTrackLatencyStats> for proto::DataTrackLatencyStats {
+    fn from(stats: DataTrackLatencyStats) -> Self {
+        let ms = |duration: std::time::Duration| duration.as_secs_f64() * 1000.0;
+        Self {
+            samples: stats.samples,
+            p50_ms: ms(stats.p50),
+            p90_ms: ms(stats.p90),
+            p99_ms: ms(stats.p99),
+            max_ms: ms(stats.max),
+        }
+    }
+}
+
 impl From<proto::DataTrackSubscribeOptions> for DataTrackSubscribeOptions {
     fn from(msg: proto::DataTrackSubscribeOptions) -> Self {
         let mut options = Self::new();
@@ -198,6 +276,15 @@ impl From<&PublishError> for proto::PublishDataTrackErrorCode {
     }
 }
 
+impl From<SchemaDefinitionError> for proto::PublishDataTrackError {
+    fn from(err: SchemaDefinitionError) -> Self {
+        proto::PublishDataTrackError {
+            code: proto::PublishDataTrackErrorCode::InvalidSchema as i32,
+            message: err.to_string(),
+        }
+    }
+}
+
 impl From<PublishError> for proto::PublishDataTrackError {
     fn from(err: PublishError) -> Self {
         proto::PublishDataTrackError {
@@ -212,6 +299,7 @@ impl From<PushFrameErrorReason> for proto::LocalDataTrackTryPushErrorCode {
         match reason {
             PushFrameErrorReason::TrackUnpublished => Self::TrackUnpublished,
             PushFrameErrorReason::QueueFull => Self::QueueFull,
+            PushFrameErrorReason::ValidationFailed => Self::ValidationFailed,
         }
     }
 }
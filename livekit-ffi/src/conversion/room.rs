@@ -14,6 +14,7 @@
 
 use crate::{proto, server::room::FfiRoom};
 use livekit::{
+    candidate_pair::{CandidateInfo, PeerConnectionTarget},
     e2ee::{
         key_provider::{KeyProvider, KeyProviderOptions},
         E2eeOptions, EncryptionType,
@@ -249,6 +250,7 @@ impl From<proto::RtcConfig> for RtcConfiguration {
                     proto::ContinualGatheringPolicy::try_from(x).unwrap().into()
                 }),
             ice_servers: value.ice_servers.into_iter().map(Into::into).collect(),
+            ..default
         }
     }
 }
@@ -324,6 +326,30 @@ impl From<proto::DataPacketKind> for DataPacketKind {
     }
 }
 
+impl From<PeerConnectionTarget> for proto::PeerConnectionTarget {
+    fn from(value: PeerConnectionTarget) -> Self {
+        match value {
+            PeerConnectionTarget::Publisher => Self::PcPublisher,
+            PeerConnectionTarget::Subscriber => Self::PcSubscriber,
+        }
+    }
+}
+
+impl From<CandidateInfo> for proto::CandidateInfo {
+    fn from(value: CandidateInfo) -> Self {
+        Self {
+            candidate_type: value.candidate_type.map(|t| proto::IceCandidateType::from(t).into()),
+            protocol: value.protocol,
+            address: value.address,
+            port: value.port.into(),
+            relay_protocol: value
+                .relay_protocol
+                .map(|p| proto::IceServerTransportProtocol::from(p).into()),
+            url: value.url,
+        }
+    }
+}
+
 impl From<DataPacketKind> for proto::DataPacketKind {
     fn from(value: DataPacketKind) -> Self {
         match value {
@@ -337,10 +363,12 @@ impl From<proto::TrackPublishOptions> for TrackPublishOptions {
     fn from(opts: proto::TrackPublishOptions) -> Self {
         let default_publish_options = TrackPublishOptions::default();
         let video_codec = opts.video_codec.map(|x| proto::VideoCodec::try_from(x).ok()).flatten();
+        let backup_codec = opts.backup_codec.map(|x| proto::VideoCodec::try_from(x).ok()).flatten();
         let source = opts.source.map(|x| proto::TrackSource::try_from(x).ok()).flatten();
 
         Self {
             video_codec: video_codec.map(Into::into).unwrap_or(default_publish_options.video_codec),
+            backup_codec: backup_codec.map(Into::into),
             source: source.map(Into::into).unwrap_or(default_publish_options.source),
             video_encoding: opts
                 .video_encoding
@@ -352,6 +380,7 @@ impl From<proto::TrackPublishOptions> for TrackPublishOptions {
                 .or(default_publish_options.audio_encoding),
             dtx: opts.dtx.unwrap_or(default_publish_options.dtx),
             red: opts.red.unwrap_or(default_publish_options.red),
+            opus: default_publish_options.opus,
             simulcast: opts.simulcast.unwrap_or(default_publish_options.simulcast),
             stream: opts.stream.unwrap_or(default_publish_options.stream),
             simulcast_layers: default_publish_options.simulcast_layers,
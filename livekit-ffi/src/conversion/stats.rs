@@ -12,6 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::time::UNIX_EPOCH;
+
+use livekit::stats_monitor::{
+    CandidatePairSummary, StatsDirection, StatsReport, TrackStatsSummary,
+};
 use livekit::webrtc::{
     prelude::DataChannelState,
     stats::{
@@ -634,3 +639,73 @@ impl From<rtc::dictionaries::CertificateStats> for proto::CertificateStats {
         }
     }
 }
+
+impl From<StatsDirection> for proto::StatsDirection {
+    fn from(value: StatsDirection) -> Self {
+        match value {
+            StatsDirection::Inbound => Self::Inbound,
+            StatsDirection::Outbound => Self::Outbound,
+        }
+    }
+}
+
+impl From<CandidatePairSummary> for proto::CandidatePairSummary {
+    fn from(value: CandidatePairSummary) -> Self {
+        Self {
+            local_candidate_type: value
+                .local_candidate_type
+                .map(|v| proto::IceCandidateType::from(v) as i32),
+            remote_candidate_type: value
+                .remote_candidate_type
+                .map(|v| proto::IceCandidateType::from(v) as i32),
+            protocol: value.protocol,
+            relay_protocol: value
+                .relay_protocol
+                .map(|v| proto::IceServerTransportProtocol::from(v) as i32),
+            round_trip_time_ms: value.round_trip_time.map(|rtt| rtt.as_secs_f64() * 1000.0),
+            available_outgoing_bitrate: value.available_outgoing_bitrate,
+        }
+    }
+}
+
+impl From<TrackStatsSummary> for proto::TrackStatsSummary {
+    fn from(value: TrackStatsSummary) -> Self {
+        Self {
+            track_sid: value.track_sid.map(String::from),
+            participant_identity: value.participant_identity.map(|identity| identity.0),
+            rtc_track_id: value.rtc_track_id,
+            kind: proto::TrackKind::from(value.kind).into(),
+            direction: proto::StatsDirection::from(value.direction).into(),
+            codec: value.codec,
+            bitrate: value.bitrate,
+            frames_per_second: value.frames_per_second,
+            width: value.resolution.map(|dimension| dimension.0),
+            height: value.resolution.map(|dimension| dimension.1),
+            packet_loss_percent: value.packet_loss_percent,
+            jitter_ms: value.jitter.map(|jitter| jitter.as_secs_f64() * 1000.0),
+            freeze_count: value.freeze_count,
+            total_freeze_duration_ms: value.total_freeze_duration.as_secs_f64() * 1000.0,
+            pause_count: value.pause_count,
+            total_pause_duration_ms: value.total_pause_duration.as_secs_f64() * 1000.0,
+            quality_limitation_reason: proto::QualityLimitationReason::from(
+                value.quality_limitation_reason,
+            )
+            .into(),
+            candidate_pair: value.candidate_pair.map(Into::into),
+        }
+    }
+}
+
+impl From<StatsReport> for proto::StatsReport {
+    fn from(value: StatsReport) -> Self {
+        Self {
+            timestamp_ms: value
+                .timestamp
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as i64)
+                .unwrap_or_default(),
+            elapsed_ms: value.elapsed.as_millis() as u64,
+            tracks: value.tracks.into_iter().map(Into::into).collect(),
+        }
+    }
+}
@@ -104,6 +104,7 @@ impl FfiAudioStream {
                     queue_size_frames: new_stream
                         .queue_size_frames
                         .map(|capacity| capacity as usize),
+                    ..Default::default()
                 };
 
                 // When the audio filter supports separate rates (v2), it
@@ -122,16 +123,18 @@ impl FfiAudioStream {
                 let audio_filter_options = new_stream.audio_filter_options.unwrap_or_default();
                 let filter_session = match &audio_filter {
                     Some(audio_filter) => {
-                        let session = audio_filter.clone().new_session(
+                        match audio_filter.clone().new_session(
                             input_sample_rate,
                             output_sample_rate,
                             &audio_filter_options,
                             info.as_ref().map(|i| i.stream_info.clone()).unwrap(),
-                        );
-                        if session.is_none() {
-                            log::error!("failed to initialize the audio filter. it will not be enabled for this session.");
+                        ) {
+                            Ok(session) => Some(session),
+                            Err(err) => {
+                                log::error!("failed to initialize the audio filter, it will not be enabled for this session: {err}");
+                                None
+                            }
                         }
-                        session
                     }
                     None => None,
                 };
@@ -279,6 +282,7 @@ impl FfiAudioStream {
                 let track_sid = track.sid();
                 let options = NativeAudioStreamOptions {
                     queue_size_frames: request.queue_size_frames.map(|capacity| capacity as usize),
+                    ..Default::default()
                 };
 
                 let mut track_finished_rx = track_finished_tx.subscribe();
@@ -320,15 +324,18 @@ impl FfiAudioStream {
                                 room_handle: ffi_participant.room.handle_id,
                             };
 
-                            let session = filter.clone().new_session(
+                            let session = match filter.clone().new_session(
                                 input_sample_rate as u32,
                                 output_sample_rate as u32,
                                 &options,
                                 info.stream_info.clone(),
-                            );
-                            if session.is_none() {
-                                log::error!("failed to initialize the audio filter. it will not be enabled for this session.");
-                            }
+                            ) {
+                                Ok(session) => Some(session),
+                                Err(err) => {
+                                    log::error!("failed to initialize the audio filter, it will not be enabled for this session: {err}");
+                                    None
+                                }
+                            };
                             (session, Some(info))
                         }
                         None => (None, None),
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use super::{FfiHandle, FfiServer};
-use crate::{proto, FfiHandleId, FfiResult};
+use crate::{conversion::data_track::validator, proto, FfiError, FfiHandleId, FfiResult};
 use futures_util::StreamExt;
 use livekit::data_track::{
     DataTrackFrame, DataTrackStream, DataTrackSubscribeError, DataTrackSubscribeOptions,
@@ -81,6 +81,14 @@ impl FfiLocalDataTrack {
         let error = self.inner.try_push(frame).err().map(Into::into);
         Ok(proto::LocalDataTrackTryPushResponse { error })
     }
+
+    pub fn get_stats(
+        self,
+        _server: &'static FfiServer,
+        _request: proto::LocalDataTrackGetStatsRequest,
+    ) -> FfiResult<proto::LocalDataTrackGetStatsResponse> {
+        Ok(proto::LocalDataTrackGetStatsResponse { stats: self.inner.stats().into() })
+    }
 }
 
 impl FfiRemoteDataTrack {
@@ -118,11 +126,27 @@ impl FfiRemoteDataTrack {
         Ok(proto::RemoteDataTrackSetPipelineOptionsResponse::default())
     }
 
+    pub fn get_stats(
+        self,
+        _server: &'static FfiServer,
+        _request: proto::RemoteDataTrackGetStatsRequest,
+    ) -> FfiResult<proto::RemoteDataTrackGetStatsResponse> {
+        Ok(proto::RemoteDataTrackGetStatsResponse { stats: self.inner.stats().into() })
+    }
+
     pub fn subscribe(
         self,
         server: &'static FfiServer,
         request: proto::SubscribeDataTrackRequest,
     ) -> FfiResult<proto::SubscribeDataTrackResponse> {
+        let definition = request.options.validation_definition.clone();
+        let mut options = DataTrackSubscribeOptions::from(request.options);
+        if let Some(definition) = definition {
+            let validator = validator(self.inner.info().schema(), &definition)
+                .map_err(|err| FfiError::InvalidRequest(err.to_string().into()))?;
+            options = options.with_validator(validator);
+        }
+
         let handle_id = server.next_id();
         let (drop_tx, drop_rx) = oneshot::channel();
         let notify_read = Arc::new(Notify::new());
@@ -136,7 +160,7 @@ impl FfiRemoteDataTrack {
         server.store_handle(handle_id, stream);
 
         let task = SubscriptionTask { server, handle_id, notify_read, eos_event, drop_rx };
-        let task_handle = server.async_runtime.spawn(task.run(self.inner, request.options.into()));
+        let task_handle = server.async_runtime.spawn(task.run(self.inner, options));
         server.watch_panic(task_handle);
 
         let stream =
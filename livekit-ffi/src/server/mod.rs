@@ -45,6 +45,7 @@ pub mod platform_audio;
 pub mod requests;
 pub mod resampler;
 pub mod room;
+pub mod stats_monitor;
 mod utils;
 pub mod video_source;
 pub mod video_stream;
@@ -293,11 +293,14 @@ impl FfiParticipant {
         let local = self.guard_local_participant()?;
 
         let handle = server.async_runtime.spawn(async move {
-            let result = match local.publish_data_track(request.options).await {
-                Ok(track) => {
-                    let ffi_track = FfiLocalDataTrack::from_track(server, track);
-                    proto::publish_data_track_callback::Result::Track(ffi_track)
-                }
+            let result = match DataTrackOptions::try_from(request.options) {
+                Ok(options) => match local.publish_data_track(options).await {
+                    Ok(track) => {
+                        let ffi_track = FfiLocalDataTrack::from_track(server, track);
+                        proto::publish_data_track_callback::Result::Track(ffi_track)
+                    }
+                    Err(err) => proto::publish_data_track_callback::Result::Error(err.into()),
+                },
                 Err(err) => proto::publish_data_track_callback::Result::Error(err.into()),
             };
             let callback = proto::PublishDataTrackCallback { async_id, result: Some(result) };
@@ -28,7 +28,7 @@ use super::{
     participant::FfiParticipant,
     platform_audio, resampler,
     room::{self, FfiPublication, FfiTrack},
-    video_source, video_stream, FfiError, FfiResult, FfiServer,
+    stats_monitor, video_source, video_stream, FfiError, FfiResult, FfiServer,
 };
 use crate::proto;
 
@@ -251,6 +251,34 @@ fn on_set_remote_track_publication_quality(
     Ok(proto::SetRemoteTrackPublicationQualityResponse {})
 }
 
+fn on_set_remote_track_publication_fps(
+    server: &'static FfiServer,
+    request: proto::SetRemoteTrackPublicationFpsRequest,
+) -> FfiResult<proto::SetRemoteTrackPublicationFpsResponse> {
+    let ffi_publication =
+        server.retrieve_handle::<FfiPublication>(request.track_publication_handle)?;
+
+    let TrackPublication::Remote(publication) = &ffi_publication.publication else {
+        return Err(FfiError::InvalidRequest("publication is not a RemotePublication".into()));
+    };
+    publication.set_video_fps(request.fps);
+    Ok(proto::SetRemoteTrackPublicationFpsResponse {})
+}
+
+fn on_set_remote_track_publication_temporal_layer(
+    server: &'static FfiServer,
+    request: proto::SetRemoteTrackPublicationTemporalLayerRequest,
+) -> FfiResult<proto::SetRemoteTrackPublicationTemporalLayerResponse> {
+    let ffi_publication =
+        server.retrieve_handle::<FfiPublication>(request.track_publication_handle)?;
+
+    let TrackPublication::Remote(publication) = &ffi_publication.publication else {
+        return Err(FfiError::InvalidRequest("publication is not a RemotePublication".into()));
+    };
+    publication.set_preferred_temporal_layer(request.temporal_layer);
+    Ok(proto::SetRemoteTrackPublicationTemporalLayerResponse {})
+}
+
 fn on_set_local_metadata(
     server: &'static FfiServer,
     set_local_metadata: proto::SetLocalMetadataRequest,
@@ -772,6 +800,14 @@ fn on_e2ee_request(
     Ok(proto::E2eeResponse { message: Some(msg) })
 }
 
+fn on_new_stats_monitor(
+    server: &'static FfiServer,
+    request: proto::NewStatsMonitorRequest,
+) -> FfiResult<proto::NewStatsMonitorResponse> {
+    let monitor = stats_monitor::FfiStatsMonitor::setup(server, request)?;
+    Ok(proto::NewStatsMonitorResponse { monitor })
+}
+
 fn on_get_session_stats(
     server: &'static FfiServer,
     get_session_stats: proto::GetSessionStatsRequest,
@@ -1316,6 +1352,15 @@ fn on_local_data_track_try_push(
     track.try_push(server, request)
 }
 
+fn on_local_data_track_get_stats(
+    server: &'static FfiServer,
+    request: proto::LocalDataTrackGetStatsRequest,
+) -> FfiResult<proto::LocalDataTrackGetStatsResponse> {
+    let track =
+        server.retrieve_handle::<data_track::FfiLocalDataTrack>(request.track_handle)?.clone();
+    track.get_stats(server, request)
+}
+
 fn on_subscribe_local_data_track(
     server: &'static FfiServer,
     request: proto::SubscribeDataTrackRequest,
@@ -1343,6 +1388,15 @@ fn on_remote_data_track_set_pipeline_options(
     track.set_pipeline_options(server, request)
 }
 
+fn on_remote_data_track_get_stats(
+    server: &'static FfiServer,
+    request: proto::RemoteDataTrackGetStatsRequest,
+) -> FfiResult<proto::RemoteDataTrackGetStatsResponse> {
+    let track =
+        server.retrieve_handle::<data_track::FfiRemoteDataTrack>(request.track_handle)?.clone();
+    track.get_stats(server, request)
+}
+
 fn on_data_track_stream_read(
     server: &'static FfiServer,
     request: proto::DataTrackStreamReadRequest,
@@ -1402,6 +1456,7 @@ pub fn handle_request(
         Request::RemixAndResample(req) => remix_and_resample(server, req)?.into(),
         Request::E2ee(req) => on_e2ee_request(server, req)?.into(),
         Request::GetSessionStats(req) => on_get_session_stats(server, req)?.into(),
+        Request::NewStatsMonitor(req) => on_new_stats_monitor(server, req)?.into(),
         Request::NewSoxResampler(req) => on_new_sox_resampler(server, req)?.into(),
         Request::PushSoxResampler(req) => on_push_sox_resampler(server, req)?.into(),
         Request::FlushSoxResampler(req) => on_flush_sox_resampler(server, req)?.into(),
@@ -1424,6 +1479,12 @@ pub fn handle_request(
         Request::SetRemoteTrackPublicationQuality(req) => {
             on_set_remote_track_publication_quality(server, req)?.into()
         }
+        Request::SetRemoteTrackPublicationFps(req) => {
+            on_set_remote_track_publication_fps(server, req)?.into()
+        }
+        Request::SetRemoteTrackPublicationTemporalLayer(req) => {
+            on_set_remote_track_publication_temporal_layer(server, req)?.into()
+        }
         Request::SendStreamHeader(req) => on_send_stream_header(server, req)?.into(),
         Request::SendStreamChunk(req) => on_send_stream_chunk(server, req)?.into(),
         Request::SendStreamTrailer(req) => on_send_stream_trailer(server, req)?.into(),
@@ -1458,6 +1519,7 @@ pub fn handle_request(
         }
         Request::LocalDataTrackUnpublish(req) => on_local_data_track_unpublish(server, req)?.into(),
         Request::LocalDataTrackTryPush(req) => on_local_data_track_try_push(server, req)?.into(),
+        Request::LocalDataTrackGetStats(req) => on_local_data_track_get_stats(server, req)?.into(),
         Request::SubscribeDataTrack(req) => on_subscribe_local_data_track(server, req)?.into(),
         Request::RemoteDataTrackIsPublished(req) => {
             on_remote_data_track_is_published(server, req)?.into()
@@ -1465,6 +1527,9 @@ pub fn handle_request(
         Request::RemoteDataTrackSetPipelineOptions(req) => {
             on_remote_data_track_set_pipeline_options(server, req)?.into()
         }
+        Request::RemoteDataTrackGetStats(req) => {
+            on_remote_data_track_get_stats(server, req)?.into()
+        }
         Request::DataTrackStreamRead(req) => on_data_track_stream_read(server, req)?.into(),
         Request::DefineSchema(req) => on_define_schema(server, req)?.into(),
         Request::GetSchema(req) => on_get_schema(server, req)?.into(),
@@ -168,6 +168,9 @@ impl FfiRoom {
                                 PluginError::OnLoad(_) => " — ensure you are connecting to LiveKit Cloud and that the filter is configured correctly",
                                 PluginError::Library(_) => " — the filter dylib could not be loaded",
                                 PluginError::NotImplemented(_) => " — the filter dylib is missing a required entry point",
+                                PluginError::Incompatible { .. } => " — the filter dylib is not compatible with this SDK version",
+                                PluginError::Failed(_) => "",
+                                PluginError::Panicked(_) => " — the filter dylib panicked",
                             };
                             log::error!("audio filter disabled, continuing without it: {e}{hint}");
                         }
@@ -1586,6 +1589,17 @@ async fn forward_event(
         RoomEvent::DataTrackUnpublished(sid) => {
             let _ = send_event(proto::DataTrackUnpublished { sid: sid.to_string() }.into());
         }
+        RoomEvent::SelectedCandidatePairChanged { target, pair } => {
+            let _ = send_event(
+                proto::SelectedCandidatePairChanged {
+                    target: proto::PeerConnectionTarget::from(target).into(),
+                    local: pair.local.into(),
+                    remote: pair.remote.into(),
+                    round_trip_time: pair.round_trip_time.map(|rtt| rtt.as_secs_f64()),
+                }
+                .into(),
+            );
+        }
         _ => {
             log::warn!("unhandled room event: {:?}", event);
         }
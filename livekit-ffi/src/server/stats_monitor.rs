@@ -0,0 +1,105 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use livekit::stats_monitor::{StatsMonitor, StatsMonitorOptions};
+use tokio::sync::oneshot;
+
+use super::{room::FfiRoom, FfiHandle};
+use crate::{proto, server, FfiHandleId, FfiResult};
+
+pub struct FfiStatsMonitor {
+    pub handle_id: FfiHandleId,
+
+    #[allow(dead_code)]
+    self_dropped_tx: oneshot::Sender<()>, // Stop the monitor on drop
+}
+
+impl FfiHandle for FfiStatsMonitor {}
+
+impl FfiStatsMonitor {
+    /// Start polling the room stats and forward every report to the foreign language.
+    ///
+    /// The monitor stops when its handle or the room handle is dropped, a
+    /// [`proto::StatsMonitorEos`] is sent when the room disconnects.
+    pub fn setup(
+        server: &'static server::FfiServer,
+        request: proto::NewStatsMonitorRequest,
+    ) -> FfiResult<proto::OwnedStatsMonitor> {
+        let ffi_room = server.retrieve_handle::<FfiRoom>(request.room_handle)?.clone();
+
+        let mut options = StatsMonitorOptions::default();
+        if let Some(interval_ms) = request.interval_ms {
+            options.interval = Duration::from_millis(interval_ms.max(1) as u64);
+        }
+        let monitor = ffi_room.inner.room.stats_monitor(options);
+
+        let (self_dropped_tx, self_dropped_rx) = oneshot::channel();
+        let handle_id = server.next_id();
+        let handle = server.async_runtime.spawn(Self::monitor_task(
+            server,
+            handle_id,
+            monitor,
+            self_dropped_rx,
+            server.watch_handle_dropped(request.room_handle),
+        ));
+        server.watch_panic(handle);
+
+        server.store_handle(handle_id, Self { handle_id, self_dropped_tx });
+        Ok(proto::OwnedStatsMonitor { handle: proto::FfiOwnedHandle { id: handle_id } })
+    }
+
+    async fn monitor_task(
+        server: &'static server::FfiServer,
+        monitor_handle: FfiHandleId,
+        mut monitor: StatsMonitor,
+        mut self_dropped_rx: oneshot::Receiver<()>,
+        mut handle_dropped_rx: oneshot::Receiver<()>,
+    ) {
+        loop {
+            tokio::select! {
+                _ = &mut self_dropped_rx => return,
+                _ = &mut handle_dropped_rx => return,
+                report = monitor.next() => {
+                    let Some(report) = report else {
+                        break;
+                    };
+                    if let Err(err) = server.send_event(
+                        proto::StatsMonitorEvent {
+                            monitor_handle,
+                            message: Some(proto::stats_monitor_event::Message::Report(
+                                report.into(),
+                            )),
+                        }
+                        .into(),
+                    ) {
+                        log::warn!("failed to send stats report: {}", err);
+                    }
+                }
+            }
+        }
+
+        if let Err(err) = server.send_event(
+            proto::StatsMonitorEvent {
+                monitor_handle,
+                message: Some(proto::stats_monitor_event::Message::Eos(proto::StatsMonitorEos {})),
+            }
+            .into(),
+        ) {
+            log::warn!("failed to send stats monitor EOS: {}", err);
+        }
+    }
+}
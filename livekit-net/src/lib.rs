@@ -12,12 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod options;
 mod transport;
 mod types;
 
+#[cfg(feature = "rustls-tls-native-roots")]
+pub mod tls;
+
 #[cfg(feature = "__native")]
 mod native;
 
+pub use options::{ClientIdentity, ProxyOptions, TransportOptions};
 pub use transport::{
     HttpClient, HttpClientExt, HttpMethod, WsClient, WsConnectResult, WsConnection,
 };
@@ -91,6 +96,57 @@ pub fn http_client() -> Option<Arc<dyn HttpClient>> {
     }
 }
 
+/// Resolve a WebSocket client honoring `options`.
+///
+/// Default options resolve like [`ws_client`]. Other options can't be applied to an explicitly
+/// registered client, which owns its networking, so they fail with [`TransportError::Other`]
+/// rather than connecting without the requested proxy or TLS settings. On native builds a
+/// transport configured with `options` is returned, shared by every caller passing equal
+/// options.
+///
+pub fn ws_client_with(
+    options: &TransportOptions,
+) -> Result<Option<Arc<dyn WsClient>>, TransportError> {
+    if options.is_default() {
+        return Ok(ws_client());
+    }
+    if WS.get().is_some() {
+        return Err(TransportError::Other(
+            "transport options can't be applied to the registered WebSocket client".into(),
+        ));
+    }
+    #[cfg(feature = "__native")]
+    {
+        Ok(Some(native::configured_transport(options)?))
+    }
+    #[cfg(not(feature = "__native"))]
+    {
+        Ok(None)
+    }
+}
+
+/// Resolve an HTTP client honoring `options`. See [`ws_client_with`].
+pub fn http_client_with(
+    options: &TransportOptions,
+) -> Result<Option<Arc<dyn HttpClient>>, TransportError> {
+    if options.is_default() {
+        return Ok(http_client());
+    }
+    if HTTP.get().is_some() {
+        return Err(TransportError::Other(
+            "transport options can't be applied to the registered HTTP client".into(),
+        ));
+    }
+    #[cfg(feature = "__native")]
+    {
+        Ok(Some(native::configured_transport(options)?))
+    }
+    #[cfg(not(feature = "__native"))]
+    {
+        Ok(None)
+    }
+}
+
 #[cfg(feature = "__native")]
 pub mod testing {
     use crate::{HttpClient, WsClient};
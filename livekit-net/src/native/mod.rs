@@ -13,12 +13,17 @@
 // limitations under the License.
 
 mod connection;
+#[cfg(feature = "__native-tokio")]
 mod proxy;
 
 use crate::{
-    Header, HttpClient, HttpMethod, HttpResponse, TransportError, WsClient, WsConnectResult,
+    Header, HttpClient, HttpMethod, HttpResponse, TransportError, TransportOptions, WsClient,
+    WsConnectResult,
+};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
 };
-use std::sync::Arc;
 
 fn error_chain(e: &dyn std::error::Error) -> String {
     let mut msg = e.to_string();
@@ -39,6 +44,12 @@ pub struct NativeTransport {
     // TCP/TLS setup (and root-store loading) on every call.
     #[cfg(feature = "__native-tokio")]
     http: reqwest::Client,
+    // Explicit proxy for WebSocket connections; `None` falls back to the environment.
+    #[cfg(feature = "__native-tokio")]
+    proxy: Option<crate::ProxyOptions>,
+    // Custom TLS for WebSocket connections; `None` uses the platform roots.
+    #[cfg(all(feature = "__native-tokio", feature = "rustls-tls-native-roots"))]
+    tls: Option<Arc<crate::tls::ClientConfig>>,
 }
 
 impl NativeTransport {
@@ -46,6 +57,52 @@ impl NativeTransport {
         Self {
             #[cfg(feature = "__native-tokio")]
             http: reqwest::Client::new(),
+            #[cfg(feature = "__native-tokio")]
+            proxy: None,
+            #[cfg(all(feature = "__native-tokio", feature = "rustls-tls-native-roots"))]
+            tls: None,
+        }
+    }
+
+    #[cfg(feature = "__native-tokio")]
+    pub(crate) fn with_options(options: &TransportOptions) -> Result<Self, TransportError> {
+        let mut builder = reqwest::Client::builder();
+        if let Some(proxy) = &options.proxy {
+            builder = builder.proxy(self::proxy::Proxy::from_options(proxy)?.to_reqwest()?);
+        }
+
+        #[cfg(feature = "rustls-tls-native-roots")]
+        let tls = match options.has_custom_tls() {
+            true => {
+                let config = crate::tls::client_config(options)?;
+                builder = builder.use_preconfigured_tls(config.clone());
+                Some(Arc::new(config))
+            }
+            false => None,
+        };
+        #[cfg(not(feature = "rustls-tls-native-roots"))]
+        if options.has_custom_tls() {
+            return Err(TransportError::Other(
+                "custom TLS options require the rustls-tls-native-roots feature".into(),
+            ));
+        }
+
+        let http = builder.build().map_err(|e| TransportError::Other(error_chain(&e)))?;
+        Ok(Self {
+            http,
+            proxy: options.proxy.clone(),
+            #[cfg(feature = "rustls-tls-native-roots")]
+            tls,
+        })
+    }
+
+    #[cfg(feature = "__native-async")]
+    pub(crate) fn with_options(options: &TransportOptions) -> Result<Self, TransportError> {
+        match options.is_default() {
+            true => Ok(Self::new()),
+            false => Err(TransportError::Other(
+                "transport options are only supported by the native-tokio backend".into(),
+            )),
         }
     }
 }
@@ -59,6 +116,84 @@ fn default_transport() -> Arc<NativeTransport> {
     Arc::clone(DEFAULT.get_or_init(|| Arc::new(NativeTransport::new())))
 }
 
+/// The transport for non-default options, memoized per options for the same reason as
+/// [`default_transport`]. Keyed by a digest of the options so the cache does not keep a copy
+/// of the private key or proxy password around.
+pub(crate) fn configured_transport(
+    options: &TransportOptions,
+) -> Result<Arc<NativeTransport>, TransportError> {
+    use std::sync::OnceLock;
+    static CONFIGURED: OnceLock<Mutex<RecentTransports>> = OnceLock::new();
+    CONFIGURED
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .get_or_try_insert(fingerprint(options), || NativeTransport::with_options(options))
+}
+
+/// Transports for the last few distinct options, most recently used first. Bounded so an
+/// application cycling through options (e.g. rotating credentials) doesn't leak transports.
+#[derive(Default)]
+struct RecentTransports {
+    entries: VecDeque<([u8; 32], Arc<NativeTransport>)>,
+}
+
+impl RecentTransports {
+    const CAPACITY: usize = 8;
+
+    fn get_or_try_insert(
+        &mut self,
+        key: [u8; 32],
+        create: impl FnOnce() -> Result<NativeTransport, TransportError>,
+    ) -> Result<Arc<NativeTransport>, TransportError> {
+        let entry = match self.entries.iter().position(|(k, _)| *k == key) {
+            Some(index) => self.entries.remove(index).unwrap(),
+            None => {
+                self.entries.truncate(Self::CAPACITY - 1);
+                (key, Arc::new(create()?))
+            }
+        };
+        let transport = Arc::clone(&entry.1);
+        self.entries.push_front(entry);
+        Ok(transport)
+    }
+}
+
+/// SHA-256 over every field of `options`, each length-prefixed so distinct options
+/// cannot collide by shifting bytes between fields.
+fn fingerprint(options: &TransportOptions) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+
+    fn field(hasher: &mut Sha256, value: Option<&[u8]>) {
+        match value {
+            Some(bytes) => {
+                hasher.update([1]);
+                hasher.update((bytes.len() as u64).to_be_bytes());
+                hasher.update(bytes);
+            }
+            None => hasher.update([0]),
+        }
+    }
+
+    let mut hasher = Sha256::new();
+    let proxy = options.proxy.as_ref();
+    field(&mut hasher, proxy.map(|p| p.url.as_bytes()));
+    field(&mut hasher, proxy.and_then(|p| p.username.as_deref()).map(str::as_bytes));
+    field(&mut hasher, proxy.and_then(|p| p.password.as_deref()).map(str::as_bytes));
+    hasher.update((options.root_certificates.len() as u64).to_be_bytes());
+    for root in &options.root_certificates {
+        field(&mut hasher, Some(root));
+    }
+    let identity = options.client_identity.as_ref();
+    field(&mut hasher, identity.map(|i| i.certificate_chain.as_slice()));
+    field(&mut hasher, identity.map(|i| i.private_key.as_slice()));
+    hasher.update((options.pinned_certificates.len() as u64).to_be_bytes());
+    for pin in &options.pinned_certificates {
+        hasher.update(pin);
+    }
+    hasher.finalize().into()
+}
+
 pub(crate) fn native_ws_client() -> Arc<dyn WsClient> {
     default_transport()
 }
@@ -97,7 +232,7 @@ impl WsClient for NativeTransport {
 
         let connect_fut = async {
             #[cfg(feature = "__native-tokio")]
-            let ws = self::proxy::connect_ws(request, &parsed).await?;
+            let ws = self::proxy::connect_ws(request, &parsed, self).await?;
             #[cfg(feature = "__native-async")]
             let (ws, _) = async_tungstenite::async_std::connect_async(request).await.map_err(
                 |e: async_tungstenite::tungstenite::Error| {
@@ -194,3 +329,61 @@ impl HttpClient for NativeTransport {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClientIdentity, ProxyOptions};
+
+    #[test]
+    fn fingerprint_covers_secrets() {
+        let mut options = TransportOptions {
+            client_identity: Some(ClientIdentity::from_pem("CERT", "KEY")),
+            ..Default::default()
+        };
+        let base = fingerprint(&options);
+        assert_eq!(base, fingerprint(&options.clone()));
+
+        options.client_identity = Some(ClientIdentity::from_pem("CERT", "OTHER KEY"));
+        assert_ne!(base, fingerprint(&options));
+
+        // Bytes moved from one field to the next must not collide.
+        options.client_identity = Some(ClientIdentity::from_pem("CERTK", "EY"));
+        assert_ne!(base, fingerprint(&options));
+
+        options.client_identity = None;
+        options.proxy = Some(ProxyOptions::new("http://proxy").with_credentials("a", "b"));
+        let with_password = fingerprint(&options);
+        options.proxy = Some(ProxyOptions::new("http://proxy").with_credentials("a", "c"));
+        assert_ne!(with_password, fingerprint(&options));
+    }
+
+    #[test]
+    fn recent_transports_evict_least_recently_used() {
+        let mut recent = RecentTransports::default();
+        let mut get = |key: usize| {
+            recent.get_or_try_insert([key as u8; 32], || Ok(NativeTransport::new())).unwrap()
+        };
+
+        let first = get(0);
+        let second = Arc::downgrade(&get(1));
+        for key in 2..RecentTransports::CAPACITY {
+            get(key);
+        }
+        assert!(Arc::ptr_eq(&first, &get(0)));
+
+        // Full, the second transport is now the least recently used
+        get(RecentTransports::CAPACITY);
+        assert!(second.upgrade().is_none());
+        assert!(Arc::ptr_eq(&first, &get(0)));
+        assert_eq!(recent.entries.len(), RecentTransports::CAPACITY);
+    }
+
+    #[test]
+    fn recent_transports_keep_failed_options_out() {
+        let mut recent = RecentTransports::default();
+        let err = recent.get_or_try_insert([0; 32], || Err(TransportError::Closed));
+        assert!(err.is_err());
+        assert!(recent.entries.is_empty());
+    }
+}
@@ -0,0 +1,283 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! rustls configuration built from [`TransportOptions`].
+//!
+//! Shared by the native WebSocket/HTTP transport and the `livekit-api` service clients, so
+//! a given set of options trusts (and pins) the same certificates everywhere.
+
+use std::sync::Arc;
+
+use sha2::{Digest, Sha256};
+use tokio_rustls::rustls::{
+    self,
+    client::{
+        danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+        WebPkiServerVerifier,
+    },
+    pki_types::{pem::PemObject, CertificateDer, PrivateKeyDer, ServerName, UnixTime},
+    DigitallySignedStruct, RootCertStore, SignatureScheme,
+};
+
+use crate::{TransportError, TransportOptions};
+
+pub use tokio_rustls::rustls::ClientConfig;
+
+/// Builds a client config trusting the platform roots plus `options.root_certificates`,
+/// presenting `options.client_identity` and enforcing `options.pinned_certificates`.
+///
+/// Proxy settings are not part of the TLS config and are ignored here.
+///
+pub fn client_config(options: &TransportOptions) -> Result<ClientConfig, TransportError> {
+    let roots = Arc::new(root_store(&options.root_certificates)?);
+    let builder = ClientConfig::builder();
+    let builder = if options.pinned_certificates.is_empty() {
+        builder.with_root_certificates(roots)
+    } else {
+        let inner = WebPkiServerVerifier::builder(roots)
+            .build()
+            .map_err(|e| TransportError::Other(format!("invalid root certificates: {e}")))?;
+        builder.dangerous().with_custom_certificate_verifier(Arc::new(PinnedVerifier {
+            inner,
+            pins: options.pinned_certificates.clone(),
+        }))
+    };
+
+    let Some(identity) = &options.client_identity else {
+        return Ok(builder.with_no_client_auth());
+    };
+    let chain = CertificateDer::pem_slice_iter(&identity.certificate_chain)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| TransportError::Other(format!("invalid client certificate: {e}")))?;
+    let key = PrivateKeyDer::from_pem_slice(&identity.private_key)
+        .map_err(|e| TransportError::Other(format!("invalid client private key: {e}")))?;
+    builder
+        .with_client_auth_cert(chain, key)
+        .map_err(|e| TransportError::Other(format!("invalid client identity: {e}")))
+}
+
+/// The platform roots plus `extra` PEM certificates. Failing to load platform roots is only
+/// an error when there is nothing else to trust.
+fn root_store(extra: &[Vec<u8>]) -> Result<RootCertStore, TransportError> {
+    let mut roots = RootCertStore::empty();
+
+    let native = rustls_native_certs::load_native_certs();
+    if !native.errors.is_empty() {
+        log::warn!("Native root CA certificate loading errors: {:?}", native.errors);
+    }
+    let total = native.certs.len();
+    let (added, ignored) = roots.add_parsable_certificates(native.certs);
+    log::debug!("Added {added}/{total} native root certificates ({ignored} ignored)");
+
+    for pem in extra {
+        for cert in CertificateDer::pem_slice_iter(pem) {
+            let cert =
+                cert.map_err(|e| TransportError::Other(format!("invalid root certificate: {e}")))?;
+            roots
+                .add(cert)
+                .map_err(|e| TransportError::Other(format!("invalid root certificate: {e}")))?;
+        }
+    }
+
+    if roots.is_empty() {
+        return Err(TransportError::Connection(format!(
+            "Could not load any native root certificates: {:?}",
+            native.errors
+        )));
+    }
+    Ok(roots)
+}
+
+/// Runs the regular webpki validation, then requires the leaf certificate to be pinned.
+#[derive(Debug)]
+struct PinnedVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    pins: Vec<[u8; 32]>,
+}
+
+impl ServerCertVerifier for PinnedVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let verified = self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        )?;
+        let digest: [u8; 32] = Sha256::digest(end_entity.as_ref()).into();
+        if !self.pins.contains(&digest) {
+            return Err(rustls::Error::General("server certificate is not pinned".into()));
+        }
+        Ok(verified)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ClientIdentity;
+    use rcgen::{BasicConstraints, CertificateParams, CertifiedKey, IsCa, KeyPair};
+    use tokio::io::{duplex, AsyncReadExt, AsyncWriteExt};
+    use tokio_rustls::{
+        rustls::{server::WebPkiClientVerifier, ServerConfig},
+        TlsAcceptor, TlsConnector,
+    };
+
+    struct Pki {
+        ca: CertifiedKey,
+        server: CertifiedKey,
+        client: CertifiedKey,
+    }
+
+    fn pki() -> Pki {
+        let mut params = CertificateParams::new(Vec::new()).unwrap();
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        let key_pair = KeyPair::generate().unwrap();
+        let ca = CertifiedKey { cert: params.self_signed(&key_pair).unwrap(), key_pair };
+
+        let leaf = |name: &str| {
+            let key_pair = KeyPair::generate().unwrap();
+            let cert = CertificateParams::new(vec![name.to_owned()])
+                .unwrap()
+                .signed_by(&key_pair, &ca.cert, &ca.key_pair)
+                .unwrap();
+            CertifiedKey { cert, key_pair }
+        };
+        let server = leaf("localhost");
+        let client = leaf("client.localhost");
+        Pki { ca, server, client }
+    }
+
+    /// Runs a handshake against a server presenting `pki.server` and, with `require_client`,
+    /// demanding a client certificate issued by the CA. Returns whether a byte made it
+    /// through to the server.
+    async fn handshake(pki: &Pki, options: &TransportOptions, require_client: bool) -> bool {
+        let server_key = PrivateKeyDer::try_from(pki.server.key_pair.serialize_der()).unwrap();
+        let builder = ServerConfig::builder();
+        let builder = match require_client {
+            true => {
+                let mut roots = RootCertStore::empty();
+                roots.add(pki.ca.cert.der().clone()).unwrap();
+                builder.with_client_cert_verifier(
+                    WebPkiClientVerifier::builder(Arc::new(roots)).build().unwrap(),
+                )
+            }
+            false => builder.with_no_client_auth(),
+        };
+        let server_config =
+            builder.with_single_cert(vec![pki.server.cert.der().clone()], server_key).unwrap();
+
+        let (client_io, server_io) = duplex(64 * 1024);
+        let server = tokio::spawn(async move {
+            let mut stream = TlsAcceptor::from(Arc::new(server_config)).accept(server_io).await?;
+            let mut byte = [0u8; 1];
+            stream.read_exact(&mut byte).await?;
+            Ok::<_, std::io::Error>(byte[0])
+        });
+
+        let connector = TlsConnector::from(Arc::new(client_config(options).unwrap()));
+        let name = ServerName::try_from("localhost").unwrap();
+        // Keep the client open until the server is done: it still writes session tickets.
+        let _client = match connector.connect(name, client_io).await {
+            Ok(mut stream) => {
+                let _ = stream.write_all(&[42]).await;
+                let _ = stream.flush().await;
+                Some(stream)
+            }
+            Err(_) => None,
+        };
+        matches!(server.await.unwrap(), Ok(42))
+    }
+
+    fn trusting(pki: &Pki) -> TransportOptions {
+        TransportOptions {
+            root_certificates: vec![pki.ca.cert.pem().into_bytes()],
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn pinned_certificate_is_enforced() {
+        let pki = pki();
+        let mut options = trusting(&pki);
+
+        options.pinned_certificates = vec![Sha256::digest(pki.server.cert.der()).into()];
+        assert!(handshake(&pki, &options, false).await);
+
+        options.pinned_certificates = vec![Sha256::digest(pki.ca.cert.der()).into()];
+        assert!(!handshake(&pki, &options, false).await);
+    }
+
+    #[tokio::test]
+    async fn pinning_keeps_chain_validation() {
+        let pki = pki();
+        // Pinned but not trusted: the regular validation still rejects it.
+        let options = TransportOptions {
+            pinned_certificates: vec![Sha256::digest(pki.server.cert.der()).into()],
+            ..Default::default()
+        };
+        assert!(!handshake(&pki, &options, false).await);
+    }
+
+    #[tokio::test]
+    async fn client_identity_is_presented() {
+        let pki = pki();
+        let mut options = trusting(&pki);
+        assert!(!handshake(&pki, &options, true).await);
+
+        options.client_identity = Some(ClientIdentity::from_pem(
+            pki.client.cert.pem(),
+            pki.client.key_pair.serialize_pem(),
+        ));
+        assert!(handshake(&pki, &options, true).await);
+    }
+
+    #[test]
+    fn rejects_invalid_identity() {
+        let options = TransportOptions {
+            client_identity: Some(ClientIdentity::from_pem("not a certificate", "not a key")),
+            ..Default::default()
+        };
+        assert!(client_config(&options).is_err());
+    }
+}
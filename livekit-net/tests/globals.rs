@@ -13,8 +13,9 @@
 // limitations under the License.
 
 use livekit_net::{
-    http_client, set_http_client, set_ws_client, ws_client, Header, HttpClientExt, HttpMethod,
-    HttpResponse, TransportError, WsClient, WsConnectResult, WsConnection,
+    http_client, http_client_with, set_http_client, set_ws_client, ws_client, ws_client_with,
+    Header, HttpClientExt, HttpMethod, HttpResponse, ProxyOptions, TransportError,
+    TransportOptions, WsClient, WsConnectResult, WsConnection,
 };
 use std::sync::Arc;
 
@@ -73,4 +74,11 @@ async fn registered_clients_are_returned() {
     let ws = ws_client().expect("ws client should be registered");
     let conn = ws.connect("ws://x".into(), vec![], 1000).await.unwrap().connection;
     assert!(conn.recv().await.unwrap().is_none());
+
+    // Registered clients own their networking, options they can't honor are refused
+    assert!(ws_client_with(&TransportOptions::default()).unwrap().is_some());
+    let proxied =
+        TransportOptions { proxy: Some(ProxyOptions::new("http://proxy")), ..Default::default() };
+    assert!(ws_client_with(&proxied).is_err());
+    assert!(http_client_with(&proxied).is_err());
 }
@@ -0,0 +1,194 @@
+# Copyright 2026 LiveKit, Inc.
+#
+# Licensed under the Apache License, Version 2.0 (the "License");
+# you may not use this file except in compliance with the License.
+# You may obtain a copy of the License at
+#
+#     http://www.apache.org/licenses/LICENSE-2.0
+#
+# Unless required by applicable law or agreed to in writing, software
+# distributed under the License is distributed on an "AS IS" BASIS,
+# WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+# See the License for the specific language governing permissions and
+# limitations under the License.
+
+# Exercises the room bindings (built with `--features room`) end-to-end.
+#
+# Run with `cargo make python-test`. The tests which need a server run against
+# a LiveKit dev server (`livekit-server --dev`) at LIVEKIT_URL and are skipped
+# when it is not set.
+
+import sys
+import os
+
+sys.path.append(os.path.abspath(os.path.join(os.path.dirname(__file__), '..', 'packages', 'python')))
+
+import asyncio
+import unittest
+import uuid
+from livekit_uniffi import *
+
+LIVEKIT_URL = os.environ.get("LIVEKIT_URL")
+CREDENTIALS = ApiCredentials(
+    key=os.environ.get("LIVEKIT_API_KEY", "devkey"),
+    secret=os.environ.get("LIVEKIT_API_SECRET", "secret"),
+)
+
+
+def join_token(room, identity):
+    grants = VideoGrants(
+        room_create=False,
+        room_list=False,
+        room_record=False,
+        room_admin=False,
+        room_join=True,
+        room=room,
+        destination_room="",
+        can_publish=True,
+        can_subscribe=True,
+        can_publish_data=True,
+        can_publish_sources=[],
+        can_update_own_metadata=True,
+        ingress_admin=False,
+        hidden=False,
+        recorder=False,
+    )
+    return token_generate(
+        options=TokenOptions(identity=identity, video_grants=grants),
+        credentials=CREDENTIALS,
+    )
+
+
+class EventCollector(RoomDelegate):
+    """Collects room events and lets tests wait for a matching one."""
+
+    def __init__(self, loop):
+        self.loop = loop
+        self.queue = asyncio.Queue()
+
+    def on_event(self, event):
+        # Called from a Rust runtime thread.
+        self.loop.call_soon_threadsafe(self.queue.put_nowait, event)
+
+    async def wait_for(self, predicate, timeout=10):
+        async def wait():
+            while True:
+                event = await self.queue.get()
+                if predicate(event):
+                    return event
+
+        return await asyncio.wait_for(wait(), timeout)
+
+
+class EchoHandler(RpcHandler):
+    def handle(self, data):
+        if data.payload == "fail":
+            raise RpcError.Failed(code=42, message="requested failure", data=None)
+        return f"echo:{data.payload}"
+
+
+class OfflineTest(unittest.IsolatedAsyncioTestCase):
+    async def test_connect_to_unreachable_server_fails(self):
+        delegate = EventCollector(asyncio.get_running_loop())
+        options = RoomOptions(join_retries=0)
+        with self.assertRaises(RoomError):
+            await Room.connect("ws://127.0.0.1:1", "invalid", options, delegate)
+
+    async def test_audio_source_validates_frames(self):
+        source = AudioSource(48000, 1, 100)
+        self.assertEqual(source.sample_rate(), 48000)
+        frame = AudioFrame(data=[0] * 479, sample_rate=48000, num_channels=1, samples_per_channel=480)
+        with self.assertRaises(RoomError.InvalidArgument):
+            await source.capture_frame(frame)
+        frame.data = [0] * 480
+        await source.capture_frame(frame)
+
+    def test_video_source_validates_frames(self):
+        source = VideoSource(4, 2, False)
+        frame = VideoFrame(width=4, height=2, rotation=VideoRotation.ROTATION0, timestamp_us=0, data=bytes(11))
+        with self.assertRaises(RoomError.InvalidArgument):
+            source.capture_frame(frame)
+        frame.data = bytes(4 * 2 + 2 * 2 * 1)
+        source.capture_frame(frame)
+
+    def test_local_tracks(self):
+        track = LocalAudioTrack("microphone", AudioSource(48000, 1, 100))
+        self.assertEqual(track.name(), "microphone")
+        track.mute()
+        self.assertTrue(track.is_muted())
+
+
+@unittest.skipUnless(LIVEKIT_URL, "LIVEKIT_URL is not set")
+class RoomTest(unittest.IsolatedAsyncioTestCase):
+    async def asyncSetUp(self):
+        loop = asyncio.get_running_loop()
+        name = f"uniffi-{uuid.uuid4().hex[:8]}"
+        self.events_a = EventCollector(loop)
+        self.events_b = EventCollector(loop)
+        self.room_a = await Room.connect(LIVEKIT_URL, join_token(name, "alice"), RoomOptions(), self.events_a)
+        self.room_b = await Room.connect(LIVEKIT_URL, join_token(name, "bob"), RoomOptions(), self.events_b)
+        await self.events_a.wait_for(
+            lambda e: e.is_participant_active() and e.participant.identity() == "bob"
+        )
+
+    async def asyncTearDown(self):
+        await self.room_a.disconnect()
+        await self.room_b.disconnect()
+
+    async def test_participants(self):
+        self.assertEqual(self.room_a.connection_state(), ConnectionState.CONNECTED)
+        self.assertEqual(self.room_a.local_participant().identity(), "alice")
+        self.assertIn("bob", self.room_a.remote_participants())
+
+    async def test_data_packet(self):
+        await self.room_a.local_participant().publish_data(DataPacket(payload=b"hello", topic="greeting"))
+        event = await self.events_b.wait_for(lambda e: e.is_data_received())
+        self.assertEqual(bytes(event.payload), b"hello")
+        self.assertEqual(event.topic, "greeting")
+        self.assertEqual(event.participant_identity, "alice")
+
+    async def test_rpc(self):
+        self.room_b.local_participant().register_rpc_method("echo", EchoHandler())
+        alice = self.room_a.local_participant()
+
+        response = await alice.perform_rpc(PerformRpcData(destination_identity="bob", method="echo", payload="hi"))
+        self.assertEqual(response, "echo:hi")
+
+        with self.assertRaises(RpcError.Failed) as failure:
+            await alice.perform_rpc(PerformRpcData(destination_identity="bob", method="echo", payload="fail"))
+        self.assertEqual(failure.exception.code, 42)
+
+    async def test_text_stream(self):
+        await self.room_a.local_participant().send_text("streamed text", StreamTextOptions(topic="chat"))
+        event = await self.events_b.wait_for(lambda e: e.is_text_stream_opened())
+        self.assertEqual(event.topic, "chat")
+        self.assertEqual(await event.reader.read_all(), "streamed text")
+
+    async def test_audio_track(self):
+        source = AudioSource(48000, 1, 100)
+        track = LocalAudioTrack("tone", source)
+        publication = await self.room_a.local_participant().publish_audio_track(
+            track, TrackPublishOptions(source=TrackSource.MICROPHONE)
+        )
+        self.assertEqual(publication.kind(), TrackKind.AUDIO)
+
+        event = await self.events_b.wait_for(lambda e: e.is_track_subscribed())
+        self.assertTrue(event.track.is_audio())
+        stream = AudioStream(event.track.track, 48000, 1)
+
+        async def capture():
+            frame = AudioFrame(data=[1000] * 480, sample_rate=48000, num_channels=1, samples_per_channel=480)
+            while True:
+                await source.capture_frame(frame)
+
+        capture_task = asyncio.create_task(capture())
+        try:
+            frame = await asyncio.wait_for(stream.next(), 10)
+            self.assertIsNotNone(frame)
+            self.assertEqual(frame.sample_rate, 48000)
+        finally:
+            capture_task.cancel()
+
+
+if __name__ == "__main__":
+    unittest.main()
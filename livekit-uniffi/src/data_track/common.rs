@@ -13,9 +13,12 @@
 // limitations under the License.
 
 use bytes::Bytes;
-use livekit_datatrack::api::{DataTrackFrameEncoding, DataTrackSchemaEncoding, DataTrackSid};
+use livekit_datatrack::api::{
+    DataTrackFrameEncoding, DataTrackSchemaEncoding, DataTrackSid, SchemaDefinitionError,
+};
 use livekit_protocol as proto;
 use prost::Message;
+use std::sync::Arc;
 
 uniffi::custom_type!(DataTrackSid, String, {
     remote,
@@ -93,6 +96,33 @@ impl From<livekit_datatrack::api::DataTrackSchemaId> for DataTrackSchemaId {
     }
 }
 
+/// Checks frame payloads against a schema definition.
+///
+/// FFI wrapper around [`livekit_datatrack::api::FrameValidator`]. Attach to
+/// [`DataTrackOptions`](super::local::DataTrackOptions) to reject invalid frames on push, or to
+/// [`DataTrackSubscribeOptions`](super::remote::DataTrackSubscribeOptions) to discard them
+/// on receipt.
+///
+#[derive(uniffi::Object)]
+pub struct FrameValidator(pub(crate) Arc<dyn livekit_datatrack::api::FrameValidator>);
+
+#[uniffi::export]
+impl FrameValidator {
+    /// Creates a validator from a schema definition.
+    ///
+    /// See [`livekit_datatrack::api::validator_for_schema`] for how the definition
+    /// is interpreted.
+    ///
+    #[uniffi::constructor]
+    pub fn for_schema(
+        schema: DataTrackSchemaId,
+        definition: String,
+    ) -> Result<Arc<Self>, SchemaDefinitionError> {
+        livekit_datatrack::api::validator_for_schema(&schema.into(), &definition)
+            .map(|validator| Arc::new(Self(validator)))
+    }
+}
+
 /// Signal response crossing the FFI boundary could not be processed.
 #[derive(uniffi::Error, thiserror::Error, Debug)]
 #[uniffi(flat_error)]
@@ -13,13 +13,16 @@
 // limitations under the License.
 
 use super::common::{
-    deserialize_signal_response, DataTrackFrame, DataTrackInfo, DataTrackSchemaId,
+    deserialize_signal_response, DataTrackFrame, DataTrackInfo, DataTrackSchemaId, FrameValidator,
     HandleSignalResponseError,
 };
 use bytes::Bytes;
 use futures_util::StreamExt;
 use livekit_datatrack::{
-    api::{DataTrack, DataTrackFrameEncoding, Local, PublishError, PushFrameErrorReason},
+    api::{
+        DataTrack, DataTrackFrameEncoding, DataTrackReliability, Local, LocalDataTrackStats,
+        PublishError, PushFrameErrorReason,
+    },
     backend::{local, EncryptionProvider},
 };
 use livekit_protocol as proto;
@@ -59,6 +62,11 @@ impl LocalDataTrack {
         self.0.try_push(frame.into()).map_err(|err| err.reason())
     }
 
+    /// Returns delivery statistics for the track.
+    pub fn stats(&self) -> LocalDataTrackStats {
+        self.0.stats()
+    }
+
     /// Unpublishes the track.
     pub fn unpublish(&self) {
         self.0.unpublish();
@@ -77,6 +85,10 @@ pub struct DataTrackOptions {
     pub schema: Option<DataTrackSchemaId>,
     #[uniffi(default)]
     pub frame_encoding: Option<DataTrackFrameEncoding>,
+    #[uniffi(default)]
+    pub reliability: Option<DataTrackReliability>,
+    #[uniffi(default)]
+    pub validator: Option<Arc<FrameValidator>>,
 }
 
 impl From<DataTrackOptions> for livekit_datatrack::api::DataTrackOptions {
@@ -88,6 +100,12 @@ impl From<DataTrackOptions> for livekit_datatrack::api::DataTrackOptions {
         if let Some(frame_encoding) = source.frame_encoding {
             options = options.with_frame_encoding(frame_encoding);
         }
+        if let Some(reliability) = source.reliability {
+            options = options.with_reliability(reliability);
+        }
+        if let Some(validator) = source.validator {
+            options = options.with_validator(validator.0.clone());
+        }
         options
     }
 }
@@ -184,6 +202,24 @@ impl LocalDataTrackManager {
         Ok(())
     }
 
+    /// Handles a NACK payload sent by a subscriber of a reliable track.
+    ///
+    /// The payload is produced by [`RemoteDataTrackManagerDelegate::on_nack_available`]
+    /// on the subscriber side and delivered out-of-band over the reliable data channel.
+    ///
+    pub fn handle_nack_received(&self, payload: Bytes) {
+        _ = self.input.send(local::InputEvent::NackReceived(payload));
+    }
+
+    /// Reports packets from [`LocalDataTrackManagerDelegate::on_packets_available`] which
+    /// the transport discarded before sending.
+    ///
+    /// This is optional and only used for [`LocalDataTrack::stats`].
+    ///
+    pub fn handle_packets_dropped(&self, packets: Vec<Bytes>) {
+        _ = self.input.send(local::InputEvent::PacketsDropped(packets));
+    }
+
     /// Handles a serialized `PublishDataTrackResponse` signal response from the SFU.
     pub fn handle_sfu_publish_response(&self, res: &[u8]) -> Result<(), HandleSignalResponseError> {
         let proto::signal_response::Message::PublishDataTrackResponse(msg) =
@@ -13,12 +13,13 @@
 // limitations under the License.
 
 use super::common::{
-    deserialize_signal_response, DataTrackFrame, DataTrackInfo, HandleSignalResponseError,
+    deserialize_signal_response, DataTrackFrame, DataTrackInfo, FrameValidator,
+    HandleSignalResponseError,
 };
 use bytes::Bytes;
 use futures_util::StreamExt;
 use livekit_datatrack::{
-    api::{DataTrack, DataTrackSid, DataTrackSubscribeError, Remote},
+    api::{DataTrack, DataTrackSid, DataTrackSubscribeError, Remote, RemoteDataTrackStats},
     backend::{remote, DecryptionProvider},
 };
 use livekit_protocol as proto;
@@ -29,7 +30,7 @@ use tokio_util::sync::{CancellationToken, DropGuard};
 
 /// Data track published by the remote participant.
 #[derive(uniffi::Object)]
-pub struct RemoteDataTrack(DataTrack<Remote>);
+pub struct RemoteDataTrack(pub(crate) DataTrack<Remote>);
 
 #[uniffi::export(async_runtime = "tokio")]
 impl RemoteDataTrack {
@@ -57,6 +58,11 @@ impl RemoteDataTrack {
         self.0.publisher_identity().to_string()
     }
 
+    /// Returns delivery statistics for the track.
+    pub fn stats(&self) -> RemoteDataTrackStats {
+        self.0.stats()
+    }
+
     /// Subscribes to the data track.
     pub async fn subscribe(&self) -> Result<DataTrackStream, DataTrackSubscribeError> {
         self.0.subscribe().await.map(|stream| DataTrackStream(Mutex::new(stream)))
@@ -77,12 +83,18 @@ impl RemoteDataTrack {
 #[derive(uniffi::Record)]
 pub struct DataTrackSubscribeOptions {
     pub buffer_size: u32,
+    #[uniffi(default)]
+    pub validator: Option<Arc<FrameValidator>>,
 }
 
 impl From<DataTrackSubscribeOptions> for livekit_datatrack::api::DataTrackSubscribeOptions {
     fn from(options: DataTrackSubscribeOptions) -> Self {
-        livekit_datatrack::api::DataTrackSubscribeOptions::default()
-            .with_buffer_size(options.buffer_size as usize)
+        let mut result = livekit_datatrack::api::DataTrackSubscribeOptions::default()
+            .with_buffer_size(options.buffer_size as usize);
+        if let Some(validator) = options.validator {
+            result = result.with_validator(validator.0.clone());
+        }
+        result
     }
 }
 
@@ -122,6 +134,13 @@ pub trait RemoteDataTrackManagerDelegate: Send + Sync {
 
     /// A track with the given SID has been unpublished by a remote participant.
     fn on_track_unpublished(&self, sid: DataTrackSid);
+
+    /// NACK payload to be delivered to the publisher with the given identity
+    /// over the reliable data channel.
+    ///
+    /// The publisher passes the payload to `LocalDataTrackManager::handle_nack_received`.
+    ///
+    fn on_nack_available(&self, publisher_identity: String, payload: Bytes);
 }
 
 #[uniffi::export]
@@ -255,6 +274,9 @@ impl DelegateForwardTask {
                 let req = proto::signal_request::Message::UpdateDataSubscription(req.into());
                 self.forward_signal_request(req);
             }
+            remote::OutputEvent::NackAvailable(event) => {
+                self.delegate.on_nack_available(event.publisher_identity, event.payload)
+            }
         }
     }
 
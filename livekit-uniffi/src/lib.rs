@@ -15,6 +15,10 @@
 /// Data tracks core from [`livekit-datatrack`].
 pub mod data_track;
 
+/// Rooms, participants and tracks from [`livekit`].
+#[cfg(feature = "room")]
+pub mod room;
+
 /// Access token generation and verification from [`livekit-api::access_token`].
 pub mod access_token;
 
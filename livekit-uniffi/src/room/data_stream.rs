@@ -0,0 +1,213 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::RoomError;
+use bytes::{Bytes, BytesMut};
+use futures_util::StreamExt;
+use livekit::data_stream::api;
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+
+/// Options for sending a text stream.
+///
+/// FFI wrapper around [`api::StreamTextOptions`].
+///
+#[derive(uniffi::Record)]
+pub struct StreamTextOptions {
+    pub topic: String,
+    #[uniffi(default)]
+    pub attributes: HashMap<String, String>,
+    /// Identities of the recipients, all participants if empty.
+    #[uniffi(default)]
+    pub destination_identities: Vec<String>,
+    /// Stream ID, generated if unset.
+    #[uniffi(default)]
+    pub id: Option<String>,
+}
+
+impl From<StreamTextOptions> for api::StreamTextOptions {
+    fn from(source: StreamTextOptions) -> Self {
+        let mut options = api::StreamTextOptions::new_with_topic(source.topic);
+        options.attributes = source.attributes;
+        options.destination_identities =
+            source.destination_identities.into_iter().map(Into::into).collect();
+        options.id = source.id;
+        options
+    }
+}
+
+/// Options for sending a byte stream.
+///
+/// FFI wrapper around [`api::StreamByteOptions`].
+///
+#[derive(uniffi::Record)]
+pub struct StreamByteOptions {
+    pub topic: String,
+    #[uniffi(default)]
+    pub attributes: HashMap<String, String>,
+    /// Identities of the recipients, all participants if empty.
+    #[uniffi(default)]
+    pub destination_identities: Vec<String>,
+    /// Stream ID, generated if unset.
+    #[uniffi(default)]
+    pub id: Option<String>,
+    #[uniffi(default)]
+    pub mime_type: Option<String>,
+    /// File name presented to recipients.
+    #[uniffi(default)]
+    pub name: Option<String>,
+}
+
+impl From<StreamByteOptions> for api::StreamByteOptions {
+    fn from(source: StreamByteOptions) -> Self {
+        let mut options = api::StreamByteOptions::new_with_topic(source.topic);
+        options.attributes = source.attributes;
+        options.destination_identities =
+            source.destination_identities.into_iter().map(Into::into).collect();
+        options.id = source.id;
+        options.mime_type = source.mime_type;
+        options.name = source.name;
+        options
+    }
+}
+
+/// Information about a text data stream.
+#[derive(uniffi::Record)]
+pub struct TextStreamInfo {
+    pub id: String,
+    pub topic: String,
+    /// Creation time in milliseconds since the Unix epoch.
+    pub timestamp: i64,
+    pub total_length: Option<u64>,
+    pub mime_type: String,
+    pub attributes: HashMap<String, String>,
+}
+
+impl From<api::TextStreamInfo> for TextStreamInfo {
+    fn from(info: api::TextStreamInfo) -> Self {
+        Self {
+            attributes: info.attributes(),
+            id: info.id,
+            topic: info.topic,
+            timestamp: info.timestamp.timestamp_millis(),
+            total_length: info.total_length,
+            mime_type: info.mime_type,
+        }
+    }
+}
+
+/// Information about a byte data stream.
+#[derive(uniffi::Record)]
+pub struct ByteStreamInfo {
+    pub id: String,
+    pub topic: String,
+    /// Creation time in milliseconds since the Unix epoch.
+    pub timestamp: i64,
+    pub total_length: Option<u64>,
+    pub mime_type: String,
+    pub name: String,
+    pub attributes: HashMap<String, String>,
+}
+
+impl From<api::ByteStreamInfo> for ByteStreamInfo {
+    fn from(info: api::ByteStreamInfo) -> Self {
+        Self {
+            attributes: info.attributes(),
+            id: info.id,
+            topic: info.topic,
+            timestamp: info.timestamp.timestamp_millis(),
+            total_length: info.total_length,
+            mime_type: info.mime_type,
+            name: info.name,
+        }
+    }
+}
+
+/// Reader for an incoming text data stream.
+///
+/// The stream is kept open as long as its reader exists; dropping the reader closes it.
+///
+#[derive(uniffi::Object)]
+pub struct TextStreamReader {
+    info: api::TextStreamInfo,
+    reader: Mutex<api::TextStreamReader>,
+}
+
+impl TextStreamReader {
+    pub(crate) fn new(reader: api::TextStreamReader) -> Self {
+        use api::StreamReader;
+        Self { info: reader.info().clone(), reader: Mutex::new(reader) }
+    }
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl TextStreamReader {
+    pub fn info(&self) -> TextStreamInfo {
+        self.info.clone().into()
+    }
+
+    /// Returns the next chunk of text or `None` once the stream has been closed.
+    pub async fn next(&self) -> Result<Option<String>, RoomError> {
+        Ok(self.reader.lock().await.next().await.transpose()?)
+    }
+
+    /// Reads the remainder of the stream, returning once it has been closed.
+    pub async fn read_all(&self) -> Result<String, RoomError> {
+        let mut reader = self.reader.lock().await;
+        let mut text = String::new();
+        while let Some(chunk) = reader.next().await {
+            text.push_str(&chunk?);
+        }
+        Ok(text)
+    }
+}
+
+/// Reader for an incoming byte data stream.
+///
+/// The stream is kept open as long as its reader exists; dropping the reader closes it.
+///
+#[derive(uniffi::Object)]
+pub struct ByteStreamReader {
+    info: api::ByteStreamInfo,
+    reader: Mutex<api::ByteStreamReader>,
+}
+
+impl ByteStreamReader {
+    pub(crate) fn new(reader: api::ByteStreamReader) -> Self {
+        use api::StreamReader;
+        Self { info: reader.info().clone(), reader: Mutex::new(reader) }
+    }
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl ByteStreamReader {
+    pub fn info(&self) -> ByteStreamInfo {
+        self.info.clone().into()
+    }
+
+    /// Returns the next chunk of bytes or `None` once the stream has been closed.
+    pub async fn next(&self) -> Result<Option<Bytes>, RoomError> {
+        Ok(self.reader.lock().await.next().await.transpose()?)
+    }
+
+    /// Reads the remainder of the stream, returning once it has been closed.
+    pub async fn read_all(&self) -> Result<Bytes, RoomError> {
+        let mut reader = self.reader.lock().await;
+        let mut buffer = BytesMut::new();
+        while let Some(chunk) = reader.next().await {
+            buffer.extend_from_slice(&chunk?);
+        }
+        Ok(buffer.freeze())
+    }
+}
@@ -0,0 +1,271 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    data_stream::{ByteStreamReader, TextStreamReader},
+    participant::{ConnectionQuality, RemoteParticipant},
+    track::{LocalTrackPublication, RemoteTrack, RemoteTrackPublication},
+    ConnectionState,
+};
+use crate::data_track::remote::RemoteDataTrack;
+use livekit::{participant::Participant, DataPacketKind};
+use livekit_datatrack::api::DataTrackSid;
+use std::{collections::HashMap, sync::Arc};
+
+#[uniffi::remote(Enum)]
+pub enum DataPacketKind {
+    Lossy,
+    Reliable,
+}
+
+/// Event emitted by a [`Room`](super::Room).
+///
+/// FFI counterpart of [`livekit::RoomEvent`]. Participants which may be either local or
+/// remote are referred to by identity.
+///
+#[derive(uniffi::Enum)]
+pub enum RoomEvent {
+    /// Remote participant joined the room.
+    ParticipantConnected {
+        participant: Arc<RemoteParticipant>,
+    },
+    /// Remote participant is active and ready to receive data messages.
+    ParticipantActive {
+        participant: Arc<RemoteParticipant>,
+    },
+    /// Remote participant disconnected from the room.
+    ParticipantDisconnected {
+        participant: Arc<RemoteParticipant>,
+    },
+    LocalTrackPublished {
+        publication: Arc<LocalTrackPublication>,
+    },
+    LocalTrackUnpublished {
+        publication: Arc<LocalTrackPublication>,
+    },
+    TrackPublished {
+        publication: Arc<RemoteTrackPublication>,
+        participant: Arc<RemoteParticipant>,
+    },
+    TrackUnpublished {
+        publication: Arc<RemoteTrackPublication>,
+        participant: Arc<RemoteParticipant>,
+    },
+    TrackSubscribed {
+        track: RemoteTrack,
+        publication: Arc<RemoteTrackPublication>,
+        participant: Arc<RemoteParticipant>,
+    },
+    TrackUnsubscribed {
+        track: RemoteTrack,
+        publication: Arc<RemoteTrackPublication>,
+        participant: Arc<RemoteParticipant>,
+    },
+    TrackSubscriptionFailed {
+        participant: Arc<RemoteParticipant>,
+        track_sid: String,
+        error: String,
+    },
+    TrackMuted {
+        participant_identity: String,
+        track_sid: String,
+    },
+    TrackUnmuted {
+        participant_identity: String,
+        track_sid: String,
+    },
+    RoomMetadataChanged {
+        old_metadata: String,
+        metadata: String,
+    },
+    ParticipantMetadataChanged {
+        participant_identity: String,
+        old_metadata: String,
+        metadata: String,
+    },
+    ParticipantNameChanged {
+        participant_identity: String,
+        old_name: String,
+        name: String,
+    },
+    ParticipantAttributesChanged {
+        participant_identity: String,
+        changed_attributes: HashMap<String, String>,
+    },
+    ActiveSpeakersChanged {
+        speaker_identities: Vec<String>,
+    },
+    ConnectionQualityChanged {
+        participant_identity: String,
+        quality: ConnectionQuality,
+    },
+    DataReceived {
+        payload: Vec<u8>,
+        topic: Option<String>,
+        kind: DataPacketKind,
+        participant_identity: Option<String>,
+    },
+    /// A remote participant opened a text data stream.
+    TextStreamOpened {
+        reader: Arc<TextStreamReader>,
+        topic: String,
+        participant_identity: String,
+    },
+    /// A remote participant opened a byte data stream.
+    ByteStreamOpened {
+        reader: Arc<ByteStreamReader>,
+        topic: String,
+        participant_identity: String,
+    },
+    /// A remote participant published a data track.
+    DataTrackPublished {
+        track: Arc<RemoteDataTrack>,
+    },
+    /// A remote participant unpublished a data track.
+    DataTrackUnpublished {
+        sid: DataTrackSid,
+    },
+    ConnectionStateChanged {
+        state: ConnectionState,
+    },
+    /// The room was disconnected, `reason` is the name of the [`livekit::DisconnectReason`].
+    Disconnected {
+        reason: String,
+    },
+    Reconnecting,
+    Reconnected,
+}
+
+fn identity(participant: &Participant) -> String {
+    participant.identity().into()
+}
+
+impl RoomEvent {
+    /// Converts an event from the room, returning `None` for events not exposed over FFI.
+    pub(crate) fn from_event(event: livekit::RoomEvent) -> Option<Self> {
+        use livekit::RoomEvent as E;
+
+        let remote = |participant| Arc::new(RemoteParticipant(participant));
+        let event = match event {
+            E::ParticipantConnected(participant) => {
+                Self::ParticipantConnected { participant: remote(participant) }
+            }
+            E::ParticipantActive(participant) => {
+                Self::ParticipantActive { participant: remote(participant) }
+            }
+            E::ParticipantDisconnected(participant) => {
+                Self::ParticipantDisconnected { participant: remote(participant) }
+            }
+            E::LocalTrackPublished { publication, .. } => Self::LocalTrackPublished {
+                publication: Arc::new(LocalTrackPublication(publication)),
+            },
+            E::LocalTrackUnpublished { publication, .. } => Self::LocalTrackUnpublished {
+                publication: Arc::new(LocalTrackPublication(publication)),
+            },
+            E::TrackPublished { publication, participant } => Self::TrackPublished {
+                publication: Arc::new(RemoteTrackPublication(publication)),
+                participant: remote(participant),
+            },
+            E::TrackUnpublished { publication, participant } => Self::TrackUnpublished {
+                publication: Arc::new(RemoteTrackPublication(publication)),
+                participant: remote(participant),
+            },
+            E::TrackSubscribed { track, publication, participant } => Self::TrackSubscribed {
+                track: track.into(),
+                publication: Arc::new(RemoteTrackPublication(publication)),
+                participant: remote(participant),
+            },
+            E::TrackUnsubscribed { track, publication, participant } => Self::TrackUnsubscribed {
+                track: track.into(),
+                publication: Arc::new(RemoteTrackPublication(publication)),
+                participant: remote(participant),
+            },
+            E::TrackSubscriptionFailed { participant, error, track_sid } => {
+                Self::TrackSubscriptionFailed {
+                    participant: remote(participant),
+                    track_sid: track_sid.into(),
+                    error: error.to_string(),
+                }
+            }
+            E::TrackMuted { participant, publication } => Self::TrackMuted {
+                participant_identity: identity(&participant),
+                track_sid: publication.sid().into(),
+            },
+            E::TrackUnmuted { participant, publication } => Self::TrackUnmuted {
+                participant_identity: identity(&participant),
+                track_sid: publication.sid().into(),
+            },
+            E::RoomMetadataChanged { old_metadata, metadata } => {
+                Self::RoomMetadataChanged { old_metadata, metadata }
+            }
+            E::ParticipantMetadataChanged { participant, old_metadata, metadata } => {
+                Self::ParticipantMetadataChanged {
+                    participant_identity: identity(&participant),
+                    old_metadata,
+                    metadata,
+                }
+            }
+            E::ParticipantNameChanged { participant, old_name, name } => {
+                Self::ParticipantNameChanged {
+                    participant_identity: identity(&participant),
+                    old_name,
+                    name,
+                }
+            }
+            E::ParticipantAttributesChanged { participant, changed_attributes } => {
+                Self::ParticipantAttributesChanged {
+                    participant_identity: identity(&participant),
+                    changed_attributes,
+                }
+            }
+            E::ActiveSpeakersChanged { speakers } => Self::ActiveSpeakersChanged {
+                speaker_identities: speakers.iter().map(identity).collect(),
+            },
+            E::ConnectionQualityChanged { quality, participant } => {
+                Self::ConnectionQualityChanged {
+                    participant_identity: identity(&participant),
+                    quality,
+                }
+            }
+            E::DataReceived { payload, topic, kind, participant } => Self::DataReceived {
+                payload: Arc::unwrap_or_clone(payload),
+                topic,
+                kind,
+                participant_identity: participant.map(|p| p.identity().into()),
+            },
+            E::TextStreamOpened { reader, topic, participant_identity } => Self::TextStreamOpened {
+                reader: Arc::new(TextStreamReader::new(reader.take()?)),
+                topic,
+                participant_identity: participant_identity.into(),
+            },
+            E::ByteStreamOpened { reader, topic, participant_identity } => Self::ByteStreamOpened {
+                reader: Arc::new(ByteStreamReader::new(reader.take()?)),
+                topic,
+                participant_identity: participant_identity.into(),
+            },
+            E::DataTrackPublished(track) => {
+                Self::DataTrackPublished { track: Arc::new(RemoteDataTrack(track)) }
+            }
+            E::DataTrackUnpublished(sid) => Self::DataTrackUnpublished { sid },
+            E::ConnectionStateChanged(state) => Self::ConnectionStateChanged { state },
+            E::Disconnected { reason } => {
+                Self::Disconnected { reason: reason.as_str_name().to_string() }
+            }
+            E::Reconnecting => Self::Reconnecting,
+            E::Reconnected => Self::Reconnected,
+            _ => return None,
+        };
+        Some(event)
+    }
+}
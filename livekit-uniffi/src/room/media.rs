@@ -0,0 +1,309 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    track::{RemoteAudioTrack, RemoteVideoTrack},
+    RoomError,
+};
+use crate::runtime;
+use futures_util::StreamExt;
+use livekit::webrtc::{
+    audio_source::native::NativeAudioSource,
+    audio_stream::native::NativeAudioStream,
+    prelude::{AudioSourceOptions, BoxVideoFrame, I420Buffer, VideoBuffer, VideoResolution},
+    video_source::native::NativeVideoSource,
+    video_stream::native::NativeVideoStream,
+};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Block of interleaved 16-bit PCM audio.
+#[derive(uniffi::Record)]
+pub struct AudioFrame {
+    pub data: Vec<i16>,
+    pub sample_rate: u32,
+    pub num_channels: u32,
+    pub samples_per_channel: u32,
+}
+
+impl From<livekit::webrtc::audio_frame::AudioFrame<'static>> for AudioFrame {
+    fn from(frame: livekit::webrtc::audio_frame::AudioFrame<'static>) -> Self {
+        Self {
+            data: frame.data.into_owned(),
+            sample_rate: frame.sample_rate,
+            num_channels: frame.num_channels,
+            samples_per_channel: frame.samples_per_channel,
+        }
+    }
+}
+
+#[derive(uniffi::Enum, Clone, Copy)]
+pub enum VideoRotation {
+    Rotation0,
+    Rotation90,
+    Rotation180,
+    Rotation270,
+}
+
+impl From<VideoRotation> for livekit::webrtc::prelude::VideoRotation {
+    fn from(rotation: VideoRotation) -> Self {
+        use livekit::webrtc::prelude::VideoRotation as R;
+        match rotation {
+            VideoRotation::Rotation0 => R::VideoRotation0,
+            VideoRotation::Rotation90 => R::VideoRotation90,
+            VideoRotation::Rotation180 => R::VideoRotation180,
+            VideoRotation::Rotation270 => R::VideoRotation270,
+        }
+    }
+}
+
+impl From<livekit::webrtc::prelude::VideoRotation> for VideoRotation {
+    fn from(rotation: livekit::webrtc::prelude::VideoRotation) -> Self {
+        use livekit::webrtc::prelude::VideoRotation as R;
+        match rotation {
+            R::VideoRotation0 => VideoRotation::Rotation0,
+            R::VideoRotation90 => VideoRotation::Rotation90,
+            R::VideoRotation180 => VideoRotation::Rotation180,
+            R::VideoRotation270 => VideoRotation::Rotation270,
+        }
+    }
+}
+
+/// Video frame in I420 format.
+///
+/// `data` holds the Y, U and V planes back to back without padding: the Y plane is
+/// `width * height` bytes and each chroma plane `((width + 1) / 2) * ((height + 1) / 2)`.
+///
+#[derive(uniffi::Record)]
+pub struct VideoFrame {
+    pub width: u32,
+    pub height: u32,
+    pub rotation: VideoRotation,
+    /// Capture time in microseconds.
+    pub timestamp_us: i64,
+    pub data: Vec<u8>,
+}
+
+fn chroma_size(width: u32, height: u32) -> (usize, usize) {
+    (width.div_ceil(2) as usize, height.div_ceil(2) as usize)
+}
+
+/// Copies `rows` rows of `row_len` bytes between planes with different strides.
+fn copy_plane(
+    src: &[u8],
+    src_stride: usize,
+    dst: &mut [u8],
+    dst_stride: usize,
+    row_len: usize,
+    rows: usize,
+) {
+    for row in 0..rows {
+        dst[row * dst_stride..][..row_len].copy_from_slice(&src[row * src_stride..][..row_len]);
+    }
+}
+
+impl VideoFrame {
+    fn to_i420(&self) -> Result<I420Buffer, RoomError> {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let (chroma_width, chroma_height) = chroma_size(self.width, self.height);
+        let luma_len = width * height;
+        let chroma_len = chroma_width * chroma_height;
+        if self.data.len() != luma_len + 2 * chroma_len {
+            return Err(RoomError::InvalidArgument(format!(
+                "expected {} bytes of I420 data for {}x{}, got {}",
+                luma_len + 2 * chroma_len,
+                self.width,
+                self.height,
+                self.data.len()
+            )));
+        }
+        let (src_y, src_uv) = self.data.split_at(luma_len);
+        let (src_u, src_v) = src_uv.split_at(chroma_len);
+
+        let mut buffer = I420Buffer::new(self.width, self.height);
+        let (stride_y, stride_u, stride_v) = buffer.strides();
+        let (dst_y, dst_u, dst_v) = buffer.data_mut();
+        copy_plane(src_y, width, dst_y, stride_y as usize, width, height);
+        copy_plane(src_u, chroma_width, dst_u, stride_u as usize, chroma_width, chroma_height);
+        copy_plane(src_v, chroma_width, dst_v, stride_v as usize, chroma_width, chroma_height);
+        Ok(buffer)
+    }
+}
+
+impl From<BoxVideoFrame> for VideoFrame {
+    fn from(frame: BoxVideoFrame) -> Self {
+        let buffer = frame.buffer.to_i420();
+        let (width, height) = (buffer.width(), buffer.height());
+        let (chroma_width, chroma_height) = chroma_size(width, height);
+        let luma_len = width as usize * height as usize;
+        let chroma_len = chroma_width * chroma_height;
+
+        let mut data = vec![0; luma_len + 2 * chroma_len];
+        let (dst_y, dst_uv) = data.split_at_mut(luma_len);
+        let (dst_u, dst_v) = dst_uv.split_at_mut(chroma_len);
+        let (stride_y, stride_u, stride_v) = buffer.strides();
+        let (src_y, src_u, src_v) = buffer.data();
+        copy_plane(
+            src_y,
+            stride_y as usize,
+            dst_y,
+            width as usize,
+            width as usize,
+            height as usize,
+        );
+        copy_plane(src_u, stride_u as usize, dst_u, chroma_width, chroma_width, chroma_height);
+        copy_plane(src_v, stride_v as usize, dst_v, chroma_width, chroma_width, chroma_height);
+
+        Self {
+            width,
+            height,
+            rotation: frame.rotation.into(),
+            timestamp_us: frame.timestamp_us,
+            data,
+        }
+    }
+}
+
+/// Source of audio frames for a [`LocalAudioTrack`](super::track::LocalAudioTrack).
+#[derive(uniffi::Object)]
+pub struct AudioSource(pub(crate) NativeAudioSource);
+
+#[uniffi::export(async_runtime = "tokio")]
+impl AudioSource {
+    /// Creates a source for audio in the given format.
+    ///
+    /// Captured frames are buffered for up to `queue_size_ms` before
+    /// [`AudioSource::capture_frame`] waits for the buffer to drain.
+    ///
+    #[uniffi::constructor]
+    pub fn new(sample_rate: u32, num_channels: u32, queue_size_ms: u32) -> Arc<Self> {
+        let source = NativeAudioSource::new(
+            AudioSourceOptions::default(),
+            sample_rate,
+            num_channels,
+            queue_size_ms,
+        );
+        Arc::new(Self(source))
+    }
+
+    pub fn sample_rate(&self) -> u32 {
+        self.0.sample_rate()
+    }
+
+    pub fn num_channels(&self) -> u32 {
+        self.0.num_channels()
+    }
+
+    /// Pushes a frame to the source.
+    pub async fn capture_frame(&self, frame: AudioFrame) -> Result<(), RoomError> {
+        if frame.data.len() != (frame.num_channels * frame.samples_per_channel) as usize {
+            return Err(RoomError::InvalidArgument(format!(
+                "expected {} samples, got {}",
+                frame.num_channels * frame.samples_per_channel,
+                frame.data.len()
+            )));
+        }
+        let source = self.0.clone();
+        runtime::run(async move {
+            let frame = livekit::webrtc::audio_frame::AudioFrame {
+                data: frame.data.into(),
+                sample_rate: frame.sample_rate,
+                num_channels: frame.num_channels,
+                samples_per_channel: frame.samples_per_channel,
+            };
+            source.capture_frame(&frame).await
+        })
+        .await
+        .map_err(Into::into)
+    }
+
+    /// Discards buffered frames which have not been sent yet.
+    pub fn clear_buffer(&self) {
+        self.0.clear_buffer()
+    }
+}
+
+/// Source of video frames for a [`LocalVideoTrack`](super::track::LocalVideoTrack).
+#[derive(uniffi::Object)]
+pub struct VideoSource(pub(crate) NativeVideoSource);
+
+#[uniffi::export]
+impl VideoSource {
+    /// Creates a source for video of the given resolution.
+    ///
+    /// Set `is_screencast` for screen content, which favors resolution over frame rate.
+    ///
+    #[uniffi::constructor]
+    pub fn new(width: u32, height: u32, is_screencast: bool) -> Arc<Self> {
+        let source = NativeVideoSource::new(VideoResolution { width, height }, is_screencast);
+        Arc::new(Self(source))
+    }
+
+    /// Pushes a frame to the source.
+    pub fn capture_frame(&self, frame: VideoFrame) -> Result<(), RoomError> {
+        let buffer = frame.to_i420()?;
+        self.0.capture_frame(&livekit::webrtc::prelude::VideoFrame {
+            rotation: frame.rotation.into(),
+            timestamp_us: frame.timestamp_us,
+            frame_metadata: None,
+            buffer,
+        });
+        Ok(())
+    }
+}
+
+/// Stream of [`AudioFrame`]s received on a [`RemoteAudioTrack`].
+///
+/// Frames are remixed and resampled to the format requested when creating the stream.
+/// The stream stops receiving once dropped.
+///
+#[derive(uniffi::Object)]
+pub struct AudioStream(Mutex<NativeAudioStream>);
+
+#[uniffi::export(async_runtime = "tokio")]
+impl AudioStream {
+    #[uniffi::constructor]
+    pub fn new(track: Arc<RemoteAudioTrack>, sample_rate: u32, num_channels: u32) -> Arc<Self> {
+        let _rt = runtime::runtime().enter();
+        let stream =
+            NativeAudioStream::new(track.0.rtc_track(), sample_rate as i32, num_channels as i32);
+        Arc::new(Self(Mutex::new(stream)))
+    }
+
+    /// Returns the next frame or `None` once the track has ended.
+    pub async fn next(&self) -> Option<AudioFrame> {
+        self.0.lock().await.next().await.map(Into::into)
+    }
+}
+
+/// Stream of [`VideoFrame`]s received on a [`RemoteVideoTrack`].
+///
+/// Frames are converted to I420. The stream stops receiving once dropped.
+///
+#[derive(uniffi::Object)]
+pub struct VideoStream(Mutex<NativeVideoStream>);
+
+#[uniffi::export(async_runtime = "tokio")]
+impl VideoStream {
+    #[uniffi::constructor]
+    pub fn new(track: Arc<RemoteVideoTrack>) -> Arc<Self> {
+        let _rt = runtime::runtime().enter();
+        Arc::new(Self(Mutex::new(NativeVideoStream::new(track.0.rtc_track()))))
+    }
+
+    /// Returns the next frame or `None` once the track has ended.
+    pub async fn next(&self) -> Option<VideoFrame> {
+        self.0.lock().await.next().await.map(Into::into)
+    }
+}
@@ -0,0 +1,201 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Rooms, participants and tracks from the [`livekit`] crate.
+//!
+//! Unlike the rest of this crate, this module depends on WebRTC and is therefore only
+//! built with the `room` feature. Connect with [`Room::connect`], passing a [`RoomDelegate`]
+//! which receives every [`RoomEvent`] until the room is disconnected or dropped.
+//!
+
+use crate::runtime;
+use livekit::data_stream::api::StreamError;
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::sync::mpsc;
+use tokio_util::sync::{CancellationToken, DropGuard};
+
+pub mod data_stream;
+pub mod events;
+pub mod media;
+pub mod participant;
+pub mod rpc;
+pub mod track;
+
+use events::RoomEvent;
+use participant::{LocalParticipant, RemoteParticipant};
+
+/// Error returned by room operations.
+#[derive(uniffi::Error, thiserror::Error, Debug)]
+#[uniffi(flat_error)]
+pub enum RoomError {
+    #[error(transparent)]
+    Room(#[from] livekit::RoomError),
+    #[error(transparent)]
+    Rtc(#[from] livekit::webrtc::RtcError),
+    #[error(transparent)]
+    Stream(#[from] StreamError),
+    #[error("Invalid argument: {0}")]
+    InvalidArgument(String),
+}
+
+#[uniffi::remote(Enum)]
+pub enum ConnectionState {
+    Disconnected,
+    Connected,
+    Reconnecting,
+}
+
+/// Options for [`Room::connect`].
+///
+/// FFI wrapper around [`livekit::RoomOptions`], exposing the commonly used subset.
+///
+#[derive(uniffi::Record)]
+pub struct RoomOptions {
+    #[uniffi(default = true)]
+    pub auto_subscribe: bool,
+    #[uniffi(default = false)]
+    pub adaptive_stream: bool,
+    #[uniffi(default = false)]
+    pub dynacast: bool,
+    #[uniffi(default = 3)]
+    pub join_retries: u32,
+    /// Timeout for each signal connection attempt.
+    #[uniffi(default)]
+    pub connect_timeout: Option<Duration>,
+}
+
+impl From<RoomOptions> for livekit::RoomOptions {
+    fn from(source: RoomOptions) -> Self {
+        let mut options = livekit::RoomOptions::default();
+        options.auto_subscribe = source.auto_subscribe;
+        options.adaptive_stream = source.adaptive_stream;
+        options.dynacast = source.dynacast;
+        options.join_retries = source.join_retries;
+        if let Some(connect_timeout) = source.connect_timeout {
+            options.connect_timeout = connect_timeout;
+        }
+        options
+    }
+}
+
+/// Delegate for receiving events from a [`Room`].
+#[uniffi::export(with_foreign)]
+pub trait RoomDelegate: Send + Sync {
+    /// An event occurred in the room.
+    ///
+    /// Events are delivered one at a time and in order; blocking here delays
+    /// subsequent events.
+    ///
+    fn on_event(&self, event: RoomEvent);
+}
+
+/// Connection to a LiveKit room.
+///
+/// The room stays connected until [`Room::disconnect`] is called or the object is dropped.
+///
+#[derive(uniffi::Object)]
+pub struct Room {
+    inner: Arc<livekit::Room>,
+    _guard: DropGuard,
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl Room {
+    /// Connects to a room.
+    ///
+    /// Events are forwarded to `delegate` starting with the first event emitted after
+    /// joining, so none are missed between connecting and registering for events.
+    ///
+    #[uniffi::constructor]
+    pub async fn connect(
+        url: String,
+        token: String,
+        options: RoomOptions,
+        delegate: Arc<dyn RoomDelegate>,
+    ) -> Result<Arc<Self>, RoomError> {
+        let (room, events) =
+            runtime::run(async move { livekit::Room::connect(&url, &token, options.into()).await })
+                .await?;
+
+        let token = CancellationToken::new();
+        let delegate_forward = DelegateForwardTask { events, delegate, token: token.clone() };
+        runtime::runtime().spawn(delegate_forward.run());
+
+        Ok(Self { inner: Arc::new(room), _guard: token.drop_guard() }.into())
+    }
+
+    /// Name of the room.
+    pub fn name(&self) -> String {
+        self.inner.name()
+    }
+
+    /// Server assigned identifier of the room, available shortly after connecting.
+    pub async fn sid(&self) -> String {
+        let inner = self.inner.clone();
+        runtime::run(async move { inner.sid().await }).await.into()
+    }
+
+    pub fn metadata(&self) -> String {
+        self.inner.metadata()
+    }
+
+    pub fn connection_state(&self) -> ConnectionState {
+        self.inner.connection_state()
+    }
+
+    /// The participant representing this client.
+    pub fn local_participant(&self) -> Arc<LocalParticipant> {
+        Arc::new(LocalParticipant(self.inner.local_participant()))
+    }
+
+    /// Participants currently in the room, keyed by identity.
+    pub fn remote_participants(&self) -> HashMap<String, Arc<RemoteParticipant>> {
+        self.inner
+            .remote_participants()
+            .into_iter()
+            .map(|(identity, participant)| {
+                (identity.into(), Arc::new(RemoteParticipant(participant)))
+            })
+            .collect()
+    }
+
+    /// Disconnects from the room.
+    pub async fn disconnect(&self) -> Result<(), RoomError> {
+        let inner = self.inner.clone();
+        runtime::run(async move { inner.close().await }).await.map_err(Into::into)
+    }
+}
+
+/// Task for forwarding room events to the foreign [`RoomDelegate`].
+struct DelegateForwardTask {
+    events: mpsc::UnboundedReceiver<livekit::RoomEvent>,
+    delegate: Arc<dyn RoomDelegate>,
+    token: CancellationToken,
+}
+
+impl DelegateForwardTask {
+    async fn run(mut self) {
+        loop {
+            tokio::select! {
+                _ = self.token.cancelled() => break,
+                event = self.events.recv() => {
+                    let Some(event) = event else { break };
+                    if let Some(event) = RoomEvent::from_event(event) {
+                        self.delegate.on_event(event);
+                    }
+                }
+            }
+        }
+    }
+}
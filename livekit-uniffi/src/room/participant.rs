@@ -0,0 +1,282 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    data_stream::{ByteStreamInfo, StreamByteOptions, StreamTextOptions, TextStreamInfo},
+    rpc::{PerformRpcData, RpcError, RpcHandler},
+    track::{
+        LocalAudioTrack, LocalTrackPublication, LocalVideoTrack, RemoteTrackPublication,
+        TrackPublishOptions,
+    },
+    RoomError,
+};
+use crate::runtime;
+use livekit::{id::TrackSid, participant::ConnectionQuality, track::LocalTrack};
+use std::{collections::HashMap, sync::Arc};
+
+#[uniffi::remote(Enum)]
+pub enum ConnectionQuality {
+    Excellent,
+    Good,
+    Poor,
+    Lost,
+}
+
+/// User data packet sent with [`LocalParticipant::publish_data`].
+#[derive(uniffi::Record)]
+pub struct DataPacket {
+    pub payload: Vec<u8>,
+    #[uniffi(default)]
+    pub topic: Option<String>,
+    #[uniffi(default = true)]
+    pub reliable: bool,
+    /// Identities of the recipients, all participants if empty.
+    #[uniffi(default)]
+    pub destination_identities: Vec<String>,
+}
+
+impl From<DataPacket> for livekit::DataPacket {
+    fn from(packet: DataPacket) -> Self {
+        livekit::DataPacket {
+            payload: packet.payload,
+            topic: packet.topic,
+            reliable: packet.reliable,
+            destination_identities: packet
+                .destination_identities
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        }
+    }
+}
+
+/// The participant representing this client.
+#[derive(uniffi::Object)]
+pub struct LocalParticipant(pub(crate) livekit::participant::LocalParticipant);
+
+#[uniffi::export(async_runtime = "tokio")]
+impl LocalParticipant {
+    pub fn sid(&self) -> String {
+        self.0.sid().into()
+    }
+
+    pub fn identity(&self) -> String {
+        self.0.identity().into()
+    }
+
+    pub fn name(&self) -> String {
+        self.0.name()
+    }
+
+    pub fn metadata(&self) -> String {
+        self.0.metadata()
+    }
+
+    pub fn attributes(&self) -> HashMap<String, String> {
+        self.0.attributes()
+    }
+
+    pub fn is_speaking(&self) -> bool {
+        self.0.is_speaking()
+    }
+
+    pub fn audio_level(&self) -> f32 {
+        self.0.audio_level()
+    }
+
+    pub fn connection_quality(&self) -> ConnectionQuality {
+        self.0.connection_quality()
+    }
+
+    /// Published tracks, keyed by track SID.
+    pub fn track_publications(&self) -> HashMap<String, Arc<LocalTrackPublication>> {
+        self.0
+            .track_publications()
+            .into_iter()
+            .map(|(sid, publication)| (sid.into(), Arc::new(LocalTrackPublication(publication))))
+            .collect()
+    }
+
+    pub async fn set_metadata(&self, metadata: String) -> Result<(), RoomError> {
+        let inner = self.0.clone();
+        runtime::run(async move { inner.set_metadata(metadata).await }).await.map_err(Into::into)
+    }
+
+    pub async fn set_name(&self, name: String) -> Result<(), RoomError> {
+        let inner = self.0.clone();
+        runtime::run(async move { inner.set_name(name).await }).await.map_err(Into::into)
+    }
+
+    pub async fn set_attributes(
+        &self,
+        attributes: HashMap<String, String>,
+    ) -> Result<(), RoomError> {
+        let inner = self.0.clone();
+        runtime::run(async move { inner.set_attributes(attributes).await })
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Sends a user data packet.
+    pub async fn publish_data(&self, packet: DataPacket) -> Result<(), RoomError> {
+        let inner = self.0.clone();
+        runtime::run(async move { inner.publish_data(packet.into()).await })
+            .await
+            .map_err(Into::into)
+    }
+
+    pub async fn publish_audio_track(
+        &self,
+        track: Arc<LocalAudioTrack>,
+        options: TrackPublishOptions,
+    ) -> Result<Arc<LocalTrackPublication>, RoomError> {
+        self.publish_track(LocalTrack::Audio(track.0.clone()), options).await
+    }
+
+    pub async fn publish_video_track(
+        &self,
+        track: Arc<LocalVideoTrack>,
+        options: TrackPublishOptions,
+    ) -> Result<Arc<LocalTrackPublication>, RoomError> {
+        self.publish_track(LocalTrack::Video(track.0.clone()), options).await
+    }
+
+    /// Unpublishes the track with the given SID.
+    pub async fn unpublish_track(&self, sid: String) -> Result<(), RoomError> {
+        let sid = TrackSid::try_from(sid)
+            .map_err(|sid| RoomError::InvalidArgument(format!("invalid track SID: {sid}")))?;
+        let inner = self.0.clone();
+        runtime::run(async move { inner.unpublish_track(&sid).await.map(|_| ()) })
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Invokes a method registered by a remote participant, returning its response payload.
+    pub async fn perform_rpc(&self, data: PerformRpcData) -> Result<String, RpcError> {
+        let inner = self.0.clone();
+        runtime::run(async move { inner.perform_rpc(data.into()).await }).await.map_err(Into::into)
+    }
+
+    /// Registers a handler for RPC invocations of `method` by remote participants.
+    ///
+    /// Registering the same method again replaces the previous handler.
+    ///
+    pub fn register_rpc_method(&self, method: String, handler: Arc<dyn RpcHandler>) {
+        let _rt = runtime::runtime().enter();
+        self.0.register_rpc_method(method, move |data| {
+            let handler = handler.clone();
+            Box::pin(super::rpc::invoke_handler(handler, data))
+        });
+    }
+
+    pub fn unregister_rpc_method(&self, method: String) {
+        self.0.unregister_rpc_method(method);
+    }
+
+    /// Sends text to participants in the room as a single text stream.
+    pub async fn send_text(
+        &self,
+        text: String,
+        options: StreamTextOptions,
+    ) -> Result<TextStreamInfo, RoomError> {
+        let inner = self.0.clone();
+        let info =
+            runtime::run(async move { inner.send_text(&text, options.into()).await }).await?;
+        Ok(info.into())
+    }
+
+    /// Sends bytes to participants in the room as a single byte stream.
+    pub async fn send_bytes(
+        &self,
+        data: Vec<u8>,
+        options: StreamByteOptions,
+    ) -> Result<ByteStreamInfo, RoomError> {
+        let inner = self.0.clone();
+        let info =
+            runtime::run(async move { inner.send_bytes(data, options.into()).await }).await?;
+        Ok(info.into())
+    }
+
+    /// Sends the file at `path` to participants in the room as a byte stream.
+    pub async fn send_file(
+        &self,
+        path: String,
+        options: StreamByteOptions,
+    ) -> Result<ByteStreamInfo, RoomError> {
+        let inner = self.0.clone();
+        let info = runtime::run(async move { inner.send_file(path, options.into()).await }).await?;
+        Ok(info.into())
+    }
+}
+
+impl LocalParticipant {
+    async fn publish_track(
+        &self,
+        track: LocalTrack,
+        options: TrackPublishOptions,
+    ) -> Result<Arc<LocalTrackPublication>, RoomError> {
+        let inner = self.0.clone();
+        let publication =
+            runtime::run(async move { inner.publish_track(track, options.into()).await }).await?;
+        Ok(Arc::new(LocalTrackPublication(publication)))
+    }
+}
+
+/// A participant in the room other than this client.
+#[derive(uniffi::Object)]
+pub struct RemoteParticipant(pub(crate) livekit::participant::RemoteParticipant);
+
+#[uniffi::export]
+impl RemoteParticipant {
+    pub fn sid(&self) -> String {
+        self.0.sid().into()
+    }
+
+    pub fn identity(&self) -> String {
+        self.0.identity().into()
+    }
+
+    pub fn name(&self) -> String {
+        self.0.name()
+    }
+
+    pub fn metadata(&self) -> String {
+        self.0.metadata()
+    }
+
+    pub fn attributes(&self) -> HashMap<String, String> {
+        self.0.attributes()
+    }
+
+    pub fn is_speaking(&self) -> bool {
+        self.0.is_speaking()
+    }
+
+    pub fn audio_level(&self) -> f32 {
+        self.0.audio_level()
+    }
+
+    pub fn connection_quality(&self) -> ConnectionQuality {
+        self.0.connection_quality()
+    }
+
+    /// Published tracks, keyed by track SID.
+    pub fn track_publications(&self) -> HashMap<String, Arc<RemoteTrackPublication>> {
+        self.0
+            .track_publications()
+            .into_iter()
+            .map(|(sid, publication)| (sid.into(), Arc::new(RemoteTrackPublication(publication))))
+            .collect()
+    }
+}
@@ -0,0 +1,118 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use livekit::participant::RpcErrorCode;
+use std::{sync::Arc, time::Duration};
+
+/// Request for [`LocalParticipant::perform_rpc`](super::participant::LocalParticipant::perform_rpc).
+#[derive(uniffi::Record)]
+pub struct PerformRpcData {
+    pub destination_identity: String,
+    pub method: String,
+    pub payload: String,
+    /// How long to wait for the response; defaults to 15 seconds.
+    #[uniffi(default)]
+    pub response_timeout: Option<Duration>,
+}
+
+impl From<PerformRpcData> for livekit::participant::PerformRpcData {
+    fn from(data: PerformRpcData) -> Self {
+        let mut inner =
+            livekit::participant::PerformRpcData::new(data.destination_identity, data.method)
+                .with_payload(data.payload);
+        if let Some(response_timeout) = data.response_timeout {
+            inner = inner.with_response_timeout(response_timeout);
+        }
+        inner
+    }
+}
+
+/// Incoming RPC invocation passed to an [`RpcHandler`].
+#[derive(uniffi::Record)]
+pub struct RpcInvocationData {
+    pub request_id: String,
+    pub caller_identity: String,
+    pub payload: String,
+    pub response_timeout: Duration,
+}
+
+impl From<livekit::participant::RpcInvocationData> for RpcInvocationData {
+    fn from(data: livekit::participant::RpcInvocationData) -> Self {
+        Self {
+            request_id: data.request_id,
+            caller_identity: data.caller_identity.into(),
+            payload: data.payload,
+            response_timeout: data.response_timeout,
+        }
+    }
+}
+
+/// Error of a failed RPC.
+///
+/// Handlers return it to report an application error to the caller; codes below 1000
+/// are free for application use.
+///
+#[derive(uniffi::Error, thiserror::Error, Debug)]
+pub enum RpcError {
+    #[error("RPC error {code}: {message}")]
+    Failed { code: u32, message: String, data: Option<String> },
+}
+
+impl From<livekit::participant::RpcError> for RpcError {
+    fn from(err: livekit::participant::RpcError) -> Self {
+        Self::Failed { code: err.code, message: err.message, data: err.data }
+    }
+}
+
+impl From<RpcError> for livekit::participant::RpcError {
+    fn from(err: RpcError) -> Self {
+        let RpcError::Failed { code, message, data } = err;
+        livekit::participant::RpcError::new(code, message, data)
+    }
+}
+
+impl From<uniffi::UnexpectedUniFFICallbackError> for RpcError {
+    fn from(err: uniffi::UnexpectedUniFFICallbackError) -> Self {
+        Self::Failed {
+            code: RpcErrorCode::ApplicationError as u32,
+            message: err.reason,
+            data: None,
+        }
+    }
+}
+
+/// Handler for invocations of a registered RPC method.
+#[uniffi::export(with_foreign)]
+pub trait RpcHandler: Send + Sync {
+    /// Handles an invocation, returning the response payload.
+    ///
+    /// Invoked on a dedicated blocking thread, so the handler may block until the
+    /// response is ready, within the invocation's `response_timeout`.
+    ///
+    fn handle(&self, data: RpcInvocationData) -> Result<String, RpcError>;
+}
+
+pub(crate) async fn invoke_handler(
+    handler: Arc<dyn RpcHandler>,
+    data: livekit::participant::RpcInvocationData,
+) -> Result<String, livekit::participant::RpcError> {
+    match tokio::task::spawn_blocking(move || handler.handle(data.into())).await {
+        Ok(result) => result.map_err(Into::into),
+        Err(_) => Err(livekit::participant::RpcError::new(
+            RpcErrorCode::ApplicationError as u32,
+            "RPC handler panicked".into(),
+            None,
+        )),
+    }
+}
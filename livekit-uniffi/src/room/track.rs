@@ -0,0 +1,322 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::media::{AudioSource, VideoSource};
+use crate::runtime;
+use livekit::{
+    options::VideoCodec,
+    track::{TrackKind, TrackSource},
+    webrtc::prelude::{RtcAudioSource, RtcVideoSource},
+};
+use std::sync::Arc;
+
+#[uniffi::remote(Enum)]
+pub enum TrackKind {
+    Audio,
+    Video,
+}
+
+#[uniffi::remote(Enum)]
+pub enum TrackSource {
+    Unknown,
+    Camera,
+    Microphone,
+    Screenshare,
+    ScreenshareAudio,
+}
+
+#[uniffi::remote(Enum)]
+pub enum VideoCodec {
+    VP8,
+    H264,
+    VP9,
+    AV1,
+    H265,
+}
+
+/// Audio track created by the local participant, fed by an [`AudioSource`].
+#[derive(uniffi::Object)]
+pub struct LocalAudioTrack(pub(crate) livekit::track::LocalAudioTrack);
+
+#[uniffi::export]
+impl LocalAudioTrack {
+    #[uniffi::constructor]
+    pub fn new(name: String, source: Arc<AudioSource>) -> Arc<Self> {
+        let source = RtcAudioSource::Native(source.0.clone());
+        Arc::new(Self(livekit::track::LocalAudioTrack::create_audio_track(&name, source)))
+    }
+
+    pub fn sid(&self) -> String {
+        self.0.sid().into()
+    }
+
+    pub fn name(&self) -> String {
+        self.0.name()
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.0.is_muted()
+    }
+
+    pub fn mute(&self) {
+        let _rt = runtime::runtime().enter();
+        self.0.mute()
+    }
+
+    pub fn unmute(&self) {
+        let _rt = runtime::runtime().enter();
+        self.0.unmute()
+    }
+}
+
+/// Video track created by the local participant, fed by a [`VideoSource`].
+#[derive(uniffi::Object)]
+pub struct LocalVideoTrack(pub(crate) livekit::track::LocalVideoTrack);
+
+#[uniffi::export]
+impl LocalVideoTrack {
+    #[uniffi::constructor]
+    pub fn new(name: String, source: Arc<VideoSource>) -> Arc<Self> {
+        let source = RtcVideoSource::Native(source.0.clone());
+        Arc::new(Self(livekit::track::LocalVideoTrack::create_video_track(&name, source)))
+    }
+
+    pub fn sid(&self) -> String {
+        self.0.sid().into()
+    }
+
+    pub fn name(&self) -> String {
+        self.0.name()
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.0.is_muted()
+    }
+
+    pub fn mute(&self) {
+        let _rt = runtime::runtime().enter();
+        self.0.mute()
+    }
+
+    pub fn unmute(&self) {
+        let _rt = runtime::runtime().enter();
+        self.0.unmute()
+    }
+}
+
+/// Audio track published by a remote participant.
+///
+/// Receive its frames with [`AudioStream`](super::media::AudioStream).
+///
+#[derive(uniffi::Object)]
+pub struct RemoteAudioTrack(pub(crate) livekit::track::RemoteAudioTrack);
+
+#[uniffi::export]
+impl RemoteAudioTrack {
+    pub fn sid(&self) -> String {
+        self.0.sid().into()
+    }
+
+    pub fn name(&self) -> String {
+        self.0.name()
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.0.is_muted()
+    }
+}
+
+/// Video track published by a remote participant.
+///
+/// Receive its frames with [`VideoStream`](super::media::VideoStream).
+///
+#[derive(uniffi::Object)]
+pub struct RemoteVideoTrack(pub(crate) livekit::track::RemoteVideoTrack);
+
+#[uniffi::export]
+impl RemoteVideoTrack {
+    pub fn sid(&self) -> String {
+        self.0.sid().into()
+    }
+
+    pub fn name(&self) -> String {
+        self.0.name()
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.0.is_muted()
+    }
+}
+
+/// Track subscribed from a remote participant.
+#[derive(uniffi::Enum)]
+pub enum RemoteTrack {
+    Audio { track: Arc<RemoteAudioTrack> },
+    Video { track: Arc<RemoteVideoTrack> },
+}
+
+impl From<livekit::track::RemoteTrack> for RemoteTrack {
+    fn from(track: livekit::track::RemoteTrack) -> Self {
+        match track {
+            livekit::track::RemoteTrack::Audio(track) => {
+                Self::Audio { track: Arc::new(RemoteAudioTrack(track)) }
+            }
+            livekit::track::RemoteTrack::Video(track) => {
+                Self::Video { track: Arc::new(RemoteVideoTrack(track)) }
+            }
+        }
+    }
+}
+
+/// Encoding parameters of a published video track.
+#[derive(uniffi::Record)]
+pub struct VideoEncoding {
+    pub max_bitrate: u64,
+    pub max_framerate: f64,
+}
+
+/// Options for publishing a track.
+///
+/// FFI wrapper around [`livekit::options::TrackPublishOptions`], exposing the commonly used
+/// subset. Unset fields use the SDK defaults.
+///
+#[derive(uniffi::Record)]
+pub struct TrackPublishOptions {
+    pub source: TrackSource,
+    #[uniffi(default)]
+    pub video_codec: Option<VideoCodec>,
+    /// Codec published on demand for subscribers that can't decode `video_codec`.
+    #[uniffi(default)]
+    pub backup_codec: Option<VideoCodec>,
+    /// Encoding of the highest quality layer; computed from the resolution if unset.
+    #[uniffi(default)]
+    pub video_encoding: Option<VideoEncoding>,
+    #[uniffi(default = true)]
+    pub simulcast: bool,
+    #[uniffi(default = true)]
+    pub dtx: bool,
+    #[uniffi(default = true)]
+    pub red: bool,
+}
+
+impl From<TrackPublishOptions> for livekit::options::TrackPublishOptions {
+    fn from(source: TrackPublishOptions) -> Self {
+        let mut options = livekit::options::TrackPublishOptions::default();
+        options.source = source.source;
+        if let Some(video_codec) = source.video_codec {
+            options.video_codec = video_codec;
+        }
+        options.backup_codec = source.backup_codec;
+        options.video_encoding =
+            source.video_encoding.map(|encoding| livekit::options::VideoEncoding {
+                max_bitrate: encoding.max_bitrate,
+                max_framerate: encoding.max_framerate,
+            });
+        options.simulcast = source.simulcast;
+        options.dtx = source.dtx;
+        options.red = source.red;
+        options
+    }
+}
+
+/// Publication of a track by the local participant.
+#[derive(uniffi::Object)]
+pub struct LocalTrackPublication(pub(crate) livekit::publication::LocalTrackPublication);
+
+#[uniffi::export]
+impl LocalTrackPublication {
+    pub fn sid(&self) -> String {
+        self.0.sid().into()
+    }
+
+    pub fn name(&self) -> String {
+        self.0.name()
+    }
+
+    pub fn kind(&self) -> TrackKind {
+        self.0.kind()
+    }
+
+    pub fn source(&self) -> TrackSource {
+        self.0.source()
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.0.is_muted()
+    }
+
+    pub fn mute(&self) {
+        let _rt = runtime::runtime().enter();
+        self.0.mute()
+    }
+
+    pub fn unmute(&self) {
+        let _rt = runtime::runtime().enter();
+        self.0.unmute()
+    }
+}
+
+/// Publication of a track by a remote participant.
+#[derive(uniffi::Object)]
+pub struct RemoteTrackPublication(pub(crate) livekit::publication::RemoteTrackPublication);
+
+#[uniffi::export]
+impl RemoteTrackPublication {
+    pub fn sid(&self) -> String {
+        self.0.sid().into()
+    }
+
+    pub fn name(&self) -> String {
+        self.0.name()
+    }
+
+    pub fn kind(&self) -> TrackKind {
+        self.0.kind()
+    }
+
+    pub fn source(&self) -> TrackSource {
+        self.0.source()
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.0.is_muted()
+    }
+
+    pub fn is_subscribed(&self) -> bool {
+        self.0.is_subscribed()
+    }
+
+    /// The subscribed track, if any.
+    pub fn track(&self) -> Option<RemoteTrack> {
+        self.0.track().map(Into::into)
+    }
+
+    /// Subscribes to or unsubscribes from the track, when auto-subscribe is disabled.
+    pub fn set_subscribed(&self, subscribed: bool) {
+        let _rt = runtime::runtime().enter();
+        self.0.set_subscribed(subscribed)
+    }
+
+    /// Pauses or resumes media delivery without unsubscribing.
+    pub fn set_enabled(&self, enabled: bool) {
+        let _rt = runtime::runtime().enter();
+        self.0.set_enabled(enabled)
+    }
+
+    /// Limits the frame rate of a video track, 0 removes the limit.
+    pub fn set_video_fps(&self, fps: u32) {
+        let _rt = runtime::runtime().enter();
+        self.0.set_video_fps(fps)
+    }
+}
@@ -15,6 +15,9 @@
 use std::sync::OnceLock;
 use tokio::runtime::Runtime;
 
+#[cfg(feature = "room")]
+use std::future::Future;
+
 /// Returns the process-global Tokio runtime, initializing it on first use.
 pub(crate) fn runtime() -> &'static Runtime {
     static RUNTIME: OnceLock<Runtime> = OnceLock::new();
@@ -26,3 +29,20 @@ pub(crate) fn runtime() -> &'static Runtime {
             .expect("Failed to build livekit-uniffi tokio runtime")
     })
 }
+
+/// Runs a future to completion on the global runtime.
+///
+/// Room APIs spawn long-lived tasks onto the current Tokio runtime, so they
+/// must not be driven by the executor of the foreign caller.
+///
+#[cfg(feature = "room")]
+pub(crate) async fn run<F>(future: F) -> F::Output
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    match runtime().spawn(future).await {
+        Ok(output) => output,
+        Err(err) => std::panic::resume_unwind(err.into_panic()),
+    }
+}
@@ -38,6 +38,11 @@ pub mod webrtc {
 
 pub use room::*;
 
+/// Recording and replay of signal and data channel traffic.
+pub mod recording {
+    pub use livekit_api::signal_client::recording::*;
+}
+
 /// `use livekit::prelude::*;` to import livekit types
 pub mod prelude;
 
@@ -56,4 +61,7 @@ pub mod dispatcher {
 
 pub use plugin::*;
 
+#[cfg(feature = "testing")]
+pub mod testing;
+
 mod utils;
@@ -14,20 +14,48 @@
 
 use std::{
     collections::HashMap,
-    ffi::{c_char, c_void, CString},
+    ffi::{c_char, c_void, CStr, CString},
     pin::Pin,
-    sync::{Arc, LazyLock},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, LazyLock,
+    },
     task::{Context, Poll},
     time::Duration,
 };
 
 use futures_util::Stream;
 use libloading::{Library, Symbol};
-use libwebrtc::{audio_stream::native::NativeAudioStream, prelude::AudioFrame};
+use libwebrtc::{
+    audio_stream::native::NativeAudioStream, native::audio_processor::AudioProcessor,
+    prelude::AudioFrame,
+};
 use parking_lot::RwLock;
 use serde::Serialize;
 use serde_json::json;
 
+use self::abi::{capability, error_kind};
+
+/// Constants of the audio filter plugin C ABI, mirrored from `livekit_audio_plugin::abi` so the
+/// SDK doesn't depend on the plugin crate. A test keeps both in sync.
+mod abi {
+    pub const ABI_VERSION: u32 = 3;
+
+    pub mod capability {
+        pub const SEPARATE_RATES: u32 = 1 << 0;
+        pub const FLOAT: u32 = 1 << 1;
+        pub const STREAM_INFO: u32 = 1 << 2;
+        pub const TOKEN_REFRESH: u32 = 1 << 3;
+        pub const ERRORS: u32 = 1 << 4;
+        pub const UNLOAD: u32 = 1 << 5;
+    }
+
+    pub mod error_kind {
+        pub const FAILED: i32 = 1;
+        pub const PANIC: i32 = 2;
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum PluginError {
     #[error("dylib error: {0}")]
@@ -36,6 +64,12 @@ pub enum PluginError {
     NotImplemented(String),
     #[error("on_load rejected by plugin (code {0})")]
     OnLoad(i32),
+    #[error("incompatible plugin ABI version {plugin} (host supports up to {host})")]
+    Incompatible { plugin: u32, host: u32 },
+    #[error("plugin failed: {0}")]
+    Failed(String),
+    #[error("plugin panicked: {0}")]
+    Panicked(String),
 }
 
 type OnLoadFn = unsafe extern "C" fn(options: *const c_char) -> i32;
@@ -57,6 +91,9 @@ type ProcessF32Fn = unsafe extern "C" fn(*const c_void, usize, *const f32, *mut
 type ProcessF32V2Fn = unsafe extern "C" fn(*const c_void, usize, *const f32, usize, *mut f32);
 type UpdateStreamInfoFn = unsafe extern "C" fn(*const c_void, *const c_char);
 type UpdateRefreshedTokenFn = unsafe extern "C" fn(*const c_char, *const c_char);
+type NegotiateFn = unsafe extern "C" fn(host_version: u32, capabilities: *mut u32) -> u32;
+type LastErrorFn = unsafe extern "C" fn(*const c_void, kind: *mut i32) -> *const c_char;
+type OnUnloadFn = unsafe extern "C" fn();
 
 static REGISTERED_PLUGINS: LazyLock<RwLock<HashMap<String, Arc<AudioFilterPlugin>>>> =
     LazyLock::new(|| RwLock::new(HashMap::new()));
@@ -65,6 +102,12 @@ pub fn register_audio_filter_plugin(id: String, plugin: Arc<AudioFilterPlugin>)
     REGISTERED_PLUGINS.write().insert(id, plugin);
 }
 
+/// Removes a plugin from the registry. The library is unloaded once the returned plugin and its
+/// sessions are dropped.
+pub fn unregister_audio_filter_plugin(id: &str) -> Option<Arc<AudioFilterPlugin>> {
+    REGISTERED_PLUGINS.write().remove(id)
+}
+
 pub fn registered_audio_filter_plugin(id: &str) -> Option<Arc<AudioFilterPlugin>> {
     REGISTERED_PLUGINS.read().get(id).cloned()
 }
@@ -73,6 +116,35 @@ pub fn registered_audio_filter_plugins() -> Vec<Arc<AudioFilterPlugin>> {
     REGISTERED_PLUGINS.read().values().map(|v| v.clone()).collect()
 }
 
+/// What a plugin supports, negotiated with ABI v3 plugins and probed from the functions exported
+/// by older ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AudioFilterCapabilities {
+    pub abi_version: u32,
+    /// Sessions may use different input and output sample rates.
+    pub separate_rates: bool,
+    pub float: bool,
+    pub stream_info: bool,
+    pub token_refresh: bool,
+    /// Errors and panics are reported, see [`PluginError::Failed`] and [`PluginError::Panicked`].
+    pub errors: bool,
+    pub unload: bool,
+}
+
+impl AudioFilterCapabilities {
+    fn from_flags(abi_version: u32, flags: u32) -> Self {
+        Self {
+            abi_version,
+            separate_rates: flags & capability::SEPARATE_RATES != 0,
+            float: flags & capability::FLOAT != 0,
+            stream_info: flags & capability::STREAM_INFO != 0,
+            token_refresh: flags & capability::TOKEN_REFRESH != 0,
+            errors: flags & capability::ERRORS != 0,
+            unload: flags & capability::UNLOAD != 0,
+        }
+    }
+}
+
 pub struct AudioFilterPlugin {
     lib: Library,
     dependencies: Vec<Library>,
@@ -86,6 +158,9 @@ pub struct AudioFilterPlugin {
     process_f32_v2_fn_ptr: *const c_void,
     update_stream_info_fn_ptr: *const c_void,
     update_token_fn_ptr: *const c_void,
+    last_error_fn_ptr: *const c_void,
+    on_unload_fn_ptr: *const c_void,
+    capabilities: AudioFilterCapabilities,
 }
 
 impl AudioFilterPlugin {
@@ -176,6 +251,31 @@ impl AudioFilterPlugin {
             }
         };
 
+        let last_error_fn_ptr = optional_symbol::<LastErrorFn>(&lib, b"audio_filter_last_error");
+        let on_unload_fn_ptr = optional_symbol::<OnUnloadFn>(&lib, b"audio_filter_on_unload");
+        let negotiate_fn_ptr = optional_symbol::<NegotiateFn>(&lib, b"audio_filter_negotiate");
+
+        let has_v2 = !create_v2_fn_ptr.is_null() && !process_i16_v2_fn_ptr.is_null();
+        let capabilities = if negotiate_fn_ptr.is_null() {
+            AudioFilterCapabilities {
+                abi_version: if has_v2 { 2 } else { 1 },
+                separate_rates: has_v2,
+                float: true,
+                stream_info: true,
+                token_refresh: !update_token_fn_ptr.is_null(),
+                errors: false,
+                unload: false,
+            }
+        } else {
+            let negotiate: NegotiateFn = unsafe { std::mem::transmute(negotiate_fn_ptr) };
+            let mut capabilities = negotiate_capabilities(negotiate)?;
+            capabilities.separate_rates &= has_v2;
+            capabilities.token_refresh &= !update_token_fn_ptr.is_null();
+            capabilities.errors &= !last_error_fn_ptr.is_null();
+            capabilities.unload &= !on_unload_fn_ptr.is_null();
+            capabilities
+        };
+
         Ok(Self {
             lib,
             dependencies: Default::default(),
@@ -189,6 +289,32 @@ impl AudioFilterPlugin {
             process_f32_v2_fn_ptr,
             update_stream_info_fn_ptr,
             update_token_fn_ptr,
+            last_error_fn_ptr,
+            on_unload_fn_ptr,
+            capabilities,
+        })
+    }
+
+    pub fn capabilities(&self) -> AudioFilterCapabilities {
+        self.capabilities
+    }
+
+    /// Error reported by the last call on `session`, or by the last global call when null.
+    fn last_error(&self, session: *const c_void) -> Option<PluginError> {
+        if !self.capabilities.errors {
+            return None;
+        }
+        let last_error: LastErrorFn = unsafe { std::mem::transmute(self.last_error_fn_ptr) };
+        let mut kind = 0;
+        let message = unsafe { last_error(session, &mut kind) };
+        if message.is_null() {
+            return None;
+        }
+        let message = unsafe { CStr::from_ptr(message) }.to_string_lossy().into_owned();
+        Some(match kind {
+            error_kind::PANIC => PluginError::Panicked(message),
+            error_kind::FAILED => PluginError::Failed(message),
+            kind => PluginError::Failed(format!("{message} (unknown error kind {kind})")),
         })
     }
 
@@ -214,7 +340,7 @@ impl AudioFilterPlugin {
         if res == 0 {
             Ok(())
         } else {
-            Err(PluginError::OnLoad(res))
+            Err(self.last_error(std::ptr::null()).unwrap_or(PluginError::OnLoad(res)))
         }
     }
 
@@ -227,10 +353,13 @@ impl AudioFilterPlugin {
         let url = CString::new(url).unwrap();
         let token = CString::new(token).unwrap();
         unsafe { update_token_fn(url.as_ptr(), token.as_ptr()) }
+        if let Some(err) = self.last_error(std::ptr::null()) {
+            log::error!("audio filter failed to update token: {}", err);
+        }
     }
 
     pub fn supports_separate_rates(&self) -> bool {
-        !self.create_v2_fn_ptr.is_null() && !self.process_i16_v2_fn_ptr.is_null()
+        self.capabilities.separate_rates
     }
 
     pub fn new_session<S: AsRef<str>>(
@@ -239,7 +368,7 @@ impl AudioFilterPlugin {
         output_sample_rate: u32,
         options: S,
         stream_info: AudioFilterStreamInfo,
-    ) -> Option<AudioFilterSession> {
+    ) -> Result<AudioFilterSession, PluginError> {
         let options = CString::new(options.as_ref()).unwrap_or(CString::new("").unwrap());
 
         let stream_info = serde_json::to_string(&stream_info).unwrap();
@@ -260,16 +389,56 @@ impl AudioFilterPlugin {
             unsafe { create_fn(input_sample_rate, options.as_ptr(), stream_info.as_ptr()) }
         };
         if ptr.is_null() {
-            return None;
+            return Err(self.last_error(std::ptr::null()).unwrap_or_else(|| {
+                PluginError::Failed("audio_filter_create returned null".into())
+            }));
+        }
+
+        Ok(AudioFilterSession {
+            plugin: self.clone(),
+            ptr,
+            failed: AtomicBool::new(false),
+            input_sample_rate,
+            output_sample_rate,
+            rejected_rate: None,
+        })
+    }
+}
+
+impl Drop for AudioFilterPlugin {
+    fn drop(&mut self) {
+        if self.capabilities.unload {
+            let on_unload: OnUnloadFn = unsafe { std::mem::transmute(self.on_unload_fn_ptr) };
+            unsafe { on_unload() };
         }
+    }
+}
+
+/// Agrees on an ABI version with a v3 plugin.
+fn negotiate_capabilities(negotiate: NegotiateFn) -> Result<AudioFilterCapabilities, PluginError> {
+    let mut flags = 0;
+    let version = unsafe { negotiate(abi::ABI_VERSION, &mut flags) };
+    if version == 0 || version > abi::ABI_VERSION {
+        return Err(PluginError::Incompatible { plugin: version, host: abi::ABI_VERSION });
+    }
+    Ok(AudioFilterCapabilities::from_flags(version, flags))
+}
 
-        Some(AudioFilterSession { plugin: self.clone(), ptr })
+fn optional_symbol<T>(lib: &Library, name: &[u8]) -> *const c_void {
+    match unsafe { lib.get::<Symbol<T>>(name) } {
+        Ok(sym) => unsafe { sym.try_as_raw_ptr() }.unwrap_or(std::ptr::null_mut()),
+        Err(_) => std::ptr::null(),
     }
 }
 
 pub struct AudioFilterSession {
     plugin: Arc<AudioFilterPlugin>,
     ptr: *const c_void,
+    failed: AtomicBool,
+    input_sample_rate: u32,
+    output_sample_rate: u32,
+    /// Last frame rate refused by the [`AudioProcessor`] adapter, to only log a mismatch once.
+    rejected_rate: Option<u32>,
 }
 
 impl AudioFilterSession {
@@ -284,7 +453,7 @@ impl AudioFilterSession {
         input: &[i16],
         out_num_samples: usize,
         output: &mut [i16],
-    ) {
+    ) -> Result<(), PluginError> {
         if !self.plugin.process_i16_v2_fn_ptr.is_null() {
             let process: ProcessI16V2Fn =
                 unsafe { std::mem::transmute(self.plugin.process_i16_v2_fn_ptr) };
@@ -302,6 +471,7 @@ impl AudioFilterSession {
                 unsafe { std::mem::transmute(self.plugin.process_i16_fn_ptr) };
             unsafe { process(self.ptr, in_num_samples, input.as_ptr(), output.as_mut_ptr()) };
         }
+        self.check_error()
     }
 
     pub fn process_f32(
@@ -310,7 +480,7 @@ impl AudioFilterSession {
         input: &[f32],
         out_num_samples: usize,
         output: &mut [f32],
-    ) {
+    ) -> Result<(), PluginError> {
         if !self.plugin.process_f32_v2_fn_ptr.is_null() {
             let process: ProcessF32V2Fn =
                 unsafe { std::mem::transmute(self.plugin.process_f32_v2_fn_ptr) };
@@ -328,6 +498,7 @@ impl AudioFilterSession {
                 unsafe { std::mem::transmute(self.plugin.process_f32_fn_ptr) };
             unsafe { process(self.ptr, in_num_samples, input.as_ptr(), output.as_mut_ptr()) };
         }
+        self.check_error()
     }
 
     pub fn update_stream_info(&self, info: AudioFilterStreamInfo) {
@@ -339,6 +510,54 @@ impl AudioFilterSession {
         let info_json = serde_json::to_string(&info).unwrap();
         let info_json = CString::new(info_json).unwrap_or(CString::new("").unwrap());
         unsafe { update_stream_info_fn(self.ptr, info_json.as_ptr()) }
+        if let Err(err) = self.check_error() {
+            log::error!("audio filter failed to update stream info: {}", err);
+        }
+    }
+
+    /// Whether a call on this session reported an error. A session that panicked outputs silence.
+    pub fn has_failed(&self) -> bool {
+        self.failed.load(Ordering::Relaxed)
+    }
+
+    fn check_error(&self) -> Result<(), PluginError> {
+        match self.plugin.last_error(self.ptr) {
+            Some(err) => {
+                self.failed.store(true, Ordering::Relaxed);
+                Err(err)
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// Runs the filter in place, the session must be created with equal input and output rates.
+/// Frames at another rate are left untouched.
+impl AudioProcessor for AudioFilterSession {
+    fn process(&mut self, frame: &mut AudioFrame<'_>) {
+        if frame.sample_rate != self.input_sample_rate
+            || self.input_sample_rate != self.output_sample_rate
+        {
+            if self.rejected_rate.replace(frame.sample_rate) != Some(frame.sample_rate) {
+                log::error!(
+                    "audio filter session runs at {}Hz -> {}Hz, skipping {}Hz frames",
+                    self.input_sample_rate,
+                    self.output_sample_rate,
+                    frame.sample_rate
+                );
+            }
+            return;
+        }
+
+        let num_samples = frame.data.len();
+        let mut out = vec![0; num_samples];
+        let first_failure = !self.has_failed();
+        if let Err(err) = self.process_i16(num_samples, &frame.data, num_samples, &mut out) {
+            if first_failure {
+                log::error!("audio filter failed: {}", err);
+            }
+        }
+        frame.data = out.into();
     }
 }
 
@@ -405,12 +624,17 @@ impl Stream for AudioFilterAudioStream {
                 let data = this.buffer.drain(..this.input_frame_size).collect::<Vec<_>>();
                 let mut out: Vec<i16> = vec![0; this.output_frame_size];
 
-                this.session.process_i16(
+                let first_failure = !this.session.has_failed();
+                if let Err(err) = this.session.process_i16(
                     this.input_frame_size,
                     &data,
                     this.output_frame_size,
                     &mut out,
-                );
+                ) {
+                    if first_failure {
+                        log::error!("audio filter failed: {}", err);
+                    }
+                }
 
                 return Poll::Ready(Some(AudioFrame {
                     data: out.into(),
@@ -443,3 +667,57 @@ unsafe impl Send for AudioFilterPlugin {}
 unsafe impl Sync for AudioFilterPlugin {}
 unsafe impl Send for AudioFilterSession {}
 unsafe impl Sync for AudioFilterSession {}
+
+#[cfg(test)]
+mod tests {
+    use livekit_audio_plugin::abi as plugin_abi;
+
+    use super::*;
+
+    #[test]
+    fn abi_matches_plugin_sdk() {
+        assert_eq!(abi::ABI_VERSION, plugin_abi::ABI_VERSION);
+
+        assert_eq!(capability::SEPARATE_RATES, plugin_abi::capability::SEPARATE_RATES);
+        assert_eq!(capability::FLOAT, plugin_abi::capability::FLOAT);
+        assert_eq!(capability::STREAM_INFO, plugin_abi::capability::STREAM_INFO);
+        assert_eq!(capability::TOKEN_REFRESH, plugin_abi::capability::TOKEN_REFRESH);
+        assert_eq!(capability::ERRORS, plugin_abi::capability::ERRORS);
+        assert_eq!(capability::UNLOAD, plugin_abi::capability::UNLOAD);
+
+        assert_eq!(error_kind::FAILED, plugin_abi::error_kind::FAILED);
+        assert_eq!(error_kind::PANIC, plugin_abi::error_kind::PANIC);
+    }
+
+    unsafe extern "C" fn negotiate_newer(_host_version: u32, capabilities: *mut u32) -> u32 {
+        *capabilities = capability::ERRORS;
+        abi::ABI_VERSION + 1
+    }
+
+    unsafe extern "C" fn negotiate_none(_host_version: u32, _capabilities: *mut u32) -> u32 {
+        0
+    }
+
+    unsafe extern "C" fn negotiate_older(host_version: u32, capabilities: *mut u32) -> u32 {
+        *capabilities = capability::FLOAT | capability::UNLOAD;
+        host_version.min(2)
+    }
+
+    #[test]
+    fn negotiation_rejects_incompatible_plugins() {
+        let host = abi::ABI_VERSION;
+        assert!(matches!(
+            negotiate_capabilities(negotiate_newer),
+            Err(PluginError::Incompatible { plugin, host: h }) if plugin == host + 1 && h == host
+        ));
+        assert!(matches!(
+            negotiate_capabilities(negotiate_none),
+            Err(PluginError::Incompatible { plugin: 0, .. })
+        ));
+
+        let capabilities = negotiate_capabilities(negotiate_older).unwrap();
+        assert_eq!(capabilities.abi_version, 2);
+        assert!(capabilities.float && capabilities.unload);
+        assert!(!capabilities.errors && !capabilities.separate_rates);
+    }
+}
@@ -26,12 +26,13 @@ pub use crate::{
         RemoteParticipant, RpcError, RpcErrorCode, RpcInvocationData,
     },
     publication::{LocalTrackPublication, RemoteTrackPublication, TrackPublication},
+    stats_monitor::{StatsMonitor, StatsMonitorOptions, StatsReport, TrackStatsSummary},
     track::{
         AudioTrack, LocalAudioTrack, LocalTrack, LocalVideoTrack, PublishTimingEvent,
         PublishTimingEventStream, PublishTimingStage, PublishingLayer, PublishingLayerQuality,
         RemoteAudioTrack, RemoteTrack, RemoteVideoTrack, StreamState, SubscribeTimingEvent,
         SubscribeTimingEventStream, SubscribeTimingStage, Track, TrackDimension, TrackKind,
-        TrackSource, VideoTrack,
+        TrackSource, VideoSink, VideoSinkInfo, VideoTrack,
     },
     ConnectionState, DataPacket, DataPacketKind, Room, RoomError, RoomEvent, RoomOptions,
     RoomResult, RoomSdkOptions, SipDTMF, Transcription, TranscriptionSegment,
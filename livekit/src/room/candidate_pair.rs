@@ -0,0 +1,220 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The network path selected by ICE for each peer connection.
+//!
+//! Read it on demand with [`Room::selected_candidate_pairs`](crate::Room::selected_candidate_pairs),
+//! or follow [`RoomEvent::SelectedCandidatePairChanged`](crate::RoomEvent::SelectedCandidatePairChanged).
+
+use std::time::Duration;
+
+use libwebrtc::stats::{
+    dictionaries::IceCandidateStats, IceCandidateType, IceServerTransportProtocol, RtcStats,
+};
+use livekit_protocol as proto;
+
+/// The peer connection a candidate pair belongs to.
+///
+/// In single peer connection mode, both directions go through the publisher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PeerConnectionTarget {
+    Publisher,
+    Subscriber,
+}
+
+impl From<proto::SignalTarget> for PeerConnectionTarget {
+    fn from(value: proto::SignalTarget) -> Self {
+        match value {
+            proto::SignalTarget::Publisher => Self::Publisher,
+            proto::SignalTarget::Subscriber => Self::Subscriber,
+        }
+    }
+}
+
+/// One end of a candidate pair.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandidateInfo {
+    pub candidate_type: Option<IceCandidateType>,
+    /// Transport protocol of the candidate ("udp" or "tcp").
+    pub protocol: String,
+    pub address: String,
+    pub port: u16,
+    /// Protocol used to reach the TURN server, only set for local relay candidates.
+    pub relay_protocol: Option<IceServerTransportProtocol>,
+    /// URL of the STUN or TURN server the candidate was obtained from.
+    pub url: Option<String>,
+}
+
+impl From<&IceCandidateStats> for CandidateInfo {
+    fn from(stats: &IceCandidateStats) -> Self {
+        Self {
+            candidate_type: stats.candidate_type,
+            protocol: stats.protocol.clone(),
+            address: stats.address.clone(),
+            port: u16::try_from(stats.port).unwrap_or_default(),
+            relay_protocol: stats.relay_protocol,
+            url: (!stats.url.is_empty()).then(|| stats.url.clone()),
+        }
+    }
+}
+
+/// The candidate pair currently carrying a peer connection's traffic.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectedCandidatePair {
+    pub local: CandidateInfo,
+    pub remote: CandidateInfo,
+    pub round_trip_time: Option<Duration>,
+}
+
+impl SelectedCandidatePair {
+    /// Whether the media goes through a TURN server.
+    pub fn is_relayed(&self) -> bool {
+        self.local.candidate_type == Some(IceCandidateType::Relay)
+            || self.remote.candidate_type == Some(IceCandidateType::Relay)
+    }
+
+    /// Extracts the selected pair from the stats report of a single peer connection.
+    pub(crate) fn from_stats(stats: &[RtcStats]) -> Option<Self> {
+        let selected_id = stats.iter().find_map(|stat| match stat {
+            RtcStats::Transport(t) if !t.transport.selected_candidate_pair_id.is_empty() => {
+                Some(t.transport.selected_candidate_pair_id.as_str())
+            }
+            _ => None,
+        });
+        let pair = stats.iter().find_map(|stat| match stat {
+            RtcStats::CandidatePair(pair) => match selected_id {
+                Some(id) => (pair.rtc.id == id).then_some(pair),
+                // Not every implementation reports the selected pair on the transport
+                None => pair.candidate_pair.nominated.then_some(pair),
+            },
+            _ => None,
+        })?;
+        let pair = &pair.candidate_pair;
+
+        let local = stats.iter().find_map(|stat| match stat {
+            RtcStats::LocalCandidate(c) if c.rtc.id == pair.local_candidate_id => {
+                Some(CandidateInfo::from(&c.local_candidate))
+            }
+            _ => None,
+        })?;
+        let remote = stats.iter().find_map(|stat| match stat {
+            RtcStats::RemoteCandidate(c) if c.rtc.id == pair.remote_candidate_id => {
+                Some(CandidateInfo::from(&c.remote_candidate))
+            }
+            _ => None,
+        })?;
+
+        Some(Self {
+            local,
+            remote,
+            round_trip_time: (pair.current_round_trip_time > 0.0)
+                .then(|| Duration::from_secs_f64(pair.current_round_trip_time)),
+        })
+    }
+}
+
+/// Selected candidate pairs of both peer connections, `None` until ICE has connected.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SelectedCandidatePairs {
+    pub publisher: Option<SelectedCandidatePair>,
+    /// Always `None` in single peer connection mode.
+    pub subscriber: Option<SelectedCandidatePair>,
+}
+
+#[cfg(test)]
+mod tests {
+    use libwebrtc::stats::{
+        dictionaries, CandidatePairStats, LocalCandidateStats, RemoteCandidateStats, TransportStats,
+    };
+
+    use super::*;
+
+    fn rtc(id: &str) -> dictionaries::RtcStats {
+        dictionaries::RtcStats { id: id.to_owned(), ..Default::default() }
+    }
+
+    fn pair(id: &str, local: &str, nominated: bool) -> RtcStats {
+        RtcStats::CandidatePair(CandidatePairStats {
+            rtc: rtc(id),
+            candidate_pair: dictionaries::CandidatePairStats {
+                local_candidate_id: local.to_owned(),
+                remote_candidate_id: "R1".to_owned(),
+                nominated,
+                current_round_trip_time: 0.05,
+                ..Default::default()
+            },
+        })
+    }
+
+    fn local(id: &str, candidate_type: IceCandidateType) -> RtcStats {
+        RtcStats::LocalCandidate(LocalCandidateStats {
+            rtc: rtc(id),
+            local_candidate: dictionaries::IceCandidateStats {
+                candidate_type: Some(candidate_type),
+                protocol: "udp".to_owned(),
+                address: "203.0.113.7".to_owned(),
+                port: 50000,
+                relay_protocol: (candidate_type == IceCandidateType::Relay)
+                    .then_some(IceServerTransportProtocol::Tls),
+                url: "turns:turn.example.com:443?transport=tcp".to_owned(),
+                ..Default::default()
+            },
+        })
+    }
+
+    fn stats() -> Vec<RtcStats> {
+        vec![
+            pair("CP1", "L1", true),
+            pair("CP2", "L2", false),
+            local("L1", IceCandidateType::Host),
+            local("L2", IceCandidateType::Relay),
+            RtcStats::RemoteCandidate(RemoteCandidateStats {
+                rtc: rtc("R1"),
+                remote_candidate: dictionaries::IceCandidateStats {
+                    candidate_type: Some(IceCandidateType::Host),
+                    protocol: "udp".to_owned(),
+                    ..Default::default()
+                },
+            }),
+        ]
+    }
+
+    #[test]
+    fn selected_pair_is_read_from_the_transport() {
+        let mut stats = stats();
+        stats.push(RtcStats::Transport(TransportStats {
+            rtc: rtc("T1"),
+            transport: dictionaries::TransportStats {
+                selected_candidate_pair_id: "CP2".to_owned(),
+                ..Default::default()
+            },
+        }));
+
+        let pair = SelectedCandidatePair::from_stats(&stats).unwrap();
+        assert!(pair.is_relayed());
+        assert_eq!(pair.local.relay_protocol, Some(IceServerTransportProtocol::Tls));
+        assert_eq!(pair.local.url.as_deref(), Some("turns:turn.example.com:443?transport=tcp"));
+        assert_eq!(pair.local.port, 50000);
+        assert_eq!(pair.round_trip_time, Some(Duration::from_millis(50)));
+    }
+
+    #[test]
+    fn nominated_pair_is_used_without_transport_stats() {
+        let pair = SelectedCandidatePair::from_stats(&stats()).unwrap();
+        assert_eq!(pair.local.candidate_type, Some(IceCandidateType::Host));
+        assert!(!pair.is_relayed());
+
+        assert_eq!(SelectedCandidatePair::from_stats(&[]), None);
+    }
+}
@@ -0,0 +1,823 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Recording and playback of data tracks in the [MCAP](https://mcap.dev) file format.
+//!
+//! [`McapRecorder`] writes frames received on remote data tracks to an MCAP file, one
+//! channel per track, so sessions can be inspected offline with tools such as Foxglove.
+//! [`McapPlayer`] publishes the channels of an MCAP file as local data tracks, pushing
+//! messages with their original timing.
+//!
+//! Schema and frame encodings map onto MCAP's well-known
+//! [schema](https://mcap.dev/spec/registry#schema-encodings) and
+//! [message](https://mcap.dev/spec/registry#message-encodings) encodings.
+//!
+//! Message timestamps are recorded as follows:
+//!
+//! - `log_time`: when the frame was received, in nanoseconds since the UNIX epoch.
+//! - `publish_time`: the frame's [user timestamp](DataTrackFrame::user_timestamp),
+//!   assumed to be in milliseconds since the UNIX epoch and converted to nanoseconds.
+//!   Equal to `log_time` if the frame has no user timestamp.
+//!
+
+use super::{
+    DataTrackFrame, DataTrackFrameEncoding, DataTrackOptions, DataTrackSchemaEncoding,
+    DataTrackSchemaId, DataTrackSubscribeError, LocalDataTrack, PublishError, RemoteDataTrack,
+};
+use crate::{id::ParticipantIdentity, participant::LocalParticipant, RoomError};
+use bytes::Bytes;
+use futures_util::StreamExt;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{BufReader, BufWriter, Cursor, Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use thiserror::Error;
+use tokio::sync::{oneshot, watch};
+
+/// Channel metadata key holding the identity of the track's publisher.
+pub const PUBLISHER_IDENTITY_KEY: &str = "livekit.publisher_identity";
+
+/// Channel metadata key holding the name of the track.
+pub const TRACK_NAME_KEY: &str = "livekit.track_name";
+
+/// Channel metadata key holding the SID of the track at the time of recording.
+pub const TRACK_SID_KEY: &str = "livekit.track_sid";
+
+/// An error that can occur when recording or playing back an MCAP file.
+#[derive(Debug, Error)]
+pub enum McapError {
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("mcap: {0}")]
+    Mcap(#[from] ::mcap::McapError),
+    #[error("room: {0}")]
+    Room(#[from] RoomError),
+    #[error("subscribe: {0}")]
+    Subscribe(#[from] DataTrackSubscribeError),
+    #[error("publish: {0}")]
+    Publish(#[from] PublishError),
+    #[error("recorder already finished")]
+    Finished,
+    #[error("file has no summary section to read messages in log time order")]
+    NotIndexed,
+}
+
+type Writer = ::mcap::Writer<BufWriter<File>>;
+
+/// Records frames received on remote data tracks to an MCAP file.
+///
+/// File IO happens on a dedicated writer thread, so recording never blocks the tasks
+/// receiving frames.
+///
+/// A frame's [user timestamp](DataTrackFrame::user_timestamp) is taken to be in
+/// milliseconds since the UNIX epoch, as set by
+/// [`DataTrackFrame::with_user_timestamp_now`], and is written as the message's
+/// `publish_time` in nanoseconds.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use livekit::prelude::*;
+/// # use livekit::data_track::mcap::{McapRecorder, McapError};
+/// # async fn example(room: Room, track: RemoteDataTrack) -> Result<(), McapError> {
+/// let mut recorder = McapRecorder::create("session.mcap")?;
+/// recorder.record(&room.local_participant(), &track).await?;
+///
+/// // ...
+///
+/// recorder.finish().await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+pub struct McapRecorder {
+    sink: RecordSink,
+    /// Schema IDs already written to the file, keyed by publisher and schema.
+    schemas: HashMap<(ParticipantIdentity, DataTrackSchemaId), u16>,
+    close_tx: watch::Sender<bool>,
+    tasks: Vec<livekit_runtime::JoinHandle<()>>,
+    done_rx: oneshot::Receiver<Result<(), McapError>>,
+}
+
+impl McapRecorder {
+    /// Creates a recorder writing to a new file at the given path.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, McapError> {
+        let file = File::create(path)?;
+        let writer = ::mcap::Writer::new(BufWriter::new(file))?;
+        let (commands_tx, commands_rx) = mpsc::channel();
+        let (done_tx, done_rx) = oneshot::channel();
+        std::thread::Builder::new()
+            .name("mcap-writer".into())
+            .spawn(move || _ = done_tx.send(write_thread(writer, commands_rx)))?;
+        let (close_tx, _) = watch::channel(false);
+        Ok(Self {
+            sink: RecordSink { commands: commands_tx },
+            schemas: HashMap::new(),
+            close_tx,
+            tasks: Vec::new(),
+            done_rx,
+        })
+    }
+
+    /// Subscribes to a remote data track and records its frames as an MCAP channel.
+    ///
+    /// If the track declares a schema, its definition is looked up with
+    /// [`LocalParticipant::get_schema`] and stored alongside the channel. Recording
+    /// continues until the track is unpublished or [`Self::finish`] is called.
+    ///
+    pub async fn record(
+        &mut self,
+        local_participant: &LocalParticipant,
+        track: &RemoteDataTrack,
+    ) -> Result<(), McapError> {
+        let info = track.info();
+        let publisher_identity = ParticipantIdentity::from(track.publisher_identity());
+
+        let schema_id = match info.schema() {
+            Some(schema) => match self.schemas.get(&(publisher_identity.clone(), schema.clone())) {
+                Some(id) => *id,
+                None => {
+                    let definition = local_participant
+                        .get_schema(schema.clone(), publisher_identity.clone())
+                        .await?;
+                    self.add_schema(&publisher_identity, schema, definition).await?
+                }
+            },
+            None => 0, // MCAP reserves zero for channels without a schema
+        };
+        let channel_id = self
+            .add_channel(
+                schema_id,
+                &publisher_identity,
+                info.name(),
+                &info.sid().to_string(),
+                info.frame_encoding(),
+            )
+            .await?;
+
+        let stream = track.subscribe().await?;
+        let task =
+            RecordTask { sink: self.sink.clone(), channel_id, close_rx: self.close_tx.subscribe() };
+        self.tasks.push(livekit_runtime::spawn(task.run(stream)));
+        Ok(())
+    }
+
+    /// Stops recording all tracks and finalizes the file.
+    pub async fn finish(mut self) -> Result<(), McapError> {
+        _ = self.close_tx.send(true);
+        for task in self.tasks.drain(..) {
+            task.await;
+        }
+        // The writer thread finalizes the file once every sender is dropped.
+        let Self { sink, done_rx, .. } = self;
+        drop(sink);
+        done_rx.await.map_err(|_| McapError::Finished)?
+    }
+
+    async fn add_schema(
+        &mut self,
+        publisher_identity: &ParticipantIdentity,
+        schema: &DataTrackSchemaId,
+        definition: String,
+    ) -> Result<u16, McapError> {
+        let (reply, reply_rx) = oneshot::channel();
+        self.sink.send(WriteCommand::AddSchema {
+            name: schema.name().to_string(),
+            encoding: schema_encoding_name(schema.encoding()).to_string(),
+            data: definition.into_bytes(),
+            reply,
+        })?;
+        let id = reply_rx.await.map_err(|_| McapError::Finished)??;
+        self.schemas.insert((publisher_identity.clone(), schema.clone()), id);
+        Ok(id)
+    }
+
+    async fn add_channel(
+        &self,
+        schema_id: u16,
+        publisher_identity: &ParticipantIdentity,
+        track_name: &str,
+        track_sid: &str,
+        frame_encoding: Option<&DataTrackFrameEncoding>,
+    ) -> Result<u16, McapError> {
+        let metadata = BTreeMap::from([
+            (PUBLISHER_IDENTITY_KEY.to_string(), publisher_identity.to_string()),
+            (TRACK_NAME_KEY.to_string(), track_name.to_string()),
+            (TRACK_SID_KEY.to_string(), track_sid.to_string()),
+        ]);
+        let (reply, reply_rx) = oneshot::channel();
+        self.sink.send(WriteCommand::AddChannel {
+            schema_id,
+            topic: format!("{}/{}", publisher_identity, track_name),
+            message_encoding: frame_encoding.map(frame_encoding_name).unwrap_or_default().into(),
+            metadata,
+            reply,
+        })?;
+        reply_rx.await.map_err(|_| McapError::Finished)?
+    }
+}
+
+/// Requests processed by the recorder's writer thread.
+enum WriteCommand {
+    AddSchema {
+        name: String,
+        encoding: String,
+        data: Vec<u8>,
+        reply: oneshot::Sender<Result<u16, McapError>>,
+    },
+    AddChannel {
+        schema_id: u16,
+        topic: String,
+        message_encoding: String,
+        metadata: BTreeMap<String, String>,
+        reply: oneshot::Sender<Result<u16, McapError>>,
+    },
+    Message {
+        header: ::mcap::records::MessageHeader,
+        payload: Bytes,
+    },
+}
+
+/// Handle for queueing writes to the recorder's writer thread.
+#[derive(Clone)]
+struct RecordSink {
+    commands: mpsc::Sender<WriteCommand>,
+}
+
+impl RecordSink {
+    fn send(&self, command: WriteCommand) -> Result<(), McapError> {
+        self.commands.send(command).map_err(|_| McapError::Finished)
+    }
+
+    fn write(&self, channel_id: u16, sequence: u32, frame: &DataTrackFrame, log_time: u64) {
+        let publish_time =
+            frame.user_timestamp().and_then(|ts| ts.checked_mul(1_000_000)).unwrap_or(log_time);
+        let header =
+            ::mcap::records::MessageHeader { channel_id, sequence, log_time, publish_time };
+        // Payloads are reference counted, so queueing the message doesn't copy it.
+        _ = self.send(WriteCommand::Message { header, payload: frame.payload() });
+    }
+}
+
+/// Writes queued records until all senders are dropped, then finalizes the file.
+fn write_thread(
+    mut writer: Writer,
+    commands: mpsc::Receiver<WriteCommand>,
+) -> Result<(), McapError> {
+    for command in commands {
+        match command {
+            WriteCommand::AddSchema { name, encoding, data, reply } => {
+                _ = reply.send(writer.add_schema(&name, &encoding, &data).map_err(Into::into));
+            }
+            WriteCommand::AddChannel { schema_id, topic, message_encoding, metadata, reply } => {
+                let result = writer.add_channel(schema_id, &topic, &message_encoding, &metadata);
+                _ = reply.send(result.map_err(Into::into));
+            }
+            WriteCommand::Message { header, payload } => {
+                if let Err(err) = writer.write_to_known_channel(&header, &payload) {
+                    log::warn!("Failed to write MCAP message: {}", err);
+                }
+            }
+        }
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+/// Task recording frames received on a single track.
+struct RecordTask {
+    sink: RecordSink,
+    channel_id: u16,
+    close_rx: watch::Receiver<bool>,
+}
+
+impl RecordTask {
+    async fn run(mut self, mut stream: super::DataTrackStream) {
+        let mut sequence: u32 = 0;
+        loop {
+            tokio::select! {
+                _ = self.close_rx.wait_for(|closed| *closed) => break,
+                frame = stream.next() => {
+                    let Some(frame) = frame else { break };
+                    let log_time = unix_time_nanos(SystemTime::now());
+                    self.sink.write(self.channel_id, sequence, &frame, log_time);
+                    sequence = sequence.wrapping_add(1);
+                }
+            }
+        }
+    }
+}
+
+/// Publishes the channels of an MCAP file as local data tracks.
+///
+/// Each channel is published as a track named after its [`TRACK_NAME_KEY`] metadata,
+/// or its topic if absent. When several channels share a track name, for example the same
+/// track recorded from two publishers, each of them is named after its topic instead.
+/// Channel schemas are [defined](LocalParticipant::define_schema) before publishing.
+///
+/// The file must be indexed, as written by [`McapRecorder`]. Messages are read from disk
+/// on a dedicated reader thread as playback progresses, so files larger than memory can be
+/// played. A message's `publish_time` is restored as the frame's
+/// [user timestamp](DataTrackFrame::user_timestamp), in milliseconds since the UNIX epoch.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use livekit::prelude::*;
+/// # use livekit::data_track::mcap::{McapPlayer, McapError};
+/// # async fn example(room: Room) -> Result<(), McapError> {
+/// let player = McapPlayer::open("session.mcap")?;
+/// player.play(&room.local_participant()).await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+pub struct McapPlayer {
+    source: McapSource,
+    summary: ::mcap::Summary,
+    speed: f64,
+}
+
+impl McapPlayer {
+    /// Opens the MCAP file at the given path and reads its summary.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, McapError> {
+        let source = McapSource::Path(path.as_ref().to_path_buf());
+        let summary = read_summary(&mut source.open()?)?;
+        Ok(Self { source, summary, speed: 1.0 })
+    }
+
+    /// Creates a player from the contents of an MCAP file.
+    pub fn from_bytes(data: impl Into<Bytes>) -> Result<Self, McapError> {
+        let source = McapSource::Bytes(data.into());
+        let summary = read_summary(&mut source.open()?)?;
+        Ok(Self { source, summary, speed: 1.0 })
+    }
+
+    /// Sets the playback speed relative to the original timing.
+    ///
+    /// Defaults to `1.0`. Values greater than one play back faster; non-positive values
+    /// push all messages without delay.
+    ///
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    /// Publishes each channel as a local data track and pushes its messages.
+    ///
+    /// Messages are pushed in `log_time` order, spaced out according to the difference
+    /// between their log times. Channels whose track can't be published under their name
+    /// are skipped. Returns once all messages have been pushed; tracks are unpublished when
+    /// this future completes.
+    ///
+    pub async fn play(&self, local_participant: &LocalParticipant) -> Result<(), McapError> {
+        let mut tracks: HashMap<u16, LocalDataTrack> = HashMap::new();
+        let mut defined_schemas: Vec<DataTrackSchemaId> = Vec::new();
+        for track in self.tracks() {
+            let mut options = DataTrackOptions::new(track.name.clone());
+            if let Some(encoding) = track.frame_encoding {
+                options = options.with_frame_encoding(encoding);
+            }
+            if let Some((id, definition)) = track.schema {
+                if !defined_schemas.contains(&id) {
+                    local_participant.define_schema(id.clone(), definition).await?;
+                    defined_schemas.push(id.clone());
+                }
+                options = options.with_schema(id);
+            }
+            match local_participant.publish_data_track(options).await {
+                Ok(published) => _ = tracks.insert(track.channel_id, published),
+                Err(err @ (PublishError::DuplicateName | PublishError::InvalidName)) => {
+                    log::warn!("Skipping MCAP channel '{}': {}", track.name, err);
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        let mut messages = self.messages()?;
+        let mut start: Option<(livekit_runtime::Instant, u64)> = None;
+        while let Some(message) = messages.recv().await {
+            let message = message?;
+            let (start_instant, first_log_time) =
+                *start.get_or_insert((livekit_runtime::Instant::now(), message.log_time));
+            if self.speed > 0.0 {
+                let offset = Duration::from_nanos(message.log_time.saturating_sub(first_log_time));
+                let target = start_instant + offset.div_f64(self.speed);
+                let now = livekit_runtime::Instant::now();
+                if target > now {
+                    livekit_runtime::sleep(target - now).await;
+                }
+            }
+            let Some(track) = tracks.get(&message.channel_id) else { continue };
+            if let Err(err) = track.try_push(message.frame) {
+                log::warn!("Failed to push frame on '{}': {}", track.info().name(), err);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the channels to publish, ordered by channel ID.
+    fn tracks(&self) -> Vec<PlaybackTrack> {
+        let message_counts = self.summary.stats.as_ref().map(|stats| &stats.channel_message_counts);
+        let mut channels: Vec<_> = self
+            .summary
+            .channels
+            .values()
+            .filter(|channel| {
+                message_counts.is_none_or(|counts| counts.get(&channel.id).is_some_and(|n| *n > 0))
+            })
+            .collect();
+        channels.sort_by_key(|channel| channel.id);
+
+        let track_name = |channel: &::mcap::Channel| {
+            channel.metadata.get(TRACK_NAME_KEY).unwrap_or(&channel.topic).clone()
+        };
+        let mut name_counts: HashMap<String, usize> = HashMap::new();
+        for channel in &channels {
+            *name_counts.entry(track_name(channel)).or_default() += 1;
+        }
+
+        channels
+            .into_iter()
+            .map(|channel| {
+                let mut name = track_name(channel);
+                if name_counts[&name] > 1 {
+                    // Recorded topics are unique per publisher
+                    name = channel.topic.clone();
+                }
+                let schema = channel.schema.as_ref().map(|schema| {
+                    let id = DataTrackSchemaId::new(
+                        schema.name.clone(),
+                        schema_encoding_from_name(&schema.encoding),
+                    );
+                    (id, String::from_utf8_lossy(&schema.data).into_owned())
+                });
+                PlaybackTrack {
+                    channel_id: channel.id,
+                    name,
+                    frame_encoding: frame_encoding_from_name(&channel.message_encoding),
+                    schema,
+                }
+            })
+            .collect()
+    }
+
+    /// Starts reading messages in `log_time` order on a reader thread.
+    ///
+    /// At most [`Self::READ_AHEAD`] messages are read ahead of playback. Reading stops
+    /// early when the receiver is dropped.
+    ///
+    fn messages(
+        &self,
+    ) -> Result<tokio::sync::mpsc::Receiver<Result<PlaybackMessage, McapError>>, McapError> {
+        let source = self.source.open()?;
+        let reader = ::mcap::sans_io::indexed_reader::IndexedReader::new(&self.summary)?;
+        let (messages_tx, messages_rx) = tokio::sync::mpsc::channel(Self::READ_AHEAD);
+        std::thread::Builder::new().name("mcap-reader".into()).spawn(move || {
+            if let Err(err) = read_thread(source, reader, &messages_tx) {
+                _ = messages_tx.blocking_send(Err(err));
+            }
+        })?;
+        Ok(messages_rx)
+    }
+
+    /// Maximum number of messages read ahead of playback.
+    const READ_AHEAD: usize = 64;
+}
+
+/// Where an [`McapPlayer`] reads the file from.
+enum McapSource {
+    Path(PathBuf),
+    Bytes(Bytes),
+}
+
+trait ReadSeek: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> ReadSeek for T {}
+
+impl McapSource {
+    fn open(&self) -> Result<Box<dyn ReadSeek>, McapError> {
+        Ok(match self {
+            Self::Path(path) => Box::new(BufReader::new(File::open(path)?)),
+            Self::Bytes(data) => Box::new(Cursor::new(data.clone())),
+        })
+    }
+}
+
+/// Reads the summary section of an MCAP file, required to read messages in `log_time` order.
+fn read_summary(source: &mut dyn ReadSeek) -> Result<::mcap::Summary, McapError> {
+    use ::mcap::sans_io::summary_reader::{SummaryReadEvent, SummaryReader};
+
+    let mut reader = SummaryReader::new();
+    while let Some(event) = reader.next_event() {
+        match event? {
+            SummaryReadEvent::ReadRequest(need) => {
+                let read = source.read(reader.insert(need))?;
+                reader.notify_read(read);
+            }
+            SummaryReadEvent::SeekRequest(to) => {
+                let position = source.seek(to)?;
+                reader.notify_seeked(position);
+            }
+        }
+    }
+    reader.finish().ok_or(McapError::NotIndexed)
+}
+
+/// Reads the chunks requested by `reader` and sends its messages until all are read or the
+/// receiver is dropped.
+fn read_thread(
+    mut source: Box<dyn ReadSeek>,
+    mut reader: ::mcap::sans_io::indexed_reader::IndexedReader,
+    messages_tx: &tokio::sync::mpsc::Sender<Result<PlaybackMessage, McapError>>,
+) -> Result<(), McapError> {
+    use ::mcap::sans_io::indexed_reader::IndexedReadEvent;
+
+    let mut chunk = Vec::new();
+    while let Some(event) = reader.next_event() {
+        match event? {
+            IndexedReadEvent::ReadChunkRequest { offset, length } => {
+                source.seek(SeekFrom::Start(offset))?;
+                chunk.resize(length, 0);
+                source.read_exact(&mut chunk)?;
+                reader.insert_chunk_record_data(offset, &chunk)?;
+            }
+            IndexedReadEvent::Message { header, data } => {
+                let mut frame = DataTrackFrame::new(data.to_vec());
+                if header.publish_time != header.log_time {
+                    frame = frame.with_user_timestamp(header.publish_time / 1_000_000);
+                }
+                let message = PlaybackMessage {
+                    channel_id: header.channel_id,
+                    log_time: header.log_time,
+                    frame,
+                };
+                if messages_tx.blocking_send(Ok(message)).is_err() {
+                    break;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A channel to publish as a local data track.
+struct PlaybackTrack {
+    channel_id: u16,
+    name: String,
+    frame_encoding: Option<DataTrackFrameEncoding>,
+    /// Schema ID and definition.
+    schema: Option<(DataTrackSchemaId, String)>,
+}
+
+/// A message to push, read from the file by [`McapPlayer`].
+struct PlaybackMessage {
+    channel_id: u16,
+    /// Log time in nanoseconds since the UNIX epoch.
+    log_time: u64,
+    frame: DataTrackFrame,
+}
+
+fn unix_time_nanos(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map(|d| d.as_nanos() as u64).unwrap_or_default()
+}
+
+fn schema_encoding_name(encoding: &DataTrackSchemaEncoding) -> &str {
+    match encoding {
+        DataTrackSchemaEncoding::Protobuf => "protobuf",
+        DataTrackSchemaEncoding::Flatbuffer => "flatbuffer",
+        DataTrackSchemaEncoding::Ros1Msg => "ros1msg",
+        DataTrackSchemaEncoding::Ros2Msg => "ros2msg",
+        DataTrackSchemaEncoding::Ros2Idl => "ros2idl",
+        DataTrackSchemaEncoding::OmgIdl => "omgidl",
+        DataTrackSchemaEncoding::JsonSchema => "jsonschema",
+        DataTrackSchemaEncoding::Custom(name) => name,
+        _ => "",
+    }
+}
+
+fn schema_encoding_from_name(name: &str) -> DataTrackSchemaEncoding {
+    match name {
+        "protobuf" => DataTrackSchemaEncoding::Protobuf,
+        "flatbuffer" => DataTrackSchemaEncoding::Flatbuffer,
+        "ros1msg" => DataTrackSchemaEncoding::Ros1Msg,
+        "ros2msg" => DataTrackSchemaEncoding::Ros2Msg,
+        "ros2idl" => DataTrackSchemaEncoding::Ros2Idl,
+        "omgidl" => DataTrackSchemaEncoding::OmgIdl,
+        "jsonschema" => DataTrackSchemaEncoding::JsonSchema,
+        "" => DataTrackSchemaEncoding::Other,
+        name => DataTrackSchemaEncoding::Custom(name.to_string()),
+    }
+}
+
+fn frame_encoding_name(encoding: &DataTrackFrameEncoding) -> &str {
+    match encoding {
+        DataTrackFrameEncoding::Ros1 => "ros1",
+        DataTrackFrameEncoding::Cdr => "cdr",
+        DataTrackFrameEncoding::Protobuf => "protobuf",
+        DataTrackFrameEncoding::Flatbuffer => "flatbuffer",
+        DataTrackFrameEncoding::Cbor => "cbor",
+        DataTrackFrameEncoding::Msgpack => "msgpack",
+        DataTrackFrameEncoding::Json => "json",
+        DataTrackFrameEncoding::Custom(name) => name,
+        _ => "",
+    }
+}
+
+fn frame_encoding_from_name(name: &str) -> Option<DataTrackFrameEncoding> {
+    let encoding = match name {
+        "ros1" => DataTrackFrameEncoding::Ros1,
+        "cdr" => DataTrackFrameEncoding::Cdr,
+        "protobuf" => DataTrackFrameEncoding::Protobuf,
+        "flatbuffer" => DataTrackFrameEncoding::Flatbuffer,
+        "cbor" => DataTrackFrameEncoding::Cbor,
+        "msgpack" => DataTrackFrameEncoding::Msgpack,
+        "json" => DataTrackFrameEncoding::Json,
+        "" => return None,
+        name => DataTrackFrameEncoding::Custom(name.to_string()),
+    };
+    Some(encoding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(DataTrackSchemaEncoding::Protobuf)]
+    #[test_case(DataTrackSchemaEncoding::Ros2Idl)]
+    #[test_case(DataTrackSchemaEncoding::JsonSchema)]
+    #[test_case(DataTrackSchemaEncoding::Custom("my-idl".into()))]
+    fn test_schema_encoding_roundtrip(encoding: DataTrackSchemaEncoding) {
+        assert_eq!(schema_encoding_from_name(schema_encoding_name(&encoding)), encoding);
+    }
+
+    #[test_case(DataTrackFrameEncoding::Cdr)]
+    #[test_case(DataTrackFrameEncoding::Msgpack)]
+    #[test_case(DataTrackFrameEncoding::Json)]
+    #[test_case(DataTrackFrameEncoding::Custom("my-format".into()))]
+    fn test_frame_encoding_roundtrip(encoding: DataTrackFrameEncoding) {
+        assert_eq!(frame_encoding_from_name(frame_encoding_name(&encoding)), Some(encoding));
+    }
+
+    #[tokio::test]
+    async fn test_record_playback_roundtrip() {
+        let path = std::env::temp_dir()
+            .join(format!("livekit-mcap-roundtrip-{}.mcap", std::process::id()));
+        let publisher = ParticipantIdentity::from("robot".to_string());
+        let schema = DataTrackSchemaId::new("example.Pose", DataTrackSchemaEncoding::Protobuf);
+        let definition = "syntax = \"proto3\";".to_string();
+
+        let mut recorder = McapRecorder::create(&path).unwrap();
+        let schema_id = recorder.add_schema(&publisher, &schema, definition.clone()).await.unwrap();
+        let channel_id = recorder
+            .add_channel(
+                schema_id,
+                &publisher,
+                "pose",
+                "DTR_1",
+                Some(&DataTrackFrameEncoding::Protobuf),
+            )
+            .await
+            .unwrap();
+        let first = DataTrackFrame::new(vec![1, 2, 3]).with_user_timestamp(1_700_000_000_000);
+        let second = DataTrackFrame::new(vec![4, 5]);
+        recorder.sink.write(channel_id, 0, &first, 1_000_000_000);
+        recorder.sink.write(channel_id, 1, &second, 1_250_000_000);
+        recorder.finish().await.unwrap();
+
+        let player = McapPlayer::open(&path).unwrap();
+        let tracks = player.tracks();
+        assert_eq!(tracks.len(), 1);
+        let track = &tracks[0];
+        assert_eq!(track.name, "pose");
+        assert_eq!(track.frame_encoding, Some(DataTrackFrameEncoding::Protobuf));
+        assert_eq!(track.schema, Some((schema, definition)));
+
+        let messages = read_messages(&player).await;
+        _ = std::fs::remove_file(&path);
+        let [first_message, second_message] = &messages[..] else { panic!("expected 2 messages") };
+        assert_eq!(first_message.channel_id, channel_id);
+        assert_eq!(first_message.log_time, 1_000_000_000);
+        assert_eq!(first_message.frame.payload(), first.payload());
+        assert_eq!(first_message.frame.user_timestamp(), first.user_timestamp());
+        assert_eq!(second_message.log_time, 1_250_000_000);
+        assert_eq!(second_message.frame.payload(), second.payload());
+        assert_eq!(second_message.frame.user_timestamp(), None);
+    }
+
+    async fn read_messages(player: &McapPlayer) -> Vec<PlaybackMessage> {
+        let mut messages_rx = player.messages().unwrap();
+        let mut messages = Vec::new();
+        while let Some(message) = messages_rx.recv().await {
+            messages.push(message.unwrap());
+        }
+        messages
+    }
+
+    /// Records messages with the given channels and log times, returning the file contents.
+    async fn record(
+        channels: &[(&str, &str)],
+        messages: &[(usize, u64, DataTrackFrame)],
+    ) -> Vec<u8> {
+        let path = std::env::temp_dir().join(format!(
+            "livekit-mcap-{}-{}.mcap",
+            std::process::id(),
+            RECORDING.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+        ));
+        let recorder = McapRecorder::create(&path).unwrap();
+        let mut channel_ids = Vec::new();
+        for (index, (publisher, track_name)) in channels.iter().enumerate() {
+            let publisher = ParticipantIdentity::from(publisher.to_string());
+            let sid = format!("DTR_{}", index);
+            channel_ids
+                .push(recorder.add_channel(0, &publisher, track_name, &sid, None).await.unwrap());
+        }
+        for (sequence, (channel, log_time, frame)) in messages.iter().enumerate() {
+            recorder.sink.write(channel_ids[*channel], sequence as u32, frame, *log_time);
+        }
+        recorder.finish().await.unwrap();
+        let data = std::fs::read(&path).unwrap();
+        _ = std::fs::remove_file(&path);
+        data
+    }
+
+    static RECORDING: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+    #[tokio::test]
+    async fn test_user_timestamp_recorded_in_milliseconds() {
+        let frame = DataTrackFrame::new(vec![1]).with_user_timestamp(1_700_000_000_123);
+        let data = record(&[("robot", "pose")], &[(0, 5, frame)]).await;
+
+        let message = ::mcap::MessageStream::new(&data).unwrap().next().unwrap().unwrap();
+        assert_eq!(message.publish_time, 1_700_000_000_123_000_000);
+        assert_eq!(message.log_time, 5);
+    }
+
+    #[tokio::test]
+    async fn test_playback_in_log_time_order() {
+        let frame = |byte: u8| DataTrackFrame::new(vec![byte]);
+        let data = record(
+            &[("robot", "pose"), ("robot", "scan")],
+            &[(0, 300, frame(3)), (1, 100, frame(1)), (0, 200, frame(2))],
+        )
+        .await;
+
+        let player = McapPlayer::from_bytes(data).unwrap();
+        let messages = read_messages(&player).await;
+        let payloads: Vec<_> = messages.iter().map(|message| message.frame.payload()).collect();
+        assert_eq!(payloads, [&[1u8][..], &[2], &[3]]);
+    }
+
+    #[tokio::test]
+    async fn test_shared_track_names_use_topics() {
+        let data = record(
+            &[("robot-1", "pose"), ("robot-2", "pose"), ("robot-1", "scan")],
+            &[
+                (0, 1, DataTrackFrame::new(vec![1])),
+                (1, 2, DataTrackFrame::new(vec![2])),
+                (2, 3, DataTrackFrame::new(vec![3])),
+            ],
+        )
+        .await;
+
+        let player = McapPlayer::from_bytes(data).unwrap();
+        let names: Vec<_> = player.tracks().into_iter().map(|track| track.name).collect();
+        assert_eq!(names, ["robot-1/pose", "robot-2/pose", "scan"]);
+    }
+
+    #[test]
+    fn test_unindexed_file_rejected() {
+        let mut writer = ::mcap::WriteOptions::new()
+            .emit_summary_records(false)
+            .emit_summary_offsets(false)
+            .create(Cursor::new(Vec::new()))
+            .unwrap();
+        writer.finish().unwrap();
+        let data = writer.into_inner().into_inner();
+        assert!(matches!(McapPlayer::from_bytes(data), Err(McapError::NotIndexed)));
+    }
+
+    #[test]
+    fn test_unspecified_frame_encoding() {
+        assert_eq!(
+            frame_encoding_from_name(frame_encoding_name(&DataTrackFrameEncoding::Other)),
+            None
+        );
+    }
+}
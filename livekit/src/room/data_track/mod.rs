@@ -14,3 +14,7 @@
 
 // Re-export everything in the "api" module publicly.
 pub use livekit_datatrack::api::*;
+
+/// Recording and playback of data tracks in the MCAP file format.
+#[cfg(feature = "data-track-mcap")]
+pub mod mcap;
@@ -152,6 +152,7 @@ impl E2eeManager {
         if let Some(handler) = packet_trailer_handler.as_ref() {
             frame_cryptor.set_packet_trailer_handler(handler);
         }
+        track.set_frame_cryptor(frame_cryptor.clone());
         self.setup_cryptor(&frame_cryptor);
 
         let mut inner = self.inner.lock();
@@ -195,6 +196,7 @@ impl E2eeManager {
         if let Some(handler) = packet_trailer_handler.as_ref() {
             frame_cryptor.set_packet_trailer_handler(handler);
         }
+        track.set_frame_cryptor(frame_cryptor.clone());
         self.setup_cryptor(&frame_cryptor);
 
         let mut inner = self.inner.lock();
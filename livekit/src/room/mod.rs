@@ -19,13 +19,14 @@ use libwebrtc::{
     native::frame_cryptor::EncryptionState,
     prelude::{
         ContinualGatheringPolicy, IceTransportsType, MediaStream, MediaStreamTrack,
-        RtcConfiguration,
+        RelayTransportPolicy, RtcConfiguration, TcpCandidatePolicy,
     },
     rtp_transceiver::RtpTransceiver,
     RtcError,
 };
 use livekit_api::signal_client::{
-    SignalOptions, SignalSdkOptions, CLIENT_PROTOCOL_DEFAULT, SIGNAL_CONNECT_TIMEOUT,
+    RegionUrlProvider, SignalOptions, SignalRecorder, SignalSdkOptions, SignalWsClient,
+    CLIENT_PROTOCOL_DEFAULT, SIGNAL_CONNECT_TIMEOUT,
 };
 use livekit_data_stream::backend as ds;
 use livekit_datatrack::{
@@ -51,9 +52,14 @@ use tokio::sync::{
 };
 
 pub use self::{
+    candidate_pair::{
+        CandidateInfo, PeerConnectionTarget, SelectedCandidatePair, SelectedCandidatePairs,
+    },
     data_stream::api::*,
     e2ee::{manager::E2eeManager, E2eeOptions},
     participant::{ClientCapability, ParticipantKind, ParticipantKindDetail, ParticipantState},
+    stats_monitor::{StatsMonitor, StatsMonitorOptions},
+    token_source::{ConnectionDetails, TokenSource, TokenSourceError},
 };
 pub use crate::rtc_engine::SimulateScenario;
 use crate::{
@@ -67,8 +73,12 @@ use crate::{
     },
     utils::{observer::Dispatcher, promise::Promise},
 };
+pub use livekit_api::{
+    signal_client::RegionLatency, ClientIdentity, ProxyOptions, TransportOptions,
+};
 
 pub use livekit_data_stream as data_stream;
+pub mod candidate_pair;
 pub mod data_track;
 pub mod e2ee;
 pub mod id;
@@ -76,6 +86,8 @@ pub mod options;
 pub mod participant;
 pub mod publication;
 pub mod rpc;
+pub mod stats_monitor;
+pub mod token_source;
 pub mod track;
 
 pub const SDK_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -96,6 +108,8 @@ pub enum RoomError {
     AlreadyClosed,
     #[error("request error: {reason:?} - {message}")]
     Request { reason: proto::request_response::Reason, message: String },
+    #[error("token source: {0}")]
+    TokenSource(#[from] TokenSourceError),
 }
 
 #[derive(Clone, Debug)]
@@ -277,6 +291,11 @@ pub enum RoomEvent {
     TokenRefreshed {
         token: String,
     },
+    /// ICE switched a peer connection to another network path.
+    SelectedCandidatePairChanged {
+        target: PeerConnectionTarget,
+        pair: SelectedCandidatePair,
+    },
     /// A remote participant published a data track.
     DataTrackPublished(RemoteDataTrack),
     /// A remote participant has unpublished a data track.
@@ -451,6 +470,14 @@ pub struct RoomOptions {
     /// Timeout for each individual signal connection attempt
     pub connect_timeout: Duration,
     pub data_stream: RoomDataStreamOptions,
+    /// Records signal and data channel traffic of this room, see [`crate::recording`].
+    pub recorder: Option<SignalRecorder>,
+    /// Proxy and TLS settings for signalling. Media still flows over WebRTC, see
+    /// `rtc_config` for TURN servers.
+    pub transport: TransportOptions,
+    /// WebSocket client for this room's signal connections instead of the process-wide
+    /// one, e.g. a [`ReplayWsClient`](crate::recording::ReplayWsClient).
+    pub signal_ws_client: Option<SignalWsClient>,
 }
 
 impl Default for RoomOptions {
@@ -468,12 +495,19 @@ impl Default for RoomOptions {
                                       * JoinResponse */
                 continual_gathering_policy: ContinualGatheringPolicy::GatherContinually,
                 ice_transport_type: IceTransportsType::All,
+                port_range: None,
+                excluded_network_types: vec![],
+                tcp_candidate_policy: TcpCandidatePolicy::Enabled,
+                relay_transport_policy: RelayTransportPolicy::All,
             },
             join_retries: 3,
             sdk_options: RoomSdkOptions::default(),
             single_peer_connection: true,
             connect_timeout: SIGNAL_CONNECT_TIMEOUT,
             data_stream: Default::default(),
+            recorder: None,
+            transport: TransportOptions::default(),
+            signal_ws_client: None,
         }
     }
 }
@@ -564,9 +598,56 @@ impl Debug for RoomSession {
 
 impl Room {
     pub async fn connect(
+        url: &str,
+        token: &str,
+        options: RoomOptions,
+    ) -> RoomResult<(Self, mpsc::UnboundedReceiver<RoomEvent>)> {
+        Self::connect_inner(url, token, options, None).await
+    }
+
+    /// Prepares a later [`Room::connect`] to `url`, ahead of the user choosing to join.
+    ///
+    /// Measures the round trip to each region of a LiveKit Cloud project and caches
+    /// them ranked by it, so the connection goes straight to the fastest region.
+    /// Self-hosted servers have no regions; only `url` is probed. Returns the
+    /// measured regions, fastest first.
+    ///
+    /// Pass the options the room will connect with, so probes use the same transport.
+    ///
+    pub async fn prepare_connection(
+        url: &str,
+        token: &str,
+        options: &RoomOptions,
+    ) -> RoomResult<Vec<RegionLatency>> {
+        RegionUrlProvider::rank_region_urls(url, token, &options.transport)
+            .await
+            .map_err(|err| RoomError::Engine(err.into()))
+    }
+
+    /// Connects with the details returned by `token_source`.
+    ///
+    /// The source is consulted again before each full reconnect when the current token
+    /// is about to expire, e.g. after a long disconnect.
+    ///
+    pub async fn connect_with_token_source(
+        token_source: Arc<dyn TokenSource>,
+        options: RoomOptions,
+    ) -> RoomResult<(Self, mpsc::UnboundedReceiver<RoomEvent>)> {
+        let details = token_source.fetch().await?;
+        Self::connect_inner(
+            &details.server_url,
+            &details.participant_token,
+            options,
+            Some(token_source),
+        )
+        .await
+    }
+
+    async fn connect_inner(
         url: &str,
         token: &str,
         mut options: RoomOptions,
+        token_source: Option<Arc<dyn TokenSource>>,
     ) -> RoomResult<(Self, mpsc::UnboundedReceiver<RoomEvent>)> {
         // TODO(theomonnom): move connection logic to the RoomSession
 
@@ -582,6 +663,9 @@ impl Room {
         signal_options.connect_timeout = options.connect_timeout;
         signal_options.use_legacy_data_streams =
             options.data_stream.use_legacy_client_implementation;
+        signal_options.recorder = options.recorder.clone();
+        signal_options.transport = options.transport.clone();
+        signal_options.ws_client = options.signal_ws_client.clone();
         let (rtc_engine, join_response, engine_events) = RtcEngine::connect(
             url,
             token,
@@ -590,6 +674,7 @@ impl Room {
                 signal_options,
                 join_retries: options.join_retries,
                 single_peer_connection: options.single_peer_connection,
+                token_source,
             },
             Some(e2ee_manager.clone()),
         )
@@ -907,6 +992,23 @@ impl Room {
         self.inner.rtc_engine.get_stats().await
     }
 
+    /// The candidate pairs ICE currently uses, i.e. whether the media flows directly or through
+    /// a TURN server, and over which protocol.
+    pub async fn selected_candidate_pairs(&self) -> EngineResult<SelectedCandidatePairs> {
+        let stats = self.inner.rtc_engine.get_stats().await?;
+        Ok(SelectedCandidatePairs {
+            publisher: SelectedCandidatePair::from_stats(&stats.publisher_stats),
+            subscriber: SelectedCandidatePair::from_stats(&stats.subscriber_stats),
+        })
+    }
+
+    /// Polls [`Room::get_stats`] at a fixed interval and streams per-track summaries.
+    ///
+    /// Rates are averaged over the interval, so the first report is emitted after two polls.
+    pub fn stats_monitor(&self, options: StatsMonitorOptions) -> StatsMonitor {
+        StatsMonitor::new(Arc::downgrade(&self.inner), options)
+    }
+
     pub fn subscribe(&self) -> mpsc::UnboundedReceiver<RoomEvent> {
         self.inner.dispatcher.register()
     }
@@ -1045,6 +1147,14 @@ impl RoomSession {
             }
             EngineEvent::RoomUpdate { room } => self.handle_room_update(room),
             EngineEvent::RoomMoved { moved } => self.handle_room_moved(moved),
+            EngineEvent::SelectedCandidatePairChanged { target, stats } => {
+                if let Some(pair) = SelectedCandidatePair::from_stats(&stats) {
+                    self.dispatcher.dispatch(&RoomEvent::SelectedCandidatePairChanged {
+                        target: target.into(),
+                        pair,
+                    });
+                }
+            }
             EngineEvent::Resuming(tx) => self.handle_resuming(tx),
             EngineEvent::Resumed(tx) => self.handle_resumed(tx),
             EngineEvent::SignalResumed { reconnect_response, tx } => {
@@ -2040,6 +2150,19 @@ impl RoomSession {
             }
         };
 
+        let backup_codec = crate::options::backup_codec_options(&publication.publish_options())
+            .map(|o| o.video_codec);
+        if let Some(backup_codec) = backup_codec {
+            let backup_qualities = update
+                .subscribed_codecs
+                .iter()
+                .find(|sc| sc.codec.eq_ignore_ascii_case(backup_codec.as_str()))
+                .map(|sc| sc.qualities.clone());
+            if let Some(backup_qualities) = backup_qualities {
+                self.handle_backup_codec_qualities(&publication, &video_track, backup_qualities);
+            }
+        }
+
         let qualities: Vec<proto::SubscribedQuality> = if !update.subscribed_codecs.is_empty() {
             // This is the requested codec, which we also advertise in simulcast_codecs and use
             // for sender codec preferences, so it should match the SFU's subscribed codec key.
@@ -2065,6 +2188,10 @@ impl RoomSession {
                 .find(|sc| sc.codec.to_lowercase() == codec)
                 .map(|sc| sc.qualities.clone())
                 .unwrap_or_else(|| {
+                    if backup_codec.is_some() {
+                        // The update only concerns the backup codec
+                        return Vec::new();
+                    }
                     log::warn!("dynacast: codec '{}' not found in subscribed_codecs, falling back to first", codec);
                     update
                         .subscribed_codecs
@@ -2097,6 +2224,43 @@ impl RoomSession {
         }
     }
 
+    /// Applies the qualities requested for the backup codec of a local track, publishing it on
+    /// first use. Requests arriving while it is being published are applied once it is.
+    fn handle_backup_codec_qualities(
+        &self,
+        publication: &LocalTrackPublication,
+        video_track: &LocalVideoTrack,
+        qualities: Vec<proto::SubscribedQuality>,
+    ) {
+        let backup = match video_track.request_backup_qualities(&qualities) {
+            Ok(Some(backup)) => backup,
+            Ok(None) => return,
+            Err(e) => {
+                log::error!(
+                    "dynacast: failed to set backup codec layers for {}: {}",
+                    publication.sid(),
+                    e
+                );
+                return;
+            }
+        };
+
+        let local_participant = self.local_participant.clone();
+        let publication = publication.clone();
+        let video_track = video_track.clone();
+        livekit_runtime::spawn(async move {
+            if let Err(e) =
+                local_participant.publish_backup_codec(&publication, &video_track, backup).await
+            {
+                log::error!(
+                    "dynacast: failed to publish backup codec for {}: {}",
+                    publication.sid(),
+                    e
+                );
+            }
+        });
+    }
+
     /// Create a new participant
     /// Also add it to the participants list
     fn create_participant(
@@ -2126,6 +2290,7 @@ impl RoomSession {
             attributes,
             joined_at,
             self.options.auto_subscribe,
+            self.options.adaptive_stream,
             permission,
             client_protocol,
             capabilities,
@@ -107,6 +107,58 @@ impl FrameMetadataFeatures {
     }
 }
 
+/// Opus packet duration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OpusPacketTime {
+    Ms10,
+    #[default]
+    Ms20,
+    Ms40,
+    Ms60,
+}
+
+impl OpusPacketTime {
+    pub fn as_millis(&self) -> u32 {
+        match self {
+            OpusPacketTime::Ms10 => 10,
+            OpusPacketTime::Ms20 => 20,
+            OpusPacketTime::Ms40 => 40,
+            OpusPacketTime::Ms60 => 60,
+        }
+    }
+}
+
+/// Opus encoder configuration of a published audio track.
+///
+/// Applied by rewriting the fmtp line of the track in the publisher SDP, so it takes effect
+/// regardless of what the SFU answers.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpusOptions {
+    /// Encode two channels. The source must provide stereo audio.
+    pub stereo: bool,
+    /// In-band forward error correction.
+    pub fec: bool,
+    pub packet_time: OpusPacketTime,
+    /// Encoder complexity hint, from 0 (fastest) to 10 (best quality). libwebrtc picks a
+    /// platform default when unset.
+    pub complexity: Option<u8>,
+    /// Constant bitrate instead of variable bitrate.
+    pub cbr: bool,
+}
+
+impl Default for OpusOptions {
+    fn default() -> Self {
+        Self {
+            stereo: false,
+            fec: true,
+            packet_time: OpusPacketTime::default(),
+            complexity: None,
+            cbr: false,
+        }
+    }
+}
+
 impl AudioPreset {
     pub const fn new(max_bitrate: u64) -> Self {
         Self { encoding: AudioEncoding { max_bitrate } }
@@ -119,8 +171,15 @@ pub struct TrackPublishOptions {
     pub video_encoding: Option<VideoEncoding>,
     pub audio_encoding: Option<AudioEncoding>,
     pub video_codec: VideoCodec,
+    /// Codec also published for subscribers that can't decode `video_codec`, e.g. VP8 next to
+    /// AV1 or H265. The second encoder is only started once the SFU requests it. Ignored when
+    /// equal to `video_codec` or when end-to-end encryption is enabled.
+    pub backup_codec: Option<VideoCodec>,
     pub dtx: bool,
     pub red: bool,
+    /// Opus encoder configuration of audio tracks. When unset, libwebrtc defaults are used
+    /// (mono, in-band FEC, 20ms packets).
+    pub opus: Option<OpusOptions>,
     pub simulcast: bool,
     /// Custom simulcast layer presets (low, mid). When set, these override the
     /// SDK's built-in defaults which reduce fps on lower layers.
@@ -159,8 +218,10 @@ impl Default for TrackPublishOptions {
             video_encoding: None,
             audio_encoding: None,
             video_codec: VideoCodec::VP8,
+            backup_codec: None,
             dtx: true,
             red: true,
+            opus: None,
             simulcast: true,
             simulcast_layers: None,
             source: TrackSource::Unknown,
@@ -174,6 +235,99 @@ impl Default for TrackPublishOptions {
     }
 }
 
+/// Changes applied to a published track by
+/// [`LocalTrackPublication::update_encodings`](crate::publication::LocalTrackPublication::update_encodings).
+///
+/// Anything left unset keeps its current value.
+///
+#[derive(Debug, Clone, Default)]
+pub struct EncodingUpdate {
+    /// Per-layer changes of a video track, ignored for audio tracks.
+    pub layers: Vec<LayerEncodingUpdate>,
+    /// Degradation preference of a video track, ignored for audio tracks.
+    pub degradation_preference: Option<DegradationPreference>,
+    /// Max bitrate of an audio track, ignored for video tracks.
+    pub audio_max_bitrate: Option<u64>,
+}
+
+/// Changes to a single layer of a published video track.
+///
+/// Layers are matched by quality, as reported by
+/// [`LocalVideoTrack::publishing_layers`]. A track published without simulcast (or with
+/// SVC) has a single `High` layer.
+///
+#[derive(Debug, Clone)]
+pub struct LayerEncodingUpdate {
+    pub quality: PublishingLayerQuality,
+    pub max_bitrate: Option<u64>,
+    pub max_framerate: Option<f64>,
+    /// A disabled layer is no longer advertised to the SFU and stays off until enabled
+    /// again, even when subscribers request it through dynacast.
+    pub active: Option<bool>,
+}
+
+impl LayerEncodingUpdate {
+    pub fn new(quality: PublishingLayerQuality) -> Self {
+        Self { quality, max_bitrate: None, max_framerate: None, active: None }
+    }
+}
+
+/// Applies `layers` to the sender `encodings`, returning whether any of them changed.
+/// `disabled_rids` tracks the layers turned off by the user.
+pub(crate) fn apply_layer_updates(
+    encodings: &mut [RtpEncodingParameters],
+    layers: &[LayerEncodingUpdate],
+    disabled_rids: &mut Vec<String>,
+) -> RoomResult<bool> {
+    let mut changed = false;
+    for layer in layers {
+        let quality = proto::VideoQuality::from(layer.quality);
+        let encoding = encodings
+            .iter_mut()
+            .find(|e| video_quality_for_rid_or_default(&e.rid) == quality)
+            .ok_or_else(|| {
+                RoomError::Internal(format!(
+                    "no {} layer is published for this track",
+                    layer.quality
+                ))
+            })?;
+
+        if let Some(max_bitrate) = layer.max_bitrate {
+            changed |= encoding.max_bitrate != Some(max_bitrate);
+            encoding.max_bitrate = Some(max_bitrate);
+        }
+        if let Some(max_framerate) = layer.max_framerate {
+            changed |= encoding.max_framerate != Some(max_framerate);
+            encoding.max_framerate = Some(max_framerate);
+        }
+        if let Some(active) = layer.active {
+            changed |= encoding.active != active;
+            encoding.active = active;
+            disabled_rids.retain(|rid| *rid != encoding.rid);
+            if !active {
+                disabled_rids.push(encoding.rid.clone());
+            }
+        }
+    }
+    Ok(changed)
+}
+
+/// Options used to publish the backup codec of a track, if it has one.
+///
+/// The encodings are computed for the backup codec, so an explicit `video_encoding` and the
+/// SVC `scalability_mode` of the primary codec are not carried over.
+///
+pub(crate) fn backup_codec_options(options: &TrackPublishOptions) -> Option<TrackPublishOptions> {
+    let codec = options.backup_codec.filter(|codec| *codec != options.video_codec)?;
+    Some(TrackPublishOptions {
+        video_codec: codec,
+        backup_codec: None,
+        video_encoding: None,
+        scalability_mode: None,
+        ..options.clone()
+    })
+}
+
 /// Returns the appropriate degradation preference for a video track.
 ///
 /// If the user explicitly set a preference in `TrackPublishOptions`, that is returned.
@@ -555,11 +709,92 @@ pub mod screenshare {
 
 #[cfg(test)]
 mod tests {
+    use libwebrtc::prelude::RtpEncodingParameters;
+
     use super::{
-        get_default_degradation_preference, DegradationPreference, TrackPublishOptions,
-        VideoEncoderBackend,
+        apply_layer_updates, backup_codec_options, get_default_degradation_preference,
+        DegradationPreference, LayerEncodingUpdate, TrackPublishOptions, VideoCodec,
+        VideoEncoderBackend, VideoEncoding,
     };
-    use crate::prelude::TrackSource;
+    use crate::prelude::{PublishingLayerQuality, TrackSource};
+
+    fn encoding(rid: &str, max_bitrate: u64) -> RtpEncodingParameters {
+        RtpEncodingParameters {
+            active: true,
+            rid: rid.to_owned(),
+            max_bitrate: Some(max_bitrate),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn layer_updates_are_matched_by_quality() {
+        let mut encodings = vec![encoding("q", 150_000), encoding("f", 1_700_000)];
+        let mut disabled = Vec::new();
+
+        let low = LayerEncodingUpdate {
+            active: Some(false),
+            ..LayerEncodingUpdate::new(PublishingLayerQuality::Low)
+        };
+        let high = LayerEncodingUpdate {
+            max_bitrate: Some(1_000_000),
+            max_framerate: Some(15.0),
+            ..LayerEncodingUpdate::new(PublishingLayerQuality::High)
+        };
+        assert!(apply_layer_updates(&mut encodings, &[low.clone(), high.clone()], &mut disabled)
+            .unwrap());
+        assert!(!encodings[0].active);
+        assert_eq!(disabled, ["q"]);
+        assert_eq!(encodings[0].max_bitrate, Some(150_000));
+        assert_eq!(encodings[1].max_bitrate, Some(1_000_000));
+        assert_eq!(encodings[1].max_framerate, Some(15.0));
+
+        // Applying the same update again changes nothing
+        assert!(!apply_layer_updates(&mut encodings, &[low, high], &mut disabled).unwrap());
+        assert_eq!(disabled, ["q"]);
+
+        let low = LayerEncodingUpdate {
+            active: Some(true),
+            ..LayerEncodingUpdate::new(PublishingLayerQuality::Low)
+        };
+        assert!(apply_layer_updates(&mut encodings, &[low], &mut disabled).unwrap());
+        assert!(encodings[0].active && disabled.is_empty());
+
+        let medium = LayerEncodingUpdate::new(PublishingLayerQuality::Medium);
+        assert!(apply_layer_updates(&mut encodings, &[medium], &mut disabled).is_err());
+
+        // A single rid-less encoding is the high layer
+        let mut encodings = vec![encoding("", 1_700_000)];
+        let high = LayerEncodingUpdate {
+            max_bitrate: Some(800_000),
+            ..LayerEncodingUpdate::new(PublishingLayerQuality::High)
+        };
+        assert!(apply_layer_updates(&mut encodings, &[high], &mut disabled).unwrap());
+        assert_eq!(encodings[0].max_bitrate, Some(800_000));
+    }
+
+    #[test]
+    fn backup_codec_options_use_backup_codec_defaults() {
+        assert!(backup_codec_options(&TrackPublishOptions::default()).is_none());
+
+        let same_codec =
+            TrackPublishOptions { backup_codec: Some(VideoCodec::VP8), ..Default::default() };
+        assert!(backup_codec_options(&same_codec).is_none());
+
+        let options = TrackPublishOptions {
+            video_codec: VideoCodec::AV1,
+            backup_codec: Some(VideoCodec::VP8),
+            video_encoding: Some(VideoEncoding { max_bitrate: 1_000_000, max_framerate: 30.0 }),
+            scalability_mode: Some("L3T3_KEY".to_owned()),
+            source: TrackSource::Camera,
+            ..Default::default()
+        };
+        let backup = backup_codec_options(&options).unwrap();
+        assert_eq!(backup.video_codec, VideoCodec::VP8);
+        assert_eq!(backup.backup_codec, None);
+        assert!(backup.video_encoding.is_none() && backup.scalability_mode.is_none());
+        assert_eq!(backup.source, TrackSource::Camera);
+    }
 
     #[test]
     fn track_publish_options_default_encoder_is_auto() {
@@ -33,7 +33,9 @@ use crate::{
     },
     data_track::{self, DataTrack, DataTrackOptions, DataTrackSchemaId, Local},
     e2ee::EncryptionType,
-    options::{self, compute_video_encodings, video_layers_from_encodings, TrackPublishOptions},
+    options::{
+        self, compute_video_encodings, video_layers_from_encodings, TrackPublishOptions, VideoCodec,
+    },
     prelude::*,
     room::rpc::{RpcError, RpcErrorCode, RpcInvocationData},
     rtc_engine::lk_runtime::LkRuntime,
@@ -65,6 +67,25 @@ fn needs_video_sender_transformer(
     !options.frame_metadata_features.is_empty() || has_publish_timing_subscribers
 }
 
+/// Audio features advertised to the server for an audio track published with `options`.
+fn audio_track_features(options: &TrackPublishOptions) -> Vec<proto::AudioTrackFeature> {
+    let mut features = Vec::new();
+
+    if options.opus.as_ref().is_some_and(|opus| opus.stereo) {
+        features.push(proto::AudioTrackFeature::TfStereo);
+    }
+
+    if !options.dtx {
+        features.push(proto::AudioTrackFeature::TfNoDtx);
+    }
+
+    if options.preconnect_buffer {
+        features.push(proto::AudioTrackFeature::TfPreconnectBuffer);
+    }
+
+    features
+}
+
 #[derive(Default)]
 struct LocalEvents {
     local_track_published: Mutex<Option<LocalTrackPublishedHandler>>,
@@ -395,10 +416,6 @@ impl LocalParticipant {
             ..Default::default()
         };
 
-        if options.preconnect_buffer {
-            req.audio_features.push(proto::AudioTrackFeature::TfPreconnectBuffer as i32);
-        }
-
         req.packet_trailer_features =
             options.frame_metadata_features.to_proto().into_iter().map(|f| f as i32).collect();
 
@@ -425,7 +442,11 @@ impl LocalParticipant {
                         .and_then(|e| e.scalability_mode.as_ref())
                         .map(|m| options::spatial_layers_from_scalability_mode(m) > 1)
                         .unwrap_or(false);
-                if (options.simulcast && encodings.len() > 1) || is_svc_multilayer {
+                let backup_codec = self.backup_codec(&options);
+                if (options.simulcast && encodings.len() > 1)
+                    || is_svc_multilayer
+                    || backup_codec.is_some()
+                {
                     req.simulcast_codecs = vec![proto::SimulcastCodec {
                         codec: options.video_codec.as_str().to_string(),
                         cid: track.rtc_track().id(),
@@ -433,11 +454,27 @@ impl LocalParticipant {
                         ..Default::default()
                     }];
                 }
+
+                // The backup sender is only created once the SFU requests this codec, see
+                // `publish_backup_codec`.
+                if let Some(backup_codec) = backup_codec {
+                    req.simulcast_codecs.push(proto::SimulcastCodec {
+                        codec: backup_codec.as_str().to_string(),
+                        ..Default::default()
+                    });
+                    req.backup_codec_policy = proto::BackupCodecPolicy::Simulcast as i32;
+                }
             }
             LocalTrack::Audio(_audio_track) => {
+                let stereo = options.opus.as_ref().is_some_and(|opus| opus.stereo);
+                req.audio_features =
+                    audio_track_features(&options).into_iter().map(|f| f as i32).collect();
+
                 // Setup audio encoding
+                let default_preset =
+                    if stereo { &options::audio::MUSIC_STEREO } else { &options::audio::MUSIC };
                 let audio_encoding =
-                    options.audio_encoding.as_ref().unwrap_or(&options::audio::MUSIC.encoding);
+                    options.audio_encoding.as_ref().unwrap_or(&default_preset.encoding);
 
                 encodings.push(RtpEncodingParameters {
                     max_bitrate: Some(audio_encoding.max_bitrate),
@@ -452,6 +489,24 @@ impl LocalParticipant {
         // set track for publication to listen mute/unmute events
         publication.set_track(Some(track.clone().into()));
 
+        // advertise the layers changed by `update_encodings`
+        publication.on_layers_updated(Some(Box::new({
+            let rtc_engine = self.inner.rtc_engine.clone();
+            move |publication, layers| {
+                let rtc_engine = rtc_engine.clone();
+                livekit_runtime::spawn(async move {
+                    rtc_engine
+                        .send_request(proto::signal_request::Message::UpdateLayers(
+                            proto::UpdateVideoLayers {
+                                track_sid: publication.sid().to_string(),
+                                layers,
+                            },
+                        ))
+                        .await;
+                });
+            }
+        })));
+
         let transceiver =
             self.inner.rtc_engine.create_sender(track.clone(), options.clone(), encodings).await?;
 
@@ -502,6 +557,10 @@ impl LocalParticipant {
             }
         }
 
+        if let Err(err) = track.restore_frame_transformer() {
+            log::warn!("failed to restore the frame transformer of {}: {}", publication.sid(), err);
+        }
+
         self.inner.rtc_engine.publisher_negotiation_needed();
 
         publication.update_publish_options(options);
@@ -516,6 +575,87 @@ impl LocalParticipant {
         Ok(publication)
     }
 
+    /// The backup codec to advertise for a track published with `options`.
+    fn backup_codec(&self, options: &TrackPublishOptions) -> Option<VideoCodec> {
+        let backup = options::backup_codec_options(options)?;
+        if self.local.encryption_type != EncryptionType::None {
+            log::warn!("backup codecs are not supported with end-to-end encryption, ignoring");
+            return None;
+        }
+        Some(backup.video_codec)
+    }
+
+    /// Starts sending `track` with the backup codec of its publication, on a second sender
+    /// attached to the same track sid. `backup` comes from
+    /// [`LocalVideoTrack::request_backup_qualities`], whose latest qualities are applied.
+    pub(crate) async fn publish_backup_codec(
+        &self,
+        publication: &LocalTrackPublication,
+        track: &LocalVideoTrack,
+        backup: LocalVideoTrack,
+    ) -> RoomResult<()> {
+        let options = publication.publish_options();
+        // Never advertised to the SFU when encrypted, see `backup_codec`
+        let backup_options = options::backup_codec_options(&options)
+            .filter(|_| self.local.encryption_type == EncryptionType::None);
+        let Some(backup_options) = backup_options else {
+            track.discard_backup_track(&backup);
+            return Ok(());
+        };
+
+        let resolution = track.rtc_source().video_resolution();
+        let encodings =
+            compute_video_encodings(resolution.width, resolution.height, &backup_options);
+        let layers = video_layers_from_encodings(resolution.width, resolution.height, &encodings);
+        let req = proto::AddTrackRequest {
+            cid: backup.rtc_track().id(),
+            name: track.name(),
+            r#type: proto::TrackType::Video as i32,
+            width: resolution.width,
+            height: resolution.height,
+            muted: track.is_muted(),
+            source: proto::TrackSource::from(options.source) as i32,
+            sid: track.sid().to_string(),
+            simulcast_codecs: vec![proto::SimulcastCodec {
+                codec: backup_options.video_codec.as_str().to_string(),
+                cid: backup.rtc_track().id(),
+                layers: layers.clone(),
+                ..Default::default()
+            }],
+            layers,
+            ..Default::default()
+        };
+
+        let result = async {
+            self.inner.rtc_engine.add_track(req).await?;
+            self.inner
+                .rtc_engine
+                .create_sender(LocalTrack::Video(backup.clone()), backup_options, encodings)
+                .await
+        }
+        .await;
+        let transceiver = match result {
+            Ok(transceiver) => transceiver,
+            Err(err) => {
+                // Roll back so that a later quality update tries again
+                track.discard_backup_track(&backup);
+                return Err(err.into());
+            }
+        };
+
+        match track.attach_backup_transceiver(&backup, transceiver.clone()) {
+            Ok(false) => {
+                // The track was unpublished while the backup was being set up
+                self.inner.rtc_engine.remove_track(transceiver.sender())?;
+                Ok(())
+            }
+            attached => {
+                self.inner.rtc_engine.publisher_negotiation_needed();
+                attached.map(|_| ())
+            }
+        }
+    }
+
     pub async fn set_metadata(&self, metadata: String) -> RoomResult<()> {
         if let Ok(response) = timeout(REQUEST_TIMEOUT, {
             let request_id = self.inner.rtc_engine.session().signal_client().next_request_id();
@@ -671,12 +811,22 @@ impl LocalParticipant {
             self.inner.rtc_engine.remove_track(sender)?;
             track.set_transceiver(None);
 
+            if let LocalTrack::Video(video_track) = &track {
+                if let Some(backup) = video_track.take_backup_track() {
+                    if let Some(transceiver) = backup.transceiver() {
+                        self.inner.rtc_engine.remove_track(transceiver.sender())?;
+                    }
+                    backup.set_transceiver(None);
+                }
+            }
+
             if let Some(local_track_unpublished) =
                 self.local.events.local_track_unpublished.lock().as_ref()
             {
                 local_track_unpublished(self.clone(), publication.clone());
             }
 
+            publication.on_layers_updated(None);
             publication.set_track(None);
             self.inner.rtc_engine.publisher_negotiation_needed();
 
@@ -1174,7 +1324,7 @@ impl LocalParticipant {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::options::FrameMetadataFeatures;
+    use crate::options::{FrameMetadataFeatures, OpusOptions};
 
     #[test]
     fn timing_subscribers_request_video_sender_transformer_without_frame_metadata() {
@@ -1209,4 +1359,19 @@ mod tests {
 
         assert!(!needs_video_sender_transformer(&options, false));
     }
+
+    #[test]
+    fn audio_track_features_reflect_opus_options_and_dtx() {
+        let options = TrackPublishOptions {
+            dtx: false,
+            opus: Some(OpusOptions { stereo: true, ..Default::default() }),
+            ..Default::default()
+        };
+        assert_eq!(
+            audio_track_features(&options),
+            vec![proto::AudioTrackFeature::TfStereo, proto::AudioTrackFeature::TfNoDtx]
+        );
+
+        assert!(audio_track_features(&TrackPublishOptions::default()).is_empty());
+    }
 }
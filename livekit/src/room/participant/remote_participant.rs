@@ -52,6 +52,7 @@ struct RemoteEvents {
 struct RemoteInfo {
     events: Arc<RemoteEvents>,
     auto_subscribe: bool, // better way to access this from room?
+    adaptive_stream: bool,
 }
 
 #[derive(Clone)]
@@ -84,6 +85,7 @@ impl RemoteParticipant {
         attributes: HashMap<String, String>,
         joined_at: i64,
         auto_subscribe: bool,
+        adaptive_stream: bool,
         permission: Option<proto::ParticipantPermission>,
         client_protocol: i32,
         capabilities: Vec<ClientCapability>,
@@ -104,7 +106,11 @@ impl RemoteParticipant {
                 client_protocol,
                 capabilities,
             ),
-            remote: Arc::new(RemoteInfo { events: Default::default(), auto_subscribe }),
+            remote: Arc::new(RemoteInfo {
+                events: Default::default(),
+                auto_subscribe,
+                adaptive_stream,
+            }),
         }
     }
 
@@ -384,7 +390,22 @@ impl RemoteParticipant {
         publication.on_subscribed({
             let events = self.remote.events.clone();
             let participant = self.clone();
+            let adaptive_stream = self.remote.adaptive_stream;
             move |publication, track| {
+                if let (true, RemoteTrack::Video(video_track)) = (adaptive_stream, &track) {
+                    video_track.on_adaptive_stream_changed({
+                        let publication = publication.clone();
+                        move |settings| {
+                            if settings.enabled {
+                                publication.set_enabled(true);
+                                publication.update_video_dimensions(settings.dimension);
+                            } else {
+                                publication.set_enabled(false);
+                            }
+                        }
+                    });
+                }
+
                 if let Some(track_subscribed) = events.track_subscribed.lock().as_ref() {
                     track_subscribed(participant.clone(), publication, track);
                 }
@@ -395,6 +416,10 @@ impl RemoteParticipant {
             let events = self.remote.events.clone();
             let participant = self.clone();
             move |publication, track| {
+                if let RemoteTrack::Video(video_track) = &track {
+                    video_track.clear_adaptive_stream_handler();
+                }
+
                 if let Some(track_unsubscribed) = events.track_unsubscribed.lock().as_ref() {
                     track_unsubscribed(participant.clone(), publication, track);
                 }
@@ -413,6 +438,7 @@ impl RemoteParticipant {
                         disabled: !enabled,
                         width,
                         height,
+                        fps: publication.video_fps().unwrap_or_default(),
                         ..Default::default()
                     };
 
@@ -438,6 +464,7 @@ impl RemoteParticipant {
                         disabled: !enabled,
                         width,
                         height,
+                        fps: publication.video_fps().unwrap_or_default(),
                         ..Default::default()
                     };
 
@@ -460,6 +487,7 @@ impl RemoteParticipant {
                     let update_track_settings = proto::UpdateTrackSettings {
                         track_sids: vec![tsid.clone()],
                         quality,
+                        fps: publication.video_fps().unwrap_or_default(),
                         ..Default::default()
                     };
 
@@ -476,6 +504,28 @@ impl RemoteParticipant {
                 });
             }
         });
+
+        publication.on_video_fps_changed({
+            let rtc_engine = self.inner.rtc_engine.clone();
+            move |publication, fps| {
+                let rtc_engine = rtc_engine.clone();
+                livekit_runtime::spawn(async move {
+                    let tsid: String = publication.sid().into();
+                    let update_track_settings = publication.video_track_settings();
+
+                    log::debug!(
+                        "subscriber: sending UpdateTrackSettings to SFU: track={}, fps={}",
+                        tsid,
+                        fps,
+                    );
+                    rtc_engine
+                        .send_request(proto::signal_request::Message::TrackSetting(
+                            update_track_settings,
+                        ))
+                        .await
+                });
+            }
+        });
     }
 
     pub(crate) fn remove_publication(&self, sid: &TrackSid) -> Option<TrackPublication> {
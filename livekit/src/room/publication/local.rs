@@ -14,15 +14,24 @@
 
 use std::{fmt::Debug, sync::Arc};
 
+use libwebrtc::native::frame_transformer::FrameTransformer;
 use livekit_protocol::{self as proto, AudioTrackFeature, PacketTrailerFeature};
 use parking_lot::Mutex;
 
 use super::TrackPublicationInner;
-use crate::{e2ee::EncryptionType, options::TrackPublishOptions, prelude::*};
+use crate::{
+    e2ee::EncryptionType,
+    options::{self, AudioEncoding, EncodingUpdate, TrackPublishOptions},
+    prelude::*,
+};
+
+pub(crate) type LayersUpdatedHandler =
+    Box<dyn Fn(LocalTrackPublication, Vec<proto::VideoLayer>) + Send>;
 
 #[derive(Default)]
 struct LocalInfo {
     publish_options: Mutex<TrackPublishOptions>,
+    layers_updated: Mutex<Option<LayersUpdatedHandler>>,
 }
 
 #[derive(Clone)]
@@ -57,6 +66,10 @@ impl LocalTrackPublication {
         *self.inner.events.unmuted.lock() = Some(Box::new(f));
     }
 
+    pub(crate) fn on_layers_updated(&self, f: Option<LayersUpdatedHandler>) {
+        *self.local.layers_updated.lock() = f;
+    }
+
     pub(crate) fn set_track(&self, track: Option<Track>) {
         super::set_track(&self.inner, &TrackPublication::Local(self.clone()), track);
     }
@@ -78,6 +91,122 @@ impl LocalTrackPublication {
         self.local.publish_options.lock().clone()
     }
 
+    /// Changes the encodings of the published track without republishing it.
+    ///
+    /// For video tracks, the layers advertised to the SFU are updated to match, and
+    /// [`publish_options`](Self::publish_options) reflects the new degradation preference and
+    /// the encoding of the `High` layer. A backup codec keeps its own encodings.
+    ///
+    pub fn update_encodings(&self, update: EncodingUpdate) -> RoomResult<()> {
+        let track = self
+            .track()
+            .ok_or_else(|| RoomError::Internal("publication has no track".to_owned()))?;
+        let sender = track
+            .transceiver()
+            .map(|transceiver| transceiver.sender())
+            .ok_or_else(|| RoomError::Internal("track is not published".to_owned()))?;
+
+        match &track {
+            LocalTrack::Video(video_track) => {
+                // Serialized with dynacast, which also writes the sender parameters
+                let mut disabled_rids = video_track.lock_disabled_layer_rids();
+                let mut params = sender.parameters();
+                let mut publish_options = self.publish_options();
+                let mut new_disabled_rids = disabled_rids.clone();
+                options::apply_layer_updates(
+                    &mut params.encodings,
+                    &update.layers,
+                    &mut new_disabled_rids,
+                )?;
+                if let Some(preference) = update.degradation_preference {
+                    params.set_degradation_preference(preference);
+                    publish_options.degradation_preference = Some(preference);
+                }
+
+                let high = params.encodings.iter().find(|e| {
+                    options::video_quality_for_rid_or_default(&e.rid) == proto::VideoQuality::High
+                });
+                let high_updated =
+                    update.layers.iter().any(|layer| layer.quality == PublishingLayerQuality::High);
+                if let Some(high) = high.filter(|_| high_updated) {
+                    let resolution = video_track.rtc_source().video_resolution();
+                    let encoding = publish_options.video_encoding.get_or_insert_with(|| {
+                        options::compute_appropriate_encoding(
+                            video_track.source() == TrackSource::Screenshare,
+                            resolution.width,
+                            resolution.height,
+                            publish_options.video_codec,
+                        )
+                    });
+                    if let Some(max_bitrate) = high.max_bitrate {
+                        encoding.max_bitrate = max_bitrate;
+                    }
+                    if let Some(max_framerate) = high.max_framerate {
+                        encoding.max_framerate = max_framerate;
+                    }
+                }
+
+                // Layers disabled by the user are no longer advertised, so the SFU stops
+                // requesting them
+                let advertised: Vec<_> = params
+                    .encodings
+                    .iter()
+                    .filter(|e| !new_disabled_rids.contains(&e.rid))
+                    .cloned()
+                    .collect();
+                let layers = if advertised.is_empty() {
+                    Vec::new()
+                } else {
+                    let resolution = video_track.rtc_source().video_resolution();
+                    options::video_layers_from_encodings(
+                        resolution.width,
+                        resolution.height,
+                        &advertised,
+                    )
+                };
+
+                sender.set_parameters(params)?;
+                *disabled_rids = new_disabled_rids;
+                drop(disabled_rids);
+                self.update_publish_options(publish_options);
+
+                if let Some(layers_updated) = self.local.layers_updated.lock().as_ref() {
+                    layers_updated(self.clone(), layers);
+                }
+            }
+            LocalTrack::Audio(_) => {
+                let Some(max_bitrate) = update.audio_max_bitrate else {
+                    return Ok(());
+                };
+                let mut params = sender.parameters();
+                let mut publish_options = self.publish_options();
+                for encoding in &mut params.encodings {
+                    encoding.max_bitrate = Some(max_bitrate);
+                }
+                sender.set_parameters(params)?;
+                publish_options.audio_encoding = Some(AudioEncoding { max_bitrate });
+                self.update_publish_options(publish_options);
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs `transformer` on every encoded frame of the published track, before it is
+    /// packetized.
+    ///
+    /// With end-to-end encryption the transformer runs before encryption, and it always sees the
+    /// payload without packet trailers. Pass `None` to remove it.
+    ///
+    pub fn set_frame_transformer(
+        &self,
+        transformer: Option<Arc<dyn FrameTransformer>>,
+    ) -> RoomResult<()> {
+        let track = self
+            .track()
+            .ok_or_else(|| RoomError::Internal("publication has no track".to_owned()))?;
+        track.set_frame_transformer(transformer)
+    }
+
     pub fn mute(&self) {
         if let Some(track) = self.track() {
             track.mute();
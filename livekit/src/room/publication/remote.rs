@@ -18,7 +18,12 @@ use livekit_protocol::{self as proto, AudioTrackFeature, PacketTrailerFeature};
 use parking_lot::{Mutex, RwLock};
 
 use super::{PermissionStatus, SubscriptionStatus, TrackPublication, TrackPublicationInner};
-use crate::{e2ee::EncryptionType, prelude::*, track::VideoQuality};
+use crate::{
+    e2ee::EncryptionType,
+    options::{self, VideoCodec},
+    prelude::*,
+    track::VideoQuality,
+};
 
 type SubscribedHandler = Box<dyn Fn(RemoteTrackPublication, RemoteTrack) + Send>;
 type UnsubscribedHandler = Box<dyn Fn(RemoteTrackPublication, RemoteTrack) + Send>;
@@ -30,6 +35,19 @@ type SubscriptionUpdateNeededHandler = Box<dyn Fn(RemoteTrackPublication, bool)
 type EnabledStatusChangedHandler = Box<dyn Fn(RemoteTrackPublication, bool) + Send>;
 type VideoDimensionsChangedHandler = Box<dyn Fn(RemoteTrackPublication, TrackDimension) + Send>;
 type VideoQualityChangedHandler = Box<dyn Fn(RemoteTrackPublication, VideoQuality) + Send>;
+type VideoFpsChangedHandler = Box<dyn Fn(RemoteTrackPublication, u32) + Send>;
+
+/// Temporal layers libwebrtc encodes per simulcast stream, and for SVC with the L3T3 mode.
+const SIMULCAST_TEMPORAL_LAYERS: u32 = 3;
+/// Temporal layers libwebrtc encodes per simulcast stream of a screen share.
+const SCREENSHARE_TEMPORAL_LAYERS: u32 = 2;
+
+/// Layer selection requested by the application, kept so frame rate updates don't reset it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VideoLayerSelection {
+    Quality(VideoQuality),
+    Dimension(TrackDimension),
+}
 
 #[derive(Default)]
 struct RemoteEvents {
@@ -41,12 +59,16 @@ struct RemoteEvents {
     enabled_status_changed: Mutex<Option<EnabledStatusChangedHandler>>,
     video_dimensions_changed: Mutex<Option<VideoDimensionsChangedHandler>>,
     video_quality_changed: Mutex<Option<VideoQualityChangedHandler>>,
+    video_fps_changed: Mutex<Option<VideoFpsChangedHandler>>,
 }
 
 #[derive(Debug)]
 struct RemoteInfo {
     subscribed: bool,
     allowed: bool,
+    /// Max frame rate requested from the SFU, 0 when unlimited.
+    fps: u32,
+    selection: Option<VideoLayerSelection>,
 }
 
 struct RemoteInner {
@@ -78,7 +100,12 @@ impl RemoteTrackPublication {
         Self {
             inner: super::new_inner(info, track.map(Into::into)),
             remote: Arc::new(RemoteInner {
-                info: RwLock::new(RemoteInfo { subscribed: auto_subscribe, allowed: true }),
+                info: RwLock::new(RemoteInfo {
+                    subscribed: auto_subscribe,
+                    allowed: true,
+                    fps: 0,
+                    selection: None,
+                }),
                 events: Default::default(),
             }),
         }
@@ -109,6 +136,9 @@ impl RemoteTrackPublication {
             if let Some(subscribed) = self.remote.events.subscribed.lock().as_ref() {
                 subscribed(self.clone(), track);
             }
+            if self.video_fps().is_some() {
+                self.emit_video_fps();
+            }
         }
 
         self.emit_subscription_update(old_subscription_state);
@@ -233,6 +263,13 @@ impl RemoteTrackPublication {
         *self.remote.events.video_quality_changed.lock() = Some(Box::new(f));
     }
 
+    pub(crate) fn on_video_fps_changed(
+        &self,
+        f: impl Fn(RemoteTrackPublication, u32) + Send + 'static,
+    ) {
+        *self.remote.events.video_fps_changed.lock() = Some(Box::new(f));
+    }
+
     pub fn set_subscribed(&self, subscribed: bool) {
         let old_subscription_state = self.subscription_status();
         let old_permission_state = self.permission_status();
@@ -273,6 +310,7 @@ impl RemoteTrackPublication {
             log::warn!("Cannot set video quality for a track that is not simulcasted");
             return;
         }
+        self.remote.info.write().selection = Some(VideoLayerSelection::Quality(quality));
         if let Some(video_quality_changed) =
             self.remote.events.video_quality_changed.lock().as_ref()
         {
@@ -280,6 +318,113 @@ impl RemoteTrackPublication {
         }
     }
 
+    /// Limits the frame rate the SFU forwards for this video track, pass 0 to remove the limit.
+    ///
+    /// The SFU drops temporal layers until the forwarded frame rate doesn't exceed `fps`, so the
+    /// received frame rate depends on the layers the publisher encodes.
+    pub fn set_video_fps(&self, fps: u32) {
+        if self.kind() != TrackKind::Video {
+            log::warn!("Cannot set the frame rate of a track that is not a video track");
+            return;
+        }
+        self.remote.info.write().fps = fps;
+        // The limit is sent once subscribed otherwise, see set_track
+        if self.is_subscribed() {
+            self.emit_video_fps();
+        }
+    }
+
+    fn emit_video_fps(&self) {
+        let fps = self.remote.info.read().fps;
+        if let Some(video_fps_changed) = self.remote.events.video_fps_changed.lock().as_ref() {
+            video_fps_changed(self.clone(), fps)
+        }
+    }
+
+    /// Max frame rate requested with [`Self::set_video_fps`], `None` when unlimited.
+    pub fn video_fps(&self) -> Option<u32> {
+        let fps = self.remote.info.read().fps;
+        (fps != 0).then_some(fps)
+    }
+
+    /// Selects the highest temporal layer to receive, 0 being the base layer.
+    ///
+    /// The SFU selects temporal layers by frame rate. The temporal structure isn't signaled,
+    /// so it is estimated from the published layers with libwebrtc's defaults, where each layer
+    /// doubles the frame rate. Use [`Self::set_video_fps`] when the publisher's frame rate is
+    /// known.
+    pub fn set_preferred_temporal_layer(&self, layer: u32) {
+        let (layer_count, max_fps) = self.temporal_structure();
+        self.set_video_fps(temporal_layer_fps(layer, layer_count, max_fps));
+    }
+
+    /// Number of temporal layers and frame rate of the top published layer, estimated from the
+    /// published layers and the SDK's default encodings.
+    fn temporal_structure(&self) -> (u32, u32) {
+        let info = self.proto_info();
+        let screenshare = self.source() == TrackSource::Screenshare;
+        let svc = info.codecs.iter().any(|codec| {
+            codec.video_layer_mode
+                == proto::video_layer::Mode::MultipleSpatialLayersPerStream as i32
+        });
+        let layer_count = if svc {
+            SIMULCAST_TEMPORAL_LAYERS
+        } else if info.layers.len() > 1 {
+            if screenshare {
+                SCREENSHARE_TEMPORAL_LAYERS
+            } else {
+                SIMULCAST_TEMPORAL_LAYERS
+            }
+        } else {
+            1
+        };
+
+        // info.width/height follow update_video_dimensions, the layers keep the published size
+        let (width, height) = info
+            .layers
+            .iter()
+            .map(|layer| (layer.width, layer.height))
+            .max_by_key(|(width, height)| width * height)
+            .unwrap_or((info.width, info.height));
+        let encoding = options::compute_appropriate_encoding(
+            screenshare,
+            width,
+            height,
+            VideoCodec::VP8, // Only affects the bitrate
+        );
+        (layer_count, encoding.max_framerate.round() as u32)
+    }
+
+    /// Track settings for the current frame rate limit, keeping the requested quality or
+    /// dimensions.
+    pub(crate) fn video_track_settings(&self) -> proto::UpdateTrackSettings {
+        let (fps, selection) = {
+            let info = self.remote.info.read();
+            (info.fps, info.selection)
+        };
+        let mut settings = proto::UpdateTrackSettings {
+            track_sids: vec![self.sid().into()],
+            disabled: !self.is_enabled(),
+            fps,
+            ..Default::default()
+        };
+        match selection {
+            Some(VideoLayerSelection::Quality(quality)) => {
+                settings.quality = proto::VideoQuality::from(quality).into();
+            }
+            Some(VideoLayerSelection::Dimension(TrackDimension(width, height))) => {
+                settings.width = width;
+                settings.height = height;
+            }
+            None => {
+                let TrackDimension(width, height) = self.dimension();
+                settings.width = width;
+                settings.height = height;
+            }
+        }
+        settings
+    }
+
     pub fn set_enabled(&self, enabled: bool) {
         if self.is_subscribed() && enabled != self.is_enabled() {
             let track = self.track().unwrap();
@@ -300,6 +445,7 @@ impl RemoteTrackPublication {
 
     pub fn update_video_dimensions(&self, dimension: TrackDimension) {
         if self.is_subscribed() {
+            self.remote.info.write().selection = Some(VideoLayerSelection::Dimension(dimension));
             if dimension != self.dimension() {
                 let TrackDimension(width, height) = dimension;
                 let mut new_info = self.proto_info();
@@ -404,3 +550,87 @@ impl RemoteTrackPublication {
         self.inner.info.read().frame_metadata_features.clone()
     }
 }
+
+/// Frame rate limit selecting temporal `layer` out of `layer_count` layers that each double the
+/// frame rate up to `max_fps`, 0 (unlimited) for the top layer.
+fn temporal_layer_fps(layer: u32, layer_count: u32, max_fps: u32) -> u32 {
+    let top_layer = layer_count.saturating_sub(1);
+    if layer >= top_layer {
+        return 0;
+    }
+    (max_fps >> (top_layer - layer)).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn video_publication(
+        layers: &[(u32, u32)],
+        source: proto::TrackSource,
+    ) -> RemoteTrackPublication {
+        let (width, height) = layers.last().copied().unwrap_or_default();
+        let info = proto::TrackInfo {
+            sid: "TR_video".into(),
+            r#type: proto::TrackType::Video as i32,
+            source: source as i32,
+            simulcast: layers.len() > 1,
+            width,
+            height,
+            layers: layers
+                .iter()
+                .map(|&(width, height)| proto::VideoLayer { width, height, ..Default::default() })
+                .collect(),
+            ..Default::default()
+        };
+        RemoteTrackPublication::new(info, None, true)
+    }
+
+    #[test]
+    fn temporal_layer_fps_halves_per_layer() {
+        assert_eq!(temporal_layer_fps(0, 3, 30), 7);
+        assert_eq!(temporal_layer_fps(1, 3, 30), 15);
+        assert_eq!(temporal_layer_fps(2, 3, 30), 0);
+        assert_eq!(temporal_layer_fps(7, 3, 30), 0);
+        assert_eq!(temporal_layer_fps(0, 2, 15), 7);
+        assert_eq!(temporal_layer_fps(0, 1, 30), 0, "a single layer is never limited");
+        assert_eq!(temporal_layer_fps(0, 3, 2), 1, "the base layer is never dropped");
+    }
+
+    #[test]
+    fn temporal_structure_from_published_layers() {
+        let camera =
+            video_publication(&[(320, 180), (640, 360), (1280, 720)], proto::TrackSource::Camera);
+        assert_eq!(camera.temporal_structure(), (SIMULCAST_TEMPORAL_LAYERS, 30));
+
+        let screenshare =
+            video_publication(&[(640, 360), (1280, 720)], proto::TrackSource::ScreenShare);
+        assert_eq!(screenshare.temporal_structure(), (SCREENSHARE_TEMPORAL_LAYERS, 15));
+
+        let single = video_publication(&[(640, 360)], proto::TrackSource::Camera);
+        assert_eq!(single.temporal_structure(), (1, 25));
+    }
+
+    #[test]
+    fn fps_keeps_requested_quality() {
+        let publication =
+            video_publication(&[(320, 180), (640, 360), (1280, 720)], proto::TrackSource::Camera);
+
+        let settings = publication.video_track_settings();
+        assert_eq!((settings.width, settings.height, settings.fps), (1280, 720, 0));
+
+        publication.set_video_quality(VideoQuality::Low);
+        publication.set_preferred_temporal_layer(1);
+        assert_eq!(publication.video_fps(), Some(15));
+
+        let settings = publication.video_track_settings();
+        assert_eq!(settings.quality, proto::VideoQuality::Low as i32);
+        assert_eq!((settings.width, settings.height, settings.fps), (0, 0, 15));
+
+        publication.set_video_quality(VideoQuality::Medium);
+        publication.set_video_fps(0);
+        let settings = publication.video_track_settings();
+        assert_eq!(settings.quality, proto::VideoQuality::Medium as i32);
+        assert_eq!(settings.fps, 0);
+    }
+}
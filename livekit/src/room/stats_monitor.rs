@@ -0,0 +1,750 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Periodic, aggregated session statistics.
+//!
+//! [`Room::get_stats`](crate::Room::get_stats) returns raw, cumulative [`RtcStats`] snapshots.
+//! A [`StatsMonitor`] polls those snapshots at a fixed interval, diffs the cumulative counters
+//! and joins the RTP, codec, transport and candidate pair entries into one
+//! [`TrackStatsSummary`] per published or subscribed track.
+
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::Weak,
+    task::{Context, Poll},
+    time::{Duration, SystemTime},
+};
+
+use libwebrtc::stats::{
+    CandidatePairStats, IceCandidateType, IceServerTransportProtocol, QualityLimitationReason,
+    RtcStats,
+};
+use tokio::sync::mpsc;
+use tokio_stream::Stream;
+
+use super::RoomSession;
+use crate::{prelude::*, rtc_engine::SessionStats};
+
+const STATS_MONITOR_BUFFER: usize = 4;
+
+/// Options for [`Room::stats_monitor`](crate::Room::stats_monitor).
+#[derive(Debug, Clone)]
+pub struct StatsMonitorOptions {
+    /// Time between two reports. Rates are averaged over this window.
+    pub interval: Duration,
+}
+
+impl Default for StatsMonitorOptions {
+    fn default() -> Self {
+        Self { interval: Duration::from_secs(2) }
+    }
+}
+
+/// Whether a summary describes media sent or received by the local participant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatsDirection {
+    Inbound,
+    Outbound,
+}
+
+/// The ICE candidate pair currently carrying a track.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandidatePairSummary {
+    pub local_candidate_type: Option<IceCandidateType>,
+    pub remote_candidate_type: Option<IceCandidateType>,
+    /// Transport protocol of the local candidate ("udp" or "tcp").
+    pub protocol: String,
+    /// Protocol used to reach the TURN server when the local candidate is a relay.
+    pub relay_protocol: Option<IceServerTransportProtocol>,
+    pub round_trip_time: Option<Duration>,
+    /// Bandwidth estimate in bits per second, if known.
+    pub available_outgoing_bitrate: Option<f64>,
+}
+
+/// Quality summary of a single track over the last monitor interval.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackStatsSummary {
+    /// `None` when the WebRTC track couldn't be matched with a publication (e.g. it was just
+    /// unpublished).
+    pub track_sid: Option<TrackSid>,
+    pub participant_identity: Option<ParticipantIdentity>,
+    pub rtc_track_id: String,
+    pub kind: TrackKind,
+    pub direction: StatsDirection,
+    /// Mime type of the negotiated codec, e.g. "video/VP8".
+    pub codec: Option<String>,
+    /// Bits per second, summed over all simulcast layers.
+    pub bitrate: f64,
+    pub frames_per_second: Option<f64>,
+    /// Resolution of the received stream, or of the highest active simulcast layer.
+    pub resolution: Option<TrackDimension>,
+    /// Percentage of packets lost during the last interval, as reported by the receiver.
+    pub packet_loss_percent: f64,
+    pub jitter: Option<Duration>,
+    pub freeze_count: u32,
+    pub total_freeze_duration: Duration,
+    pub pause_count: u32,
+    pub total_pause_duration: Duration,
+    /// Why the encoder is limiting quality, only meaningful for outbound video.
+    pub quality_limitation_reason: QualityLimitationReason,
+    pub candidate_pair: Option<CandidatePairSummary>,
+}
+
+/// A report emitted by [`StatsMonitor`] after every interval.
+#[derive(Debug, Clone)]
+pub struct StatsReport {
+    pub timestamp: SystemTime,
+    /// Time covered by the rates of this report.
+    pub elapsed: Duration,
+    pub tracks: Vec<TrackStatsSummary>,
+}
+
+/// A stream of [`StatsReport`]s, see [`Room::stats_monitor`](crate::Room::stats_monitor).
+///
+/// The monitor stops polling when it is dropped, and the stream ends once the room is
+/// disconnected.
+pub struct StatsMonitor {
+    rx: mpsc::Receiver<StatsReport>,
+}
+
+impl StatsMonitor {
+    pub(crate) fn new(session: Weak<RoomSession>, options: StatsMonitorOptions) -> Self {
+        let (tx, rx) = mpsc::channel(STATS_MONITOR_BUFFER);
+        livekit_runtime::spawn(monitor_task(session, options, tx));
+        Self { rx }
+    }
+}
+
+impl Stream for StatsMonitor {
+    type Item = StatsReport;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().rx.poll_recv(cx)
+    }
+}
+
+async fn monitor_task(
+    session: Weak<RoomSession>,
+    options: StatsMonitorOptions,
+    tx: mpsc::Sender<StatsReport>,
+) {
+    let mut aggregator = StatsAggregator::default();
+    let mut interval = livekit_runtime::interval(options.interval);
+    let mut last_poll = None;
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {},
+            _ = tx.closed() => break,
+        }
+
+        let Some(inner) = session.upgrade() else {
+            break;
+        };
+        if inner.info.read().state == ConnectionState::Disconnected {
+            break;
+        }
+
+        let stats = match inner.rtc_engine.get_stats().await {
+            Ok(stats) => stats,
+            Err(err) => {
+                log::warn!("stats monitor failed to get stats: {}", err);
+                continue;
+            }
+        };
+        let tracks = track_lookup(&inner);
+        drop(inner);
+
+        let now = livekit_runtime::Instant::now();
+        let elapsed = last_poll.map(|last| now - last).unwrap_or_default();
+        last_poll = Some(now);
+
+        let summaries = aggregator.update(&stats, &tracks, elapsed);
+        if elapsed.is_zero() {
+            // The first poll only establishes the baseline for the rates
+            continue;
+        }
+
+        let report = StatsReport { timestamp: SystemTime::now(), elapsed, tracks: summaries };
+        if tx.send(report).await.is_err() {
+            break;
+        }
+    }
+}
+
+/// Maps WebRTC track ids to the publication they belong to.
+fn track_lookup(session: &RoomSession) -> HashMap<String, (TrackSid, ParticipantIdentity)> {
+    let mut participants = vec![Participant::Local(session.local_participant.clone())];
+    participants
+        .extend(session.remote_participants.read().values().cloned().map(Participant::Remote));
+
+    let mut tracks = HashMap::new();
+    for participant in participants {
+        let identity = participant.identity();
+        for (sid, publication) in participant.track_publications() {
+            if let Some(track) = publication.track() {
+                tracks.insert(track.rtc_track().id(), (sid, identity.clone()));
+            }
+        }
+    }
+    tracks
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct RtpCounters {
+    bytes: u64,
+    packets: u64,
+    lost: i64,
+}
+
+/// Per track accumulator, simulcast layers are merged into one entry.
+#[derive(Debug)]
+struct TrackAccumulator {
+    summary: TrackStatsSummary,
+    bytes: u64,
+    packets: u64,
+    lost: i64,
+    layer_area: u32,
+}
+
+/// Turns consecutive [`SessionStats`] snapshots into [`TrackStatsSummary`]s.
+#[derive(Debug, Default)]
+pub(crate) struct StatsAggregator {
+    previous: HashMap<String, RtpCounters>,
+}
+
+impl StatsAggregator {
+    pub(crate) fn update(
+        &mut self,
+        stats: &SessionStats,
+        tracks: &HashMap<String, (TrackSid, ParticipantIdentity)>,
+        elapsed: Duration,
+    ) -> Vec<TrackStatsSummary> {
+        let mut current = HashMap::new();
+        let mut accumulators: Vec<TrackAccumulator> = Vec::new();
+
+        for (prefix, pc_stats) in
+            [("pub", &stats.publisher_stats), ("sub", &stats.subscriber_stats)]
+        {
+            let index = PcStatsIndex::new(pc_stats);
+            for stat in pc_stats {
+                match stat {
+                    RtcStats::InboundRtp(inbound) => {
+                        let counters = RtpCounters {
+                            bytes: inbound.inbound.bytes_received,
+                            packets: inbound.received.packets_received,
+                            lost: inbound.received.packets_lost,
+                        };
+                        let key = format!("{}:{}", prefix, inbound.rtc.id);
+                        let delta = counters.delta(self.previous.get(&key));
+                        current.insert(key, counters);
+
+                        let Some(kind) = track_kind(&inbound.stream.kind) else {
+                            continue;
+                        };
+                        let rtc_track_id = inbound.inbound.track_identifier.clone();
+                        let stream = &inbound.inbound;
+                        let mut summary = new_summary(
+                            rtc_track_id,
+                            kind,
+                            StatsDirection::Inbound,
+                            tracks,
+                            &index,
+                            &inbound.stream.codec_id,
+                            &inbound.stream.transport_id,
+                        );
+                        summary.jitter = duration_from_secs(inbound.received.jitter);
+                        if kind == TrackKind::Video {
+                            summary.frames_per_second = Some(stream.frames_per_second);
+                            summary.resolution = dimension(stream.frame_width, stream.frame_height);
+                            summary.freeze_count = stream.freeze_count;
+                            summary.total_freeze_duration =
+                                duration_from_secs(stream.total_freeze_duration)
+                                    .unwrap_or_default();
+                            summary.pause_count = stream.pause_count;
+                            summary.total_pause_duration =
+                                duration_from_secs(stream.total_pause_duration).unwrap_or_default();
+                        }
+                        accumulate(&mut accumulators, summary, delta, 0);
+                    }
+                    RtcStats::OutboundRtp(outbound) => {
+                        let remote = index.remote_inbound.get(outbound.rtc.id.as_str());
+                        let counters = RtpCounters {
+                            bytes: outbound.sent.bytes_sent,
+                            packets: outbound.sent.packets_sent,
+                            lost: remote.map(|r| r.received.packets_lost).unwrap_or_default(),
+                        };
+                        let key = format!("{}:{}", prefix, outbound.rtc.id);
+                        let delta = counters.delta(self.previous.get(&key));
+                        current.insert(key, counters);
+
+                        let Some(kind) = track_kind(&outbound.stream.kind) else {
+                            continue;
+                        };
+                        let Some(rtc_track_id) = index
+                            .media_sources
+                            .get(outbound.outbound.media_source_id.as_str())
+                            .map(|source| source.source.track_identifier.clone())
+                        else {
+                            continue;
+                        };
+                        let layer = &outbound.outbound;
+                        let mut summary = new_summary(
+                            rtc_track_id,
+                            kind,
+                            StatsDirection::Outbound,
+                            tracks,
+                            &index,
+                            &outbound.stream.codec_id,
+                            &outbound.stream.transport_id,
+                        );
+                        summary.jitter = remote.and_then(|r| duration_from_secs(r.received.jitter));
+                        let mut layer_area = 0;
+                        if kind == TrackKind::Video {
+                            summary.quality_limitation_reason = layer.quality_limitation_reason;
+                            if layer.frames_per_second > 0.0 {
+                                summary.frames_per_second = Some(layer.frames_per_second);
+                                summary.resolution =
+                                    dimension(layer.frame_width, layer.frame_height);
+                                layer_area = layer.frame_width * layer.frame_height;
+                            }
+                        }
+                        accumulate(&mut accumulators, summary, delta, layer_area);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        self.previous = current;
+
+        let secs = elapsed.as_secs_f64();
+        accumulators
+            .into_iter()
+            .map(|acc| {
+                let mut summary = acc.summary;
+                if secs > 0.0 {
+                    summary.bitrate = acc.bytes as f64 * 8.0 / secs;
+                }
+                let expected = match summary.direction {
+                    // Sent packets already include the ones the receiver reported lost
+                    StatsDirection::Outbound => acc.packets as i64,
+                    StatsDirection::Inbound => acc.packets as i64 + acc.lost,
+                };
+                summary.packet_loss_percent = loss_percent(acc.lost, expected);
+                summary
+            })
+            .collect()
+    }
+}
+
+impl RtpCounters {
+    /// Counters are cumulative, a counter going backwards means the stream was recreated.
+    fn delta(&self, previous: Option<&RtpCounters>) -> RtpCounters {
+        let Some(previous) = previous else {
+            return RtpCounters::default();
+        };
+        RtpCounters {
+            bytes: self.bytes.saturating_sub(previous.bytes),
+            packets: self.packets.saturating_sub(previous.packets),
+            lost: (self.lost - previous.lost).max(0),
+        }
+    }
+}
+
+/// Lookup tables of the stats referenced by RTP streams of a single peer connection.
+struct PcStatsIndex<'a> {
+    codecs: HashMap<&'a str, &'a libwebrtc::stats::CodecStats>,
+    media_sources: HashMap<&'a str, &'a libwebrtc::stats::MediaSourceStats>,
+    remote_inbound: HashMap<&'a str, &'a libwebrtc::stats::RemoteInboundRtpStats>,
+    transports: HashMap<&'a str, &'a libwebrtc::stats::TransportStats>,
+    candidate_pairs: HashMap<&'a str, &'a CandidatePairStats>,
+    local_candidates: HashMap<&'a str, &'a libwebrtc::stats::LocalCandidateStats>,
+    remote_candidates: HashMap<&'a str, &'a libwebrtc::stats::RemoteCandidateStats>,
+}
+
+impl<'a> PcStatsIndex<'a> {
+    fn new(stats: &'a [RtcStats]) -> Self {
+        let mut index = Self {
+            codecs: HashMap::new(),
+            media_sources: HashMap::new(),
+            remote_inbound: HashMap::new(),
+            transports: HashMap::new(),
+            candidate_pairs: HashMap::new(),
+            local_candidates: HashMap::new(),
+            remote_candidates: HashMap::new(),
+        };
+        for stat in stats {
+            match stat {
+                RtcStats::Codec(s) => {
+                    index.codecs.insert(&s.rtc.id, s);
+                }
+                RtcStats::MediaSource(s) => {
+                    index.media_sources.insert(&s.rtc.id, s);
+                }
+                RtcStats::RemoteInboundRtp(s) => {
+                    index.remote_inbound.insert(&s.remote_inbound.local_id, s);
+                }
+                RtcStats::Transport(s) => {
+                    index.transports.insert(&s.rtc.id, s);
+                }
+                RtcStats::CandidatePair(s) => {
+                    index.candidate_pairs.insert(&s.rtc.id, s);
+                }
+                RtcStats::LocalCandidate(s) => {
+                    index.local_candidates.insert(&s.rtc.id, s);
+                }
+                RtcStats::RemoteCandidate(s) => {
+                    index.remote_candidates.insert(&s.rtc.id, s);
+                }
+                _ => {}
+            }
+        }
+        index
+    }
+
+    fn candidate_pair(&self, transport_id: &str) -> Option<CandidatePairSummary> {
+        let pair = match self.transports.get(transport_id) {
+            Some(transport) if !transport.transport.selected_candidate_pair_id.is_empty() => self
+                .candidate_pairs
+                .get(transport.transport.selected_candidate_pair_id.as_str())
+                .copied(),
+            // Not every implementation reports the selected pair on the transport
+            _ => self.candidate_pairs.values().copied().find(|pair| {
+                pair.candidate_pair.nominated && pair.candidate_pair.transport_id == transport_id
+            }),
+        }?;
+        let pair = &pair.candidate_pair;
+        let local = self.local_candidates.get(pair.local_candidate_id.as_str());
+        let remote = self.remote_candidates.get(pair.remote_candidate_id.as_str());
+
+        Some(CandidatePairSummary {
+            local_candidate_type: local.and_then(|c| c.local_candidate.candidate_type),
+            remote_candidate_type: remote.and_then(|c| c.remote_candidate.candidate_type),
+            protocol: local.map(|c| c.local_candidate.protocol.clone()).unwrap_or_default(),
+            relay_protocol: local.and_then(|c| c.local_candidate.relay_protocol),
+            round_trip_time: duration_from_secs(pair.current_round_trip_time),
+            available_outgoing_bitrate: (pair.available_outgoing_bitrate > 0.0)
+                .then_some(pair.available_outgoing_bitrate),
+        })
+    }
+}
+
+fn new_summary(
+    rtc_track_id: String,
+    kind: TrackKind,
+    direction: StatsDirection,
+    tracks: &HashMap<String, (TrackSid, ParticipantIdentity)>,
+    index: &PcStatsIndex,
+    codec_id: &str,
+    transport_id: &str,
+) -> TrackStatsSummary {
+    let publication = tracks.get(&rtc_track_id);
+    TrackStatsSummary {
+        track_sid: publication.map(|(sid, _)| sid.clone()),
+        participant_identity: publication.map(|(_, identity)| identity.clone()),
+        rtc_track_id,
+        kind,
+        direction,
+        codec: index.codecs.get(codec_id).map(|codec| codec.codec.mime_type.clone()),
+        bitrate: 0.0,
+        frames_per_second: None,
+        resolution: None,
+        packet_loss_percent: 0.0,
+        jitter: None,
+        freeze_count: 0,
+        total_freeze_duration: Duration::ZERO,
+        pause_count: 0,
+        total_pause_duration: Duration::ZERO,
+        quality_limitation_reason: QualityLimitationReason::None,
+        candidate_pair: index.candidate_pair(transport_id),
+    }
+}
+
+fn accumulate(
+    accumulators: &mut Vec<TrackAccumulator>,
+    summary: TrackStatsSummary,
+    delta: RtpCounters,
+    layer_area: u32,
+) {
+    let existing = accumulators.iter_mut().find(|acc| {
+        acc.summary.rtc_track_id == summary.rtc_track_id
+            && acc.summary.direction == summary.direction
+    });
+    let Some(acc) = existing else {
+        accumulators.push(TrackAccumulator {
+            summary,
+            bytes: delta.bytes,
+            packets: delta.packets,
+            lost: delta.lost,
+            layer_area,
+        });
+        return;
+    };
+
+    acc.bytes += delta.bytes;
+    acc.packets += delta.packets;
+    acc.lost += delta.lost;
+
+    let merged = &mut acc.summary;
+    if layer_area > acc.layer_area {
+        acc.layer_area = layer_area;
+        merged.frames_per_second = summary.frames_per_second;
+        merged.resolution = summary.resolution;
+    }
+    if merged.quality_limitation_reason == QualityLimitationReason::None {
+        merged.quality_limitation_reason = summary.quality_limitation_reason;
+    }
+    merged.jitter = merged.jitter.max(summary.jitter);
+    merged.codec = merged.codec.take().or(summary.codec);
+    merged.candidate_pair = merged.candidate_pair.take().or(summary.candidate_pair);
+}
+
+fn track_kind(kind: &str) -> Option<TrackKind> {
+    match kind {
+        "audio" => Some(TrackKind::Audio),
+        "video" => Some(TrackKind::Video),
+        _ => None,
+    }
+}
+
+fn dimension(width: u32, height: u32) -> Option<TrackDimension> {
+    (width > 0 && height > 0).then_some(TrackDimension(width, height))
+}
+
+fn duration_from_secs(secs: f64) -> Option<Duration> {
+    (secs.is_finite() && secs > 0.0).then(|| Duration::from_secs_f64(secs))
+}
+
+fn loss_percent(lost: i64, expected: i64) -> f64 {
+    if expected <= 0 {
+        return 0.0;
+    }
+    (lost.max(0) as f64 / expected as f64 * 100.0).min(100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use libwebrtc::stats::{
+        dictionaries, CodecStats, InboundRtpStats, LocalCandidateStats, MediaSourceStats,
+        OutboundRtpStats, RemoteCandidateStats, RemoteInboundRtpStats, TransportStats,
+    };
+
+    use super::*;
+
+    fn rtc(id: &str) -> dictionaries::RtcStats {
+        dictionaries::RtcStats { id: id.to_owned(), timestamp: 0 }
+    }
+
+    fn inbound(bytes: u64, packets: u64, lost: i64) -> RtcStats {
+        RtcStats::InboundRtp(InboundRtpStats {
+            rtc: rtc("IT01V1"),
+            stream: dictionaries::RtpStreamStats {
+                kind: "video".to_owned(),
+                codec_id: "CIT01_96".to_owned(),
+                transport_id: "T01".to_owned(),
+                ..Default::default()
+            },
+            received: dictionaries::ReceivedRtpStreamStats {
+                packets_received: packets,
+                packets_lost: lost,
+                jitter: 0.02,
+            },
+            inbound: dictionaries::InboundRtpStreamStats {
+                track_identifier: "TR_remote".to_owned(),
+                bytes_received: bytes,
+                frame_width: 640,
+                frame_height: 360,
+                frames_per_second: 30.0,
+                freeze_count: 1,
+                total_freeze_duration: 0.5,
+                ..Default::default()
+            },
+        })
+    }
+
+    fn outbound(id: &str, rid: &str, bytes: u64, packets: u64, width: u32, fps: f64) -> RtcStats {
+        RtcStats::OutboundRtp(OutboundRtpStats {
+            rtc: rtc(id),
+            stream: dictionaries::RtpStreamStats {
+                kind: "video".to_owned(),
+                codec_id: "COT01_96".to_owned(),
+                transport_id: "T01".to_owned(),
+                ..Default::default()
+            },
+            sent: dictionaries::SentRtpStreamStats { packets_sent: packets, bytes_sent: bytes },
+            outbound: dictionaries::OutboundRtpStreamStats {
+                media_source_id: "SV1".to_owned(),
+                rid: rid.to_owned(),
+                frame_width: width,
+                frame_height: width * 9 / 16,
+                frames_per_second: fps,
+                quality_limitation_reason: if rid == "f" {
+                    QualityLimitationReason::Bandwidth
+                } else {
+                    QualityLimitationReason::None
+                },
+                ..Default::default()
+            },
+        })
+    }
+
+    fn transport_stats() -> Vec<RtcStats> {
+        vec![
+            RtcStats::Transport(TransportStats {
+                rtc: rtc("T01"),
+                transport: dictionaries::TransportStats {
+                    selected_candidate_pair_id: "CP1".to_owned(),
+                    ..Default::default()
+                },
+            }),
+            RtcStats::CandidatePair(CandidatePairStats {
+                rtc: rtc("CP1"),
+                candidate_pair: dictionaries::CandidatePairStats {
+                    transport_id: "T01".to_owned(),
+                    local_candidate_id: "L1".to_owned(),
+                    remote_candidate_id: "R1".to_owned(),
+                    current_round_trip_time: 0.05,
+                    ..Default::default()
+                },
+            }),
+            RtcStats::LocalCandidate(LocalCandidateStats {
+                rtc: rtc("L1"),
+                local_candidate: dictionaries::IceCandidateStats {
+                    protocol: "udp".to_owned(),
+                    candidate_type: Some(IceCandidateType::Srflx),
+                    ..Default::default()
+                },
+            }),
+            RtcStats::RemoteCandidate(RemoteCandidateStats {
+                rtc: rtc("R1"),
+                remote_candidate: dictionaries::IceCandidateStats {
+                    candidate_type: Some(IceCandidateType::Host),
+                    ..Default::default()
+                },
+            }),
+        ]
+    }
+
+    fn codec(id: &str, mime_type: &str) -> RtcStats {
+        RtcStats::Codec(CodecStats {
+            rtc: rtc(id),
+            codec: dictionaries::CodecStats {
+                mime_type: mime_type.to_owned(),
+                ..Default::default()
+            },
+        })
+    }
+
+    fn subscriber_stats(bytes: u64, packets: u64, lost: i64) -> SessionStats {
+        let mut subscriber_stats = transport_stats();
+        subscriber_stats.push(codec("CIT01_96", "video/VP8"));
+        subscriber_stats.push(inbound(bytes, packets, lost));
+        SessionStats { publisher_stats: Vec::new(), subscriber_stats }
+    }
+
+    #[test]
+    fn inbound_rates_are_computed_from_counter_deltas() {
+        let tracks = HashMap::from([(
+            "TR_remote".to_owned(),
+            (TrackSid::try_from("TR_remote".to_owned()).unwrap(), "alice".to_owned().into()),
+        )]);
+        let mut aggregator = StatsAggregator::default();
+
+        let first = aggregator.update(&subscriber_stats(1_000, 100, 0), &tracks, Duration::ZERO);
+        assert_eq!(first[0].bitrate, 0.0);
+
+        let summaries =
+            aggregator.update(&subscriber_stats(251_000, 290, 10), &tracks, Duration::from_secs(2));
+        assert_eq!(summaries.len(), 1);
+        let summary = &summaries[0];
+        assert_eq!(summary.track_sid.as_ref().map(|sid| sid.as_str()), Some("TR_remote"));
+        assert_eq!(summary.direction, StatsDirection::Inbound);
+        assert_eq!(summary.kind, TrackKind::Video);
+        assert_eq!(summary.codec.as_deref(), Some("video/VP8"));
+        assert_eq!(summary.bitrate, 1_000_000.0);
+        assert_eq!(summary.packet_loss_percent, 5.0);
+        assert_eq!(summary.resolution, Some(TrackDimension(640, 360)));
+        assert_eq!(summary.jitter, Some(Duration::from_millis(20)));
+        assert_eq!(summary.total_freeze_duration, Duration::from_millis(500));
+
+        let pair = summary.candidate_pair.as_ref().unwrap();
+        assert_eq!(pair.local_candidate_type, Some(IceCandidateType::Srflx));
+        assert_eq!(pair.remote_candidate_type, Some(IceCandidateType::Host));
+        assert_eq!(pair.protocol, "udp");
+        assert_eq!(pair.round_trip_time, Some(Duration::from_millis(50)));
+    }
+
+    #[test]
+    fn counter_reset_does_not_produce_negative_rates() {
+        let tracks = HashMap::new();
+        let mut aggregator = StatsAggregator::default();
+        aggregator.update(&subscriber_stats(500_000, 1000, 20), &tracks, Duration::ZERO);
+
+        let summaries =
+            aggregator.update(&subscriber_stats(1_000, 10, 0), &tracks, Duration::from_secs(1));
+        assert_eq!(summaries[0].bitrate, 0.0);
+        assert_eq!(summaries[0].packet_loss_percent, 0.0);
+        assert_eq!(summaries[0].track_sid, None);
+    }
+
+    #[test]
+    fn outbound_simulcast_layers_are_merged() {
+        let publisher_stats = |scale: u64, lost: i64| {
+            let mut stats = transport_stats();
+            stats.push(codec("COT01_96", "video/VP8"));
+            stats.push(RtcStats::MediaSource(MediaSourceStats {
+                rtc: rtc("SV1"),
+                source: dictionaries::MediaSourceStats {
+                    track_identifier: "TR_local".to_owned(),
+                    kind: "video".to_owned(),
+                },
+                ..Default::default()
+            }));
+            stats.push(outbound("OT01V1", "q", 10_000 * scale, 100 * scale, 320, 15.0));
+            stats.push(outbound("OT01V2", "h", 40_000 * scale, 200 * scale, 640, 30.0));
+            stats.push(outbound("OT01V3", "f", 50_000 * scale, 200 * scale, 1280, 0.0));
+            stats.push(RtcStats::RemoteInboundRtp(RemoteInboundRtpStats {
+                rtc: rtc("RI1"),
+                received: dictionaries::ReceivedRtpStreamStats {
+                    packets_lost: lost,
+                    ..Default::default()
+                },
+                remote_inbound: dictionaries::RemoteInboundRtpStreamStats {
+                    local_id: "OT01V2".to_owned(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }));
+            SessionStats { publisher_stats: stats, subscriber_stats: Vec::new() }
+        };
+
+        let tracks = HashMap::new();
+        let mut aggregator = StatsAggregator::default();
+        aggregator.update(&publisher_stats(1, 0), &tracks, Duration::ZERO);
+        let summaries = aggregator.update(&publisher_stats(2, 25), &tracks, Duration::from_secs(1));
+
+        assert_eq!(summaries.len(), 1);
+        let summary = &summaries[0];
+        assert_eq!(summary.rtc_track_id, "TR_local");
+        assert_eq!(summary.direction, StatsDirection::Outbound);
+        assert_eq!(summary.bitrate, 800_000.0);
+        assert_eq!(summary.packet_loss_percent, 5.0);
+        // The full layer isn't sending frames, so the half layer is the highest active one
+        assert_eq!(summary.resolution, Some(TrackDimension(640, 360)));
+        assert_eq!(summary.frames_per_second, Some(30.0));
+        assert_eq!(summary.quality_limitation_reason, QualityLimitationReason::Bandwidth);
+    }
+}
@@ -0,0 +1,360 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Sources of connection details for [`Room::connect_with_token_source`].
+//!
+//! A [`TokenSource`] is consulted once before connecting and again before each full
+//! reconnect whose current token is about to expire, so rooms survive long disconnects
+//! without the application having to hand out a fresh token itself.
+//!
+//! [`Room::connect_with_token_source`]: crate::Room::connect_with_token_source
+
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    future::Future,
+    pin::Pin,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use livekit_api::access_token::Claims;
+use livekit_net::{Header, HttpClientExt, TransportError};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Tokens expiring within this margin are refreshed before a full reconnect.
+pub(crate) const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+pub type TokenSourceResult<T> = Result<T, TokenSourceError>;
+
+pub type TokenSourceFuture<'a> =
+    Pin<Box<dyn Future<Output = TokenSourceResult<ConnectionDetails>> + Send + 'a>>;
+
+#[derive(Error, Debug)]
+pub enum TokenSourceError {
+    #[error("no HTTP client available")]
+    NoHttpClient,
+    #[error("transport: {0}")]
+    Transport(#[from] TransportError),
+    #[error("token endpoint returned status {0}")]
+    Status(u16),
+    #[error("invalid token endpoint response: {0}")]
+    InvalidResponse(#[from] serde_json::Error),
+    #[error("{0}")]
+    Custom(String),
+}
+
+/// Server URL and participant token to connect with.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectionDetails {
+    #[serde(alias = "server_url")]
+    pub server_url: String,
+    #[serde(alias = "participant_token")]
+    pub participant_token: String,
+}
+
+/// Provides the details used to (re)connect to a room.
+pub trait TokenSource: Send + Sync {
+    fn fetch(&self) -> TokenSourceFuture<'_>;
+}
+
+impl Debug for dyn TokenSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TokenSource")
+    }
+}
+
+/// Always returns the same connection details.
+///
+/// The token is never refreshed, so this is only useful to drive the token source code
+/// path with a token known to outlive the session.
+///
+#[derive(Debug, Clone)]
+pub struct LiteralTokenSource(pub ConnectionDetails);
+
+impl LiteralTokenSource {
+    pub fn new(server_url: impl Into<String>, participant_token: impl Into<String>) -> Self {
+        Self(ConnectionDetails {
+            server_url: server_url.into(),
+            participant_token: participant_token.into(),
+        })
+    }
+}
+
+impl TokenSource for LiteralTokenSource {
+    fn fetch(&self) -> TokenSourceFuture<'_> {
+        Box::pin(async move { Ok(self.0.clone()) })
+    }
+}
+
+/// Fetches connection details by calling an async closure.
+pub struct CustomTokenSource<F>(F);
+
+impl<F, Fut> CustomTokenSource<F>
+where
+    F: Fn() -> Fut + Send + Sync,
+    Fut: Future<Output = TokenSourceResult<ConnectionDetails>> + Send + 'static,
+{
+    pub fn new(fetch: F) -> Self {
+        Self(fetch)
+    }
+}
+
+impl<F, Fut> TokenSource for CustomTokenSource<F>
+where
+    F: Fn() -> Fut + Send + Sync,
+    Fut: Future<Output = TokenSourceResult<ConnectionDetails>> + Send + 'static,
+{
+    fn fetch(&self) -> TokenSourceFuture<'_> {
+        Box::pin((self.0)())
+    }
+}
+
+/// Body posted to a token endpoint.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub room_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub participant_identity: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub participant_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub participant_metadata: Option<String>,
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub participant_attributes: HashMap<String, String>,
+}
+
+/// Fetches connection details from an HTTP endpoint.
+///
+/// The endpoint receives the [`TokenRequest`] as a JSON POST body and responds with
+/// `{"serverUrl": ..., "participantToken": ...}`. Requests go through the process-wide
+/// [`livekit_net::http_client`].
+///
+#[derive(Debug, Clone)]
+pub struct EndpointTokenSource {
+    url: String,
+    headers: Vec<Header>,
+    request: TokenRequest,
+}
+
+impl EndpointTokenSource {
+    pub fn new(url: impl Into<String>, request: TokenRequest) -> Self {
+        Self { url: url.into(), headers: Vec::new(), request }
+    }
+
+    /// Adds a header sent with every request, e.g. for authorization.
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push(Header { name: name.into(), value: value.into() });
+        self
+    }
+
+    async fn request(&self) -> TokenSourceResult<ConnectionDetails> {
+        let client = livekit_net::http_client().ok_or(TokenSourceError::NoHttpClient)?;
+        let mut headers = self.headers.clone();
+        headers.push(Header { name: "Content-Type".into(), value: "application/json".into() });
+        let body = serde_json::to_vec(&self.request)?;
+
+        let response = client.post(self.url.clone(), headers, body).await?;
+        if !(200..300).contains(&response.status) {
+            return Err(TokenSourceError::Status(response.status));
+        }
+        Ok(serde_json::from_slice(&response.body)?)
+    }
+}
+
+impl TokenSource for EndpointTokenSource {
+    fn fetch(&self) -> TokenSourceFuture<'_> {
+        Box::pin(self.request())
+    }
+}
+
+/// Whether `token` expires within `margin`, or cannot be parsed at all.
+pub(crate) fn expires_within(token: &str, margin: Duration) -> bool {
+    let Ok(claims) = Claims::from_unverified(token) else {
+        return true;
+    };
+    let expires_at = UNIX_EPOCH + Duration::from_secs(claims.exp as u64);
+    expires_at <= SystemTime::now() + margin
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    };
+
+    use livekit_api::access_token::{AccessToken, VideoGrants};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+        task::JoinHandle,
+    };
+
+    use super::*;
+
+    /// Head and body of the request received by a [`mock_endpoint`].
+    type ReceivedRequest = JoinHandle<(String, String)>;
+
+    /// Serves a single HTTP request on localhost, replying with `status` and `body`.
+    async fn mock_endpoint(status: u16, body: &'static str) -> (String, ReceivedRequest) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/token", listener.local_addr().unwrap());
+        let request = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut received = Vec::new();
+            let mut chunk = [0u8; 1024];
+            let head_len = loop {
+                let n = socket.read(&mut chunk).await.unwrap();
+                received.extend_from_slice(&chunk[..n]);
+                if let Some(pos) = received.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break pos;
+                }
+            };
+            let head = String::from_utf8_lossy(&received[..head_len]).to_lowercase();
+            let content_length = head
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .map_or(0, |len| len.trim().parse().unwrap());
+            while received.len() < head_len + 4 + content_length {
+                let n = socket.read(&mut chunk).await.unwrap();
+                received.extend_from_slice(&chunk[..n]);
+            }
+            let request_body = String::from_utf8(received[head_len + 4..].to_vec()).unwrap();
+
+            let response = format!(
+                "HTTP/1.1 {status} Mock\r\ncontent-type: application/json\r\n\
+                 content-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+            (head, request_body)
+        });
+        (url, request)
+    }
+
+    fn token(ttl: Duration) -> String {
+        AccessToken::with_api_key("devkey", "secret")
+            .with_identity("alice")
+            .with_grants(VideoGrants { room_join: true, room: "room".into(), ..Default::default() })
+            .with_ttl(ttl)
+            .to_jwt()
+            .unwrap()
+    }
+
+    #[test]
+    fn expiry_margin() {
+        assert!(!expires_within(&token(Duration::from_secs(3600)), TOKEN_REFRESH_MARGIN));
+        assert!(expires_within(&token(Duration::from_secs(30)), TOKEN_REFRESH_MARGIN));
+        assert!(expires_within("not a token", TOKEN_REFRESH_MARGIN));
+    }
+
+    #[tokio::test]
+    async fn custom_source_is_called_per_fetch() {
+        let calls = Arc::new(AtomicU32::new(0));
+        let source: Arc<dyn TokenSource> = Arc::new(CustomTokenSource::new({
+            let calls = calls.clone();
+            move || {
+                let n = calls.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    Ok(ConnectionDetails {
+                        server_url: "ws://localhost:7880".into(),
+                        participant_token: format!("token-{n}"),
+                    })
+                }
+            }
+        }));
+
+        assert_eq!(source.fetch().await.unwrap().participant_token, "token-0");
+        assert_eq!(source.fetch().await.unwrap().participant_token, "token-1");
+    }
+
+    #[tokio::test]
+    async fn literal_source() {
+        let source = LiteralTokenSource::new("ws://localhost:7880", "token");
+        let details = source.fetch().await.unwrap();
+        assert_eq!(details.server_url, "ws://localhost:7880");
+        assert_eq!(details.participant_token, "token");
+    }
+
+    #[test]
+    fn endpoint_payloads() {
+        let request = TokenRequest {
+            room_name: Some("room".into()),
+            participant_identity: Some("alice".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            serde_json::to_string(&request).unwrap(),
+            r#"{"roomName":"room","participantIdentity":"alice"}"#
+        );
+
+        let expected = ConnectionDetails {
+            server_url: "wss://example.livekit.cloud".into(),
+            participant_token: "token".into(),
+        };
+        let camel: ConnectionDetails = serde_json::from_str(
+            r#"{"serverUrl":"wss://example.livekit.cloud","participantToken":"token"}"#,
+        )
+        .unwrap();
+        let snake: ConnectionDetails = serde_json::from_str(
+            r#"{"server_url":"wss://example.livekit.cloud","participant_token":"token"}"#,
+        )
+        .unwrap();
+        assert_eq!(camel, expected);
+        assert_eq!(snake, expected);
+    }
+
+    #[tokio::test]
+    async fn endpoint_source_posts_request() {
+        let (url, request) = mock_endpoint(
+            200,
+            r#"{"serverUrl":"wss://example.livekit.cloud","participantToken":"token"}"#,
+        )
+        .await;
+        let source = EndpointTokenSource::new(
+            url,
+            TokenRequest {
+                room_name: Some("room".into()),
+                participant_identity: Some("alice".into()),
+                ..Default::default()
+            },
+        )
+        .with_header("Authorization", "Bearer sandbox");
+
+        let details = source.fetch().await.unwrap();
+        assert_eq!(details.server_url, "wss://example.livekit.cloud");
+        assert_eq!(details.participant_token, "token");
+
+        let (head, body) = request.await.unwrap();
+        assert!(head.starts_with("post /token "), "{head}");
+        assert!(head.contains("authorization: bearer sandbox"), "{head}");
+        assert!(head.contains("content-type: application/json"), "{head}");
+        assert_eq!(body, r#"{"roomName":"room","participantIdentity":"alice"}"#);
+    }
+
+    #[tokio::test]
+    async fn endpoint_source_errors() {
+        let (url, _) = mock_endpoint(403, "").await;
+        let result = EndpointTokenSource::new(url, TokenRequest::default()).fetch().await;
+        assert!(matches!(result, Err(TokenSourceError::Status(403))), "{result:?}");
+
+        let (url, _) = mock_endpoint(200, r#"{"serverUrl":"wss://example.livekit.cloud"}"#).await;
+        let result = EndpointTokenSource::new(url, TokenRequest::default()).fetch().await;
+        assert!(matches!(result, Err(TokenSourceError::InvalidResponse(_))), "{result:?}");
+    }
+}
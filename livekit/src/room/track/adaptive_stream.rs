@@ -0,0 +1,350 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Weak},
+    time::Duration,
+};
+
+use parking_lot::Mutex;
+
+use crate::{
+    prelude::*,
+    utils::debouncer::{self, Debouncer},
+};
+
+const ADAPTIVE_STREAM_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Size in physical pixels and visibility of a view rendering a [`RemoteVideoTrack`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VideoSinkInfo {
+    pub width: u32,
+    pub height: u32,
+    pub visible: bool,
+}
+
+impl VideoSinkInfo {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self { width, height, visible: true }
+    }
+}
+
+/// A render sink registered with [`RemoteVideoTrack::add_video_sink`].
+///
+/// Keep it alive for as long as the view renders the track and report size or visibility changes
+/// on it. Dropping it removes the sink.
+pub struct VideoSink {
+    id: u64,
+    registry: Weak<Mutex<SinkRegistry>>,
+}
+
+impl std::fmt::Debug for VideoSink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VideoSink").field("id", &self.id).finish()
+    }
+}
+
+impl VideoSink {
+    pub fn update(&self, info: VideoSinkInfo) {
+        self.modify(|sink| *sink = info);
+    }
+
+    pub fn set_size(&self, width: u32, height: u32) {
+        self.modify(|sink| {
+            sink.width = width;
+            sink.height = height;
+        });
+    }
+
+    pub fn set_visible(&self, visible: bool) {
+        self.modify(|sink| sink.visible = visible);
+    }
+
+    pub fn info(&self) -> Option<VideoSinkInfo> {
+        self.registry.upgrade()?.lock().sinks.get(&self.id).copied()
+    }
+
+    fn modify(&self, f: impl FnOnce(&mut VideoSinkInfo)) {
+        let Some(registry) = self.registry.upgrade() else {
+            return;
+        };
+        if let Some(sink) = registry.lock().sinks.get_mut(&self.id) {
+            f(sink);
+        }
+        schedule_update(&registry);
+    }
+}
+
+impl Drop for VideoSink {
+    fn drop(&mut self) {
+        let Some(registry) = self.registry.upgrade() else {
+            return;
+        };
+        registry.lock().sinks.remove(&self.id);
+        schedule_update(&registry);
+    }
+}
+
+/// Subscription settings derived from the registered sinks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct AdaptiveStreamSettings {
+    /// False when no sink is visible, the track should be paused.
+    pub enabled: bool,
+    /// Largest visible sink, only meaningful when enabled.
+    pub dimension: TrackDimension,
+}
+
+type SettingsHandler = Arc<dyn Fn(AdaptiveStreamSettings) + Send + Sync>;
+
+#[derive(Default)]
+struct SinkRegistry {
+    sinks: HashMap<u64, VideoSinkInfo>,
+    next_id: u64,
+    /// Settings sent last, `None` until the first sink is registered.
+    applied: Option<AdaptiveStreamSettings>,
+    handler: Option<SettingsHandler>,
+    debouncer: Option<Debouncer>,
+    /// Bumped on every sink change, so a flush can tell whether it raced with one.
+    generation: u64,
+}
+
+impl SinkRegistry {
+    fn settings(&self) -> AdaptiveStreamSettings {
+        let mut visible = self.sinks.values().filter(|sink| sink.visible).peekable();
+        let enabled = visible.peek().is_some();
+        let dimension = visible.fold(TrackDimension(0, 0), |TrackDimension(w, h), sink| {
+            TrackDimension(w.max(sink.width), h.max(sink.height))
+        });
+        AdaptiveStreamSettings { enabled, dimension }
+    }
+}
+
+/// Aggregates the render sinks of a remote video track.
+#[derive(Clone, Default)]
+pub(crate) struct AdaptiveStream {
+    registry: Arc<Mutex<SinkRegistry>>,
+}
+
+impl AdaptiveStream {
+    pub fn add_sink(&self, info: VideoSinkInfo) -> VideoSink {
+        let id = {
+            let mut registry = self.registry.lock();
+            let id = registry.next_id;
+            registry.next_id += 1;
+            registry.sinks.insert(id, info);
+            id
+        };
+        schedule_update(&self.registry);
+        VideoSink { id, registry: Arc::downgrade(&self.registry) }
+    }
+
+    /// Called with the new settings whenever the aggregated sinks change. Nothing is reported
+    /// until the first sink is registered, so tracks consumed without declaring sinks keep
+    /// their current settings.
+    pub fn set_handler(&self, handler: impl Fn(AdaptiveStreamSettings) + Send + Sync + 'static) {
+        let has_sinks = {
+            let mut registry = self.registry.lock();
+            registry.handler = Some(Arc::new(handler));
+            // A new subscription needs the current settings again
+            registry.applied = None;
+            registry.debouncer = None;
+            !registry.sinks.is_empty()
+        };
+        if has_sinks {
+            flush(&self.registry);
+        }
+    }
+
+    pub fn clear_handler(&self) {
+        let mut registry = self.registry.lock();
+        registry.handler = None;
+        registry.debouncer = None;
+    }
+}
+
+/// Resuming a paused track is applied immediately, every other change is debounced so resizing
+/// a view doesn't flood the SFU with track settings.
+fn schedule_update(registry: &Arc<Mutex<SinkRegistry>>) {
+    let mut guard = registry.lock();
+    if guard.handler.is_none() {
+        return;
+    }
+    guard.generation += 1;
+
+    let settings = guard.settings();
+    let resumed = settings.enabled && !matches!(guard.applied, Some(applied) if applied.enabled);
+    if resumed {
+        guard.debouncer = None;
+        drop(guard);
+        flush(registry);
+        return;
+    }
+
+    // call() returns an error if the debouncer has finished
+    if guard.debouncer.is_none() || guard.debouncer.as_ref().unwrap().call().is_err() {
+        guard.debouncer = Some(debounce_flush(registry));
+    }
+}
+
+fn debounce_flush(registry: &Arc<Mutex<SinkRegistry>>) -> Debouncer {
+    let registry = Arc::downgrade(registry);
+    debouncer::debounce(ADAPTIVE_STREAM_DEBOUNCE, async move {
+        let Some(registry) = registry.upgrade() else {
+            return;
+        };
+        let flushed = flush(&registry);
+
+        // Changes made once the timer fired only reached this debouncer, flush them again
+        let mut guard = registry.lock();
+        if guard.generation != flushed && guard.handler.is_some() {
+            guard.debouncer = Some(debounce_flush(&registry));
+        }
+    })
+}
+
+/// Reports the current settings if they changed, returns the generation they were taken at.
+fn flush(registry: &Mutex<SinkRegistry>) -> u64 {
+    let (handler, settings, generation) = {
+        let mut registry = registry.lock();
+        let generation = registry.generation;
+        let settings = registry.settings();
+        if registry.applied == Some(settings) {
+            return generation;
+        }
+        let Some(handler) = registry.handler.clone() else {
+            return generation;
+        };
+        registry.applied = Some(settings);
+        (handler, settings, generation)
+    };
+    handler(settings);
+    generation
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    fn adaptive_stream() -> (AdaptiveStream, mpsc::UnboundedReceiver<AdaptiveStreamSettings>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let stream = AdaptiveStream::default();
+        stream.set_handler(move |settings| {
+            let _ = tx.send(settings);
+        });
+        (stream, rx)
+    }
+
+    /// Runs the pending debounce timers, the clock must be paused.
+    async fn settle() {
+        // Let spawned debounce tasks arm their timers before moving the clock
+        tokio::task::yield_now().await;
+        tokio::time::advance(ADAPTIVE_STREAM_DEBOUNCE * 3).await;
+        tokio::task::yield_now().await;
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn largest_visible_sink_wins() {
+        let (stream, mut rx) = adaptive_stream();
+
+        let thumbnail = stream.add_sink(VideoSinkInfo::new(320, 180));
+        assert_eq!(
+            rx.recv().await.unwrap(),
+            AdaptiveStreamSettings { enabled: true, dimension: TrackDimension(320, 180) }
+        );
+
+        let mut main = VideoSinkInfo::new(1280, 720);
+        main.visible = false;
+        let main = stream.add_sink(main);
+        settle().await;
+        assert!(rx.try_recv().is_err(), "hidden sinks don't change the settings");
+
+        main.set_visible(true);
+        settle().await;
+        assert_eq!(rx.try_recv().unwrap().dimension, TrackDimension(1280, 720));
+
+        drop(main);
+        settle().await;
+        assert_eq!(rx.try_recv().unwrap().dimension, TrackDimension(320, 180));
+        drop(thumbnail);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn resizes_are_debounced() {
+        let (stream, mut rx) = adaptive_stream();
+        let sink = stream.add_sink(VideoSinkInfo::new(100, 100));
+        rx.recv().await.unwrap();
+
+        for size in 101..120 {
+            sink.set_size(size, size);
+        }
+        settle().await;
+        assert_eq!(rx.try_recv().unwrap().dimension, TrackDimension(119, 119));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn paused_when_nothing_is_visible() {
+        let (stream, mut rx) = adaptive_stream();
+        let sink = stream.add_sink(VideoSinkInfo::new(640, 360));
+        rx.recv().await.unwrap();
+
+        sink.set_visible(false);
+        settle().await;
+        assert!(!rx.try_recv().unwrap().enabled);
+
+        // Resuming isn't debounced
+        sink.set_visible(true);
+        assert!(rx.try_recv().unwrap().enabled);
+
+        drop(sink);
+        settle().await;
+        assert!(!rx.try_recv().unwrap().enabled);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn changes_during_flush_are_not_lost() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let stream = AdaptiveStream::default();
+        let sink = Arc::new(Mutex::new(None::<VideoSink>));
+        let resize = sink.clone();
+        stream.set_handler(move |settings| {
+            // A resize racing with the flush reporting the previous one
+            if settings.dimension == TrackDimension(200, 200) {
+                if let Some(sink) = resize.lock().as_ref() {
+                    sink.set_size(300, 300);
+                }
+            }
+            let _ = tx.send(settings);
+        });
+        let added = stream.add_sink(VideoSinkInfo::new(100, 100));
+        *sink.lock() = Some(added);
+        rx.recv().await.unwrap();
+
+        sink.lock().as_ref().unwrap().set_size(200, 200);
+        settle().await;
+        assert_eq!(rx.try_recv().unwrap().dimension, TrackDimension(200, 200));
+        settle().await;
+        assert_eq!(rx.try_recv().unwrap().dimension, TrackDimension(300, 300));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn nothing_reported_without_sinks() {
+        let (_stream, mut rx) = adaptive_stream();
+        settle().await;
+        assert!(rx.try_recv().is_err());
+    }
+}
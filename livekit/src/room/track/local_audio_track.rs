@@ -15,7 +15,14 @@
 use core::panic;
 use std::{fmt::Debug, sync::Arc};
 
-use libwebrtc::{prelude::*, stats::RtcStats};
+use libwebrtc::{
+    native::{
+        audio_processor::AudioProcessorChain, frame_cryptor::FrameCryptor,
+        frame_transformer::FrameTransformer,
+    },
+    prelude::*,
+    stats::RtcStats,
+};
 use livekit_protocol as proto;
 
 use super::TrackInner;
@@ -129,6 +136,19 @@ impl LocalAudioTrack {
         self.source.clone()
     }
 
+    /// Processors run in order on every frame captured by this track's source, before it is
+    /// encoded. Edit the returned chain to add or remove processors.
+    ///
+    /// `None` for device sources, whose capture path belongs to the platform ADM.
+    pub fn processors(&self) -> Option<AudioProcessorChain> {
+        match &self.source {
+            #[cfg(not(target_arch = "wasm32"))]
+            RtcAudioSource::Native(native_source) => Some(native_source.processors()),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
     pub fn is_remote(&self) -> bool {
         false
     }
@@ -137,6 +157,21 @@ impl LocalAudioTrack {
         super::local_track::get_stats(&self.inner).await
     }
 
+    pub(crate) fn set_frame_transformer(
+        &self,
+        transformer: Option<Arc<dyn FrameTransformer>>,
+    ) -> RoomResult<()> {
+        super::set_frame_transformer(&self.inner, false, transformer, None)
+    }
+
+    pub(crate) fn restore_frame_transformer(&self) -> RoomResult<()> {
+        super::restore_frame_transformer(&self.inner, None)
+    }
+
+    pub(crate) fn set_frame_cryptor(&self, frame_cryptor: FrameCryptor) {
+        super::set_frame_cryptor(&self.inner, frame_cryptor);
+    }
+
     pub(crate) fn on_muted(&self, f: impl Fn(Track) + Send + 'static) {
         self.inner.events.lock().muted.replace(Box::new(f));
     }
@@ -150,6 +185,7 @@ impl LocalAudioTrack {
     }
 
     pub(crate) fn set_transceiver(&self, transceiver: Option<RtpTransceiver>) {
+        super::reset_frame_transform(&self.inner);
         self.inner.info.write().transceiver = transceiver;
     }
 
@@ -15,7 +15,11 @@
 use std::sync::Arc;
 
 use libwebrtc::enum_dispatch;
-use libwebrtc::{prelude::*, stats::RtcStats};
+use libwebrtc::{
+    native::{frame_cryptor::FrameCryptor, frame_transformer::FrameTransformer},
+    prelude::*,
+    stats::RtcStats,
+};
 use livekit_protocol as proto;
 
 use super::{track_dispatch, TrackInner};
@@ -34,6 +38,13 @@ impl LocalTrack {
        [Audio, Video];
         pub fn mute(self: &Self) -> ();
         pub fn unmute(self: &Self) -> ();
+
+        pub(crate) fn set_frame_transformer(
+            self: &Self,
+            transformer: Option<Arc<dyn FrameTransformer>>
+        ) -> RoomResult<()>;
+        pub(crate) fn restore_frame_transformer(self: &Self) -> RoomResult<()>;
+        pub(crate) fn set_frame_cryptor(self: &Self, frame_cryptor: FrameCryptor) -> ();
     );
 
     pub fn rtc_track(&self) -> MediaStreamTrack {
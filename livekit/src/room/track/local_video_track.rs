@@ -20,14 +20,19 @@ use std::{
 };
 
 use libwebrtc::{
-    native::packet_trailer::{
-        self, PacketTrailerHandler, PublishTimingObserver as RtcPublishTimingObserver,
+    native::{
+        frame_cryptor::FrameCryptor,
+        frame_transformer::FrameTransformer,
+        packet_trailer::{
+            self, PacketTrailerHandler, PublishTimingObserver as RtcPublishTimingObserver,
+        },
+        video_processor::{VideoProcessor, VideoProcessorOptions, VideoProcessorStats},
     },
     prelude::*,
     stats::RtcStats,
 };
 use livekit_protocol as proto;
-use parking_lot::Mutex;
+use parking_lot::{Mutex, MutexGuard};
 use tokio::sync::broadcast;
 use tokio_stream::{wrappers::BroadcastStream, Stream};
 
@@ -107,6 +112,18 @@ pub struct LocalVideoTrack {
     source: RtcVideoSource,
     packet_trailer_handler: Arc<Mutex<Option<PacketTrailerHandler>>>,
     publish_timing_tx: Arc<Mutex<Option<broadcast::Sender<PublishTimingEvent>>>>,
+    /// Second track fed by the same source, sent with the backup codec once the SFU asks for it
+    backup_track: Arc<Mutex<Option<BackupTrack>>>,
+    /// Rids of the layers disabled through `update_encodings`. Held while the sender parameters
+    /// are read and written back, so dynacast and `update_encodings` don't undo each other
+    disabled_layer_rids: Arc<Mutex<Vec<String>>>,
+}
+
+/// The backup codec track and the qualities the SFU last requested for it, which are
+/// applied once its sender exists.
+struct BackupTrack {
+    track: LocalVideoTrack,
+    qualities: Vec<proto::SubscribedQuality>,
 }
 
 impl Debug for LocalVideoTrack {
@@ -152,6 +169,8 @@ impl LocalVideoTrack {
             source,
             packet_trailer_handler: Arc::new(Mutex::new(None)),
             publish_timing_tx: Arc::new(Mutex::new(None)),
+            backup_track: Arc::new(Mutex::new(None)),
+            disabled_layer_rids: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
@@ -208,10 +227,16 @@ impl LocalVideoTrack {
 
     pub fn mute(&self) {
         super::set_muted(&self.inner, &Track::LocalVideo(self.clone()), true);
+        if let Some(backup) = self.backup_track() {
+            backup.disable();
+        }
     }
 
     pub fn unmute(&self) {
         super::set_muted(&self.inner, &Track::LocalVideo(self.clone()), false);
+        if let Some(backup) = self.backup_track() {
+            backup.enable();
+        }
     }
 
     pub fn rtc_track(&self) -> RtcVideoTrack {
@@ -229,6 +254,36 @@ impl LocalVideoTrack {
         self.source.clone()
     }
 
+    /// Install a [`VideoProcessor`] running on every frame captured by this track's source
+    /// before it is encoded, `None` removes it.
+    ///
+    /// The processor belongs to the source, so it also applies to the backup codec track and to
+    /// any other track sharing the source.
+    pub fn set_processor(
+        &self,
+        processor: Option<Box<dyn VideoProcessor>>,
+        options: VideoProcessorOptions,
+    ) {
+        match self.rtc_source() {
+            #[cfg(not(target_arch = "wasm32"))]
+            RtcVideoSource::Native(native_source) => {
+                native_source.set_processor(processor, options)
+            }
+            #[allow(unreachable_patterns)]
+            _ => log::warn!("video processors are only supported on native sources"),
+        }
+    }
+
+    /// Frame and drop counters of the installed processor, if any.
+    pub fn processor_stats(&self) -> Option<VideoProcessorStats> {
+        match self.rtc_source() {
+            #[cfg(not(target_arch = "wasm32"))]
+            RtcVideoSource::Native(native_source) => native_source.processor_stats(),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+
     /// Returns a stream of native local video publish-pipeline timing events.
     ///
     /// Multiple concurrent subscriptions are supported; each call returns an
@@ -310,6 +365,21 @@ impl LocalVideoTrack {
         super::local_track::get_stats(&self.inner).await
     }
 
+    pub(crate) fn set_frame_transformer(
+        &self,
+        transformer: Option<Arc<dyn FrameTransformer>>,
+    ) -> RoomResult<()> {
+        super::set_frame_transformer(&self.inner, false, transformer, self.packet_trailer_handler())
+    }
+
+    pub(crate) fn restore_frame_transformer(&self) -> RoomResult<()> {
+        super::restore_frame_transformer(&self.inner, self.packet_trailer_handler())
+    }
+
+    pub(crate) fn set_frame_cryptor(&self, frame_cryptor: FrameCryptor) {
+        super::set_frame_cryptor(&self.inner, frame_cryptor);
+    }
+
     pub(crate) fn on_muted(&self, f: impl Fn(Track) + Send + 'static) {
         self.inner.events.lock().muted.replace(Box::new(f));
     }
@@ -323,9 +393,81 @@ impl LocalVideoTrack {
     }
 
     pub(crate) fn set_transceiver(&self, transceiver: Option<RtpTransceiver>) {
+        // A new sender starts with every layer enabled
+        let mut disabled_rids = self.disabled_layer_rids.lock();
+        disabled_rids.clear();
+        super::reset_frame_transform(&self.inner);
         self.inner.info.write().transceiver = transceiver;
     }
 
+    /// Locks the rids of the layers disabled through `update_encodings`. The guard must be held
+    /// while reading and writing back the sender parameters.
+    pub(crate) fn lock_disabled_layer_rids(&self) -> MutexGuard<'_, Vec<String>> {
+        self.disabled_layer_rids.lock()
+    }
+
+    pub(crate) fn backup_track(&self) -> Option<LocalVideoTrack> {
+        self.backup_track.lock().as_ref().map(|backup| backup.track.clone())
+    }
+
+    /// Records the qualities the SFU requests for the backup codec, applying them right away
+    /// if the backup sender exists. The first request enabling a quality creates the backup
+    /// track, which is returned for the caller to publish.
+    pub(crate) fn request_backup_qualities(
+        &self,
+        qualities: &[proto::SubscribedQuality],
+    ) -> RoomResult<Option<LocalVideoTrack>> {
+        let mut backup_track = self.backup_track.lock();
+        match backup_track.as_mut() {
+            Some(backup) => {
+                backup.qualities = qualities.to_vec();
+                if backup.track.transceiver().is_some() {
+                    backup.track.set_publishing_layers(qualities)?;
+                }
+                Ok(None)
+            }
+            None if qualities.iter().any(|q| q.enabled) => {
+                let track = Self::create_video_track(&self.name(), self.rtc_source());
+                if self.is_muted() {
+                    track.disable();
+                }
+                *backup_track =
+                    Some(BackupTrack { track: track.clone(), qualities: qualities.to_vec() });
+                Ok(Some(track))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Attaches the sender of the published `backup` and applies the latest qualities
+    /// requested for it. Returns `false` if `backup` was removed in the meantime.
+    pub(crate) fn attach_backup_transceiver(
+        &self,
+        backup: &LocalVideoTrack,
+        transceiver: RtpTransceiver,
+    ) -> RoomResult<bool> {
+        let backup_track = self.backup_track.lock();
+        let Some(current) = backup_track.as_ref().filter(|b| is_same_track(&b.track, backup))
+        else {
+            return Ok(false);
+        };
+        backup.set_transceiver(Some(transceiver));
+        backup.set_publishing_layers(&current.qualities)?;
+        Ok(true)
+    }
+
+    /// Forgets `backup` after failing to publish it, so that a later request tries again.
+    pub(crate) fn discard_backup_track(&self, backup: &LocalVideoTrack) {
+        let mut backup_track = self.backup_track.lock();
+        if backup_track.as_ref().is_some_and(|b| is_same_track(&b.track, backup)) {
+            *backup_track = None;
+        }
+    }
+
+    pub(crate) fn take_backup_track(&self) -> Option<LocalVideoTrack> {
+        self.backup_track.lock().take().map(|backup| backup.track)
+    }
+
     pub(crate) fn update_info(&self, info: proto::TrackInfo) {
         super::update_info(&self.inner, &Track::LocalVideo(self.clone()), info);
     }
@@ -352,10 +494,12 @@ impl LocalVideoTrack {
     /// Toggle simulcast encoding layers on/off based on subscriber demand.
     /// Used by dynacast: the SFU tells us which quality levels are needed,
     /// and we set `encoding.active` accordingly on the RTP sender.
+    /// Layers disabled through `update_encodings` stay off whatever the SFU asks for.
     pub(crate) fn set_publishing_layers(
         &self,
         qualities: &[proto::SubscribedQuality],
     ) -> RoomResult<()> {
+        let disabled_rids = self.disabled_layer_rids.lock();
         let transceiver = self.transceiver().ok_or_else(|| {
             RoomError::Internal("cannot set publishing layers: no transceiver".into())
         })?;
@@ -380,9 +524,10 @@ impl LocalVideoTrack {
                 continue;
             };
 
-            if encoding.active != subscribed.enabled {
+            let enabled = subscribed.enabled && !disabled_rids.contains(&encoding.rid);
+            if encoding.active != enabled {
                 changed = true;
-                encoding.active = subscribed.enabled;
+                encoding.active = enabled;
             }
         }
 
@@ -425,3 +570,7 @@ impl LocalVideoTrack {
         self.set_publishing_layers(&qualities)
     }
 }
+
+fn is_same_track(a: &LocalVideoTrack, b: &LocalVideoTrack) -> bool {
+    Arc::ptr_eq(&a.inner, &b.inner)
+}
@@ -15,13 +15,22 @@
 use std::{fmt::Debug, sync::Arc};
 
 use libwebrtc::enum_dispatch;
-use libwebrtc::{prelude::*, stats::RtcStats};
+use libwebrtc::{
+    native::{
+        frame_cryptor::FrameCryptor,
+        frame_transformer::{self, FrameTransformer, FrameTransformerHandler},
+        packet_trailer::PacketTrailerHandler,
+    },
+    prelude::*,
+    stats::RtcStats,
+};
 use livekit_protocol::{self as proto};
 use parking_lot::{Mutex, RwLock};
 use thiserror::Error;
 
-use crate::prelude::*;
+use crate::{prelude::*, rtc_engine::lk_runtime::LkRuntime};
 
+mod adaptive_stream;
 mod audio_track;
 mod local_audio_track;
 mod local_track;
@@ -31,6 +40,7 @@ mod remote_track;
 mod remote_video_track;
 mod video_track;
 
+pub use adaptive_stream::{VideoSink, VideoSinkInfo};
 pub use audio_track::*;
 pub use local_audio_track::*;
 pub use local_track::*;
@@ -178,10 +188,21 @@ struct TrackInfo {
     pub audio_features: Vec<proto::AudioTrackFeature>,
 }
 
+/// Application frame transformer of the track, and the E2EE cryptor it is chained with.
+/// `handler` and `frame_cryptor` belong to the current sender or receiver, while
+/// `transformer` is kept to be installed again when a local track is republished.
+#[derive(Default)]
+struct FrameTransformState {
+    transformer: Option<Arc<dyn FrameTransformer>>,
+    handler: Option<FrameTransformerHandler>,
+    frame_cryptor: Option<FrameCryptor>,
+}
+
 pub(super) struct TrackInner {
     info: RwLock<TrackInfo>,
     rtc_track: MediaStreamTrack,
     events: Mutex<TrackEvents>,
+    frame_transform: Mutex<FrameTransformState>,
 }
 
 pub(super) fn new_inner(
@@ -203,6 +224,7 @@ pub(super) fn new_inner(
         }),
         rtc_track,
         events: Default::default(),
+        frame_transform: Default::default(),
     }
 }
 
@@ -240,3 +262,71 @@ pub(super) fn update_info(inner: &Arc<TrackInner>, _track: &Track, new_info: pro
     info.audio_features =
         new_info.audio_features().into_iter().map(|item| item.try_into().unwrap()).collect();
 }
+
+/// Installs the application frame transformer on the sender (local tracks) or the receiver
+/// (remote tracks) of the track. The handler is created once per sender or receiver and chained
+/// with the E2EE cryptor, or with `packet_trailer` when the track isn't encrypted; later calls
+/// only swap the transformer.
+pub(super) fn set_frame_transformer(
+    inner: &Arc<TrackInner>,
+    remote: bool,
+    transformer: Option<Arc<dyn FrameTransformer>>,
+    packet_trailer: Option<PacketTrailerHandler>,
+) -> RoomResult<()> {
+    let mut state = inner.frame_transform.lock();
+    state.transformer = transformer.clone();
+    if let Some(handler) = state.handler.as_ref() {
+        handler.set_transformer(transformer);
+        return Ok(());
+    }
+    if transformer.is_none() {
+        return Ok(());
+    }
+
+    // An unpublished local track gets the transformer once it is published again
+    let Some(transceiver) = inner.info.read().transceiver.clone() else {
+        return Ok(());
+    };
+
+    let pc_factory = LkRuntime::instance().pc_factory();
+    let handler = if remote {
+        frame_transformer::create_receiver_handler(pc_factory, &transceiver.receiver(), transformer)
+    } else {
+        frame_transformer::create_sender_handler(pc_factory, &transceiver.sender(), transformer)
+    };
+
+    if let Some(frame_cryptor) = state.frame_cryptor.as_ref() {
+        frame_cryptor.set_frame_transformer(&handler);
+    } else if let Some(packet_trailer) = packet_trailer.as_ref() {
+        handler.set_packet_trailer_handler(packet_trailer);
+    }
+
+    state.handler = Some(handler);
+    Ok(())
+}
+
+/// Forgets the handler and cryptor of the previous sender when the transceiver of a local track
+/// changes. The transformer is kept for [`restore_frame_transformer`].
+pub(super) fn reset_frame_transform(inner: &Arc<TrackInner>) {
+    let mut state = inner.frame_transform.lock();
+    state.handler = None;
+    state.frame_cryptor = None;
+}
+
+/// Installs the transformer set before the track was unpublished on its new sender.
+pub(super) fn restore_frame_transformer(
+    inner: &Arc<TrackInner>,
+    packet_trailer: Option<PacketTrailerHandler>,
+) -> RoomResult<()> {
+    let transformer = inner.frame_transform.lock().transformer.clone();
+    set_frame_transformer(inner, false, transformer, packet_trailer)
+}
+
+/// Called by the E2EE manager once the cryptor of the track is installed.
+pub(super) fn set_frame_cryptor(inner: &Arc<TrackInner>, frame_cryptor: FrameCryptor) {
+    let mut state = inner.frame_transform.lock();
+    if let Some(handler) = state.handler.as_ref() {
+        frame_cryptor.set_frame_transformer(handler);
+    }
+    state.frame_cryptor = Some(frame_cryptor);
+}
@@ -14,7 +14,14 @@
 
 use std::{fmt::Debug, sync::Arc};
 
-use libwebrtc::{prelude::*, stats::RtcStats};
+use libwebrtc::{
+    native::{
+        audio_processor::AudioProcessorChainFactory, frame_cryptor::FrameCryptor,
+        frame_transformer::FrameTransformer,
+    },
+    prelude::*,
+    stats::RtcStats,
+};
 use livekit_protocol::{self as proto, AudioTrackFeature};
 
 use super::{remote_track, TrackInner};
@@ -94,10 +101,35 @@ impl RemoteAudioTrack {
         true
     }
 
+    /// Factories of the processors run in order on every frame of this track's playout streams.
+    ///
+    /// Every stream created from [`Self::rtc_track`] builds its own chain from these factories,
+    /// including a plain `NativeAudioStream::new`. Factories added later only apply to streams
+    /// created afterwards.
+    pub fn processors(&self) -> AudioProcessorChainFactory {
+        self.rtc_track().playout_processors()
+    }
+
     pub async fn get_stats(&self) -> RoomResult<Vec<RtcStats>> {
         super::remote_track::get_stats(&self.inner).await
     }
 
+    /// Runs `transformer` on every encoded frame received for this track, before decoding.
+    ///
+    /// On encrypted tracks the transformer sees decrypted payloads. Pass `None` to remove it.
+    ///
+    /// Fails when the track has no receiver yet.
+    pub fn set_frame_transformer(
+        &self,
+        transformer: Option<Arc<dyn FrameTransformer>>,
+    ) -> RoomResult<()> {
+        super::set_frame_transformer(&self.inner, true, transformer, None)
+    }
+
+    pub(crate) fn set_frame_cryptor(&self, frame_cryptor: FrameCryptor) {
+        super::set_frame_cryptor(&self.inner, frame_cryptor);
+    }
+
     pub(crate) fn on_muted(&self, f: impl Fn(Track) + Send + 'static) {
         self.inner.events.lock().muted.replace(Box::new(f));
     }
@@ -120,3 +152,52 @@ impl RemoteAudioTrack {
         remote_track::update_info(&self.inner, &Track::RemoteAudio(self.clone()), info);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures_util::StreamExt;
+    use libwebrtc::{
+        audio_source::native::NativeAudioSource, audio_stream::native::NativeAudioStream,
+        native::audio_processor::AudioProcessor,
+        peer_connection_factory::native::PeerConnectionFactoryExt,
+    };
+
+    use super::*;
+
+    struct Fill(i16);
+
+    impl AudioProcessor for Fill {
+        fn process(&mut self, frame: &mut AudioFrame<'_>) {
+            frame.data.to_mut().fill(self.0);
+        }
+    }
+
+    #[tokio::test]
+    async fn processors_run_on_plain_streams() {
+        let factory = PeerConnectionFactory::default();
+        let source = NativeAudioSource::new(AudioSourceOptions::default(), 48_000, 1, 0);
+        let track = RemoteAudioTrack::new(
+            TrackSid::try_from("TR_audio".to_owned()).unwrap(),
+            "audio".to_owned(),
+            factory.create_audio_track("audio", source.clone()),
+        );
+        track.processors().push(Arc::new(|| Box::new(Fill(7)) as Box<dyn AudioProcessor>));
+
+        // No processors are passed to the stream, the track's chain applies by default
+        let mut stream = NativeAudioStream::new(track.rtc_track(), 48_000, 1);
+        let frame = AudioFrame {
+            data: vec![100; 480].into(),
+            sample_rate: 48_000,
+            num_channels: 1,
+            samples_per_channel: 480,
+        };
+        source.capture_frame(&frame).await.unwrap();
+
+        let received =
+            tokio::time::timeout(Duration::from_secs(5), stream.next()).await.unwrap().unwrap();
+        assert!(!received.data.is_empty());
+        assert!(received.data.iter().all(|&sample| sample == 7));
+    }
+}
@@ -15,7 +15,11 @@
 use std::sync::Arc;
 
 use libwebrtc::enum_dispatch;
-use libwebrtc::{prelude::*, stats::RtcStats};
+use libwebrtc::{
+    native::{frame_cryptor::FrameCryptor, frame_transformer::FrameTransformer},
+    prelude::*,
+    stats::RtcStats,
+};
 use livekit_protocol as proto;
 
 use super::{track_dispatch, TrackInner};
@@ -30,6 +34,11 @@ pub enum RemoteTrack {
 impl RemoteTrack {
     track_dispatch!([Audio, Video]);
 
+    enum_dispatch!(
+        [Audio, Video];
+        pub(crate) fn set_frame_cryptor(self: &Self, frame_cryptor: FrameCryptor) -> ();
+    );
+
     #[inline]
     pub fn rtc_track(&self) -> MediaStreamTrack {
         match self {
@@ -44,6 +53,20 @@ impl RemoteTrack {
             Self::Video(track) => track.get_stats().await,
         }
     }
+
+    /// Runs `transformer` on every encoded frame received for this track, before decoding.
+    ///
+    /// On encrypted tracks the transformer sees decrypted payloads. Pass `None` to remove it.
+    ///
+    pub fn set_frame_transformer(
+        &self,
+        transformer: Option<Arc<dyn FrameTransformer>>,
+    ) -> RoomResult<()> {
+        match self {
+            Self::Audio(track) => track.set_frame_transformer(transformer),
+            Self::Video(track) => track.set_frame_transformer(transformer),
+        }
+    }
 }
 
 pub(super) async fn get_stats(inner: &Arc<TrackInner>) -> RoomResult<Vec<RtcStats>> {
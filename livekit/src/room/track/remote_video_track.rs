@@ -20,7 +20,11 @@ use std::{
 };
 
 use libwebrtc::{
-    native::packet_trailer::{self, PacketTrailerHandler},
+    native::{
+        frame_cryptor::FrameCryptor,
+        frame_transformer::FrameTransformer,
+        packet_trailer::{self, PacketTrailerHandler},
+    },
     prelude::*,
     stats::RtcStats,
 };
@@ -29,7 +33,10 @@ use parking_lot::Mutex;
 use tokio::sync::broadcast;
 use tokio_stream::{wrappers::BroadcastStream, Stream};
 
-use super::{remote_track, TrackInner};
+use super::{
+    adaptive_stream::{AdaptiveStream, AdaptiveStreamSettings},
+    remote_track, TrackInner,
+};
 use crate::{prelude::*, rtc_engine::lk_runtime::LkRuntime};
 
 pub use libwebrtc::native::packet_trailer::{SubscribeTimingEvent, SubscribeTimingStage};
@@ -40,6 +47,7 @@ const SUBSCRIBE_TIMING_BUFFER: usize = 256;
 pub struct RemoteVideoTrack {
     inner: Arc<TrackInner>,
     subscribe_timing_tx: Arc<Mutex<Option<broadcast::Sender<SubscribeTimingEvent>>>>,
+    adaptive_stream: AdaptiveStream,
 }
 
 impl Debug for RemoteVideoTrack {
@@ -83,6 +91,7 @@ impl RemoteVideoTrack {
                 MediaStreamTrack::Video(rtc_track),
             )),
             subscribe_timing_tx: Arc::new(Mutex::new(None)),
+            adaptive_stream: AdaptiveStream::default(),
         }
     }
 
@@ -207,6 +216,42 @@ impl RemoteVideoTrack {
         super::remote_track::get_stats(&self.inner).await
     }
 
+    /// Declares a view rendering this track.
+    ///
+    /// When [`RoomOptions::adaptive_stream`](crate::RoomOptions::adaptive_stream) is enabled, the
+    /// SDK requests a resolution matching the largest visible sink and pauses the track while no
+    /// sink is visible. Tracks without any registered sink are left untouched.
+    pub fn add_video_sink(&self, info: VideoSinkInfo) -> VideoSink {
+        self.adaptive_stream.add_sink(info)
+    }
+
+    pub(crate) fn on_adaptive_stream_changed(
+        &self,
+        f: impl Fn(AdaptiveStreamSettings) + Send + Sync + 'static,
+    ) {
+        self.adaptive_stream.set_handler(f);
+    }
+
+    pub(crate) fn clear_adaptive_stream_handler(&self) {
+        self.adaptive_stream.clear_handler();
+    }
+
+    /// Runs `transformer` on every encoded frame received for this track, before decoding.
+    ///
+    /// On encrypted tracks the transformer sees decrypted payloads. Pass `None` to remove it.
+    ///
+    /// Fails when the track has no receiver yet.
+    pub fn set_frame_transformer(
+        &self,
+        transformer: Option<Arc<dyn FrameTransformer>>,
+    ) -> RoomResult<()> {
+        super::set_frame_transformer(&self.inner, true, transformer, self.packet_trailer_handler())
+    }
+
+    pub(crate) fn set_frame_cryptor(&self, frame_cryptor: FrameCryptor) {
+        super::set_frame_cryptor(&self.inner, frame_cryptor);
+    }
+
     pub(crate) fn on_muted(&self, f: impl Fn(Track) + Send + 'static) {
         self.inner.events.lock().muted.replace(Box::new(f));
     }
@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use libwebrtc::prelude::*;
+use libwebrtc::{prelude::*, stats::RtcStats};
 use livekit_api::signal_client::{SignalError, SignalOptions};
 use livekit_datatrack::backend as dt;
 use livekit_protocol as proto;
@@ -30,7 +30,10 @@ use crate::{
     id::ParticipantSid,
     options::TrackPublishOptions,
     prelude::LocalTrack,
-    room::DisconnectReason,
+    room::{
+        token_source::{self, TokenSource},
+        DisconnectReason,
+    },
     rtc_engine::{
         lk_runtime::LkRuntime,
         rtc_session::{RtcSession, SessionEvent, SessionEvents},
@@ -107,6 +110,9 @@ pub struct EngineOptions {
     pub join_retries: u32,
     /// Enable single peer connection mode
     pub single_peer_connection: bool,
+    /// Consulted for fresh connection details before a full reconnect
+    /// when the current token is about to expire.
+    pub token_source: Option<Arc<dyn TokenSource>>,
 }
 
 #[derive(Debug)]
@@ -175,6 +181,10 @@ pub enum EngineEvent {
     RoomMoved {
         moved: proto::RoomMovedResponse,
     },
+    SelectedCandidatePairChanged {
+        target: proto::SignalTarget,
+        stats: Vec<RtcStats>,
+    },
     /// The following events are used to notify the room about the reconnection state
     /// Since the room needs to also sync state in a good timing with the server.
     /// We synchronize the state with a one-shot channel.
@@ -701,6 +711,11 @@ impl EngineInner {
             SessionEvent::RoomMoved { moved } => {
                 let _ = self.engine_tx.send(EngineEvent::RoomMoved { moved });
             }
+            SessionEvent::SelectedCandidatePairChanged { target, stats } => {
+                let _ = self
+                    .engine_tx
+                    .send(EngineEvent::SelectedCandidatePairChanged { target, stats });
+            }
             SessionEvent::LocalTrackSubscribed { track_sid } => {
                 let _ = self.engine_tx.send(EngineEvent::LocalTrackSubscribed { track_sid });
             }
@@ -901,7 +916,7 @@ impl EngineInner {
     async fn reconnect_task(self: &Arc<Self>) -> EngineResult<()> {
         // Get the latest connection info from the signal_client (including the refreshed token
         // because the initial join token may have expired)
-        let (url, token, e2ee_manager) = {
+        let (mut url, mut token, e2ee_manager) = {
             let running_handle = self.running_handle.read();
             let signal_client = running_handle.session.signal_client();
             let e2ee_manager = running_handle.session.e2ee_manager();
@@ -939,6 +954,8 @@ impl EngineInner {
                     let _ = rx.await;
                 }
 
+                self.refresh_token(&mut url, &mut token).await;
+
                 log::error!("restarting connection... attempt: {}", i);
                 match self
                     .try_restart_connection(
@@ -1041,6 +1058,30 @@ impl EngineInner {
         ))
     }
 
+    /// Replace `url` and `token` with fresh details from the token source, if any,
+    /// when the token is about to expire. Failures are logged and the current
+    /// details kept: the restart attempt then fails on its own if they were stale.
+    async fn refresh_token(&self, url: &mut String, token: &mut String) {
+        let Some(source) = &self.options.token_source else {
+            return;
+        };
+        if !token_source::expires_within(token, token_source::TOKEN_REFRESH_MARGIN) {
+            return;
+        }
+
+        match source.fetch().await {
+            Ok(details) => {
+                log::debug!("refreshed token from token source");
+                *url = details.server_url;
+                *token = details.participant_token;
+                let _ = self
+                    .engine_tx
+                    .send(EngineEvent::RefreshToken { url: url.clone(), token: token.clone() });
+            }
+            Err(err) => log::warn!("failed to refresh token from token source: {}", err),
+        }
+    }
+
     /// Try to recover the connection by doing a full reconnect.
     /// It recreates a new RtcSession (new peer connection, new signal client, new data channels,
     /// etc...)
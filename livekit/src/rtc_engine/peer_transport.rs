@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::{
+    collections::HashMap,
     fmt::{Debug, Formatter},
     sync::Arc,
 };
@@ -23,6 +24,10 @@ use parking_lot::Mutex;
 use tokio::sync::Mutex as AsyncMutex;
 
 use super::EngineResult;
+use crate::options::OpusOptions;
+
+/// fmtp parameter read by the native audio encoder factory to set the Opus complexity.
+const OPUS_COMPLEXITY_PARAMETER: &str = "x-lk-complexity";
 
 pub type OnOfferCreated = Box<dyn FnMut(SessionDescription) + Send + Sync>;
 
@@ -34,6 +39,10 @@ struct TransportInner {
     // Publish-side target bitrate (bps) for offer munging
     max_send_bitrate_bps: Option<u64>,
     pending_initial_offer: Option<SessionDescription>,
+    // Opus options of the published audio tracks, by track id
+    opus_options: HashMap<String, OpusOptions>,
+    // Same options by mid, resolved from the last offer to munge the answer
+    opus_options_by_mid: HashMap<String, OpusOptions>,
 }
 
 pub struct PeerTransport {
@@ -66,6 +75,8 @@ impl PeerTransport {
                 single_pc_mode,
                 max_send_bitrate_bps: None,
                 pending_initial_offer: None,
+                opus_options: HashMap::new(),
+                opus_options_by_mid: HashMap::new(),
             })),
         }
     }
@@ -117,6 +128,14 @@ impl PeerTransport {
             self.peer_connection.set_local_description(pending_offer).await?;
         }
 
+        let remote_description = if remote_description.sdp_type() == SdpType::Answer
+            && !inner.opus_options_by_mid.is_empty()
+        {
+            Self::munge_opus_answer(remote_description, &inner.opus_options_by_mid)
+        } else {
+            remote_description
+        };
+
         self.peer_connection.set_remote_description(remote_description).await?;
 
         for ic in inner.pending_candidates.drain(..) {
@@ -214,6 +233,31 @@ impl PeerTransport {
         inner.max_send_bitrate_bps = bps;
     }
 
+    /// Opus options applied to the m-section sending `track_id`, from the next offer on.
+    pub async fn set_opus_options(&self, track_id: String, options: OpusOptions) {
+        let mut inner = self.inner.lock().await;
+        inner.opus_options.insert(track_id, options);
+    }
+
+    fn munge_opus_answer(
+        answer: SessionDescription,
+        options_by_mid: &HashMap<String, OpusOptions>,
+    ) -> SessionDescription {
+        let sdp = answer.to_string();
+        let munged = Self::munge_opus_options(&sdp, options_by_mid);
+        if munged == sdp {
+            return answer;
+        }
+
+        match SessionDescription::parse(&munged, answer.sdp_type()) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                log::warn!("Failed to parse opus-munged answer, using original: {e}");
+                answer
+            }
+        }
+    }
+
     /// Maximum x-google-start-bitrate (kbps).
     /// 1 Mbps is a reasonable ceiling that prevents BWE from starting too aggressively.
     const MAX_START_BITRATE_KBPS: u32 = 1000;
@@ -339,6 +383,151 @@ impl PeerTransport {
         munged
     }
 
+    /// Mids of the audio m-sections sending a track, by track id (from `a=msid`).
+    fn audio_track_mids(sdp: &str) -> HashMap<String, String> {
+        let mut mids = HashMap::new();
+        let mut in_audio_section = false;
+        let mut mid = None;
+        let mut track_id = None;
+
+        // A trailing "m=" flushes the last section
+        for line in sdp.lines().map(str::trim).chain(std::iter::once("m=")) {
+            if line.starts_with("m=") {
+                if let (true, Some(mid), Some(track_id)) = (in_audio_section, mid, track_id) {
+                    mids.insert(track_id, mid);
+                }
+                in_audio_section = line.starts_with("m=audio ");
+                mid = None;
+                track_id = None;
+            } else if let Some(value) = line.strip_prefix("a=mid:") {
+                mid = Some(value.to_owned());
+            } else if let Some(value) = line.strip_prefix("a=msid:") {
+                track_id = value.split_whitespace().nth(1).map(str::to_owned);
+            }
+        }
+
+        mids
+    }
+
+    /// Set `key=value` in an fmtp line, replacing the existing value if any.
+    fn set_fmtp_parameter(line: &str, key: &str, value: &str) -> String {
+        let Some((prefix, params)) = line.split_once(' ') else {
+            return format!("{line} {key}={value}");
+        };
+
+        let mut found = false;
+        let mut params: Vec<String> = params
+            .split(';')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(|p| {
+                if p.split('=').next() == Some(key) {
+                    found = true;
+                    format!("{key}={value}")
+                } else {
+                    p.to_owned()
+                }
+            })
+            .collect();
+        if !found {
+            params.push(format!("{key}={value}"));
+        }
+
+        format!("{prefix} {}", params.join(";"))
+    }
+
+    /// Munge the opus fmtp and ptime of the audio m-sections listed in `options_by_mid`.
+    ///
+    /// Used on the publisher offer so the SFU knows how the track is encoded, and on its answer
+    /// since the local encoder is configured from the remote description.
+    fn munge_opus_options(sdp: &str, options_by_mid: &HashMap<String, OpusOptions>) -> String {
+        // Detect what line ending the original SDP uses
+        let uses_crlf = sdp.contains("\r\n");
+        let eol = if uses_crlf { "\r\n" } else { "\n" };
+
+        let lines: Vec<&str> =
+            if uses_crlf { sdp.split("\r\n").collect() } else { sdp.split('\n').collect() };
+
+        // 1) Group lines by m-section, the first group being the session section
+        let mut sections: Vec<Vec<&str>> = vec![Vec::new()];
+        for line in lines {
+            if line.trim().starts_with("m=") {
+                sections.push(Vec::new());
+            }
+            sections.last_mut().unwrap().push(line);
+        }
+
+        // 2) Rewrite the opus fmtp and ptime lines of the matching audio sections
+        let mut out: Vec<String> = Vec::new();
+        for section in sections {
+            let options = section
+                .first()
+                .filter(|l| l.trim().starts_with("m=audio "))
+                .and(section.iter().find_map(|l| l.trim().strip_prefix("a=mid:")))
+                .and_then(|mid| options_by_mid.get(mid));
+            let Some(options) = options else {
+                out.extend(section.iter().map(|l| l.to_string()));
+                continue;
+            };
+
+            let opus_pts: Vec<&str> = section
+                .iter()
+                .filter_map(|l| l.trim().strip_prefix("a=rtpmap:"))
+                .filter_map(|rest| {
+                    let mut it = rest.split_whitespace();
+                    let pt = it.next()?;
+                    it.next()?.to_lowercase().starts_with("opus/48000").then_some(pt)
+                })
+                .collect();
+            if opus_pts.is_empty() {
+                out.extend(section.iter().map(|l| l.to_string()));
+                continue;
+            }
+
+            let flag = |enabled: bool| if enabled { "1" } else { "0" };
+            let mut ptime_written = false;
+            for line in section {
+                // Re-added with the new value below
+                if line.trim().starts_with("a=ptime:") {
+                    continue;
+                }
+
+                let is_opus_fmtp =
+                    opus_pts.iter().any(|pt| line.trim().starts_with(&format!("a=fmtp:{pt} ")));
+                if !is_opus_fmtp {
+                    out.push(line.to_string());
+                    continue;
+                }
+
+                let mut rewritten = line.trim().to_string();
+                rewritten = Self::set_fmtp_parameter(&rewritten, "stereo", flag(options.stereo));
+                rewritten =
+                    Self::set_fmtp_parameter(&rewritten, "sprop-stereo", flag(options.stereo));
+                rewritten = Self::set_fmtp_parameter(&rewritten, "useinbandfec", flag(options.fec));
+                rewritten = Self::set_fmtp_parameter(&rewritten, "cbr", flag(options.cbr));
+                if let Some(complexity) = options.complexity {
+                    rewritten = Self::set_fmtp_parameter(
+                        &rewritten,
+                        OPUS_COMPLEXITY_PARAMETER,
+                        &complexity.min(10).to_string(),
+                    );
+                }
+                out.push(rewritten);
+                if !ptime_written {
+                    out.push(format!("a=ptime:{}", options.packet_time.as_millis()));
+                    ptime_written = true;
+                }
+            }
+        }
+
+        // Re-join using same EOL
+        let mut munged = out.join(eol);
+        if !munged.ends_with(eol) {
+            munged.push_str(eol);
+        }
+        munged
+    }
+
     /// Check if a codec string represents a video codec that should get start bitrate hint.
     fn is_video_codec(codec: &str) -> bool {
         codec.starts_with("VP8/90000")
@@ -510,6 +699,31 @@ impl PeerTransport {
             }
         }
 
+        // Apply the opus options of the published audio tracks. The mids are kept to munge the
+        // answer the same way, options of unpublished tracks are dropped.
+        if !inner.opus_options.is_empty() {
+            let track_mids = Self::audio_track_mids(&sdp);
+            inner.opus_options.retain(|track_id, _| track_mids.contains_key(track_id));
+            inner.opus_options_by_mid = inner
+                .opus_options
+                .iter()
+                .map(|(track_id, options)| (track_mids[track_id].clone(), options.clone()))
+                .collect();
+
+            let opus_munged = Self::munge_opus_options(&sdp, &inner.opus_options_by_mid);
+            if opus_munged != sdp {
+                match SessionDescription::parse(&opus_munged, offer.sdp_type()) {
+                    Ok(parsed) => {
+                        offer = parsed;
+                        sdp = opus_munged;
+                    }
+                    Err(e) => {
+                        log::warn!("Failed to parse opus-munged SDP, using original: {e}");
+                    }
+                }
+            }
+        }
+
         // Apply x-google-start-bitrate for all video codecs to improve initial quality.
         // Uses min(90% of target, 1 Mbps) to prevent BWE from starting too aggressively.
         let has_video = sdp.contains(" VP8/90000")
@@ -552,7 +766,10 @@ impl PeerTransport {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
     use super::PeerTransport;
+    use crate::options::{OpusOptions, OpusPacketTime};
 
     /// Reproduces the publisher-transport self-deadlock.
     ///
@@ -590,6 +807,7 @@ mod tests {
             ice_servers: vec![],
             continual_gathering_policy: ContinualGatheringPolicy::GatherOnce,
             ice_transport_type: IceTransportsType::All,
+            ..Default::default()
         };
 
         let alice_pc = factory.create_peer_connection(config.clone()).unwrap();
@@ -941,4 +1159,71 @@ a=fmtp:111 minptime=10;stereo=1\n";
         let out = PeerTransport::munge_stereo_for_audio(sdp);
         assert_eq!(out.matches("stereo=1").count(), 1);
     }
+
+    const TWO_AUDIO_TRACKS_SDP: &str = "v=0\n\
+o=- 0 0 IN IP4 127.0.0.1\n\
+s=-\n\
+t=0 0\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111 63\n\
+a=mid:0\n\
+a=sendonly\n\
+a=msid:- TR_music\n\
+a=rtpmap:111 opus/48000/2\n\
+a=fmtp:111 minptime=10;useinbandfec=1\n\
+a=rtpmap:63 red/48000/2\n\
+a=fmtp:63 111/111\n\
+m=audio 9 UDP/TLS/RTP/SAVPF 111\n\
+a=mid:1\n\
+a=sendonly\n\
+a=msid:- TR_voice\n\
+a=rtpmap:111 opus/48000/2\n\
+a=fmtp:111 minptime=10;useinbandfec=1\n\
+a=ptime:20\n";
+
+    #[test]
+    fn audio_track_mids_are_read_from_msid() {
+        let mids = PeerTransport::audio_track_mids(TWO_AUDIO_TRACKS_SDP);
+        assert_eq!(mids.len(), 2);
+        assert_eq!(mids["TR_music"], "0");
+        assert_eq!(mids["TR_voice"], "1");
+    }
+
+    #[test]
+    fn opus_options_are_applied_to_the_matching_section_only() {
+        let options = OpusOptions {
+            stereo: true,
+            fec: false,
+            packet_time: OpusPacketTime::Ms40,
+            complexity: Some(10),
+            cbr: true,
+        };
+        let options_by_mid = HashMap::from([("0".to_owned(), options)]);
+
+        let out = PeerTransport::munge_opus_options(TWO_AUDIO_TRACKS_SDP, &options_by_mid);
+        let (music, voice) = out.split_once("m=audio 9 UDP/TLS/RTP/SAVPF 111\n").unwrap();
+
+        assert!(music.contains(
+            "a=fmtp:111 minptime=10;useinbandfec=0;stereo=1;sprop-stereo=1;cbr=1;\
+             x-lk-complexity=10\na=ptime:40\n"
+        ));
+        // RED is left alone
+        assert!(music.contains("a=fmtp:63 111/111\n"));
+        assert!(voice.contains("a=fmtp:111 minptime=10;useinbandfec=1\na=ptime:20\n"));
+    }
+
+    #[test]
+    fn opus_munging_replaces_existing_parameters_and_ptime() {
+        let options = OpusOptions { packet_time: OpusPacketTime::Ms10, ..Default::default() };
+        let options_by_mid = HashMap::from([("1".to_owned(), options)]);
+
+        let once = PeerTransport::munge_opus_options(TWO_AUDIO_TRACKS_SDP, &options_by_mid);
+        let twice = PeerTransport::munge_opus_options(&once, &options_by_mid);
+        assert_eq!(once, twice);
+
+        let (_, voice) = once.split_once("a=mid:1\n").unwrap();
+        assert!(voice.contains(
+            "a=fmtp:111 minptime=10;useinbandfec=1;stereo=0;sprop-stereo=0;cbr=0\na=ptime:10\n"
+        ));
+        assert_eq!(voice.matches("a=ptime:").count(), 1);
+    }
 }
@@ -38,6 +38,10 @@ pub enum RtcEvent {
         state: PeerConnectionState,
         target: proto::SignalTarget,
     },
+    SelectedCandidatePairChanged {
+        reason: String,
+        target: proto::SignalTarget,
+    },
     DataChannel {
         data_channel: DataChannel,
         target: proto::SignalTarget,
@@ -77,6 +81,16 @@ fn on_connection_state_change(
     })
 }
 
+fn on_ice_selected_candidate_pair_changed(
+    target: proto::SignalTarget,
+    emitter: RtcEmitter,
+) -> rtc::peer_connection::OnIceSelectedCandidatePairChanged {
+    Box::new(move |event| {
+        let _ =
+            emitter.send(RtcEvent::SelectedCandidatePairChanged { reason: event.reason, target });
+    })
+}
+
 fn on_ice_candidate(
     target: proto::SignalTarget,
     emitter: RtcEmitter,
@@ -151,6 +165,10 @@ pub fn forward_pc_events(transport: &mut PeerTransport, rtc_emitter: RtcEmitter)
         .peer_connection()
         .on_ice_candidate_error(Some(on_ice_candidate_error(signal_target, rtc_emitter.clone())));
 
+    transport.peer_connection().on_ice_selected_candidate_pair_changed(Some(
+        on_ice_selected_candidate_pair_changed(signal_target, rtc_emitter.clone()),
+    ));
+
     transport.on_offer(Some(on_offer(signal_target, rtc_emitter)));
 }
 
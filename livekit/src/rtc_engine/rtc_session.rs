@@ -84,6 +84,13 @@ pub const DEFAULT_MAX_MESSAGE_SIZE: u64 = 64000;
 /// before SCTP accepts them — queueing below that is bounded by OS/qdisc.
 pub const DATA_TRACK_BUFFERED_AMOUNT_LOW_THRESHOLD: u64 = 8 * 1024;
 
+/// Reserved user packet topic carrying data track NACKs from subscribers
+/// back to the publisher of a reliable track.
+///
+/// The SFU does not relay data track feedback, so NACKs are delivered
+/// out-of-band over the reliable data channel and intercepted on receipt.
+pub const DATA_TRACK_NACK_TOPIC: &str = "lk.data_track.nack";
+
 #[derive(Debug)]
 enum NegotiationState {
     Idle,
@@ -180,6 +187,10 @@ pub enum SessionEvent {
     RoomMoved {
         moved: proto::RoomMovedResponse,
     },
+    SelectedCandidatePairChanged {
+        target: proto::SignalTarget,
+        stats: Vec<RtcStats>,
+    },
     LocalTrackSubscribed {
         track_sid: String,
     },
@@ -928,6 +939,21 @@ impl RtcSession {
                     .send(proto::signal_request::Message::UpdateDataSubscription(event.into()))
                     .await
             }
+            OutputEvent::NackAvailable(event) => {
+                let data = proto::DataPacket {
+                    kind: DataPacketKind::Reliable as i32,
+                    destination_identities: vec![event.publisher_identity],
+                    value: Some(proto::data_packet::Value::User(proto::UserPacket {
+                        payload: event.payload.into(),
+                        topic: Some(DATA_TRACK_NACK_TOPIC.to_string()),
+                        ..Default::default()
+                    })),
+                    ..Default::default()
+                };
+                if let Err(err) = self.publish_data(data, DataPacketKind::Reliable, false).await {
+                    log::debug!("Failed to send data track NACK: {}", err);
+                }
+            }
             _ => {}
         }
     }
@@ -950,6 +976,8 @@ impl RtcSession {
                 stale_bytes,
                 packet_count,
             );
+            let event = dt::local::InputEvent::PacketsDropped(stale);
+            _ = self.inner.emitter.send(SessionEvent::LocalDataTrackInput(event));
         }
     }
 
@@ -1594,6 +1622,33 @@ impl SessionInner {
                     );
                 }
             }
+            RtcEvent::SelectedCandidatePairChanged { reason, target } => {
+                log::debug!("selected candidate pair changed ({}) {:?}", reason, target);
+
+                let pc = match target {
+                    SignalTarget::Publisher => Some(self.publisher_pc.peer_connection()),
+                    SignalTarget::Subscriber => {
+                        self.subscriber_pc.as_ref().map(PeerTransport::peer_connection)
+                    }
+                };
+                // Don't hold the rtc events loop while the stats are collected
+                if let Some(pc) = pc {
+                    let emitter = self.emitter.clone();
+                    livekit_runtime::spawn(async move {
+                        match pc.get_stats().await {
+                            Ok(stats) => {
+                                let _ = emitter.send(SessionEvent::SelectedCandidatePairChanged {
+                                    target,
+                                    stats,
+                                });
+                            }
+                            Err(err) => {
+                                log::warn!("failed to get stats for {:?}: {:?}", target, err)
+                            }
+                        }
+                    });
+                }
+            }
             RtcEvent::DataChannel { data_channel, target } => {
                 // In single PC mode, subscriber data channels come from publisher target
                 let is_subscriber_dc = if self.single_pc_mode {
@@ -1649,6 +1704,9 @@ impl SessionInner {
                 let mut packet = proto::DataPacket::decode(&*data).map_err(|err| {
                     EngineError::Internal(format!("failed to decode data packet: {}", err).into())
                 })?;
+                if let Some(recorder) = &self.options.signal_options.recorder {
+                    recorder.record_data_packet(&mut packet, true);
+                }
                 if kind == DataPacketKind::Reliable {
                     self.update_packet_rx_state(&packet);
                 }
@@ -1697,6 +1755,11 @@ impl SessionInner {
                     participant_sid.or_else(|| user.participant_sid.try_into().ok());
                 let participant_identity =
                     participant_identity.or_else(|| user.participant_identity.try_into().ok());
+                if user.topic.as_deref() == Some(DATA_TRACK_NACK_TOPIC) {
+                    let event = dt::local::InputEvent::NackReceived(user.payload.into());
+                    _ = self.emitter.send(SessionEvent::LocalDataTrackInput(event));
+                    return;
+                }
                 self.emitter.send(SessionEvent::Data {
                     kind,
                     participant_sid,
@@ -1916,6 +1979,10 @@ impl SessionInner {
         let transceiver =
             self.publisher_pc.peer_connection().add_transceiver(track.rtc_track(), init)?;
 
+        if let (TrackKind::Audio, Some(opus)) = (track.kind(), options.opus.clone()) {
+            self.publisher_pc.set_opus_options(track.rtc_track().id(), opus).await;
+        }
+
         if track.kind() == TrackKind::Video {
             transceiver.sender().set_video_encoder_backend(options.video_encoder);
 
@@ -1949,7 +2016,11 @@ impl SessionInner {
 
             matched.append(&mut partial_matched);
 
-            transceiver.set_codec_preferences(matched)?;
+            if let Err(err) = transceiver.set_codec_preferences(matched) {
+                // Don't leave a half configured sender on the publisher
+                let _ = self.publisher_pc.peer_connection().remove_track(transceiver.sender());
+                return Err(err.into());
+            }
         }
 
         Ok(transceiver)
@@ -2167,6 +2238,10 @@ impl SessionInner {
             packet.value = packet_value;
         }
 
+        if let Some(recorder) = &self.options.signal_options.recorder {
+            recorder.record_data_packet(&mut packet, false);
+        }
+
         // Send through the queue with backpressure
         let (completion_tx, completion_rx) = oneshot::channel();
         let ev = DataChannelEvent {
@@ -0,0 +1,129 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+
+use livekit_protocol as proto;
+use parking_lot::{Mutex, RwLock};
+
+use crate::id::ParticipantIdentity;
+
+/// What the fake SFU does with a signal message matched by a fault hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalFault {
+    /// Handle or send the message as usual.
+    Deliver,
+    /// Silently discard the message.
+    Drop,
+    /// Handle or send the message after the given delay. Delayed messages may
+    /// be reordered with respect to later ones.
+    Delay(Duration),
+}
+
+type SignalHook<T> = Box<dyn Fn(&ParticipantIdentity, &T) -> SignalFault + Send + Sync>;
+
+/// Fault injection hooks of a [`FakeSfu`](super::FakeSfu).
+///
+/// All faults are off by default and can be changed while rooms are connected.
+#[derive(Default)]
+pub struct FaultInjector {
+    request_hook: RwLock<Option<SignalHook<proto::signal_request::Message>>>,
+    response_hook: RwLock<Option<SignalHook<proto::signal_response::Message>>>,
+    drop_data_packets: AtomicBool,
+    reject_status: Mutex<Option<u16>>,
+}
+
+impl FaultInjector {
+    /// Sets a hook deciding the fate of each signal request received from a participant.
+    pub fn on_signal_request(
+        &self,
+        hook: impl Fn(&ParticipantIdentity, &proto::signal_request::Message) -> SignalFault
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        *self.request_hook.write() = Some(Box::new(hook));
+    }
+
+    /// Sets a hook deciding the fate of each signal response sent to a participant.
+    pub fn on_signal_response(
+        &self,
+        hook: impl Fn(&ParticipantIdentity, &proto::signal_response::Message) -> SignalFault
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        *self.response_hook.write() = Some(Box::new(hook));
+    }
+
+    /// Drops every data packet instead of relaying it, for both reliable and lossy kinds.
+    pub fn set_drop_data_packets(&self, drop: bool) {
+        self.drop_data_packets.store(drop, Ordering::Release);
+    }
+
+    /// Fails new signal connections, including resumes, with the given HTTP status.
+    pub fn reject_connections(&self, status: Option<u16>) {
+        *self.reject_status.lock() = status;
+    }
+
+    /// Removes all hooks and turns every fault off.
+    pub fn clear(&self) {
+        self.request_hook.write().take();
+        self.response_hook.write().take();
+        self.set_drop_data_packets(false);
+        self.reject_connections(None);
+    }
+
+    pub(super) fn request_fault(
+        &self,
+        identity: &ParticipantIdentity,
+        request: &proto::signal_request::Message,
+    ) -> SignalFault {
+        self.request_hook
+            .read()
+            .as_ref()
+            .map_or(SignalFault::Deliver, |hook| hook(identity, request))
+    }
+
+    pub(super) fn response_fault(
+        &self,
+        identity: &ParticipantIdentity,
+        response: &proto::signal_response::Message,
+    ) -> SignalFault {
+        self.response_hook
+            .read()
+            .as_ref()
+            .map_or(SignalFault::Deliver, |hook| hook(identity, response))
+    }
+
+    pub(super) fn drops_data_packets(&self) -> bool {
+        self.drop_data_packets.load(Ordering::Acquire)
+    }
+
+    pub(super) fn reject_status(&self) -> Option<u16> {
+        *self.reject_status.lock()
+    }
+}
+
+impl std::fmt::Debug for FaultInjector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FaultInjector")
+            .field("drop_data_packets", &self.drops_data_packets())
+            .field("reject_status", &self.reject_status())
+            .finish_non_exhaustive()
+    }
+}
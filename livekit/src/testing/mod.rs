@@ -0,0 +1,54 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! In-process fake SFU for hermetic [`Room`](crate::Room) tests.
+//!
+//! [`FakeSfu`] serves the signaling protocol over an in-memory transport
+//! registered with livekit-net, so rooms connect to [`FakeSfu::url`] like to
+//! any server. It issues join responses, answers and sends offers on two
+//! loopback peer connections per participant, and relays media tracks and
+//! data packets (and therefore RPC and data streams) between the rooms joined
+//! to the same room name.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use livekit::{testing::FakeSfu, Room, RoomOptions};
+//!
+//! let sfu = FakeSfu::start()?;
+//! let (alice, _) =
+//!     Room::connect(sfu.url(), &sfu.token("room", "alice"), RoomOptions::default()).await?;
+//! let (bob, _) =
+//!     Room::connect(sfu.url(), &sfu.token("room", "bob"), RoomOptions::default()).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Faults are injected through [`FakeSfu::faults`] and the disconnect helpers
+//! on [`FakeSfu`].
+//! Sessions recorded with a [`SignalRecorder`](crate::recording::SignalRecorder)
+//! are replayed into a connected room with [`FakeSfu::replay`].
+//!
+//! The fake SFU is not a conforming server. It only speaks the dual peer
+//! connection protocol, subscribes everyone to every track, relays a single
+//! layer of each track, and does not handle data tracks, E2EE key exchange,
+//! speaker or connection quality updates.
+
+mod fault;
+mod participant;
+mod relay;
+mod server;
+mod transport;
+
+pub use fault::{FaultInjector, SignalFault};
+pub use server::{FakeSfu, FakeSfuError, FakeSfuOptions};
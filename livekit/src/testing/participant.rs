@@ -0,0 +1,632 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Weak,
+    },
+};
+
+use libwebrtc::{peer_connection::TrackEvent, prelude::*, session_description::SdpParseError};
+use livekit_api::access_token::Claims;
+use livekit_net::WsConnection;
+use livekit_protocol as proto;
+use parking_lot::Mutex;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::mpsc;
+
+use super::{
+    fault::SignalFault,
+    relay::RelayedTrack,
+    server::{FakeRoom, SfuInner},
+    transport::MemoryConnection,
+};
+use crate::{id::ParticipantIdentity, rtc_engine::lk_runtime::LkRuntime, DisconnectReason};
+
+const RELIABLE_DC_LABEL: &str = "_reliable";
+const LOSSY_DC_LABEL: &str = "_lossy";
+
+pub(super) fn random_sid(prefix: &str) -> String {
+    let uuid = libwebrtc::native::create_random_uuid().replace('-', "");
+    format!("{prefix}{}", &uuid[..12])
+}
+
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IceCandidateJson {
+    sdp_mid: String,
+    sdp_m_line_index: i32,
+    candidate: String,
+}
+
+#[derive(Debug, Error)]
+enum HandleError {
+    #[error(transparent)]
+    Rtc(#[from] RtcError),
+    #[error(transparent)]
+    Sdp(#[from] SdpParseError),
+    #[error("invalid trickle candidate: {0}")]
+    Trickle(#[from] serde_json::Error),
+}
+
+enum ParticipantEvent {
+    Request(proto::signal_request::Message),
+    IceCandidate(proto::SignalTarget, IceCandidate),
+    Track(TrackEvent),
+    DataChannel(DataChannel),
+    Subscribe(Arc<RelayedTrack>),
+    Unsubscribe(String),
+    Negotiate,
+    Close,
+}
+
+/// State owned by the participant task.
+#[derive(Default)]
+struct TaskState {
+    /// Tracks announced with `AddTrack` whose media has not arrived yet, by cid.
+    pending_tracks: HashMap<String, proto::TrackInfo>,
+    /// Senders of relayed tracks on the subscriber connection, by track sid.
+    senders: HashMap<String, RtpSender>,
+    /// Data channels opened by the client on the publisher connection.
+    data_channels: Vec<DataChannel>,
+    offer_in_flight: bool,
+    renegotiate: bool,
+}
+
+/// Server side of one connected participant: a publisher connection receiving
+/// the client's media and data, and a subscriber connection sending everyone
+/// else's.
+pub(super) struct FakeParticipant {
+    sid: String,
+    identity: ParticipantIdentity,
+    sfu: Weak<SfuInner>,
+    room: Weak<FakeRoom>,
+    info: Mutex<proto::ParticipantInfo>,
+    signal: Mutex<Option<Arc<MemoryConnection>>>,
+    events: mpsc::UnboundedSender<ParticipantEvent>,
+    publisher_pc: PeerConnection,
+    subscriber_pc: PeerConnection,
+    reliable_dc: DataChannel,
+    lossy_dc: DataChannel,
+    published: Mutex<Vec<Arc<RelayedTrack>>>,
+    closed: AtomicBool,
+}
+
+impl FakeParticipant {
+    pub fn new(
+        sfu: &Arc<SfuInner>,
+        room: &Arc<FakeRoom>,
+        claims: &Claims,
+        signal: MemoryConnection,
+    ) -> Result<Arc<Self>, RtcError> {
+        let factory = LkRuntime::instance();
+        let publisher_pc = factory.pc_factory().create_peer_connection(Default::default())?;
+        let subscriber_pc = factory.pc_factory().create_peer_connection(Default::default())?;
+        let reliable_dc = subscriber_pc.create_data_channel(
+            RELIABLE_DC_LABEL,
+            DataChannelInit { ordered: true, ..Default::default() },
+        )?;
+        let lossy_dc = subscriber_pc.create_data_channel(
+            LOSSY_DC_LABEL,
+            DataChannelInit { ordered: false, max_retransmits: Some(0), ..Default::default() },
+        )?;
+
+        let sid = random_sid("PA_");
+        let joined_at = now_ms();
+        let grants = &claims.video;
+        let info = proto::ParticipantInfo {
+            sid: sid.clone(),
+            identity: claims.sub.clone(),
+            name: claims.name.clone(),
+            metadata: claims.metadata.clone(),
+            attributes: claims.attributes.clone(),
+            state: proto::participant_info::State::Active as i32,
+            joined_at: joined_at / 1000,
+            joined_at_ms: joined_at,
+            version: 1,
+            permission: Some(proto::ParticipantPermission {
+                can_subscribe: grants.can_subscribe,
+                can_publish: grants.can_publish,
+                can_publish_data: grants.can_publish_data,
+                can_update_metadata: grants.can_update_own_metadata,
+                hidden: grants.hidden,
+                recorder: grants.recorder,
+                ..Default::default()
+            }),
+            region: sfu.options.region.clone(),
+            ..Default::default()
+        };
+
+        let (events, events_rx) = mpsc::unbounded_channel();
+        let participant = Arc::new(Self {
+            sid,
+            identity: claims.sub.clone().into(),
+            sfu: Arc::downgrade(sfu),
+            room: Arc::downgrade(room),
+            info: Mutex::new(info),
+            signal: Default::default(),
+            events,
+            publisher_pc,
+            subscriber_pc,
+            reliable_dc,
+            lossy_dc,
+            published: Default::default(),
+            closed: Default::default(),
+        });
+        participant.forward_pc_events();
+        participant.attach_signal(signal);
+        livekit_runtime::spawn(participant.clone().run(events_rx));
+        Ok(participant)
+    }
+
+    pub fn sid(&self) -> &str {
+        &self.sid
+    }
+
+    pub fn identity(&self) -> &ParticipantIdentity {
+        &self.identity
+    }
+
+    pub fn info(&self) -> proto::ParticipantInfo {
+        self.info.lock().clone()
+    }
+
+    pub fn published_tracks(&self) -> Vec<Arc<RelayedTrack>> {
+        self.published.lock().clone()
+    }
+
+    pub fn subscribe(&self, track: Arc<RelayedTrack>) {
+        let _ = self.events.send(ParticipantEvent::Subscribe(track));
+    }
+
+    pub fn unsubscribe(&self, track_sid: &str) {
+        let _ = self.events.send(ParticipantEvent::Unsubscribe(track_sid.to_owned()));
+    }
+
+    /// Sends a new subscriber offer, e.g. to open the data channels after joining.
+    pub fn negotiate(&self) {
+        let _ = self.events.send(ParticipantEvent::Negotiate);
+    }
+
+    /// Sends a signal response, subject to the response fault hook.
+    pub fn send(self: &Arc<Self>, message: proto::signal_response::Message) {
+        let fault = self.sfu.upgrade().map_or(SignalFault::Deliver, |sfu| {
+            sfu.faults.response_fault(&self.identity, &message)
+        });
+        match fault {
+            SignalFault::Deliver => self.deliver(message),
+            SignalFault::Drop => log::debug!("fake SFU dropped response to {}", self.identity),
+            SignalFault::Delay(delay) => {
+                let participant = self.clone();
+                livekit_runtime::spawn(async move {
+                    livekit_runtime::sleep(delay).await;
+                    participant.deliver(message);
+                });
+            }
+        }
+    }
+
+    fn deliver(&self, message: proto::signal_response::Message) {
+        let frame = proto::SignalResponse { message: Some(message) }.encode_to_vec();
+        if let Some(signal) = self.signal.lock().as_ref() {
+            let _ = signal.try_send(frame);
+        }
+    }
+
+    pub fn send_leave(
+        self: &Arc<Self>,
+        reason: DisconnectReason,
+        action: proto::leave_request::Action,
+    ) {
+        self.send(proto::signal_response::Message::Leave(proto::LeaveRequest {
+            can_reconnect: action != proto::leave_request::Action::Disconnect,
+            reason: reason as i32,
+            action: action as i32,
+            regions: None,
+        }));
+    }
+
+    pub fn close_signal(&self) {
+        if let Some(signal) = self.signal.lock().take() {
+            signal.shutdown();
+        }
+    }
+
+    /// Replaces the signal connection after the client resumed its session.
+    pub fn resume(self: &Arc<Self>, signal: MemoryConnection, sfu: &SfuInner) {
+        self.attach_signal(signal);
+        self.send(proto::signal_response::Message::Reconnect(proto::ReconnectResponse {
+            server_info: Some(sfu.server_info()),
+            ..Default::default()
+        }));
+    }
+
+    /// Stops the participant task and returns the participant's final info.
+    pub fn close(&self, reason: DisconnectReason) -> proto::ParticipantInfo {
+        self.closed.store(true, Ordering::Release);
+        self.published.lock().clear();
+        self.close_signal();
+        let _ = self.events.send(ParticipantEvent::Close);
+
+        let mut info = self.info.lock();
+        info.state = proto::participant_info::State::Disconnected as i32;
+        info.disconnect_reason = reason as i32;
+        info.version += 1;
+        info.clone()
+    }
+
+    /// Sends a relayed data packet to the client on the matching data channel.
+    pub fn send_data(&self, kind: proto::data_packet::Kind, packet: &proto::DataPacket) {
+        let dc = match kind {
+            proto::data_packet::Kind::Lossy => &self.lossy_dc,
+            proto::data_packet::Kind::Reliable => &self.reliable_dc,
+        };
+        if dc.state() != DataChannelState::Open {
+            log::debug!("fake SFU dropped data packet to {}, channel not open", self.identity);
+            return;
+        }
+        if let Err(err) = dc.send(&packet.encode_to_vec(), true) {
+            log::warn!("fake SFU failed to send data packet to {}: {:?}", self.identity, err);
+        }
+    }
+
+    fn attach_signal(self: &Arc<Self>, signal: MemoryConnection) {
+        let signal = Arc::new(signal);
+        if let Some(previous) = self.signal.lock().replace(signal.clone()) {
+            previous.shutdown();
+        }
+        livekit_runtime::spawn(Self::read_signal(Arc::downgrade(self), signal));
+    }
+
+    async fn read_signal(participant: Weak<Self>, signal: Arc<MemoryConnection>) {
+        while let Ok(Some(frame)) = signal.recv().await {
+            let Some(participant) = participant.upgrade() else { break };
+            let message = match proto::SignalRequest::decode(frame.as_slice()) {
+                Ok(proto::SignalRequest { message: Some(message) }) => message,
+                Ok(_) => continue,
+                Err(err) => {
+                    log::warn!("fake SFU failed to decode SignalRequest: {:?}", err);
+                    continue;
+                }
+            };
+
+            let fault = participant.sfu.upgrade().map_or(SignalFault::Deliver, |sfu| {
+                sfu.faults.request_fault(&participant.identity, &message)
+            });
+            match fault {
+                SignalFault::Deliver => {
+                    let _ = participant.events.send(ParticipantEvent::Request(message));
+                }
+                SignalFault::Drop => {
+                    log::debug!("fake SFU dropped request from {}", participant.identity)
+                }
+                SignalFault::Delay(delay) => {
+                    let events = participant.events.clone();
+                    livekit_runtime::spawn(async move {
+                        livekit_runtime::sleep(delay).await;
+                        let _ = events.send(ParticipantEvent::Request(message));
+                    });
+                }
+            }
+        }
+    }
+
+    fn forward_pc_events(self: &Arc<Self>) {
+        for (pc, target) in [
+            (&self.publisher_pc, proto::SignalTarget::Publisher),
+            (&self.subscriber_pc, proto::SignalTarget::Subscriber),
+        ] {
+            let events = self.events.clone();
+            pc.on_ice_candidate(Some(Box::new(move |candidate| {
+                let _ = events.send(ParticipantEvent::IceCandidate(target, candidate));
+            })));
+        }
+
+        let events = self.events.clone();
+        self.publisher_pc.on_track(Some(Box::new(move |event| {
+            let _ = events.send(ParticipantEvent::Track(event));
+        })));
+
+        let events = self.events.clone();
+        self.publisher_pc.on_data_channel(Some(Box::new(move |dc| {
+            let _ = events.send(ParticipantEvent::DataChannel(dc));
+        })));
+    }
+
+    async fn run(self: Arc<Self>, mut events: mpsc::UnboundedReceiver<ParticipantEvent>) {
+        let mut state = TaskState::default();
+        while let Some(event) = events.recv().await {
+            if let ParticipantEvent::Close = event {
+                break;
+            }
+            if let Err(err) = self.handle_event(event, &mut state).await {
+                log::warn!(
+                    "fake SFU participant {} failed to handle event: {}",
+                    self.identity,
+                    err
+                );
+            }
+        }
+
+        self.publisher_pc.on_ice_candidate(None);
+        self.publisher_pc.on_track(None);
+        self.publisher_pc.on_data_channel(None);
+        self.subscriber_pc.on_ice_candidate(None);
+        for dc in state.data_channels.iter().chain([&self.reliable_dc, &self.lossy_dc]) {
+            dc.on_message(None);
+            dc.close();
+        }
+        self.publisher_pc.close();
+        self.subscriber_pc.close();
+    }
+
+    async fn handle_event(
+        self: &Arc<Self>,
+        event: ParticipantEvent,
+        state: &mut TaskState,
+    ) -> Result<(), HandleError> {
+        match event {
+            ParticipantEvent::Request(request) => self.handle_request(request, state).await?,
+            ParticipantEvent::IceCandidate(target, candidate) => {
+                let candidate_init = serde_json::to_string(&IceCandidateJson {
+                    sdp_mid: candidate.sdp_mid(),
+                    sdp_m_line_index: candidate.sdp_mline_index(),
+                    candidate: candidate.candidate(),
+                })?;
+                self.send(proto::signal_response::Message::Trickle(proto::TrickleRequest {
+                    candidate_init,
+                    target: target as i32,
+                    r#final: false,
+                }));
+            }
+            ParticipantEvent::Track(event) => {
+                let cid = event.track.id();
+                let Some(info) = state.pending_tracks.remove(&cid) else {
+                    log::warn!(
+                        "fake SFU received unannounced track {} from {}",
+                        cid,
+                        self.identity
+                    );
+                    return Ok(());
+                };
+                let track = Arc::new(RelayedTrack::new(&self.sid, &info, event.track));
+                self.published.lock().push(track.clone());
+                if let Some(room) = self.room.upgrade() {
+                    room.publish(self, track);
+                }
+            }
+            ParticipantEvent::DataChannel(dc) => {
+                let kind = match dc.label().as_str() {
+                    RELIABLE_DC_LABEL => proto::data_packet::Kind::Reliable,
+                    LOSSY_DC_LABEL => proto::data_packet::Kind::Lossy,
+                    _ => return Ok(()),
+                };
+                let participant = Arc::downgrade(self);
+                dc.on_message(Some(Box::new(move |buffer: DataBuffer| {
+                    if let Some(participant) = participant.upgrade() {
+                        participant.on_data(kind, buffer.data);
+                    }
+                })));
+                state.data_channels.push(dc);
+            }
+            ParticipantEvent::Subscribe(track) => {
+                if state.senders.contains_key(&track.sid) {
+                    return Ok(());
+                }
+                let sender =
+                    self.subscriber_pc.add_track(track.track.clone(), &[&track.stream_id])?;
+                state.senders.insert(track.sid.clone(), sender);
+                self.negotiate_subscriber(state).await?;
+            }
+            ParticipantEvent::Unsubscribe(track_sid) => {
+                if let Some(sender) = state.senders.remove(&track_sid) {
+                    self.subscriber_pc.remove_track(sender)?;
+                    self.negotiate_subscriber(state).await?;
+                }
+            }
+            ParticipantEvent::Negotiate => self.negotiate_subscriber(state).await?,
+            ParticipantEvent::Close => {}
+        }
+        Ok(())
+    }
+
+    async fn handle_request(
+        self: &Arc<Self>,
+        request: proto::signal_request::Message,
+        state: &mut TaskState,
+    ) -> Result<(), HandleError> {
+        use proto::signal_request::Message as Request;
+        use proto::signal_response::Message as Response;
+
+        match request {
+            Request::Offer(offer) => {
+                let sdp = SessionDescription::parse(&offer.sdp, SdpType::Offer)?;
+                self.publisher_pc.set_remote_description(sdp).await?;
+                let answer = self.publisher_pc.create_answer(AnswerOptions::default()).await?;
+                self.publisher_pc.set_local_description(answer.clone()).await?;
+                self.send(Response::Answer(proto::SessionDescription {
+                    r#type: "answer".to_owned(),
+                    sdp: answer.to_string(),
+                    id: offer.id,
+                    ..Default::default()
+                }));
+            }
+            Request::Answer(answer) => {
+                let sdp = SessionDescription::parse(&answer.sdp, SdpType::Answer)?;
+                self.subscriber_pc.set_remote_description(sdp).await?;
+                state.offer_in_flight = false;
+                if state.renegotiate {
+                    self.negotiate_subscriber(state).await?;
+                }
+            }
+            Request::Trickle(trickle) => {
+                let json = serde_json::from_str::<IceCandidateJson>(&trickle.candidate_init)?;
+                let candidate =
+                    IceCandidate::parse(&json.sdp_mid, json.sdp_m_line_index, &json.candidate)?;
+                match trickle.target() {
+                    proto::SignalTarget::Publisher => {
+                        self.publisher_pc.add_ice_candidate(candidate).await?
+                    }
+                    proto::SignalTarget::Subscriber => {
+                        self.subscriber_pc.add_ice_candidate(candidate).await?
+                    }
+                }
+            }
+            Request::AddTrack(add_track)
+                if self.info().tracks.iter().any(|t| t.sid == add_track.sid) =>
+            {
+                // A codec added to a published track, e.g. its backup codec, keeps the
+                // track's sid. Its media is not relayed, subscribers keep the first codec.
+                let track = self.info().tracks.into_iter().find(|t| t.sid == add_track.sid);
+                self.send(Response::TrackPublished(proto::TrackPublishedResponse {
+                    cid: add_track.cid,
+                    track,
+                }));
+            }
+            Request::AddTrack(add_track) => {
+                let track = proto::TrackInfo {
+                    sid: random_sid("TR_"),
+                    r#type: add_track.r#type,
+                    name: add_track.name,
+                    muted: add_track.muted,
+                    width: add_track.width,
+                    height: add_track.height,
+                    disable_dtx: add_track.disable_dtx,
+                    source: add_track.source,
+                    layers: add_track.layers,
+                    stereo: add_track.stereo,
+                    disable_red: add_track.disable_red,
+                    encryption: add_track.encryption,
+                    stream: add_track.stream,
+                    audio_features: add_track.audio_features,
+                    backup_codec_policy: add_track.backup_codec_policy,
+                    packet_trailer_features: add_track.packet_trailer_features,
+                    ..Default::default()
+                };
+                state.pending_tracks.insert(add_track.cid.clone(), track.clone());
+                self.update_info(|info| {
+                    info.tracks.push(track.clone());
+                    info.is_publisher = true;
+                });
+                self.send(Response::TrackPublished(proto::TrackPublishedResponse {
+                    cid: add_track.cid,
+                    track: Some(track),
+                }));
+                self.broadcast_update(false);
+            }
+            Request::Mute(mute) => {
+                self.update_info(|info| {
+                    if let Some(track) = info.tracks.iter_mut().find(|t| t.sid == mute.sid) {
+                        track.muted = mute.muted;
+                    }
+                });
+                self.broadcast_update(false);
+            }
+            Request::UpdateMetadata(update) => {
+                self.update_info(|info| {
+                    if !update.name.is_empty() {
+                        info.name = update.name;
+                    }
+                    if !update.metadata.is_empty() {
+                        info.metadata = update.metadata;
+                    }
+                    for (key, value) in update.attributes {
+                        if value.is_empty() {
+                            info.attributes.remove(&key);
+                        } else {
+                            info.attributes.insert(key, value);
+                        }
+                    }
+                });
+                self.send(Response::RequestResponse(proto::RequestResponse {
+                    request_id: update.request_id,
+                    reason: proto::request_response::Reason::Ok as i32,
+                    ..Default::default()
+                }));
+                self.broadcast_update(true);
+            }
+            Request::Leave(_) => {
+                if let Some(room) = self.room.upgrade() {
+                    room.remove(self, DisconnectReason::ClientInitiated);
+                }
+            }
+            Request::PingReq(ping) => {
+                self.send(Response::PongResp(proto::Pong {
+                    last_ping_timestamp: ping.timestamp,
+                    timestamp: now_ms(),
+                }));
+            }
+            Request::Ping(timestamp) => self.send(Response::Pong(timestamp)),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn negotiate_subscriber(self: &Arc<Self>, state: &mut TaskState) -> Result<(), RtcError> {
+        if self.closed.load(Ordering::Acquire) {
+            return Ok(());
+        }
+        if state.offer_in_flight {
+            state.renegotiate = true;
+            return Ok(());
+        }
+        let offer = self.subscriber_pc.create_offer(OfferOptions::default()).await?;
+        self.subscriber_pc.set_local_description(offer.clone()).await?;
+        state.offer_in_flight = true;
+        state.renegotiate = false;
+        self.send(proto::signal_response::Message::Offer(proto::SessionDescription {
+            r#type: "offer".to_owned(),
+            sdp: offer.to_string(),
+            ..Default::default()
+        }));
+        Ok(())
+    }
+
+    fn on_data(&self, kind: proto::data_packet::Kind, data: &[u8]) {
+        let (Some(sfu), Some(room)) = (self.sfu.upgrade(), self.room.upgrade()) else {
+            return;
+        };
+        let mut packet = match proto::DataPacket::decode(data) {
+            Ok(packet) => packet,
+            Err(err) => {
+                log::warn!("fake SFU failed to decode DataPacket: {:?}", err);
+                return;
+            }
+        };
+        // As on a real SFU, the sender is stamped by the server rather than trusted.
+        packet.participant_identity = self.identity.to_string();
+        packet.participant_sid = self.sid.clone();
+        room.relay_data(self, kind, packet, &sfu.faults);
+    }
+
+    fn update_info(&self, update: impl FnOnce(&mut proto::ParticipantInfo)) {
+        let mut info = self.info.lock();
+        update(&mut info);
+        info.version += 1;
+    }
+
+    fn broadcast_update(self: &Arc<Self>, include_self: bool) {
+        if let Some(room) = self.room.upgrade() {
+            room.broadcast_update(self, include_self);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Loopback media relay: received tracks are decoded and captured again into
+//! local tracks that can be sent to any number of subscribers.
+
+use futures_util::StreamExt;
+use libwebrtc::{
+    audio_source::native::NativeAudioSource, audio_stream::native::NativeAudioStream,
+    peer_connection_factory::native::PeerConnectionFactoryExt, prelude::*,
+    video_source::native::NativeVideoSource, video_stream::native::NativeVideoStream,
+};
+use livekit_protocol as proto;
+use tokio::sync::oneshot;
+
+use crate::rtc_engine::lk_runtime::LkRuntime;
+
+const AUDIO_SAMPLE_RATE: u32 = 48000;
+const AUDIO_QUEUE_SIZE_MS: u32 = 100;
+
+/// A published track as forwarded to subscribers. Relaying stops on drop.
+pub(super) struct RelayedTrack {
+    pub sid: String,
+    /// Stream id subscribers receive the track with, `<participant sid>|<track sid>`.
+    pub stream_id: String,
+    pub track: MediaStreamTrack,
+    _close: oneshot::Sender<()>,
+}
+
+impl RelayedTrack {
+    pub fn new(publisher_sid: &str, info: &proto::TrackInfo, received: MediaStreamTrack) -> Self {
+        let (close_tx, close_rx) = oneshot::channel();
+        let factory = LkRuntime::instance();
+
+        let track = match received {
+            MediaStreamTrack::Audio(audio) => {
+                let channels = if info.stereo { 2 } else { 1 };
+                let source = NativeAudioSource::new(
+                    AudioSourceOptions::default(),
+                    AUDIO_SAMPLE_RATE,
+                    channels,
+                    AUDIO_QUEUE_SIZE_MS,
+                );
+                let stream =
+                    NativeAudioStream::new(audio, AUDIO_SAMPLE_RATE as i32, channels as i32);
+                livekit_runtime::spawn(relay_audio(stream, source.clone(), close_rx));
+                factory.pc_factory().create_audio_track(&info.sid, source).into()
+            }
+            MediaStreamTrack::Video(video) => {
+                let resolution = VideoResolution { width: info.width, height: info.height };
+                let is_screencast = info.source == proto::TrackSource::ScreenShare as i32;
+                let source = NativeVideoSource::new(resolution, is_screencast);
+                let stream = NativeVideoStream::new(video);
+                livekit_runtime::spawn(relay_video(stream, source.clone(), close_rx));
+                factory.pc_factory().create_video_track(&info.sid, source).into()
+            }
+        };
+
+        Self {
+            sid: info.sid.clone(),
+            stream_id: format!("{}|{}", publisher_sid, info.sid),
+            track,
+            _close: close_tx,
+        }
+    }
+}
+
+async fn relay_audio(
+    mut stream: NativeAudioStream,
+    source: NativeAudioSource,
+    mut close_rx: oneshot::Receiver<()>,
+) {
+    loop {
+        tokio::select! {
+            _ = &mut close_rx => break,
+            frame = stream.next() => {
+                let Some(frame) = frame else { break };
+                if let Err(err) = source.capture_frame(&frame).await {
+                    log::warn!("fake SFU failed to relay audio frame: {:?}", err);
+                }
+            }
+        }
+    }
+    stream.close();
+}
+
+async fn relay_video(
+    mut stream: NativeVideoStream,
+    source: NativeVideoSource,
+    mut close_rx: oneshot::Receiver<()>,
+) {
+    loop {
+        tokio::select! {
+            _ = &mut close_rx => break,
+            frame = stream.next() => {
+                let Some(frame) = frame else { break };
+                source.capture_frame(&frame);
+            }
+        }
+    }
+    stream.close();
+}
@@ -0,0 +1,605 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use livekit_api::{
+    access_token::{AccessToken, TokenVerifier, VideoGrants},
+    signal_client::recording::{Record, RecordKind, ReplayOptions},
+};
+use livekit_net::{Header, TransportError};
+use livekit_protocol as proto;
+use parking_lot::Mutex;
+use thiserror::Error;
+
+use super::{
+    fault::FaultInjector,
+    participant::{random_sid, FakeParticipant},
+    transport::{self, MemoryConnection},
+};
+use crate::{id::ParticipantIdentity, DisconnectReason};
+
+/// Error starting a [`FakeSfu`].
+#[derive(Debug, Error)]
+pub enum FakeSfuError {
+    #[error("another WebSocket client is already registered with livekit-net")]
+    TransportInUse,
+}
+
+/// Options for [`FakeSfu::with_options`].
+#[derive(Debug, Clone)]
+pub struct FakeSfuOptions {
+    /// API key tokens must be signed with.
+    pub api_key: String,
+    /// API secret tokens must be signed with.
+    pub api_secret: String,
+    /// Version reported in `JoinResponse::server_info`.
+    pub server_version: String,
+    /// Region reported in `JoinResponse::server_info`.
+    pub region: String,
+}
+
+impl Default for FakeSfuOptions {
+    fn default() -> Self {
+        Self {
+            api_key: "devkey".into(),
+            api_secret: "secret".into(),
+            server_version: "1.9.0".into(),
+            region: "local".into(),
+        }
+    }
+}
+
+/// An in-process SFU that [`Room`](crate::Room)s connect to through [`FakeSfu::url`].
+///
+/// Stopping happens on drop: the URL no longer resolves and every connected
+/// participant is disconnected.
+#[derive(Debug)]
+pub struct FakeSfu {
+    inner: Arc<SfuInner>,
+}
+
+impl FakeSfu {
+    /// Starts a fake SFU with the default options.
+    pub fn start() -> Result<Self, FakeSfuError> {
+        Self::with_options(FakeSfuOptions::default())
+    }
+
+    /// Starts a fake SFU. Fails if a WebSocket client other than the fake
+    /// SFU router was already registered with livekit-net.
+    pub fn with_options(options: FakeSfuOptions) -> Result<Self, FakeSfuError> {
+        static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+
+        transport::install()?;
+        let host = format!("sfu-{}.fake.livekit.invalid", NEXT_ID.fetch_add(1, Ordering::Relaxed));
+        let inner = Arc::new(SfuInner {
+            url: format!("ws://{host}"),
+            host,
+            options,
+            faults: FaultInjector::default(),
+            rooms: Default::default(),
+        });
+        transport::register(&inner.host, &inner);
+        Ok(Self { inner })
+    }
+
+    /// URL to pass to [`Room::connect`](crate::Room::connect).
+    pub fn url(&self) -> &str {
+        &self.inner.url
+    }
+
+    /// Mints a token joining `room` as `identity` with publish and subscribe grants.
+    pub fn token(&self, room: &str, identity: &str) -> String {
+        self.access_token(room, identity).to_jwt().expect("failed to sign fake SFU token")
+    }
+
+    /// Like [`FakeSfu::token`], with a token expiring after `ttl`.
+    pub fn token_with_ttl(&self, room: &str, identity: &str, ttl: Duration) -> String {
+        self.access_token(room, identity)
+            .with_ttl(ttl)
+            .to_jwt()
+            .expect("failed to sign fake SFU token")
+    }
+
+    fn access_token(&self, room: &str, identity: &str) -> AccessToken {
+        let options = &self.inner.options;
+        AccessToken::with_api_key(&options.api_key, &options.api_secret)
+            .with_identity(identity)
+            .with_name(identity)
+            .with_grants(VideoGrants {
+                room_join: true,
+                room: room.to_owned(),
+                can_update_own_metadata: true,
+                ..Default::default()
+            })
+    }
+
+    /// Fault injection hooks applying to every participant of this SFU.
+    pub fn faults(&self) -> &FaultInjector {
+        &self.inner.faults
+    }
+
+    /// Identities of the participants currently in `room`, in join order.
+    pub fn participants(&self, room: &str) -> Vec<ParticipantIdentity> {
+        self.inner
+            .room(room)
+            .map(|room| room.participants().iter().map(|p| p.identity().clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Closes the signal connection of a participant without a leave, as a
+    /// network failure would. The client is expected to resume.
+    pub fn close_signal(&self, room: &str, identity: &str) -> bool {
+        let Some(participant) = self.inner.participant(room, identity) else {
+            return false;
+        };
+        participant.close_signal();
+        true
+    }
+
+    /// Sends a signal response to a participant, e.g. a request the fake SFU never makes on
+    /// its own such as a `SubscribedQualityUpdate`. Returns `false` if the participant is not
+    /// connected.
+    pub fn send(
+        &self,
+        room: &str,
+        identity: &str,
+        message: proto::signal_response::Message,
+    ) -> bool {
+        let Some(participant) = self.inner.participant(room, identity) else {
+            return false;
+        };
+        participant.send(message);
+        true
+    }
+
+    /// Sends a leave to a participant and removes it from its room.
+    pub fn disconnect(&self, room: &str, identity: &str, reason: DisconnectReason) -> bool {
+        let Some(participant) = self.inner.participant(room, identity) else {
+            return false;
+        };
+        participant.send_leave(reason, proto::leave_request::Action::Disconnect);
+        if let Some(room) = self.inner.room(room) {
+            room.remove(&participant, reason);
+        }
+        true
+    }
+
+    /// Replays the room activity of a signal recording to a connected participant.
+    ///
+    /// Recorded participant, room, speaker, connection quality, stream state and
+    /// permission updates are sent in order, so the participant's
+    /// [`Room`](crate::Room) observes the recorded session as if it had joined it.
+    /// Responses tied to the recording client's own negotiation and tracks are
+    /// skipped, as are updates about the recording client itself. Only the first
+    /// recorded connection is replayed.
+    ///
+    /// Returns `false` if the participant is not connected.
+    pub async fn replay(
+        &self,
+        room: &str,
+        identity: &str,
+        records: impl IntoIterator<Item = Record>,
+        options: ReplayOptions,
+    ) -> bool {
+        let Some(participant) = self.inner.participant(room, identity) else {
+            return false;
+        };
+        let start = livekit_runtime::Instant::now();
+        let mut connect_time = None;
+        let mut recorder_sid = None;
+        for record in records {
+            match record.kind {
+                RecordKind::Connect if connect_time.is_some() => break,
+                RecordKind::Connect => connect_time = Some(record.timestamp),
+                RecordKind::SignalResponse => {
+                    let Some(message) = record.signal_response().and_then(|res| res.message) else {
+                        continue;
+                    };
+                    if let proto::signal_response::Message::Join(join) = &message {
+                        recorder_sid = join.participant.as_ref().map(|p| p.sid.clone());
+                    }
+                    let Some(message) = room_activity(message, recorder_sid.as_deref()) else {
+                        continue;
+                    };
+                    if options.realtime {
+                        let offset =
+                            record.timestamp.saturating_sub(connect_time.unwrap_or_default());
+                        let now = livekit_runtime::Instant::now();
+                        livekit_runtime::sleep((start + offset).saturating_duration_since(now))
+                            .await;
+                    }
+                    participant.send(message);
+                }
+                _ => {}
+            }
+        }
+        true
+    }
+
+    /// Asks a participant to resume its session, as a server migration would.
+    pub fn request_resume(&self, room: &str, identity: &str) -> bool {
+        let Some(participant) = self.inner.participant(room, identity) else {
+            return false;
+        };
+        participant.send_leave(DisconnectReason::Migration, proto::leave_request::Action::Resume);
+        participant.close_signal();
+        true
+    }
+
+    /// Asks a participant to leave and join again with a new session, as a
+    /// server shutting down would. Returns `false` if the participant is not
+    /// connected.
+    pub fn request_reconnect(&self, room: &str, identity: &str) -> bool {
+        let Some(participant) = self.inner.participant(room, identity) else {
+            return false;
+        };
+        participant
+            .send_leave(DisconnectReason::Migration, proto::leave_request::Action::Reconnect);
+        participant.close_signal();
+        true
+    }
+}
+
+impl Drop for FakeSfu {
+    fn drop(&mut self) {
+        transport::unregister(&self.inner.host);
+        let rooms = std::mem::take(&mut *self.inner.rooms.lock());
+        for room in rooms.into_values() {
+            for participant in room.participants() {
+                participant.send_leave(
+                    DisconnectReason::RoomClosed,
+                    proto::leave_request::Action::Disconnect,
+                );
+                room.remove(&participant, DisconnectReason::RoomClosed);
+            }
+        }
+    }
+}
+
+/// Returns the recorded response if it describes room activity to replay, without
+/// updates about the participant who made the recording.
+fn room_activity(
+    message: proto::signal_response::Message,
+    recorder_sid: Option<&str>,
+) -> Option<proto::signal_response::Message> {
+    use proto::signal_response::Message;
+
+    match message {
+        Message::Update(mut update) => {
+            update.participants.retain(|p| Some(p.sid.as_str()) != recorder_sid);
+            (!update.participants.is_empty()).then_some(Message::Update(update))
+        }
+        Message::RoomUpdate(_)
+        | Message::SpeakersChanged(_)
+        | Message::ConnectionQuality(_)
+        | Message::StreamStateUpdate(_)
+        | Message::SubscriptionPermissionUpdate(_) => Some(message),
+        _ => None,
+    }
+}
+
+pub(super) struct SfuInner {
+    host: String,
+    url: String,
+    pub options: FakeSfuOptions,
+    pub faults: FaultInjector,
+    rooms: Mutex<HashMap<String, Arc<FakeRoom>>>,
+}
+
+impl std::fmt::Debug for SfuInner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SfuInner").field("url", &self.url).finish_non_exhaustive()
+    }
+}
+
+impl SfuInner {
+    fn room(&self, name: &str) -> Option<Arc<FakeRoom>> {
+        self.rooms.lock().get(name).cloned()
+    }
+
+    fn participant(&self, room: &str, identity: &str) -> Option<Arc<FakeParticipant>> {
+        self.room(room)?.participants().into_iter().find(|p| p.identity().as_str() == identity)
+    }
+
+    pub fn server_info(&self) -> proto::ServerInfo {
+        proto::ServerInfo {
+            edition: proto::server_info::Edition::Standard as i32,
+            version: self.options.server_version.clone(),
+            protocol: livekit_api::signal_client::PROTOCOL_VERSION as i32,
+            region: self.options.region.clone(),
+            node_id: self.host.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Accepts a signal connection, returning the client end of the socket.
+    pub async fn accept(
+        self: Arc<Self>,
+        url: &url::Url,
+        headers: &[Header],
+    ) -> Result<MemoryConnection, TransportError> {
+        if let Some(status) = self.faults.reject_status() {
+            return Err(TransportError::Http { status });
+        }
+
+        // Only the dual peer connection protocol is implemented; a 404 on the
+        // v1 path makes the client fall back to it.
+        if url.path().trim_end_matches('/').ends_with("/rtc/v1") {
+            return Err(TransportError::Http { status: 404 });
+        }
+
+        let token = headers
+            .iter()
+            .find(|h| h.name.eq_ignore_ascii_case("authorization"))
+            .and_then(|h| h.value.strip_prefix("Bearer "))
+            .ok_or(TransportError::Http { status: 401 })?;
+        let claims = TokenVerifier::with_api_key(&self.options.api_key, &self.options.api_secret)
+            .verify(token)
+            .map_err(|_| TransportError::Http { status: 401 })?;
+        if !claims.video.room_join || claims.video.room.is_empty() || claims.sub.is_empty() {
+            return Err(TransportError::Http { status: 401 });
+        }
+
+        let query: HashMap<_, _> = url.query_pairs().into_owned().collect();
+        let (client, server) = MemoryConnection::pair();
+
+        if query.get("reconnect").is_some_and(|v| v == "1") {
+            let sid = query.get("sid").map(String::as_str).unwrap_or_default();
+            let participant = self
+                .room(&claims.video.room)
+                .and_then(|room| room.participants().into_iter().find(|p| p.sid() == sid))
+                .ok_or(TransportError::Http { status: 404 })?;
+            participant.resume(server, &self);
+            return Ok(client);
+        }
+
+        let room = self
+            .rooms
+            .lock()
+            .entry(claims.video.room.clone())
+            .or_insert_with(|| Arc::new(FakeRoom::new(claims.video.room.clone())))
+            .clone();
+        let participant = FakeParticipant::new(&self, &room, &claims, server)
+            .map_err(|e| TransportError::Other(e.to_string()))?;
+        room.join(participant, &self);
+        Ok(client)
+    }
+}
+
+/// Participants sharing a room name on one fake SFU.
+pub(super) struct FakeRoom {
+    sid: String,
+    name: String,
+    creation_time: i64,
+    participants: Mutex<Vec<Arc<FakeParticipant>>>,
+}
+
+impl FakeRoom {
+    fn new(name: String) -> Self {
+        Self {
+            sid: random_sid("RM_"),
+            name,
+            creation_time: chrono::Utc::now().timestamp_millis(),
+            participants: Default::default(),
+        }
+    }
+
+    pub fn info(&self) -> proto::Room {
+        let participants = self.participants.lock();
+        proto::Room {
+            sid: self.sid.clone(),
+            name: self.name.clone(),
+            creation_time: self.creation_time / 1000,
+            creation_time_ms: self.creation_time,
+            num_participants: participants.len() as u32,
+            num_publishers: participants.iter().filter(|p| p.info().is_publisher).count() as u32,
+            ..Default::default()
+        }
+    }
+
+    pub fn participants(&self) -> Vec<Arc<FakeParticipant>> {
+        self.participants.lock().clone()
+    }
+
+    fn join(&self, participant: Arc<FakeParticipant>, sfu: &SfuInner) {
+        let duplicate =
+            self.participants().into_iter().find(|p| p.identity() == participant.identity());
+        if let Some(duplicate) = duplicate {
+            duplicate.send_leave(
+                DisconnectReason::DuplicateIdentity,
+                proto::leave_request::Action::Disconnect,
+            );
+            self.remove(&duplicate, DisconnectReason::DuplicateIdentity);
+        }
+
+        let others = self.participants();
+        self.participants.lock().push(participant.clone());
+
+        participant.send(proto::signal_response::Message::Join(proto::JoinResponse {
+            room: Some(self.info()),
+            participant: Some(participant.info()),
+            other_participants: others.iter().map(|p| p.info()).collect(),
+            server_version: sfu.options.server_version.clone(),
+            server_region: sfu.options.region.clone(),
+            subscriber_primary: false,
+            ping_timeout: 15,
+            ping_interval: 5,
+            server_info: Some(sfu.server_info()),
+            ..Default::default()
+        }));
+
+        self.broadcast_update(&participant, false);
+        for other in &others {
+            for track in other.published_tracks() {
+                participant.subscribe(track);
+            }
+        }
+        participant.negotiate();
+    }
+
+    /// Removes a participant, tearing down its media and notifying the others.
+    pub fn remove(&self, participant: &Arc<FakeParticipant>, reason: DisconnectReason) {
+        let removed = {
+            let mut participants = self.participants.lock();
+            let len = participants.len();
+            participants.retain(|p| !Arc::ptr_eq(p, participant));
+            participants.len() != len
+        };
+        if !removed {
+            return;
+        }
+
+        let tracks = participant.published_tracks();
+        let mut info = participant.close(reason);
+        info.tracks.clear();
+        for other in self.participants() {
+            for track in &tracks {
+                other.unsubscribe(&track.sid);
+            }
+            other.send(proto::signal_response::Message::Update(proto::ParticipantUpdate {
+                participants: vec![info.clone()],
+            }));
+        }
+    }
+
+    /// Sends the latest info of `participant` to the others, and to itself when
+    /// `include_self` is set.
+    pub fn broadcast_update(&self, participant: &Arc<FakeParticipant>, include_self: bool) {
+        let info = participant.info();
+        for other in self.participants() {
+            if !include_self && Arc::ptr_eq(&other, participant) {
+                continue;
+            }
+            other.send(proto::signal_response::Message::Update(proto::ParticipantUpdate {
+                participants: vec![info.clone()],
+            }));
+        }
+    }
+
+    /// Forwards a newly received track to every other participant.
+    pub fn publish(
+        &self,
+        publisher: &Arc<FakeParticipant>,
+        track: Arc<super::relay::RelayedTrack>,
+    ) {
+        for other in self.participants() {
+            if !Arc::ptr_eq(&other, publisher) {
+                other.subscribe(track.clone());
+            }
+        }
+    }
+
+    /// Forwards a data packet to its destinations, or to everyone else when it has none.
+    pub fn relay_data(
+        &self,
+        sender: &FakeParticipant,
+        kind: proto::data_packet::Kind,
+        packet: proto::DataPacket,
+        faults: &FaultInjector,
+    ) {
+        if faults.drops_data_packets() {
+            return;
+        }
+        for other in self.participants() {
+            if other.sid() == sender.sid() {
+                continue;
+            }
+            if !packet.destination_identities.is_empty()
+                && !packet.destination_identities.iter().any(|d| d == other.identity().as_str())
+            {
+                continue;
+            }
+            other.send_data(kind, &packet);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sfu() -> Arc<SfuInner> {
+        Arc::new(SfuInner {
+            host: "sfu-test.fake.livekit.invalid".into(),
+            url: "ws://sfu-test.fake.livekit.invalid".into(),
+            options: FakeSfuOptions::default(),
+            faults: FaultInjector::default(),
+            rooms: Default::default(),
+        })
+    }
+
+    fn bearer(token: &str) -> Vec<Header> {
+        vec![Header { name: "Authorization".into(), value: format!("Bearer {token}") }]
+    }
+
+    fn token(options: &FakeSfuOptions, secret: &str) -> String {
+        AccessToken::with_api_key(&options.api_key, secret)
+            .with_identity("alice")
+            .with_grants(VideoGrants { room_join: true, room: "test".into(), ..Default::default() })
+            .to_jwt()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_accept_rejects_v1_path() {
+        let sfu = sfu();
+        let token = token(&sfu.options, &sfu.options.api_secret);
+        let url = url::Url::parse("ws://sfu-test.fake.livekit.invalid/rtc/v1").unwrap();
+
+        let result = sfu.clone().accept(&url, &bearer(&token)).await;
+        assert!(matches!(result, Err(TransportError::Http { status: 404 })));
+    }
+
+    #[tokio::test]
+    async fn test_accept_rejects_invalid_token() {
+        let sfu = sfu();
+        let token = token(&sfu.options, "wrong-secret");
+        let url = url::Url::parse("ws://sfu-test.fake.livekit.invalid/rtc").unwrap();
+
+        let result = sfu.clone().accept(&url, &bearer(&token)).await;
+        assert!(matches!(result, Err(TransportError::Http { status: 401 })));
+        let result = sfu.clone().accept(&url, &[]).await;
+        assert!(matches!(result, Err(TransportError::Http { status: 401 })));
+    }
+
+    #[tokio::test]
+    async fn test_accept_rejects_unknown_resume() {
+        let sfu = sfu();
+        let token = token(&sfu.options, &sfu.options.api_secret);
+        let url =
+            url::Url::parse("ws://sfu-test.fake.livekit.invalid/rtc?reconnect=1&sid=PA_unknown")
+                .unwrap();
+
+        let result = sfu.clone().accept(&url, &bearer(&token)).await;
+        assert!(matches!(result, Err(TransportError::Http { status: 404 })));
+    }
+
+    #[tokio::test]
+    async fn test_accept_injected_rejection() {
+        let sfu = sfu();
+        sfu.faults.reject_connections(Some(503));
+        let url = url::Url::parse("ws://sfu-test.fake.livekit.invalid/rtc").unwrap();
+
+        let result = sfu.clone().accept(&url, &[]).await;
+        assert!(matches!(result, Err(TransportError::Http { status: 503 })));
+    }
+}
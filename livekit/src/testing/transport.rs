@@ -0,0 +1,171 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Routes WebSocket connections for fake SFU URLs to in-memory channels.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock, Weak},
+};
+
+use lazy_static::lazy_static;
+use livekit_net::{Header, TransportError, WsClient, WsConnectResult, WsConnection};
+use parking_lot::Mutex;
+use tokio::sync::{mpsc, watch};
+
+use super::{server::SfuInner, FakeSfuError};
+
+lazy_static! {
+    /// Running fake SFUs, keyed by the host of their URL.
+    static ref SERVERS: Mutex<HashMap<String, Weak<SfuInner>>> = Default::default();
+}
+
+/// Registers the routing client with livekit-net, failing if a different
+/// client was registered first.
+pub(super) fn install() -> Result<(), FakeSfuError> {
+    static ROUTER: OnceLock<Arc<dyn WsClient>> = OnceLock::new();
+    let router = ROUTER.get_or_init(|| Arc::new(RoutingWsClient)).clone();
+    livekit_net::set_ws_client(router.clone());
+
+    match livekit_net::ws_client() {
+        Some(active) if Arc::ptr_eq(&active, &router) => Ok(()),
+        _ => Err(FakeSfuError::TransportInUse),
+    }
+}
+
+pub(super) fn register(host: &str, server: &Arc<SfuInner>) {
+    SERVERS.lock().insert(host.to_owned(), Arc::downgrade(server));
+}
+
+pub(super) fn unregister(host: &str) {
+    SERVERS.lock().remove(host);
+}
+
+fn lookup(url: &url::Url) -> Option<Arc<SfuInner>> {
+    let host = url.host_str()?;
+    SERVERS.lock().get(host).and_then(Weak::upgrade)
+}
+
+/// Connects to a fake SFU when the URL belongs to one, and to the native
+/// transport otherwise, so tests mixing both keep working.
+struct RoutingWsClient;
+
+#[async_trait::async_trait]
+impl WsClient for RoutingWsClient {
+    async fn connect(
+        &self,
+        url: String,
+        headers: Vec<Header>,
+        timeout_ms: u64,
+    ) -> Result<WsConnectResult, TransportError> {
+        let parsed = url::Url::parse(&url).map_err(|e| TransportError::Other(e.to_string()))?;
+        match lookup(&parsed) {
+            Some(server) => {
+                let connection = server.accept(&parsed, &headers).await?;
+                Ok(WsConnectResult { connection: Arc::new(connection) })
+            }
+            None => {
+                livekit_net::testing::native_ws_client().connect(url, headers, timeout_ms).await
+            }
+        }
+    }
+}
+
+/// One end of an in-memory WebSocket.
+pub(super) struct MemoryConnection {
+    tx: Mutex<Option<mpsc::UnboundedSender<Vec<u8>>>>,
+    rx: tokio::sync::Mutex<mpsc::UnboundedReceiver<Vec<u8>>>,
+    closed: watch::Sender<bool>,
+}
+
+impl MemoryConnection {
+    /// Creates two connected ends; frames sent on one are received on the other.
+    pub fn pair() -> (Self, Self) {
+        let (a_tx, a_rx) = mpsc::unbounded_channel();
+        let (b_tx, b_rx) = mpsc::unbounded_channel();
+        (Self::new(a_tx, b_rx), Self::new(b_tx, a_rx))
+    }
+
+    fn new(tx: mpsc::UnboundedSender<Vec<u8>>, rx: mpsc::UnboundedReceiver<Vec<u8>>) -> Self {
+        Self {
+            tx: Mutex::new(Some(tx)),
+            rx: tokio::sync::Mutex::new(rx),
+            closed: watch::channel(false).0,
+        }
+    }
+
+    pub fn try_send(&self, frame: Vec<u8>) -> Result<(), TransportError> {
+        let tx = self.tx.lock();
+        let tx = tx.as_ref().ok_or(TransportError::Closed)?;
+        tx.send(frame).map_err(|_| TransportError::Closed)
+    }
+
+    /// Closes this end without waiting for a pending [`WsConnection::recv`],
+    /// which returns `None` instead. The other end sees the close after the
+    /// frames already sent.
+    pub fn shutdown(&self) {
+        self.tx.lock().take();
+        self.closed.send_replace(true);
+    }
+}
+
+#[async_trait::async_trait]
+impl WsConnection for MemoryConnection {
+    async fn send(&self, frame: Vec<u8>) -> Result<(), TransportError> {
+        self.try_send(frame)
+    }
+
+    async fn recv(&self) -> Result<Option<Vec<u8>>, TransportError> {
+        let mut closed = self.closed.subscribe();
+        if *closed.borrow() {
+            return Ok(None);
+        }
+        let mut rx = self.rx.lock().await;
+        tokio::select! {
+            frame = rx.recv() => Ok(frame),
+            _ = closed.wait_for(|closed| *closed) => Ok(None),
+        }
+    }
+
+    async fn close(&self) {
+        self.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_connection_roundtrip() {
+        let (client, server) = MemoryConnection::pair();
+        client.send(vec![1, 2, 3]).await.unwrap();
+        server.send(vec![4]).await.unwrap();
+
+        assert_eq!(server.recv().await.unwrap(), Some(vec![1, 2, 3]));
+        assert_eq!(client.recv().await.unwrap(), Some(vec![4]));
+    }
+
+    #[tokio::test]
+    async fn test_memory_connection_close() {
+        let (client, server) = MemoryConnection::pair();
+        server.send(vec![1]).await.unwrap();
+        server.close().await;
+
+        assert_eq!(client.recv().await.unwrap(), Some(vec![1]));
+        assert_eq!(client.recv().await.unwrap(), None);
+        assert_eq!(server.recv().await.unwrap(), None);
+        assert!(matches!(server.send(vec![1]).await, Err(TransportError::Closed)));
+    }
+}
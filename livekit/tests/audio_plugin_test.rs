@@ -0,0 +1,158 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Loads the `gain` example of `livekit-audio-plugin` through [`AudioFilterPlugin`].
+
+use std::{
+    path::PathBuf,
+    process::Command,
+    sync::{Mutex, MutexGuard},
+};
+
+use libloading::Library;
+use livekit::{
+    register_audio_filter_plugin, unregister_audio_filter_plugin, AudioFilterCapabilities,
+    AudioFilterPlugin, AudioFilterStreamInfo, PluginError,
+};
+
+/// `cargo test --workspace` builds the example next to the test binaries, build it when it is
+/// missing.
+fn plugin_path() -> PathBuf {
+    let file_name = format!("{}gain{}", std::env::consts::DLL_PREFIX, std::env::consts::DLL_SUFFIX);
+    let exe = std::env::current_exe().unwrap();
+    let path = exe.parent().unwrap().parent().unwrap().join("examples").join(&file_name);
+    if path.exists() {
+        return path;
+    }
+
+    // A separate target dir, the outer cargo still holds the lock on ours
+    let target_dir = exe.parent().unwrap().parent().unwrap().join("sample-plugin");
+    let status = Command::new(env!("CARGO"))
+        .args(["build", "--example", "gain", "--manifest-path"])
+        .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/../livekit-audio-plugin/Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .status()
+        .expect("failed to build the sample plugin");
+    assert!(status.success());
+    target_dir.join("debug").join("examples").join(file_name)
+}
+
+/// Tests share the loaded library, and so its unload counter.
+static PLUGIN_LOCK: Mutex<()> = Mutex::new(());
+
+fn lock() -> MutexGuard<'static, ()> {
+    PLUGIN_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn load() -> std::sync::Arc<AudioFilterPlugin> {
+    AudioFilterPlugin::new(plugin_path().to_str().unwrap()).unwrap()
+}
+
+/// Keeps the library mapped while plugins are dropped, to read how many times it was unloaded.
+struct UnloadProbe(Library);
+
+impl UnloadProbe {
+    fn new() -> Self {
+        Self(unsafe { Library::new(plugin_path()) }.unwrap())
+    }
+
+    fn count(&self) -> u32 {
+        let count =
+            unsafe { self.0.get::<unsafe extern "C" fn() -> u32>(b"gain_unload_count") }.unwrap();
+        unsafe { count() }
+    }
+}
+
+#[test]
+fn gain_plugin_negotiates_and_processes() {
+    let _lock = lock();
+    let plugin = load();
+
+    assert_eq!(
+        plugin.capabilities(),
+        AudioFilterCapabilities {
+            abi_version: 3,
+            separate_rates: false,
+            float: true,
+            stream_info: true,
+            token_refresh: true,
+            errors: true,
+            unload: true,
+        }
+    );
+    assert!(!plugin.supports_separate_rates());
+    plugin.on_load("wss://example", "token").unwrap();
+
+    let session = plugin
+        .clone()
+        .new_session(48000, 48000, r#"{"gain":2.0}"#, AudioFilterStreamInfo::default())
+        .unwrap();
+    let mut output = [0; 3];
+    session.process_i16(3, &[1, -2, 20000], 3, &mut output).unwrap();
+    assert_eq!(output, [2, -4, i16::MAX]);
+    assert!(!session.has_failed());
+}
+
+#[test]
+fn gain_plugin_reports_errors() {
+    let _lock = lock();
+    let plugin = load();
+
+    let err = plugin.on_load("wss://example", "").unwrap_err();
+    assert!(matches!(&err, PluginError::Failed(message) if message == "missing token"), "{err}");
+
+    let new_session = |input_rate, output_rate, options: &str| {
+        plugin.clone().new_session(
+            input_rate,
+            output_rate,
+            options,
+            AudioFilterStreamInfo::default(),
+        )
+    };
+    let err = new_session(48000, 16000, "").err().unwrap();
+    assert!(matches!(&err, PluginError::Failed(message) if message.contains("sample rates")));
+    let err = new_session(48000, 48000, "not json").err().unwrap();
+    assert!(matches!(&err, PluginError::Failed(message) if message.starts_with("invalid options")));
+
+    let session = new_session(48000, 48000, r#"{"panic":true}"#).unwrap();
+    let mut output = [-1; 3];
+    let err = session.process_i16(3, &[1, 2, 3], 3, &mut output).unwrap_err();
+    assert!(
+        matches!(&err, PluginError::Panicked(message) if message == "gain filter asked to panic")
+    );
+    assert_eq!(output, [0, 0, 0]);
+    assert!(session.has_failed());
+}
+
+#[test]
+fn gain_plugin_unloads_once_released() {
+    let _lock = lock();
+    let probe = UnloadProbe::new();
+    let unloads = probe.count();
+
+    let plugin = load();
+    let session =
+        plugin.clone().new_session(48000, 48000, "", AudioFilterStreamInfo::default()).unwrap();
+    register_audio_filter_plugin("gain".into(), plugin);
+
+    // The registry and the session both keep the plugin loaded
+    let plugin = unregister_audio_filter_plugin("gain").unwrap();
+    assert!(unregister_audio_filter_plugin("gain").is_none());
+    drop(plugin);
+    assert_eq!(probe.count(), unloads);
+
+    drop(session);
+    assert_eq!(probe.count(), unloads + 1);
+}
@@ -0,0 +1,447 @@
+// Copyright 2025 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg(feature = "testing")]
+
+use anyhow::{Context, Result};
+use livekit::{
+    options::{EncodingUpdate, LayerEncodingUpdate, TrackPublishOptions, VideoCodec},
+    prelude::*,
+    recording::{RecordingReader, ReplayOptions, SignalRecorder},
+    testing::{FakeSfu, SignalFault},
+    token_source::{ConnectionDetails, CustomTokenSource},
+    webrtc::{
+        native::frame_transformer::{
+            FrameTransformResult, FrameTransformer, TransformableFrameInfo,
+        },
+        prelude::{I420Buffer, RtcVideoSource, VideoFrame, VideoResolution, VideoRotation},
+        video_source::native::NativeVideoSource,
+    },
+};
+use livekit_protocol as proto;
+use std::{
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+use tokio::{sync::mpsc::UnboundedReceiver, time::timeout};
+
+const ROOM: &str = "fake-sfu-room";
+const EVENT_TIMEOUT: Duration = Duration::from_secs(10);
+
+async fn connect(sfu: &FakeSfu, identity: &str) -> Result<(Room, UnboundedReceiver<RoomEvent>)> {
+    let (room, events) =
+        Room::connect(sfu.url(), &sfu.token(ROOM, identity), RoomOptions::default()).await?;
+    Ok((room, events))
+}
+
+/// Waits for the first event matched by `f`, skipping the others.
+async fn wait_for<T>(
+    events: &mut UnboundedReceiver<RoomEvent>,
+    mut f: impl FnMut(RoomEvent) -> Option<T>,
+) -> Result<T> {
+    timeout(EVENT_TIMEOUT, async {
+        while let Some(event) = events.recv().await {
+            if let Some(value) = f(event) {
+                return Ok(value);
+            }
+        }
+        anyhow::bail!("event channel closed")
+    })
+    .await
+    .context("timed out waiting for event")?
+}
+
+#[test_log::test(tokio::test)]
+async fn test_participants_see_each_other() -> Result<()> {
+    let sfu = FakeSfu::start()?;
+    let (alice, _) = connect(&sfu, "alice").await?;
+    let (bob, _) = connect(&sfu, "bob").await?;
+
+    assert!(bob.remote_participants().contains_key(&alice.local_participant().identity()));
+    assert_eq!(sfu.participants(ROOM), vec!["alice".to_string().into(), "bob".to_string().into()]);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_data_packet_relay() -> Result<()> {
+    let sfu = FakeSfu::start()?;
+    let (alice, _) = connect(&sfu, "alice").await?;
+    let (_bob, mut bob_events) = connect(&sfu, "bob").await?;
+
+    let packet = DataPacket { payload: b"hello".to_vec(), reliable: true, ..Default::default() };
+    alice.local_participant().publish_data(packet).await?;
+
+    let (payload, sender) = wait_for(&mut bob_events, |event| match event {
+        RoomEvent::DataReceived { payload, participant, .. } => Some((payload, participant)),
+        _ => None,
+    })
+    .await?;
+    assert_eq!(payload.as_slice(), b"hello");
+    assert_eq!(sender.map(|p| p.identity()), Some(alice.local_participant().identity()));
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_rpc_over_fake_sfu() -> Result<()> {
+    let sfu = FakeSfu::start()?;
+    let (caller, _) = connect(&sfu, "caller").await?;
+    let (callee, _) = connect(&sfu, "callee").await?;
+
+    callee
+        .local_participant()
+        .register_rpc_method("echo".into(), |data| Box::pin(async move { Ok(data.payload) }));
+
+    let data = PerformRpcData::new(callee.local_participant().identity(), "echo")
+        .with_payload("ping")
+        .with_response_timeout(Duration::from_secs(2));
+    let response = caller.local_participant().perform_rpc(data).await?;
+    assert_eq!(response, "ping");
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_track_relay() -> Result<()> {
+    let sfu = FakeSfu::start()?;
+    let (alice, _) = connect(&sfu, "alice").await?;
+    let (_bob, mut bob_events) = connect(&sfu, "bob").await?;
+
+    let source = NativeVideoSource::new(VideoResolution { width: 320, height: 240 }, false);
+    let track = LocalVideoTrack::create_video_track("camera", RtcVideoSource::Native(source));
+    let publication = alice
+        .local_participant()
+        .publish_track(LocalTrack::Video(track), TrackPublishOptions::default())
+        .await?;
+
+    let subscribed = wait_for(&mut bob_events, |event| match event {
+        RoomEvent::TrackSubscribed { publication, .. } => Some(publication),
+        _ => None,
+    })
+    .await?;
+    assert_eq!(subscribed.sid(), publication.sid());
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_drop_data_packets() -> Result<()> {
+    let sfu = FakeSfu::start()?;
+    let (alice, _) = connect(&sfu, "alice").await?;
+    let (_bob, mut bob_events) = connect(&sfu, "bob").await?;
+
+    sfu.faults().set_drop_data_packets(true);
+    let packet = DataPacket { payload: b"lost".to_vec(), reliable: true, ..Default::default() };
+    alice.local_participant().publish_data(packet).await?;
+
+    let received = timeout(Duration::from_millis(500), async {
+        wait_for(&mut bob_events, |event| {
+            matches!(event, RoomEvent::DataReceived { .. }).then_some(())
+        })
+        .await
+    })
+    .await;
+    assert!(received.is_err(), "packet should have been dropped");
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_server_disconnect() -> Result<()> {
+    let sfu = FakeSfu::start()?;
+    let (_alice, mut alice_events) = connect(&sfu, "alice").await?;
+
+    assert!(sfu.disconnect(ROOM, "alice", DisconnectReason::ParticipantRemoved));
+
+    let reason = wait_for(&mut alice_events, |event| match event {
+        RoomEvent::Disconnected { reason } => Some(reason),
+        _ => None,
+    })
+    .await?;
+    assert_eq!(reason, DisconnectReason::ParticipantRemoved);
+    assert!(sfu.participants(ROOM).is_empty());
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_resume_after_signal_loss() -> Result<()> {
+    let sfu = FakeSfu::start()?;
+    let (_alice, mut alice_events) = connect(&sfu, "alice").await?;
+
+    assert!(sfu.close_signal(ROOM, "alice"));
+
+    wait_for(&mut alice_events, |event| matches!(event, RoomEvent::Reconnected).then_some(()))
+        .await?;
+    assert_eq!(sfu.participants(ROOM).len(), 1);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_token_refresh_on_reconnect() -> Result<()> {
+    let sfu = Arc::new(FakeSfu::start()?);
+    let fetches = Arc::new(AtomicU32::new(0));
+    let source = CustomTokenSource::new({
+        let (sfu, fetches) = (sfu.clone(), fetches.clone());
+        move || {
+            // The first token expires within the refresh margin
+            let ttl = match fetches.fetch_add(1, Ordering::SeqCst) {
+                0 => Duration::from_secs(30),
+                _ => Duration::from_secs(3600),
+            };
+            let details = ConnectionDetails {
+                server_url: sfu.url().to_owned(),
+                participant_token: sfu.token_with_ttl(ROOM, "alice", ttl),
+            };
+            async move { Ok(details) }
+        }
+    });
+
+    let (_alice, mut alice_events) =
+        Room::connect_with_token_source(Arc::new(source), RoomOptions::default()).await?;
+    assert_eq!(fetches.load(Ordering::SeqCst), 1);
+
+    assert!(sfu.request_reconnect(ROOM, "alice"));
+    wait_for(&mut alice_events, |event| matches!(event, RoomEvent::Reconnected).then_some(()))
+        .await?;
+    assert_eq!(fetches.load(Ordering::SeqCst), 2);
+    assert_eq!(sfu.participants(ROOM).len(), 1);
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_dropped_join_response() -> Result<()> {
+    let sfu = FakeSfu::start()?;
+    sfu.faults().on_signal_response(|_, response| match response {
+        proto::signal_response::Message::Join(_) => SignalFault::Drop,
+        _ => SignalFault::Deliver,
+    });
+
+    let mut options = RoomOptions::default();
+    options.join_retries = 0;
+    let result = Room::connect(sfu.url(), &sfu.token(ROOM, "alice"), options).await;
+    assert!(result.is_err());
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_replay_recorded_session() -> Result<()> {
+    let sfu = FakeSfu::start()?;
+    let path = std::env::temp_dir().join(format!("fake-sfu-replay-{}.lksr", std::process::id()));
+    let recorder = SignalRecorder::create(&path)?;
+
+    let mut options = RoomOptions::default();
+    options.recorder = Some(recorder.clone());
+    let (_alice, mut alice_events) =
+        Room::connect(sfu.url(), &sfu.token(ROOM, "alice"), options).await?;
+    let (_bob, _) = connect(&sfu, "bob").await?;
+    wait_for(&mut alice_events, |event| {
+        matches!(event, RoomEvent::ParticipantConnected(_)).then_some(())
+    })
+    .await?;
+    recorder.flush()?;
+
+    let records = RecordingReader::open(&path)?.collect::<std::io::Result<Vec<_>>>()?;
+    std::fs::remove_file(&path)?;
+
+    // Replay alice's session into a room of its own
+    let (carol, mut carol_events) =
+        Room::connect(sfu.url(), &sfu.token("replay-room", "carol"), RoomOptions::default())
+            .await?;
+    assert!(sfu.replay("replay-room", "carol", records, ReplayOptions::default()).await);
+
+    let participant = wait_for(&mut carol_events, |event| match event {
+        RoomEvent::ParticipantConnected(participant) => Some(participant),
+        _ => None,
+    })
+    .await?;
+    assert_eq!(participant.identity().as_str(), "bob");
+    assert_eq!(carol.remote_participants().len(), 1, "the recording client must be skipped");
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_backup_codec_published_on_request() -> Result<()> {
+    let sfu = FakeSfu::start()?;
+    let (added_tx, mut added) = tokio::sync::mpsc::unbounded_channel();
+    sfu.faults().on_signal_request(move |_, request| {
+        if let proto::signal_request::Message::AddTrack(add_track) = request {
+            let _ = added_tx.send(add_track.clone());
+        }
+        SignalFault::Deliver
+    });
+
+    let mut options = RoomOptions::default();
+    options.dynacast = true;
+    let (alice, _) = Room::connect(sfu.url(), &sfu.token(ROOM, "alice"), options).await?;
+
+    let source = NativeVideoSource::new(VideoResolution { width: 640, height: 480 }, false);
+    let track = LocalVideoTrack::create_video_track("camera", RtcVideoSource::Native(source));
+    let options = TrackPublishOptions {
+        video_codec: VideoCodec::VP9,
+        backup_codec: Some(VideoCodec::VP8),
+        ..Default::default()
+    };
+    let publication =
+        alice.local_participant().publish_track(LocalTrack::Video(track), options).await?;
+
+    let primary = added.recv().await.context("no AddTrack request")?;
+    let codecs: Vec<_> = primary.simulcast_codecs.iter().map(|c| c.codec.as_str()).collect();
+    assert_eq!(codecs, ["vp9", "vp8"]);
+
+    let request_backup = |enabled: bool| {
+        let update = proto::SubscribedQualityUpdate {
+            track_sid: publication.sid().to_string(),
+            subscribed_codecs: vec![proto::SubscribedCodec {
+                codec: "vp8".to_owned(),
+                qualities: vec![proto::SubscribedQuality {
+                    quality: proto::VideoQuality::Low as i32,
+                    enabled,
+                }],
+            }],
+            ..Default::default()
+        };
+        assert!(sfu.send(
+            ROOM,
+            "alice",
+            proto::signal_response::Message::SubscribedQualityUpdate(update)
+        ));
+    };
+
+    // Nothing is published for the backup codec until a subscriber needs it
+    request_backup(false);
+    assert!(timeout(Duration::from_millis(500), added.recv()).await.is_err());
+
+    request_backup(true);
+    let backup = timeout(EVENT_TIMEOUT, added.recv()).await?.context("no backup AddTrack")?;
+    assert_eq!(backup.sid, publication.sid().to_string());
+    assert_eq!(backup.simulcast_codecs.len(), 1);
+    assert_eq!(backup.simulcast_codecs[0].codec, "vp8");
+
+    // Later requests only update the layers of the published backup
+    request_backup(true);
+    assert!(timeout(Duration::from_millis(500), added.recv()).await.is_err());
+    Ok(())
+}
+
+#[test_log::test(tokio::test)]
+async fn test_update_encodings_advertises_layers() -> Result<()> {
+    let sfu = FakeSfu::start()?;
+    let (layers_tx, mut layers_updates) = tokio::sync::mpsc::unbounded_channel();
+    sfu.faults().on_signal_request(move |_, request| {
+        if let proto::signal_request::Message::UpdateLayers(update) = request {
+            let _ = layers_tx.send(update.clone());
+        }
+        SignalFault::Deliver
+    });
+
+    let (alice, _) =
+        Room::connect(sfu.url(), &sfu.token(ROOM, "alice"), RoomOptions::default()).await?;
+    let source = NativeVideoSource::new(VideoResolution { width: 1280, height: 720 }, false);
+    let track = LocalVideoTrack::create_video_track("camera", RtcVideoSource::Native(source));
+    let publication = alice
+        .local_participant()
+        .publish_track(LocalTrack::Video(track), TrackPublishOptions::default())
+        .await?;
+    let max_framerate = publication.publish_options().video_encoding.map(|e| e.max_framerate);
+
+    publication.update_encodings(EncodingUpdate {
+        layers: vec![
+            LayerEncodingUpdate {
+                active: Some(false),
+                ..LayerEncodingUpdate::new(PublishingLayerQuality::Low)
+            },
+            LayerEncodingUpdate {
+                max_bitrate: Some(1_000_000),
+                ..LayerEncodingUpdate::new(PublishingLayerQuality::High)
+            },
+        ],
+        ..Default::default()
+    })?;
+
+    let update = timeout(EVENT_TIMEOUT, layers_updates.recv()).await?.context("no UpdateLayers")?;
+    assert_eq!(update.track_sid, publication.sid().to_string());
+    assert!(!update.layers.is_empty());
+    assert!(update.layers.iter().all(|l| l.quality != proto::VideoQuality::Low as i32));
+
+    // Only the bitrate changed, the framerate is kept
+    let encoding = publication.publish_options().video_encoding.context("no video encoding")?;
+    assert_eq!(encoding.max_bitrate, 1_000_000);
+    if let Some(max_framerate) = max_framerate {
+        assert_eq!(encoding.max_framerate, max_framerate);
+    }
+    Ok(())
+}
+
+/// Counts the frames it sees.
+#[derive(Default)]
+struct CountingTransformer(AtomicU32);
+
+impl FrameTransformer for CountingTransformer {
+    fn transform(&self, _info: &TransformableFrameInfo, _payload: &[u8]) -> FrameTransformResult {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        FrameTransformResult::Forward
+    }
+}
+
+async fn wait_for_frames(transformer: &CountingTransformer) -> Result<()> {
+    timeout(EVENT_TIMEOUT, async {
+        while transformer.0.load(Ordering::SeqCst) == 0 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .context("no frame reached the transformer")
+}
+
+#[test_log::test(tokio::test)]
+async fn test_frame_transformer_survives_republish() -> Result<()> {
+    let sfu = FakeSfu::start()?;
+    let (alice, _) = connect(&sfu, "alice").await?;
+
+    let source = NativeVideoSource::new(VideoResolution { width: 320, height: 240 }, false);
+    let capture = tokio::spawn({
+        let source = source.clone();
+        async move {
+            loop {
+                source.capture_frame(&VideoFrame {
+                    rotation: VideoRotation::VideoRotation0,
+                    timestamp_us: 0,
+                    frame_metadata: None,
+                    buffer: I420Buffer::new_black(320, 240),
+                });
+                tokio::time::sleep(Duration::from_millis(33)).await;
+            }
+        }
+    });
+
+    let track = LocalTrack::Video(LocalVideoTrack::create_video_track(
+        "camera",
+        RtcVideoSource::Native(source),
+    ));
+    let publication = alice
+        .local_participant()
+        .publish_track(track.clone(), TrackPublishOptions::default())
+        .await?;
+    let transformer = Arc::new(CountingTransformer::default());
+    publication.set_frame_transformer(Some(transformer.clone()))?;
+    wait_for_frames(&transformer).await?;
+
+    // The transformer set on the first publication is installed on the new sender
+    alice.local_participant().unpublish_track(&publication.sid()).await?;
+    alice.local_participant().publish_track(track, TrackPublishOptions::default()).await?;
+    transformer.0.store(0, Ordering::SeqCst);
+    wait_for_frames(&transformer).await?;
+
+    capture.abort();
+    Ok(())
+}
@@ -38,6 +38,7 @@ fn main() {
         "src/video_track.rs",
         "src/data_channel.rs",
         "src/frame_cryptor.rs",
+        "src/frame_transformer.rs",
         "src/jsep.rs",
         "src/candidate.rs",
         "src/rtp_parameters.rs",
@@ -56,6 +57,7 @@ fn main() {
         "src/apm.rs",
         "src/audio_mixer.rs",
         "src/packet_trailer.rs",
+        "src/video_codec.rs",
     ];
 
     if is_desktop {
@@ -68,6 +70,7 @@ fn main() {
         "src/peer_connection.cpp",
         "src/peer_connection_factory.cpp",
         "src/audio_device_controller.cpp",
+        "src/audio_encoder_factory.cpp",
         "src/media_stream.cpp",
         "src/media_stream_track.cpp",
         "src/audio_track.cpp",
@@ -88,10 +91,12 @@ fn main() {
         "src/video_encoder_factory.cpp",
         "src/passthrough_video_encoder.cpp",
         "src/video_decoder_factory.cpp",
+        "src/rust_video_codec.cpp",
         "src/synthetic_audio_device.cpp",
         "src/adm_proxy.cpp",
         "src/audio_resampler.cpp",
         "src/frame_cryptor.cpp",
+        "src/frame_transformer.cpp",
         "src/global_task_queue.cpp",
         "src/prohibit_libsrtp_initialization.cpp",
         "src/apm.cpp",
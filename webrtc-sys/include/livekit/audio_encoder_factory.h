@@ -0,0 +1,51 @@
+/*
+ * Copyright 2026 LiveKit, Inc.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#pragma once
+
+#include <memory>
+#include <optional>
+#include <vector>
+
+#include "api/audio_codecs/audio_encoder_factory.h"
+#include "api/scoped_refptr.h"
+
+namespace livekit_ffi {
+
+// fmtp parameter carrying the Opus encoder complexity (0-10). It is not part
+// of RFC 7587, libwebrtc doesn't read it, so it's applied here instead.
+inline constexpr char kOpusComplexityParameter[] = "x-lk-complexity";
+
+/// Built-in audio encoders, with support for `kOpusComplexityParameter`.
+class AudioEncoderFactory : public webrtc::AudioEncoderFactory {
+ public:
+  AudioEncoderFactory();
+
+  std::vector<webrtc::AudioCodecSpec> GetSupportedEncoders() override;
+
+  std::optional<webrtc::AudioCodecInfo> QueryAudioEncoder(
+      const webrtc::SdpAudioFormat& format) override;
+
+  std::unique_ptr<webrtc::AudioEncoder> Create(
+      const webrtc::Environment& env,
+      const webrtc::SdpAudioFormat& format,
+      Options options) override;
+
+ private:
+  webrtc::scoped_refptr<webrtc::AudioEncoderFactory> builtin_;
+};
+
+}  // namespace livekit_ffi
@@ -40,6 +40,7 @@ enum class Algorithm : ::std::int32_t;
 class RtcFrameCryptorObserverWrapper;
 class NativeFrameCryptorObserver;
 class PacketTrailerHandler;
+class FrameTransformerHandler;
 
 /// Shared secret key for frame encryption.
 class KeyProvider {
@@ -163,7 +164,16 @@ class FrameCryptor {
   void set_packet_trailer_handler(
       std::shared_ptr<PacketTrailerHandler> handler) const;
 
+  /// Attach an application frame transformer for chained processing. It runs
+  /// before encryption on the send side and after decryption on the receive
+  /// side.
+  void set_frame_transformer(
+      std::shared_ptr<FrameTransformerHandler> handler) const;
+
  private:
+  /// Install e2ee_transformer_ together with the attached transformers.
+  void update_transformer_chain() const;
+
   std::shared_ptr<RtcRuntime> rtc_runtime_;
   const rust::String participant_id_;
   mutable webrtc::Mutex mutex_;
@@ -174,6 +184,10 @@ class FrameCryptor {
   mutable webrtc::scoped_refptr<NativeFrameCryptorObserver> observer_;
   mutable webrtc::scoped_refptr<webrtc::FrameTransformerInterface>
       chained_transformer_;
+  mutable webrtc::scoped_refptr<webrtc::FrameTransformerInterface>
+      packet_trailer_transformer_;
+  mutable webrtc::scoped_refptr<webrtc::FrameTransformerInterface>
+      frame_transformer_;
 };
 
 class NativeFrameCryptorObserver
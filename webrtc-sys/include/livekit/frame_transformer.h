@@ -0,0 +1,145 @@
+/*
+ * Copyright 2026 LiveKit, Inc.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#pragma once
+
+#include <stdint.h>
+
+#include <memory>
+#include <unordered_map>
+
+#include "api/frame_transformer_interface.h"
+#include "api/rtp_receiver_interface.h"
+#include "api/rtp_sender_interface.h"
+#include "api/scoped_refptr.h"
+#include "livekit/webrtc.h"
+#include "rtc_base/synchronization/mutex.h"
+#include "rust/cxx.h"
+
+namespace livekit_ffi {
+class PeerConnectionFactory;
+class RtpSender;
+class RtpReceiver;
+class PacketTrailerHandler;
+struct FrameTransformerWrapper;
+enum class TransformableFrameKind : int32_t;
+enum class TransformableFrameDirection : int32_t;
+}  // namespace livekit_ffi
+
+namespace livekit_ffi {
+
+/// Runs every frame through `first`, then through `second`.
+class ChainedFrameTransformer : public webrtc::FrameTransformerInterface,
+                                public webrtc::TransformedFrameCallback {
+ public:
+  ChainedFrameTransformer(
+      webrtc::scoped_refptr<webrtc::FrameTransformerInterface> first,
+      webrtc::scoped_refptr<webrtc::FrameTransformerInterface> second);
+
+  void Transform(
+      std::unique_ptr<webrtc::TransformableFrameInterface> frame) override;
+  void RegisterTransformedFrameCallback(
+      webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback) override;
+  void RegisterTransformedFrameSinkCallback(
+      webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback,
+      uint32_t ssrc) override;
+  void UnregisterTransformedFrameCallback() override;
+  void UnregisterTransformedFrameSinkCallback(uint32_t ssrc) override;
+
+  void OnTransformedFrame(
+      std::unique_ptr<webrtc::TransformableFrameInterface> frame) override;
+
+ private:
+  webrtc::scoped_refptr<webrtc::FrameTransformerInterface> first_;
+  webrtc::scoped_refptr<webrtc::FrameTransformerInterface> second_;
+};
+
+/// Frame transformer delegating to an application transformer written in
+/// Rust. The transformer runs synchronously on the WebRTC thread delivering
+/// the frame.
+class RustFrameTransformer : public webrtc::FrameTransformerInterface {
+ public:
+  RustFrameTransformer(TransformableFrameKind kind,
+                       TransformableFrameDirection direction,
+                       rust::Box<FrameTransformerWrapper> transformer);
+  ~RustFrameTransformer() override;
+
+  void Transform(
+      std::unique_ptr<webrtc::TransformableFrameInterface> frame) override;
+  void RegisterTransformedFrameCallback(
+      webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback) override;
+  void RegisterTransformedFrameSinkCallback(
+      webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback,
+      uint32_t ssrc) override;
+  void UnregisterTransformedFrameCallback() override;
+  void UnregisterTransformedFrameSinkCallback(uint32_t ssrc) override;
+
+ private:
+  const TransformableFrameKind kind_;
+  const TransformableFrameDirection direction_;
+  rust::Box<FrameTransformerWrapper> transformer_;
+  webrtc::Mutex mutex_;
+  webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback_;
+  std::unordered_map<uint32_t,
+                     webrtc::scoped_refptr<webrtc::TransformedFrameCallback>>
+      sink_callbacks_;
+};
+
+/// Wrapper class for Rust FFI that installs an application frame transformer
+/// on a sender or a receiver.
+class FrameTransformerHandler {
+ public:
+  FrameTransformerHandler(
+      std::shared_ptr<RtcRuntime> rtc_runtime,
+      webrtc::scoped_refptr<webrtc::RtpSenderInterface> sender,
+      rust::Box<FrameTransformerWrapper> transformer);
+
+  FrameTransformerHandler(
+      std::shared_ptr<RtcRuntime> rtc_runtime,
+      webrtc::scoped_refptr<webrtc::RtpReceiverInterface> receiver,
+      rust::Box<FrameTransformerWrapper> transformer);
+
+  ~FrameTransformerHandler() = default;
+
+  /// Chain the packet trailer transformer, for tracks without E2EE. When the
+  /// track is encrypted, FrameCryptor::set_frame_transformer builds the whole
+  /// chain instead.
+  void set_packet_trailer_handler(
+      std::shared_ptr<PacketTrailerHandler> handler) const;
+
+  /// Access the underlying transformer for chaining.
+  webrtc::scoped_refptr<RustFrameTransformer> transformer() const;
+
+ private:
+  std::shared_ptr<RtcRuntime> rtc_runtime_;
+  webrtc::scoped_refptr<RustFrameTransformer> transformer_;
+  webrtc::scoped_refptr<webrtc::RtpSenderInterface> sender_;
+  webrtc::scoped_refptr<webrtc::RtpReceiverInterface> receiver_;
+  mutable webrtc::scoped_refptr<webrtc::FrameTransformerInterface>
+      chained_transformer_;
+};
+
+std::shared_ptr<FrameTransformerHandler> new_frame_transformer_sender(
+    std::shared_ptr<PeerConnectionFactory> peer_factory,
+    std::shared_ptr<RtpSender> sender,
+    rust::Box<FrameTransformerWrapper> transformer);
+
+std::shared_ptr<FrameTransformerHandler> new_frame_transformer_receiver(
+    std::shared_ptr<PeerConnectionFactory> peer_factory,
+    std::shared_ptr<RtpReceiver> receiver,
+    rust::Box<FrameTransformerWrapper> transformer);
+
+}  // namespace livekit_ffi
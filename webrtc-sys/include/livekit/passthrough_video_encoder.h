@@ -23,9 +23,23 @@
 #include "api/video_codecs/sdp_video_format.h"
 #include "api/video_codecs/video_encoder.h"
 #include "api/video_codecs/video_encoder_factory.h"
+#include "modules/video_coding/include/video_codec_interface.h"
+#include "modules/video_coding/svc/scalable_video_controller_no_layering.h"
 
 namespace livekit_ffi {
 
+webrtc::VideoCodecType CodecTypeFromFormat(const webrtc::SdpVideoFormat& format);
+
+// Describes a single-layer frame (no simulcast or SVC) for the RTP packetizer.
+// `av1_svc_controller` is only used for AV1.
+void FillSingleLayerCodecSpecific(
+    webrtc::CodecSpecificInfo* codec_info,
+    webrtc::VideoCodecType codec_type,
+    int width,
+    int height,
+    bool keyframe,
+    webrtc::ScalableVideoControllerNoLayering* av1_svc_controller);
+
 class PassthroughVideoEncoderFactory : public webrtc::VideoEncoderFactory {
  public:
   PassthroughVideoEncoderFactory();
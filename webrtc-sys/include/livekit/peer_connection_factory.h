@@ -22,6 +22,7 @@
 #include "api/task_queue/task_queue_factory.h"
 #include "livekit/adm_proxy.h"
 #include "livekit/audio_device_controller.h"
+#include "rtc_base/synchronization/mutex.h"
 #include "media_stream.h"
 #include "rtp_parameters.h"
 #include "rust/cxx.h"
@@ -30,6 +31,8 @@
 namespace livekit_ffi {
 class PeerConnectionFactory;
 class AudioDeviceController;
+class VideoEncoderFactory;
+class VideoDecoderFactory;
 class PeerConnectionObserverWrapper;
 }  // namespace livekit_ffi
 #include "webrtc-sys/src/peer_connection_factory.rs.h"
@@ -74,12 +77,26 @@ class PeerConnectionFactory {
   std::shared_ptr<AudioDeviceController> audio_device() const;
   bool zero_playout_delay_enabled() const;
 
+  // Owned by the media engine of `peer_factory_`
+  VideoEncoderFactory* video_encoder_factory() const {
+    return video_encoder_factory_;
+  }
+  VideoDecoderFactory* video_decoder_factory() const {
+    return video_decoder_factory_;
+  }
+
  private:
   std::shared_ptr<RtcRuntime> rtc_runtime_;
   webrtc::scoped_refptr<AdmProxy> adm_proxy_;
   std::shared_ptr<AudioDeviceController> audio_device_;
   webrtc::scoped_refptr<webrtc::PeerConnectionFactoryInterface> peer_factory_;
   webrtc::Environment env_;
+  VideoEncoderFactory* video_encoder_factory_ = nullptr;
+  VideoDecoderFactory* video_decoder_factory_ = nullptr;
+  // Serializes the factory options set for each new peer connection
+  mutable webrtc::Mutex create_pc_mutex_;
+  // Factory-wide options, restored once a peer connection applied its own mask
+  webrtc::PeerConnectionFactoryInterface::Options options_;
 };
 
 std::shared_ptr<PeerConnectionFactory> create_peer_connection_factory();
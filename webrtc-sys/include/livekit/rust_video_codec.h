@@ -0,0 +1,80 @@
+/*
+ * Copyright 2026 LiveKit, Inc.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#pragma once
+
+#include <memory>
+#include <vector>
+
+#include "api/environment/environment.h"
+#include "api/video_codecs/sdp_video_format.h"
+#include "api/video_codecs/video_decoder_factory.h"
+#include "api/video_codecs/video_encoder_factory.h"
+#include "livekit/video_frame.h"
+#include "rust/cxx.h"
+
+namespace livekit_ffi {
+class PeerConnectionFactory;
+struct VideoEncoderFactoryWrapper;
+struct VideoEncoderWrapper;
+struct VideoDecoderFactoryWrapper;
+struct VideoDecoderWrapper;
+}  // namespace livekit_ffi
+#include "webrtc-sys/src/video_codec.rs.h"
+
+namespace livekit_ffi {
+
+// Encoders implemented in Rust (see webrtc-sys/src/video_codec.rs).
+class RustVideoEncoderFactory : public webrtc::VideoEncoderFactory {
+ public:
+  explicit RustVideoEncoderFactory(
+      rust::Box<VideoEncoderFactoryWrapper> factory);
+
+  std::vector<webrtc::SdpVideoFormat> GetSupportedFormats() const override;
+
+  std::unique_ptr<webrtc::VideoEncoder> Create(
+      const webrtc::Environment& env,
+      const webrtc::SdpVideoFormat& format) override;
+
+ private:
+  rust::Box<VideoEncoderFactoryWrapper> factory_;
+};
+
+// Decoders implemented in Rust (see webrtc-sys/src/video_codec.rs).
+class RustVideoDecoderFactory : public webrtc::VideoDecoderFactory {
+ public:
+  explicit RustVideoDecoderFactory(
+      rust::Box<VideoDecoderFactoryWrapper> factory);
+
+  std::vector<webrtc::SdpVideoFormat> GetSupportedFormats() const override;
+
+  std::unique_ptr<webrtc::VideoDecoder> Create(
+      const webrtc::Environment& env,
+      const webrtc::SdpVideoFormat& format) override;
+
+ private:
+  rust::Box<VideoDecoderFactoryWrapper> factory_;
+};
+
+void register_video_encoder_factory(
+    const PeerConnectionFactory& peer_factory,
+    rust::Box<VideoEncoderFactoryWrapper> factory);
+
+void register_video_decoder_factory(
+    const PeerConnectionFactory& peer_factory,
+    rust::Box<VideoDecoderFactoryWrapper> factory);
+
+}  // namespace livekit_ffi
@@ -19,6 +19,7 @@
 #include "api/video_codecs/video_decoder.h"
 #include "api/video_codecs/video_decoder_factory.h"
 #include "absl/strings/match.h"
+#include "rtc_base/synchronization/mutex.h"
 
 namespace livekit_ffi {
 class VideoDecoderFactory : public webrtc::VideoDecoderFactory {
@@ -33,8 +34,17 @@ class VideoDecoderFactory : public webrtc::VideoDecoderFactory {
   std::unique_ptr<webrtc::VideoDecoder> Create(
       const webrtc::Environment& env, const webrtc::SdpVideoFormat& format) override;
 
+  // Decoders of `factory` are used for the formats it supports, ahead of the
+  // built-in ones.
+  void AddCustomFactory(std::unique_ptr<webrtc::VideoDecoderFactory> factory);
+
  private:
+  std::vector<std::shared_ptr<webrtc::VideoDecoderFactory>> CustomFactories()
+      const;
+
   std::vector<std::unique_ptr<webrtc::VideoDecoderFactory>> factories_;
   const bool internal_h264_decoder_works_;
+  mutable webrtc::Mutex custom_factories_mutex_;
+  std::vector<std::shared_ptr<webrtc::VideoDecoderFactory>> custom_factories_;
 };
 }  // namespace livekit_ffi
@@ -23,6 +23,7 @@
 
 #include "api/video_codecs/video_encoder.h"
 #include "api/video_codecs/video_encoder_factory.h"
+#include "rtc_base/synchronization/mutex.h"
 
 namespace livekit_ffi {
 enum class VideoEncoderBackend : std::int32_t;
@@ -48,8 +49,16 @@ class VideoEncoderFactory : public webrtc::VideoEncoderFactory {
     std::unique_ptr<webrtc::VideoEncoder> Create(
         const webrtc::Environment& env, const webrtc::SdpVideoFormat& format) override;
 
+    void AddCustomFactory(std::unique_ptr<webrtc::VideoEncoderFactory> factory);
+
    private:
+    std::vector<std::shared_ptr<webrtc::VideoEncoderFactory>> CustomFactories()
+        const;
+
     std::vector<VideoEncoderBackendFactory> factories_;
+    // Registered at runtime, tried before the built-in backends
+    mutable webrtc::Mutex custom_factories_mutex_;
+    std::vector<std::shared_ptr<webrtc::VideoEncoderFactory>> custom_factories_;
   };
 
  public:
@@ -66,6 +75,10 @@ class VideoEncoderFactory : public webrtc::VideoEncoderFactory {
   std::unique_ptr<webrtc::VideoEncoder> Create(
       const webrtc::Environment& env, const webrtc::SdpVideoFormat& format) override;
 
+  // Encoders of `factory` are used for the formats it supports, ahead of the
+  // built-in backends (except for pre-encoded tracks).
+  void AddCustomFactory(std::unique_ptr<webrtc::VideoEncoderFactory> factory);
+
  private:
   std::unique_ptr<InternalFactory> internal_factory_;
 };
@@ -0,0 +1,80 @@
+/*
+ * Copyright 2026 LiveKit, Inc.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/audio_encoder_factory.h"
+
+#include <string>
+
+#include "absl/strings/match.h"
+#include "api/audio_codecs/builtin_audio_encoder_factory.h"
+#include "api/audio_codecs/opus/audio_encoder_opus.h"
+#include "rtc_base/logging.h"
+#include "rtc_base/string_to_number.h"
+
+namespace livekit_ffi {
+
+namespace {
+
+std::optional<int> OpusComplexity(const webrtc::SdpAudioFormat& format) {
+  if (!absl::EqualsIgnoreCase(format.name, "opus")) {
+    return std::nullopt;
+  }
+  auto it = format.parameters.find(kOpusComplexityParameter);
+  if (it == format.parameters.end()) {
+    return std::nullopt;
+  }
+  auto complexity = webrtc::StringToNumber<int>(it->second);
+  if (!complexity || *complexity < 0 || *complexity > 10) {
+    RTC_LOG(LS_WARNING) << "Ignoring invalid opus complexity: " << it->second;
+    return std::nullopt;
+  }
+  return complexity;
+}
+
+}  // namespace
+
+AudioEncoderFactory::AudioEncoderFactory()
+    : builtin_(webrtc::CreateBuiltinAudioEncoderFactory()) {}
+
+std::vector<webrtc::AudioCodecSpec>
+AudioEncoderFactory::GetSupportedEncoders() {
+  return builtin_->GetSupportedEncoders();
+}
+
+std::optional<webrtc::AudioCodecInfo> AudioEncoderFactory::QueryAudioEncoder(
+    const webrtc::SdpAudioFormat& format) {
+  return builtin_->QueryAudioEncoder(format);
+}
+
+std::unique_ptr<webrtc::AudioEncoder> AudioEncoderFactory::Create(
+    const webrtc::Environment& env,
+    const webrtc::SdpAudioFormat& format,
+    Options options) {
+  if (auto complexity = OpusComplexity(format)) {
+    auto config = webrtc::AudioEncoderOpus::SdpToConfig(format);
+    if (config) {
+      config->complexity = *complexity;
+      config->low_rate_complexity = *complexity;
+      if (config->IsOk()) {
+        return webrtc::AudioEncoderOpus::MakeAudioEncoder(env, *config,
+                                                          options);
+      }
+    }
+  }
+  return builtin_->Create(env, format, options);
+}
+
+}  // namespace livekit_ffi
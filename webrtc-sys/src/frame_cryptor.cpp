@@ -20,6 +20,7 @@
 
 #include "absl/types/optional.h"
 #include "api/make_ref_counted.h"
+#include "livekit/frame_transformer.h"
 #include "livekit/peer_connection.h"
 #include "livekit/peer_connection_factory.h"
 #include "livekit/packet_trailer.h"
@@ -29,54 +30,6 @@
 
 namespace livekit_ffi {
 
-class ChainedFrameTransformer : public webrtc::FrameTransformerInterface,
-                                public webrtc::TransformedFrameCallback {
- public:
-  ChainedFrameTransformer(
-      webrtc::scoped_refptr<webrtc::FrameTransformerInterface> first,
-      webrtc::scoped_refptr<webrtc::FrameTransformerInterface> second)
-      : first_(std::move(first)), second_(std::move(second)) {}
-
-  void Transform(
-      std::unique_ptr<webrtc::TransformableFrameInterface> frame) override {
-    first_->Transform(std::move(frame));
-  }
-
-  void RegisterTransformedFrameCallback(
-      webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback) override {
-    second_->RegisterTransformedFrameCallback(callback);
-    first_->RegisterTransformedFrameCallback(
-        webrtc::scoped_refptr<webrtc::TransformedFrameCallback>(this));
-  }
-
-  void RegisterTransformedFrameSinkCallback(
-      webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback,
-      uint32_t ssrc) override {
-    second_->RegisterTransformedFrameSinkCallback(callback, ssrc);
-    first_->RegisterTransformedFrameSinkCallback(
-        webrtc::scoped_refptr<webrtc::TransformedFrameCallback>(this), ssrc);
-  }
-
-  void UnregisterTransformedFrameCallback() override {
-    first_->UnregisterTransformedFrameCallback();
-    second_->UnregisterTransformedFrameCallback();
-  }
-
-  void UnregisterTransformedFrameSinkCallback(uint32_t ssrc) override {
-    first_->UnregisterTransformedFrameSinkCallback(ssrc);
-    second_->UnregisterTransformedFrameSinkCallback(ssrc);
-  }
-
-  void OnTransformedFrame(
-      std::unique_ptr<webrtc::TransformableFrameInterface> frame) override {
-    second_->Transform(std::move(frame));
-  }
-
- private:
-  webrtc::scoped_refptr<webrtc::FrameTransformerInterface> first_;
-  webrtc::scoped_refptr<webrtc::FrameTransformerInterface> second_;
-};
-
 webrtc::FrameCryptorTransformer::Algorithm AlgorithmToFrameCryptorAlgorithm(
     Algorithm algorithm) {
   switch (algorithm) {
@@ -187,29 +140,52 @@ void FrameCryptor::unregister_observer() const {
 
 void FrameCryptor::set_packet_trailer_handler(
     std::shared_ptr<PacketTrailerHandler> handler) const {
-  if (!handler) {
+  if (!handler || !handler->transformer()) {
     return;
   }
 
-  auto timestamp_transformer = handler->transformer();
-  if (!timestamp_transformer) {
+  packet_trailer_transformer_ = handler->transformer();
+  update_transformer_chain();
+}
+
+void FrameCryptor::set_frame_transformer(
+    std::shared_ptr<FrameTransformerHandler> handler) const {
+  if (!handler || !handler->transformer()) {
     return;
   }
 
-  webrtc::scoped_refptr<webrtc::FrameTransformerInterface> first;
-  webrtc::scoped_refptr<webrtc::FrameTransformerInterface> second;
+  frame_transformer_ = handler->transformer();
+  update_transformer_chain();
+}
+
+void FrameCryptor::update_transformer_chain() const {
+  // Send: application -> e2ee -> packet trailer
+  // Receive: packet trailer -> e2ee -> application
+  webrtc::scoped_refptr<webrtc::FrameTransformerInterface> chain =
+      e2ee_transformer_;
   if (sender_) {
-    first = e2ee_transformer_;
-    second = timestamp_transformer;
+    if (packet_trailer_transformer_) {
+      chain = webrtc::make_ref_counted<ChainedFrameTransformer>(
+          chain, packet_trailer_transformer_);
+    }
+    if (frame_transformer_) {
+      chain = webrtc::make_ref_counted<ChainedFrameTransformer>(
+          frame_transformer_, chain);
+    }
   } else if (receiver_) {
-    first = timestamp_transformer;
-    second = e2ee_transformer_;
+    if (packet_trailer_transformer_) {
+      chain = webrtc::make_ref_counted<ChainedFrameTransformer>(
+          packet_trailer_transformer_, chain);
+    }
+    if (frame_transformer_) {
+      chain = webrtc::make_ref_counted<ChainedFrameTransformer>(
+          chain, frame_transformer_);
+    }
   } else {
     return;
   }
 
-  chained_transformer_ =
-      webrtc::make_ref_counted<ChainedFrameTransformer>(first, second);
+  chained_transformer_ = chain;
 
   if (sender_) {
     sender_->SetEncoderToPacketizerFrameTransformer(chained_transformer_);
@@ -103,11 +103,13 @@ pub mod ffi {
         include!("livekit/rtp_receiver.h");
         include!("livekit/peer_connection_factory.h");
         include!("livekit/packet_trailer.h");
+        include!("livekit/frame_transformer.h");
 
         type RtpSender = crate::rtp_sender::ffi::RtpSender;
         type RtpReceiver = crate::rtp_receiver::ffi::RtpReceiver;
         type PeerConnectionFactory = crate::peer_connection_factory::ffi::PeerConnectionFactory;
         type PacketTrailerHandler = crate::packet_trailer::ffi::PacketTrailerHandler;
+        type FrameTransformerHandler = crate::frame_transformer::ffi::FrameTransformerHandler;
 
         pub type FrameCryptor;
 
@@ -148,6 +150,12 @@ pub mod ffi {
             self: &FrameCryptor,
             handler: SharedPtr<PacketTrailerHandler>,
         );
+
+        /// Chain an application frame transformer so it sees unencrypted payloads.
+        pub fn set_frame_transformer(
+            self: &FrameCryptor,
+            handler: SharedPtr<FrameTransformerHandler>,
+        );
     }
 
     unsafe extern "C++" {
@@ -0,0 +1,232 @@
+/*
+ * Copyright 2026 LiveKit, Inc.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/frame_transformer.h"
+
+#include <memory>
+#include <utility>
+
+#include "api/make_ref_counted.h"
+#include "livekit/packet_trailer.h"
+#include "livekit/peer_connection_factory.h"
+#include "livekit/rtp_receiver.h"
+#include "livekit/rtp_sender.h"
+#include "rtc_base/logging.h"
+#include "webrtc-sys/src/frame_transformer.rs.h"
+
+namespace livekit_ffi {
+
+namespace {
+
+TransformableFrameKind KindFromTrack(
+    webrtc::scoped_refptr<webrtc::MediaStreamTrackInterface> track) {
+  return track && track->kind() == webrtc::MediaStreamTrackInterface::kAudioKind
+             ? TransformableFrameKind::Audio
+             : TransformableFrameKind::Video;
+}
+
+}  // namespace
+
+// ChainedFrameTransformer
+
+ChainedFrameTransformer::ChainedFrameTransformer(
+    webrtc::scoped_refptr<webrtc::FrameTransformerInterface> first,
+    webrtc::scoped_refptr<webrtc::FrameTransformerInterface> second)
+    : first_(std::move(first)), second_(std::move(second)) {}
+
+void ChainedFrameTransformer::Transform(
+    std::unique_ptr<webrtc::TransformableFrameInterface> frame) {
+  first_->Transform(std::move(frame));
+}
+
+void ChainedFrameTransformer::RegisterTransformedFrameCallback(
+    webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback) {
+  second_->RegisterTransformedFrameCallback(callback);
+  first_->RegisterTransformedFrameCallback(
+      webrtc::scoped_refptr<webrtc::TransformedFrameCallback>(this));
+}
+
+void ChainedFrameTransformer::RegisterTransformedFrameSinkCallback(
+    webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback,
+    uint32_t ssrc) {
+  second_->RegisterTransformedFrameSinkCallback(callback, ssrc);
+  first_->RegisterTransformedFrameSinkCallback(
+      webrtc::scoped_refptr<webrtc::TransformedFrameCallback>(this), ssrc);
+}
+
+void ChainedFrameTransformer::UnregisterTransformedFrameCallback() {
+  first_->UnregisterTransformedFrameCallback();
+  second_->UnregisterTransformedFrameCallback();
+}
+
+void ChainedFrameTransformer::UnregisterTransformedFrameSinkCallback(
+    uint32_t ssrc) {
+  first_->UnregisterTransformedFrameSinkCallback(ssrc);
+  second_->UnregisterTransformedFrameSinkCallback(ssrc);
+}
+
+void ChainedFrameTransformer::OnTransformedFrame(
+    std::unique_ptr<webrtc::TransformableFrameInterface> frame) {
+  second_->Transform(std::move(frame));
+}
+
+// RustFrameTransformer
+
+RustFrameTransformer::RustFrameTransformer(
+    TransformableFrameKind kind,
+    TransformableFrameDirection direction,
+    rust::Box<FrameTransformerWrapper> transformer)
+    : kind_(kind), direction_(direction), transformer_(std::move(transformer)) {}
+
+RustFrameTransformer::~RustFrameTransformer() = default;
+
+void RustFrameTransformer::Transform(
+    std::unique_ptr<webrtc::TransformableFrameInterface> frame) {
+  const uint32_t ssrc = frame->GetSsrc();
+
+  TransformableFrameInfo info;
+  info.kind = kind_;
+  info.direction = direction_;
+  info.ssrc = ssrc;
+  info.rtp_timestamp = frame->GetTimestamp();
+  info.payload_type = frame->GetPayloadType();
+  info.mime_type = rust::String(frame->GetMimeType());
+  info.is_key_frame =
+      kind_ == TransformableFrameKind::Video &&
+      static_cast<webrtc::TransformableVideoFrameInterface*>(frame.get())
+          ->IsKeyFrame();
+  auto capture_time = frame->CaptureTime();
+  info.capture_time_us = capture_time.has_value() ? capture_time->us() : -1;
+
+  auto data = frame->GetData();
+  TransformedFrameData output = transformer_->transform(
+      info, rust::Slice<const uint8_t>(data.data(), data.size()));
+
+  if (output.action == FrameTransformAction::Drop) {
+    return;
+  }
+  if (output.action == FrameTransformAction::Replace) {
+    frame->SetData(
+        webrtc::ArrayView<const uint8_t>(output.data.data(), output.data.size()));
+  }
+
+  webrtc::scoped_refptr<webrtc::TransformedFrameCallback> cb;
+  {
+    webrtc::MutexLock lock(&mutex_);
+    auto it = sink_callbacks_.find(ssrc);
+    cb = it != sink_callbacks_.end() ? it->second : callback_;
+  }
+
+  if (cb) {
+    cb->OnTransformedFrame(std::move(frame));
+  } else {
+    RTC_LOG(LS_WARNING) << "RustFrameTransformer::Transform has no callback"
+                        << " ssrc=" << ssrc;
+  }
+}
+
+void RustFrameTransformer::RegisterTransformedFrameCallback(
+    webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback) {
+  webrtc::MutexLock lock(&mutex_);
+  callback_ = callback;
+}
+
+void RustFrameTransformer::RegisterTransformedFrameSinkCallback(
+    webrtc::scoped_refptr<webrtc::TransformedFrameCallback> callback,
+    uint32_t ssrc) {
+  webrtc::MutexLock lock(&mutex_);
+  sink_callbacks_[ssrc] = callback;
+}
+
+void RustFrameTransformer::UnregisterTransformedFrameCallback() {
+  webrtc::MutexLock lock(&mutex_);
+  callback_ = nullptr;
+}
+
+void RustFrameTransformer::UnregisterTransformedFrameSinkCallback(
+    uint32_t ssrc) {
+  webrtc::MutexLock lock(&mutex_);
+  sink_callbacks_.erase(ssrc);
+}
+
+// FrameTransformerHandler
+
+FrameTransformerHandler::FrameTransformerHandler(
+    std::shared_ptr<RtcRuntime> rtc_runtime,
+    webrtc::scoped_refptr<webrtc::RtpSenderInterface> sender,
+    rust::Box<FrameTransformerWrapper> transformer)
+    : rtc_runtime_(rtc_runtime), sender_(sender) {
+  transformer_ = webrtc::make_ref_counted<RustFrameTransformer>(
+      KindFromTrack(sender->track()), TransformableFrameDirection::Sender,
+      std::move(transformer));
+  sender->SetEncoderToPacketizerFrameTransformer(transformer_);
+}
+
+FrameTransformerHandler::FrameTransformerHandler(
+    std::shared_ptr<RtcRuntime> rtc_runtime,
+    webrtc::scoped_refptr<webrtc::RtpReceiverInterface> receiver,
+    rust::Box<FrameTransformerWrapper> transformer)
+    : rtc_runtime_(rtc_runtime), receiver_(receiver) {
+  transformer_ = webrtc::make_ref_counted<RustFrameTransformer>(
+      KindFromTrack(receiver->track()), TransformableFrameDirection::Receiver,
+      std::move(transformer));
+  receiver->SetDepacketizerToDecoderFrameTransformer(transformer_);
+}
+
+void FrameTransformerHandler::set_packet_trailer_handler(
+    std::shared_ptr<PacketTrailerHandler> handler) const {
+  if (!handler || !handler->transformer()) {
+    return;
+  }
+
+  // The application sees the payload without the trailer on both sides.
+  if (sender_) {
+    chained_transformer_ = webrtc::make_ref_counted<ChainedFrameTransformer>(
+        transformer_, handler->transformer());
+    sender_->SetEncoderToPacketizerFrameTransformer(chained_transformer_);
+  } else if (receiver_) {
+    chained_transformer_ = webrtc::make_ref_counted<ChainedFrameTransformer>(
+        handler->transformer(), transformer_);
+    receiver_->SetDepacketizerToDecoderFrameTransformer(chained_transformer_);
+  }
+}
+
+webrtc::scoped_refptr<RustFrameTransformer>
+FrameTransformerHandler::transformer() const {
+  return transformer_;
+}
+
+// Factory functions
+
+std::shared_ptr<FrameTransformerHandler> new_frame_transformer_sender(
+    std::shared_ptr<PeerConnectionFactory> peer_factory,
+    std::shared_ptr<RtpSender> sender,
+    rust::Box<FrameTransformerWrapper> transformer) {
+  return std::make_shared<FrameTransformerHandler>(
+      peer_factory->rtc_runtime(), sender->rtc_sender(),
+      std::move(transformer));
+}
+
+std::shared_ptr<FrameTransformerHandler> new_frame_transformer_receiver(
+    std::shared_ptr<PeerConnectionFactory> peer_factory,
+    std::shared_ptr<RtpReceiver> receiver,
+    rust::Box<FrameTransformerWrapper> transformer) {
+  return std::make_shared<FrameTransformerHandler>(
+      peer_factory->rtc_runtime(), receiver->rtc_receiver(),
+      std::move(transformer));
+}
+
+}  // namespace livekit_ffi
@@ -0,0 +1,142 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use crate::impl_thread_safety;
+
+#[cxx::bridge(namespace = "livekit_ffi")]
+pub mod ffi {
+    #[repr(i32)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum TransformableFrameKind {
+        Audio,
+        Video,
+    }
+
+    #[repr(i32)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum TransformableFrameDirection {
+        /// Encoded by the local encoder, about to be packetized.
+        Sender,
+        /// Depacketized, about to be decoded.
+        Receiver,
+    }
+
+    #[repr(i32)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum FrameTransformAction {
+        /// Forward the frame unchanged.
+        Forward,
+        /// Forward the frame with its payload replaced by `data`.
+        Replace,
+        /// Drop the frame.
+        Drop,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct TransformableFrameInfo {
+        pub kind: TransformableFrameKind,
+        pub direction: TransformableFrameDirection,
+        pub ssrc: u32,
+        pub rtp_timestamp: u32,
+        pub payload_type: u8,
+        pub mime_type: String,
+        /// Always false for audio frames.
+        pub is_key_frame: bool,
+        /// -1 when unknown
+        pub capture_time_us: i64,
+    }
+
+    #[derive(Debug)]
+    pub struct TransformedFrameData {
+        pub action: FrameTransformAction,
+        pub data: Vec<u8>,
+    }
+
+    unsafe extern "C++" {
+        include!("livekit/frame_transformer.h");
+        include!("livekit/packet_trailer.h");
+        include!("livekit/rtp_sender.h");
+        include!("livekit/rtp_receiver.h");
+        include!("livekit/peer_connection_factory.h");
+
+        type RtpSender = crate::rtp_sender::ffi::RtpSender;
+        type RtpReceiver = crate::rtp_receiver::ffi::RtpReceiver;
+        type PeerConnectionFactory = crate::peer_connection_factory::ffi::PeerConnectionFactory;
+        type PacketTrailerHandler = crate::packet_trailer::ffi::PacketTrailerHandler;
+
+        /// Runs an application frame transformer on an RTP sender or receiver.
+        pub type FrameTransformerHandler;
+
+        /// Chain the packet trailer transformer after this one on the send side, and before it
+        /// on the receive side. Used when the track is not encrypted.
+        fn set_packet_trailer_handler(
+            self: &FrameTransformerHandler,
+            handler: SharedPtr<PacketTrailerHandler>,
+        );
+
+        /// Create a frame transformer handler for a sender and install it.
+        fn new_frame_transformer_sender(
+            peer_factory: SharedPtr<PeerConnectionFactory>,
+            sender: SharedPtr<RtpSender>,
+            transformer: Box<FrameTransformerWrapper>,
+        ) -> SharedPtr<FrameTransformerHandler>;
+
+        /// Create a frame transformer handler for a receiver and install it.
+        fn new_frame_transformer_receiver(
+            peer_factory: SharedPtr<PeerConnectionFactory>,
+            receiver: SharedPtr<RtpReceiver>,
+            transformer: Box<FrameTransformerWrapper>,
+        ) -> SharedPtr<FrameTransformerHandler>;
+    }
+
+    extern "Rust" {
+        type FrameTransformerWrapper;
+
+        fn transform(
+            self: &FrameTransformerWrapper,
+            info: &TransformableFrameInfo,
+            data: &[u8],
+        ) -> TransformedFrameData;
+    }
+}
+
+impl_thread_safety!(ffi::FrameTransformerHandler, Send + Sync);
+
+pub trait FrameTransformer: Send + Sync {
+    fn transform(
+        &self,
+        info: &ffi::TransformableFrameInfo,
+        data: &[u8],
+    ) -> ffi::TransformedFrameData;
+}
+
+pub struct FrameTransformerWrapper {
+    transformer: Arc<dyn FrameTransformer>,
+}
+
+impl FrameTransformerWrapper {
+    pub fn new(transformer: Arc<dyn FrameTransformer>) -> Self {
+        Self { transformer }
+    }
+
+    fn transform(
+        &self,
+        info: &ffi::TransformableFrameInfo,
+        data: &[u8],
+    ) -> ffi::TransformedFrameData {
+        self.transformer.transform(info, data)
+    }
+}
@@ -24,6 +24,7 @@ pub mod data_channel;
 #[cfg(any(target_os = "macos", target_os = "windows", target_os = "linux"))]
 pub mod desktop_capturer;
 pub mod frame_cryptor;
+pub mod frame_transformer;
 pub mod helper;
 pub mod jsep;
 pub mod media_stream;
@@ -37,6 +38,7 @@ pub mod rtp_parameters;
 pub mod rtp_receiver;
 pub mod rtp_sender;
 pub mod rtp_transceiver;
+pub mod video_codec;
 pub mod video_frame;
 pub mod video_frame_buffer;
 pub mod video_track;
@@ -61,6 +61,8 @@ using webrtc::VideoFrame;
 using webrtc::VideoFrameBuffer;
 using webrtc::VideoFrameType;
 
+}  // namespace
+
 VideoCodecType CodecTypeFromFormat(const SdpVideoFormat& format) {
   if (format.name == "H264") {
     return webrtc::kVideoCodecH264;
@@ -80,6 +82,8 @@ VideoCodecType CodecTypeFromFormat(const SdpVideoFormat& format) {
   return webrtc::kVideoCodecGeneric;
 }
 
+namespace {
+
 VideoCodecType CodecTypeFromBuffer(livekit::EncodedVideoCodec codec) {
   switch (codec) {
     case livekit::EncodedVideoCodec::kH264:
@@ -121,6 +125,8 @@ bool IsSameCodecType(const SdpVideoFormat& a, const SdpVideoFormat& b) {
          type_a == CodecTypeFromFormat(b);
 }
 
+}  // namespace
+
 void FillSingleLayerCodecSpecific(
     CodecSpecificInfo* codec_info,
     VideoCodecType codec_type,
@@ -182,6 +188,8 @@ void FillSingleLayerCodecSpecific(
   }
 }
 
+namespace {
+
 class PassthroughVideoEncoder final : public VideoEncoder {
  public:
   PassthroughVideoEncoder(const Environment& env, const SdpVideoFormat& format)
@@ -55,6 +55,15 @@ webrtc::PeerConnectionInterface::RTCConfiguration to_native_rtc_configuration(
       static_cast<webrtc::PeerConnectionInterface::IceTransportsType>(
           config.ice_transport_type);
 
+  if (config.max_port != 0) {
+    rtc_config.port_allocator_config.min_port = config.min_port;
+    rtc_config.port_allocator_config.max_port = config.max_port;
+  }
+
+  rtc_config.tcp_candidate_policy =
+      static_cast<webrtc::PeerConnectionInterface::TcpCandidatePolicy>(
+          config.tcp_candidate_policy);
+
   return rtc_config;
 }
 
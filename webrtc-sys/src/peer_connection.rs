@@ -71,6 +71,12 @@ pub mod ffi {
         All,
     }
 
+    #[repr(i32)]
+    pub enum TcpCandidatePolicy {
+        Enabled,
+        Disabled,
+    }
+
     pub struct RtcOfferAnswerOptions {
         offer_to_receive_video: i32,
         offer_to_receive_audio: i32,
@@ -92,6 +98,12 @@ pub mod ffi {
         pub ice_servers: Vec<IceServer>,
         pub continual_gathering_policy: ContinualGatheringPolicy,
         pub ice_transport_type: IceTransportsType,
+        /// 0 when unset, like `max_port`
+        pub min_port: u16,
+        pub max_port: u16,
+        /// `rtc::AdapterType` bits, applied when the peer connection is created
+        pub network_ignore_mask: u32,
+        pub tcp_candidate_policy: TcpCandidatePolicy,
     }
 
     extern "C++" {
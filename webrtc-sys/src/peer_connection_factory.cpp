@@ -20,7 +20,6 @@
 #include <utility>
 
 #include "api/audio_codecs/builtin_audio_decoder_factory.h"
-#include "api/audio_codecs/builtin_audio_encoder_factory.h"
 #include "api/audio/builtin_audio_processing_builder.h"
 #include "api/create_modular_peer_connection_factory.h"
 #include "api/environment/environment_factory.h"
@@ -35,6 +34,7 @@
 #include "api/audio/audio_device.h"
 #include "api/audio_options.h"
 #include "livekit/adm_proxy.h"
+#include "livekit/audio_encoder_factory.h"
 #include "livekit/audio_track.h"
 #include "livekit/peer_connection.h"
 #include "livekit/rtc_error.h"
@@ -107,11 +107,16 @@ PeerConnectionFactory::PeerConnectionFactory(
 
   dependencies.adm = adm_proxy_;
 
-  dependencies.video_encoder_factory =
-      std::move(std::make_unique<livekit_ffi::VideoEncoderFactory>());
-  dependencies.video_decoder_factory =
-      std::move(std::make_unique<livekit_ffi::VideoDecoderFactory>());
-  dependencies.audio_encoder_factory = webrtc::CreateBuiltinAudioEncoderFactory();
+  auto video_encoder_factory =
+      std::make_unique<livekit_ffi::VideoEncoderFactory>();
+  auto video_decoder_factory =
+      std::make_unique<livekit_ffi::VideoDecoderFactory>();
+  video_encoder_factory_ = video_encoder_factory.get();
+  video_decoder_factory_ = video_decoder_factory.get();
+  dependencies.video_encoder_factory = std::move(video_encoder_factory);
+  dependencies.video_decoder_factory = std::move(video_decoder_factory);
+  dependencies.audio_encoder_factory =
+      webrtc::make_ref_counted<livekit_ffi::AudioEncoderFactory>();
   dependencies.audio_decoder_factory = webrtc::CreateBuiltinAudioDecoderFactory();
   dependencies.audio_processing_builder = std::make_unique<webrtc::BuiltinAudioProcessingBuilder>();
 
@@ -140,7 +145,18 @@ std::shared_ptr<PeerConnection> PeerConnectionFactory::create_peer_connection(
   std::shared_ptr<PeerConnection> pc = std::make_shared<PeerConnection>(
       rtc_runtime_, peer_factory_, std::move(observer));
 
-  if (!pc->Initialize(to_native_rtc_configuration(config))) {
+  // The network ignore mask is a factory option read when the peer connection
+  // creates its port allocator, so it is set for this peer connection only and
+  // the factory options are restored right after.
+  webrtc::MutexLock lock(&create_pc_mutex_);
+  webrtc::PeerConnectionFactoryInterface::Options options = options_;
+  options.network_ignore_mask = config.network_ignore_mask;
+  peer_factory_->SetOptions(options);
+
+  bool initialized = pc->Initialize(to_native_rtc_configuration(config));
+  peer_factory_->SetOptions(options_);
+
+  if (!initialized) {
     throw std::runtime_error(serialize_error(to_error(webrtc::RTCError(
         webrtc::RTCErrorType::INTERNAL_ERROR, "failed to initialize pc"))));
   }
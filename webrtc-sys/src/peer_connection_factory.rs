@@ -31,9 +31,9 @@ pub mod ffi {
 
     pub struct CandidatePairChangeEvent {
         selected_candidate_pair: CandidatePair,
-        last_data_received_ms: i64,
-        reason: String,
-        estimated_disconnected_time_ms: i64,
+        pub last_data_received_ms: i64,
+        pub reason: String,
+        pub estimated_disconnected_time_ms: i64,
     }
 
     extern "C++" {
@@ -0,0 +1,374 @@
+/*
+ * Copyright 2026 LiveKit, Inc.
+ *
+ * Licensed under the Apache License, Version 2.0 (the "License");
+ * you may not use this file except in compliance with the License.
+ * You may obtain a copy of the License at
+ *
+ *     http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing, software
+ * distributed under the License is distributed on an "AS IS" BASIS,
+ * WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ * See the License for the specific language governing permissions and
+ * limitations under the License.
+ */
+
+#include "livekit/rust_video_codec.h"
+
+#include <algorithm>
+#include <optional>
+#include <string>
+#include <utility>
+#include <vector>
+
+#include "api/video/encoded_image.h"
+#include "api/video/video_frame.h"
+#include "api/video_codecs/video_decoder.h"
+#include "api/video_codecs/video_encoder.h"
+#include "av1_bitstream.h"
+#include "livekit/passthrough_video_encoder.h"
+#include "livekit/peer_connection_factory.h"
+#include "livekit/video_decoder_factory.h"
+#include "livekit/video_encoder_factory.h"
+#include "modules/video_coding/include/video_codec_interface.h"
+#include "modules/video_coding/include/video_error_codes.h"
+#include "modules/video_coding/svc/scalable_video_controller_no_layering.h"
+#include "rtc_base/logging.h"
+
+namespace livekit_ffi {
+namespace {
+
+SdpVideoFormat ToRustFormat(const webrtc::SdpVideoFormat& format) {
+  SdpVideoFormat rust_format;
+  rust_format.name = rust::String(format.name);
+  for (const auto& [key, value] : format.parameters) {
+    rust_format.parameters.push_back(
+        SdpParameter{rust::String(key), rust::String(value)});
+  }
+  return rust_format;
+}
+
+webrtc::SdpVideoFormat FromRustFormat(const SdpVideoFormat& format) {
+  webrtc::CodecParameterMap parameters;
+  for (const auto& parameter : format.parameters) {
+    parameters[std::string(parameter.key)] = std::string(parameter.value);
+  }
+  return webrtc::SdpVideoFormat(std::string(format.name), parameters);
+}
+
+std::optional<EncodedVideoCodec> ToEncodedVideoCodec(
+    webrtc::VideoCodecType codec_type) {
+  switch (codec_type) {
+    case webrtc::kVideoCodecH264:
+      return EncodedVideoCodec::H264;
+    case webrtc::kVideoCodecH265:
+      return EncodedVideoCodec::H265;
+    case webrtc::kVideoCodecVP8:
+      return EncodedVideoCodec::VP8;
+    case webrtc::kVideoCodecVP9:
+      return EncodedVideoCodec::VP9;
+    case webrtc::kVideoCodecAV1:
+      return EncodedVideoCodec::AV1;
+    default:
+      return std::nullopt;
+  }
+}
+
+class RustVideoEncoder final : public webrtc::VideoEncoder {
+ public:
+  RustVideoEncoder(EncodedVideoCodec codec,
+                   webrtc::VideoCodecType codec_type,
+                   rust::Box<VideoEncoderWrapper> encoder)
+      : codec_(codec),
+        codec_type_(codec_type),
+        encoder_(std::move(encoder)),
+        implementation_name_(encoder_->implementation_name()) {}
+
+  int32_t InitEncode(const webrtc::VideoCodec* codec_settings,
+                     const Settings& settings) override {
+    if (!codec_settings || codec_settings->codecType != codec_type_) {
+      return WEBRTC_VIDEO_CODEC_ERR_PARAMETER;
+    }
+    av1_svc_controller_ = webrtc::ScalableVideoControllerNoLayering();
+
+    VideoEncoderSettings rust_settings{
+        codec_,
+        codec_settings->width,
+        codec_settings->height,
+        codec_settings->startBitrate * 1000,
+        codec_settings->maxBitrate * 1000,
+        codec_settings->maxFramerate,
+        settings.number_of_cores,
+        settings.max_payload_size,
+    };
+    if (!encoder_->init_encode(rust_settings)) {
+      RTC_LOG(LS_ERROR) << implementation_name_ << " failed to initialize";
+      return WEBRTC_VIDEO_CODEC_ERROR;
+    }
+    return WEBRTC_VIDEO_CODEC_OK;
+  }
+
+  int32_t RegisterEncodeCompleteCallback(
+      webrtc::EncodedImageCallback* callback) override {
+    encoded_image_callback_ = callback;
+    return WEBRTC_VIDEO_CODEC_OK;
+  }
+
+  int32_t Release() override {
+    encoder_->release();
+    encoded_image_callback_ = nullptr;
+    return WEBRTC_VIDEO_CODEC_OK;
+  }
+
+  int32_t Encode(const webrtc::VideoFrame& frame,
+                 const std::vector<webrtc::VideoFrameType>* frame_types) override {
+    if (!encoded_image_callback_) {
+      return WEBRTC_VIDEO_CODEC_UNINITIALIZED;
+    }
+
+    const bool keyframe_requested =
+        frame_types != nullptr &&
+        std::any_of(frame_types->begin(), frame_types->end(),
+                    [](webrtc::VideoFrameType type) {
+                      return type == webrtc::VideoFrameType::kVideoFrameKey;
+                    });
+
+    VideoFrame rust_frame(frame);
+    EncodedImageData output = encoder_->encode(rust_frame, keyframe_requested);
+    if (output.status == VideoCodecStatus::Error) {
+      return WEBRTC_VIDEO_CODEC_ERROR;
+    }
+    if (output.status == VideoCodecStatus::NoOutput || output.data.empty()) {
+      return WEBRTC_VIDEO_CODEC_OK;
+    }
+
+    std::vector<uint8_t> payload(output.data.begin(), output.data.end());
+    if (codec_type_ == webrtc::kVideoCodecAV1) {
+      livekit::av1::NormalizeForRtp(&payload);
+    }
+
+    const bool is_keyframe = output.frame_type == EncodedFrameType::Key;
+    const int width = output.width ? output.width : frame.width();
+    const int height = output.height ? output.height : frame.height();
+
+    webrtc::EncodedImage encoded_image;
+    encoded_image._encodedWidth = width;
+    encoded_image._encodedHeight = height;
+    encoded_image.SetRtpTimestamp(frame.rtp_timestamp());
+    encoded_image.ntp_time_ms_ = frame.ntp_time_ms();
+    encoded_image.capture_time_ms_ = frame.render_time_ms();
+    encoded_image.rotation_ = frame.rotation();
+    encoded_image.content_type_ = webrtc::VideoContentType::UNSPECIFIED;
+    encoded_image.timing_.flags = webrtc::VideoSendTiming::kInvalid;
+    encoded_image._frameType = is_keyframe
+                                   ? webrtc::VideoFrameType::kVideoFrameKey
+                                   : webrtc::VideoFrameType::kVideoFrameDelta;
+    encoded_image.SetColorSpace(frame.color_space());
+    encoded_image.SetEncodedData(
+        webrtc::EncodedImageBuffer::Create(payload.data(), payload.size()));
+    encoded_image.set_size(payload.size());
+    encoded_image.qp_ = output.qp;
+
+    webrtc::CodecSpecificInfo codec_info;
+    codec_info.codecSpecific = {};
+    FillSingleLayerCodecSpecific(&codec_info, codec_type_, width, height,
+                                 is_keyframe, &av1_svc_controller_);
+
+    const auto result =
+        encoded_image_callback_->OnEncodedImage(encoded_image, &codec_info);
+    if (result.error != webrtc::EncodedImageCallback::Result::OK) {
+      return WEBRTC_VIDEO_CODEC_ERROR;
+    }
+    return WEBRTC_VIDEO_CODEC_OK;
+  }
+
+  void SetRates(const RateControlParameters& parameters) override {
+    encoder_->set_rates(parameters.bitrate.get_sum_bps(),
+                        parameters.framerate_fps);
+  }
+
+  EncoderInfo GetEncoderInfo() const override {
+    EncoderInfo info;
+    info.implementation_name = implementation_name_;
+    info.scaling_settings = VideoEncoder::ScalingSettings::kOff;
+    info.is_hardware_accelerated = false;
+    info.supports_simulcast = false;
+    info.preferred_pixel_formats = {webrtc::VideoFrameBuffer::Type::kI420};
+    return info;
+  }
+
+ private:
+  EncodedVideoCodec codec_;
+  webrtc::VideoCodecType codec_type_;
+  rust::Box<VideoEncoderWrapper> encoder_;
+  std::string implementation_name_;
+  webrtc::EncodedImageCallback* encoded_image_callback_ = nullptr;
+  webrtc::ScalableVideoControllerNoLayering av1_svc_controller_;
+};
+
+class RustVideoDecoder final : public webrtc::VideoDecoder {
+ public:
+  RustVideoDecoder(EncodedVideoCodec codec,
+                   rust::Box<VideoDecoderWrapper> decoder)
+      : codec_(codec),
+        decoder_(std::move(decoder)),
+        implementation_name_(decoder_->implementation_name()) {}
+
+  bool Configure(const Settings& settings) override {
+    VideoDecoderSettings rust_settings{
+        codec_,
+        static_cast<uint32_t>(settings.max_render_resolution().Width()),
+        static_cast<uint32_t>(settings.max_render_resolution().Height()),
+        settings.number_of_cores(),
+    };
+    return decoder_->configure(rust_settings);
+  }
+
+  int32_t Decode(const webrtc::EncodedImage& input_image,
+                 bool /* missing_frames */,
+                 int64_t /* render_time_ms */) override {
+    if (!decoded_image_callback_) {
+      return WEBRTC_VIDEO_CODEC_UNINITIALIZED;
+    }
+    if (!input_image.data() || input_image.size() == 0) {
+      return WEBRTC_VIDEO_CODEC_ERR_PARAMETER;
+    }
+
+    const EncodedFrameType frame_type =
+        input_image._frameType == webrtc::VideoFrameType::kVideoFrameKey
+            ? EncodedFrameType::Key
+            : EncodedFrameType::Delta;
+    DecodedImageData output = decoder_->decode(
+        rust::Slice<const uint8_t>(input_image.data(), input_image.size()),
+        frame_type, input_image.RtpTimestamp());
+    if (output.status == VideoCodecStatus::Error) {
+      return WEBRTC_VIDEO_CODEC_ERROR;
+    }
+    if (output.status == VideoCodecStatus::NoOutput || !output.frame) {
+      return WEBRTC_VIDEO_CODEC_OK;
+    }
+
+    const webrtc::VideoFrame frame = output.frame->get();
+    webrtc::VideoFrame decoded_frame =
+        webrtc::VideoFrame::Builder()
+            .set_video_frame_buffer(frame.video_frame_buffer())
+            .set_rotation(frame.rotation())
+            .set_timestamp_rtp(input_image.RtpTimestamp())
+            .set_ntp_time_ms(input_image.ntp_time_ms_)
+            .set_color_space(input_image.ColorSpace())
+            .build();
+    decoded_image_callback_->Decoded(decoded_frame, std::nullopt,
+                                     std::nullopt);
+    return WEBRTC_VIDEO_CODEC_OK;
+  }
+
+  int32_t RegisterDecodeCompleteCallback(
+      webrtc::DecodedImageCallback* callback) override {
+    decoded_image_callback_ = callback;
+    return WEBRTC_VIDEO_CODEC_OK;
+  }
+
+  int32_t Release() override {
+    decoder_->release();
+    decoded_image_callback_ = nullptr;
+    return WEBRTC_VIDEO_CODEC_OK;
+  }
+
+  DecoderInfo GetDecoderInfo() const override {
+    DecoderInfo info;
+    info.implementation_name = implementation_name_;
+    info.is_hardware_accelerated = false;
+    return info;
+  }
+
+ private:
+  EncodedVideoCodec codec_;
+  rust::Box<VideoDecoderWrapper> decoder_;
+  std::string implementation_name_;
+  webrtc::DecodedImageCallback* decoded_image_callback_ = nullptr;
+};
+
+}  // namespace
+
+RustVideoEncoderFactory::RustVideoEncoderFactory(
+    rust::Box<VideoEncoderFactoryWrapper> factory)
+    : factory_(std::move(factory)) {}
+
+std::vector<webrtc::SdpVideoFormat>
+RustVideoEncoderFactory::GetSupportedFormats() const {
+  std::vector<webrtc::SdpVideoFormat> formats;
+  for (const auto& format : factory_->supported_formats()) {
+    formats.push_back(FromRustFormat(format));
+  }
+  return formats;
+}
+
+std::unique_ptr<webrtc::VideoEncoder> RustVideoEncoderFactory::Create(
+    const webrtc::Environment& /* env */,
+    const webrtc::SdpVideoFormat& format) {
+  const webrtc::VideoCodecType codec_type = CodecTypeFromFormat(format);
+  const std::optional<EncodedVideoCodec> codec =
+      ToEncodedVideoCodec(codec_type);
+  if (!codec) {
+    RTC_LOG(LS_ERROR) << "Unsupported codec for a Rust video encoder: "
+                      << format.name;
+    return nullptr;
+  }
+
+  rust::Box<VideoEncoderWrapper> encoder =
+      factory_->create_encoder(ToRustFormat(format));
+  if (!encoder->is_valid()) {
+    return nullptr;
+  }
+  return std::make_unique<RustVideoEncoder>(*codec, codec_type,
+                                            std::move(encoder));
+}
+
+RustVideoDecoderFactory::RustVideoDecoderFactory(
+    rust::Box<VideoDecoderFactoryWrapper> factory)
+    : factory_(std::move(factory)) {}
+
+std::vector<webrtc::SdpVideoFormat>
+RustVideoDecoderFactory::GetSupportedFormats() const {
+  std::vector<webrtc::SdpVideoFormat> formats;
+  for (const auto& format : factory_->supported_formats()) {
+    formats.push_back(FromRustFormat(format));
+  }
+  return formats;
+}
+
+std::unique_ptr<webrtc::VideoDecoder> RustVideoDecoderFactory::Create(
+    const webrtc::Environment& /* env */,
+    const webrtc::SdpVideoFormat& format) {
+  const std::optional<EncodedVideoCodec> codec =
+      ToEncodedVideoCodec(CodecTypeFromFormat(format));
+  if (!codec) {
+    RTC_LOG(LS_ERROR) << "Unsupported codec for a Rust video decoder: "
+                      << format.name;
+    return nullptr;
+  }
+
+  rust::Box<VideoDecoderWrapper> decoder =
+      factory_->create_decoder(ToRustFormat(format));
+  if (!decoder->is_valid()) {
+    return nullptr;
+  }
+  return std::make_unique<RustVideoDecoder>(*codec, std::move(decoder));
+}
+
+void register_video_encoder_factory(
+    const PeerConnectionFactory& peer_factory,
+    rust::Box<VideoEncoderFactoryWrapper> factory) {
+  peer_factory.video_encoder_factory()->AddCustomFactory(
+      std::make_unique<RustVideoEncoderFactory>(std::move(factory)));
+}
+
+void register_video_decoder_factory(
+    const PeerConnectionFactory& peer_factory,
+    rust::Box<VideoDecoderFactoryWrapper> factory) {
+  peer_factory.video_decoder_factory()->AddCustomFactory(
+      std::make_unique<RustVideoDecoderFactory>(std::move(factory)));
+}
+
+}  // namespace livekit_ffi
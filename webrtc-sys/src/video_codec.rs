@@ -0,0 +1,300 @@
+// Copyright 2026 LiveKit, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use cxx::UniquePtr;
+
+use crate::video_frame::ffi::VideoFrame;
+
+#[cxx::bridge(namespace = "livekit_ffi")]
+pub mod ffi {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    #[repr(i32)]
+    pub enum VideoCodecStatus {
+        Ok,
+        /// The frame was consumed without producing an output, e.g. dropped by rate control.
+        NoOutput,
+        Error,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct SdpParameter {
+        pub key: String,
+        pub value: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct SdpVideoFormat {
+        pub name: String,
+        pub parameters: Vec<SdpParameter>,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct VideoEncoderSettings {
+        pub codec: EncodedVideoCodec,
+        pub width: u32,
+        pub height: u32,
+        pub start_bitrate_bps: u32,
+        pub max_bitrate_bps: u32,
+        pub max_framerate: u32,
+        pub number_of_cores: i32,
+        pub max_payload_size: u64,
+    }
+
+    #[derive(Debug)]
+    pub struct EncodedImageData {
+        pub status: VideoCodecStatus,
+        pub frame_type: EncodedFrameType,
+        pub width: u32,
+        pub height: u32,
+        /// -1 when unknown
+        pub qp: i32,
+        pub data: Vec<u8>,
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct VideoDecoderSettings {
+        pub codec: EncodedVideoCodec,
+        pub max_width: u32,
+        pub max_height: u32,
+        pub number_of_cores: i32,
+    }
+
+    pub struct DecodedImageData {
+        pub status: VideoCodecStatus,
+        pub frame: UniquePtr<VideoFrame>,
+    }
+
+    extern "C++" {
+        include!("livekit/video_frame.h");
+        include!("livekit/video_track.h");
+        include!("livekit/peer_connection_factory.h");
+
+        type VideoFrame = crate::video_frame::ffi::VideoFrame;
+        type EncodedVideoCodec = crate::video_track::ffi::EncodedVideoCodec;
+        type EncodedFrameType = crate::video_track::ffi::EncodedFrameType;
+        type PeerConnectionFactory = crate::peer_connection_factory::ffi::PeerConnectionFactory;
+    }
+
+    unsafe extern "C++" {
+        include!("livekit/rust_video_codec.h");
+
+        /// Makes the encoders of `factory` available to every peer connection created by
+        /// `peer_factory`, ahead of the built-in ones.
+        fn register_video_encoder_factory(
+            peer_factory: &PeerConnectionFactory,
+            factory: Box<VideoEncoderFactoryWrapper>,
+        );
+
+        /// Makes the decoders of `factory` available to every peer connection created by
+        /// `peer_factory`, ahead of the built-in ones.
+        fn register_video_decoder_factory(
+            peer_factory: &PeerConnectionFactory,
+            factory: Box<VideoDecoderFactoryWrapper>,
+        );
+    }
+
+    extern "Rust" {
+        type VideoEncoderFactoryWrapper;
+        type VideoEncoderWrapper;
+        type VideoDecoderFactoryWrapper;
+        type VideoDecoderWrapper;
+
+        fn supported_formats(self: &VideoEncoderFactoryWrapper) -> Vec<SdpVideoFormat>;
+        fn create_encoder(
+            self: &VideoEncoderFactoryWrapper,
+            format: &SdpVideoFormat,
+        ) -> Box<VideoEncoderWrapper>;
+
+        fn is_valid(self: &VideoEncoderWrapper) -> bool;
+        fn implementation_name(self: &VideoEncoderWrapper) -> String;
+        fn init_encode(self: &mut VideoEncoderWrapper, settings: &VideoEncoderSettings) -> bool;
+        fn encode(
+            self: &mut VideoEncoderWrapper,
+            frame: &VideoFrame,
+            keyframe_requested: bool,
+        ) -> EncodedImageData;
+        fn set_rates(self: &mut VideoEncoderWrapper, bitrate_bps: u32, framerate_fps: f64);
+        fn release(self: &mut VideoEncoderWrapper);
+
+        fn supported_formats(self: &VideoDecoderFactoryWrapper) -> Vec<SdpVideoFormat>;
+        fn create_decoder(
+            self: &VideoDecoderFactoryWrapper,
+            format: &SdpVideoFormat,
+        ) -> Box<VideoDecoderWrapper>;
+
+        fn is_valid(self: &VideoDecoderWrapper) -> bool;
+        fn implementation_name(self: &VideoDecoderWrapper) -> String;
+        fn configure(self: &mut VideoDecoderWrapper, settings: &VideoDecoderSettings) -> bool;
+        fn decode(
+            self: &mut VideoDecoderWrapper,
+            data: &[u8],
+            frame_type: EncodedFrameType,
+            rtp_timestamp: u32,
+        ) -> DecodedImageData;
+        fn release(self: &mut VideoDecoderWrapper);
+    }
+}
+
+pub trait VideoEncoderFactory: Send + Sync {
+    fn supported_formats(&self) -> Vec<ffi::SdpVideoFormat>;
+    fn create_encoder(&self, format: &ffi::SdpVideoFormat) -> Option<Box<dyn VideoEncoder>>;
+}
+
+pub trait VideoEncoder: Send {
+    fn implementation_name(&self) -> String;
+    fn init_encode(&mut self, settings: &ffi::VideoEncoderSettings) -> bool;
+    fn encode(&mut self, frame: &VideoFrame, keyframe_requested: bool) -> ffi::EncodedImageData;
+    fn set_rates(&mut self, bitrate_bps: u32, framerate_fps: f64);
+    fn release(&mut self);
+}
+
+pub trait VideoDecoderFactory: Send + Sync {
+    fn supported_formats(&self) -> Vec<ffi::SdpVideoFormat>;
+    fn create_decoder(&self, format: &ffi::SdpVideoFormat) -> Option<Box<dyn VideoDecoder>>;
+}
+
+pub trait VideoDecoder: Send {
+    fn implementation_name(&self) -> String;
+    fn configure(&mut self, settings: &ffi::VideoDecoderSettings) -> bool;
+    fn decode(
+        &mut self,
+        data: &[u8],
+        frame_type: ffi::EncodedFrameType,
+        rtp_timestamp: u32,
+    ) -> ffi::DecodedImageData;
+    fn release(&mut self);
+}
+
+pub struct VideoEncoderFactoryWrapper {
+    factory: Arc<dyn VideoEncoderFactory>,
+}
+
+impl VideoEncoderFactoryWrapper {
+    pub fn new(factory: Arc<dyn VideoEncoderFactory>) -> Self {
+        Self { factory }
+    }
+
+    fn supported_formats(&self) -> Vec<ffi::SdpVideoFormat> {
+        self.factory.supported_formats()
+    }
+
+    fn create_encoder(&self, format: &ffi::SdpVideoFormat) -> Box<VideoEncoderWrapper> {
+        Box::new(VideoEncoderWrapper { encoder: self.factory.create_encoder(format) })
+    }
+}
+
+/// Holds `None` when the factory could not create an encoder for the requested format.
+pub struct VideoEncoderWrapper {
+    encoder: Option<Box<dyn VideoEncoder>>,
+}
+
+impl VideoEncoderWrapper {
+    fn is_valid(&self) -> bool {
+        self.encoder.is_some()
+    }
+
+    fn implementation_name(&self) -> String {
+        self.encoder.as_ref().map(|e| e.implementation_name()).unwrap_or_default()
+    }
+
+    fn init_encode(&mut self, settings: &ffi::VideoEncoderSettings) -> bool {
+        self.encoder.as_mut().is_some_and(|e| e.init_encode(settings))
+    }
+
+    fn encode(&mut self, frame: &VideoFrame, keyframe_requested: bool) -> ffi::EncodedImageData {
+        match self.encoder.as_mut() {
+            Some(encoder) => encoder.encode(frame, keyframe_requested),
+            None => ffi::EncodedImageData {
+                status: ffi::VideoCodecStatus::Error,
+                frame_type: ffi::EncodedFrameType::Delta,
+                width: 0,
+                height: 0,
+                qp: -1,
+                data: Vec::new(),
+            },
+        }
+    }
+
+    fn set_rates(&mut self, bitrate_bps: u32, framerate_fps: f64) {
+        if let Some(encoder) = self.encoder.as_mut() {
+            encoder.set_rates(bitrate_bps, framerate_fps);
+        }
+    }
+
+    fn release(&mut self) {
+        if let Some(encoder) = self.encoder.as_mut() {
+            encoder.release();
+        }
+    }
+}
+
+pub struct VideoDecoderFactoryWrapper {
+    factory: Arc<dyn VideoDecoderFactory>,
+}
+
+impl VideoDecoderFactoryWrapper {
+    pub fn new(factory: Arc<dyn VideoDecoderFactory>) -> Self {
+        Self { factory }
+    }
+
+    fn supported_formats(&self) -> Vec<ffi::SdpVideoFormat> {
+        self.factory.supported_formats()
+    }
+
+    fn create_decoder(&self, format: &ffi::SdpVideoFormat) -> Box<VideoDecoderWrapper> {
+        Box::new(VideoDecoderWrapper { decoder: self.factory.create_decoder(format) })
+    }
+}
+
+/// Holds `None` when the factory could not create a decoder for the requested format.
+pub struct VideoDecoderWrapper {
+    decoder: Option<Box<dyn VideoDecoder>>,
+}
+
+impl VideoDecoderWrapper {
+    fn is_valid(&self) -> bool {
+        self.decoder.is_some()
+    }
+
+    fn implementation_name(&self) -> String {
+        self.decoder.as_ref().map(|d| d.implementation_name()).unwrap_or_default()
+    }
+
+    fn configure(&mut self, settings: &ffi::VideoDecoderSettings) -> bool {
+        self.decoder.as_mut().is_some_and(|d| d.configure(settings))
+    }
+
+    fn decode(
+        &mut self,
+        data: &[u8],
+        frame_type: ffi::EncodedFrameType,
+        rtp_timestamp: u32,
+    ) -> ffi::DecodedImageData {
+        match self.decoder.as_mut() {
+            Some(decoder) => decoder.decode(data, frame_type, rtp_timestamp),
+            None => ffi::DecodedImageData {
+                status: ffi::VideoCodecStatus::Error,
+                frame: UniquePtr::null(),
+            },
+        }
+    }
+
+    fn release(&mut self) {
+        if let Some(decoder) = self.decoder.as_mut() {
+            decoder.release();
+        }
+    }
+}
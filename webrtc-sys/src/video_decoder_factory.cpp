@@ -16,6 +16,8 @@
 
 #include "livekit/video_decoder_factory.h"
 
+#include <algorithm>
+
 #include <modules/video_coding/codecs/av1/av1_svc_config.h>
 #include "api/environment/environment.h"
 #include "api/video_codecs/av1_profile.h"
@@ -67,6 +69,23 @@ bool IsInternalH264DecoderAvailable() {
   }
   return true;
 }
+
+// Appends the `supported` formats no custom factory already advertises, so
+// a codec provided by both is only negotiated once.
+void AppendFormatsNotCustom(std::vector<webrtc::SdpVideoFormat>& formats,
+                            size_t custom_format_count,
+                            const std::vector<webrtc::SdpVideoFormat>& supported) {
+  for (const auto& format : supported) {
+    const bool is_custom = std::any_of(
+        formats.begin(), formats.begin() + custom_format_count,
+        [&](const webrtc::SdpVideoFormat& custom) {
+          return custom.IsSameCodec(format);
+        });
+    if (!is_custom) {
+      formats.push_back(format);
+    }
+  }
+}
 }  // namespace
 
 VideoDecoderFactory::VideoDecoderFactory()
@@ -91,30 +110,50 @@ VideoDecoderFactory::VideoDecoderFactory()
   }
 }
 
+void VideoDecoderFactory::AddCustomFactory(
+    std::unique_ptr<webrtc::VideoDecoderFactory> factory) {
+  webrtc::MutexLock lock(&custom_factories_mutex_);
+  custom_factories_.push_back(std::move(factory));
+}
+
+std::vector<std::shared_ptr<webrtc::VideoDecoderFactory>>
+VideoDecoderFactory::CustomFactories() const {
+  webrtc::MutexLock lock(&custom_factories_mutex_);
+  return custom_factories_;
+}
+
 std::vector<webrtc::SdpVideoFormat> VideoDecoderFactory::GetSupportedFormats()
     const {
   std::vector<webrtc::SdpVideoFormat> formats;
 
-  for (const auto& factory : factories_) {
+  for (const auto& factory : CustomFactories()) {
     auto supported_formats = factory->GetSupportedFormats();
     formats.insert(formats.end(), supported_formats.begin(),
                    supported_formats.end());
   }
 
-  formats.push_back(webrtc::SdpVideoFormat(webrtc::kVp8CodecName));
+  const size_t custom_format_count = formats.size();
+  for (const auto& factory : factories_) {
+    AppendFormatsNotCustom(formats, custom_format_count,
+                           factory->GetSupportedFormats());
+  }
+
+  std::vector<webrtc::SdpVideoFormat> builtin_formats;
+  builtin_formats.push_back(webrtc::SdpVideoFormat(webrtc::kVp8CodecName));
   for (const webrtc::SdpVideoFormat& format :
        webrtc::SupportedVP9DecoderCodecs())
-    formats.push_back(format);
+    builtin_formats.push_back(format);
   if (internal_h264_decoder_works_) {
     for (const webrtc::SdpVideoFormat& h264_format :
          webrtc::SupportedH264DecoderCodecs()) {
-      formats.push_back(h264_format);
+      builtin_formats.push_back(h264_format);
     }
   }
 
-  formats.push_back(webrtc::SdpVideoFormat(
+  builtin_formats.push_back(webrtc::SdpVideoFormat(
       webrtc::SdpVideoFormat::AV1Profile0(),
       webrtc::LibaomAv1EncoderSupportedScalabilityModes()));
+  AppendFormatsNotCustom(formats, custom_format_count, builtin_formats);
   return formats;
 }
 
@@ -136,6 +175,20 @@ VideoDecoderFactory::CodecSupport VideoDecoderFactory::QueryCodecSupport(
 
 std::unique_ptr<webrtc::VideoDecoder> VideoDecoderFactory::Create(
     const webrtc::Environment& env, const webrtc::SdpVideoFormat& format) {
+  for (const auto& factory : CustomFactories()) {
+    for (const auto& supported_format : factory->GetSupportedFormats()) {
+      if (!supported_format.IsSameCodec(format))
+        continue;
+      auto decoder = factory->Create(env, format);
+      if (decoder)
+        return decoder;
+      RTC_LOG(LS_WARNING) << "Custom video decoder factory failed to create "
+                          << format.name
+                          << "; falling back to the built-in decoders.";
+      break;
+    }
+  }
+
   for (const auto& factory : factories_) {
     for (const auto& supported_format : factory->GetSupportedFormats()) {
       if (supported_format.IsSameCodec(format))
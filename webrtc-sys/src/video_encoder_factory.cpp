@@ -207,6 +207,33 @@ bool FormatSupportedByBackendFactory(VideoEncoderBackend backend,
   return supported.IsSameCodec(requested);
 }
 
+bool SupportsFormat(const webrtc::VideoEncoderFactory& factory,
+                    const webrtc::SdpVideoFormat& format) {
+  for (const auto& supported_format : factory.GetSupportedFormats()) {
+    if (supported_format.IsSameCodec(format)) {
+      return true;
+    }
+  }
+  return false;
+}
+
+// Appends the `supported` formats no custom factory already advertises, so
+// a codec provided by both is only negotiated once.
+void AppendFormatsNotCustom(std::vector<webrtc::SdpVideoFormat>& formats,
+                            size_t custom_format_count,
+                            const std::vector<webrtc::SdpVideoFormat>& supported) {
+  for (const auto& format : supported) {
+    const bool is_custom = std::any_of(
+        formats.begin(), formats.begin() + custom_format_count,
+        [&](const webrtc::SdpVideoFormat& custom) {
+          return custom.IsSameCodec(format);
+        });
+    if (!is_custom) {
+      formats.push_back(format);
+    }
+  }
+}
+
 void AddBackendFactory(
     std::vector<VideoEncoderBackendFactory>& factories,
     VideoEncoderBackend backend,
@@ -375,17 +402,37 @@ VideoEncoderFactory::InternalFactory::InternalFactory() {
   }
 }
 
+void VideoEncoderFactory::InternalFactory::AddCustomFactory(
+    std::unique_ptr<webrtc::VideoEncoderFactory> factory) {
+  webrtc::MutexLock lock(&custom_factories_mutex_);
+  custom_factories_.push_back(std::move(factory));
+}
+
+std::vector<std::shared_ptr<webrtc::VideoEncoderFactory>>
+VideoEncoderFactory::InternalFactory::CustomFactories() const {
+  webrtc::MutexLock lock(&custom_factories_mutex_);
+  return custom_factories_;
+}
+
 std::vector<webrtc::SdpVideoFormat>
 VideoEncoderFactory::InternalFactory::GetSupportedFormats() const {
-  std::vector<webrtc::SdpVideoFormat> formats = Factory().GetSupportedFormats();
+  std::vector<webrtc::SdpVideoFormat> formats;
+  for (const auto& factory : CustomFactories()) {
+    auto supported_formats = factory->GetSupportedFormats();
+    formats.insert(formats.end(), supported_formats.begin(),
+                   supported_formats.end());
+  }
+
+  const size_t custom_format_count = formats.size();
+  AppendFormatsNotCustom(formats, custom_format_count,
+                         Factory().GetSupportedFormats());
 
   for (const auto& backend_factory : factories_) {
     if (backend_factory.backend == VideoEncoderBackend::PreEncoded) {
       continue;
     }
-    auto supported_formats = backend_factory.factory->GetSupportedFormats();
-    formats.insert(formats.end(), supported_formats.begin(),
-                   supported_formats.end());
+    AppendFormatsNotCustom(formats, custom_format_count,
+                           backend_factory.factory->GetSupportedFormats());
   }
 
   // The pass-through factory would otherwise advertise codecs no real
@@ -440,6 +487,14 @@ VideoEncoderFactory::InternalFactory::QueryCodecSupport(
     std::optional<std::string> scalability_mode) const {
   auto requested_backend = BackendFromFormat(format);
   auto stripped_format = StripBackendParameter(format);
+  if (requested_backend != VideoEncoderBackend::PreEncoded) {
+    for (const auto& factory : CustomFactories()) {
+      if (SupportsFormat(*factory, stripped_format)) {
+        return webrtc::VideoEncoderFactory::CodecSupport{.is_supported = true};
+      }
+    }
+  }
+
   if (requested_backend == VideoEncoderBackend::Software) {
     auto original_format =
         webrtc::FuzzyMatchSdpVideoFormat(Factory().GetSupportedFormats(),
@@ -510,6 +565,21 @@ VideoEncoderFactory::InternalFactory::Create(
   auto stripped_format = StripBackendParameter(format);
   bool requested_backend_unavailable = false;
 
+  if (requested_backend != VideoEncoderBackend::PreEncoded) {
+    for (const auto& factory : CustomFactories()) {
+      if (!SupportsFormat(*factory, stripped_format)) {
+        continue;
+      }
+      auto encoder = factory->Create(env, stripped_format);
+      if (encoder) {
+        return encoder;
+      }
+      RTC_LOG(LS_WARNING) << "Custom video encoder factory failed to create "
+                          << stripped_format.name
+                          << "; falling back to the built-in encoders.";
+    }
+  }
+
   if (requested_backend == VideoEncoderBackend::Software) {
     auto original_format =
         webrtc::FuzzyMatchSdpVideoFormat(Factory().GetSupportedFormats(),
@@ -589,6 +659,11 @@ VideoEncoderFactory::VideoEncoderFactory() {
   internal_factory_ = std::make_unique<InternalFactory>();
 }
 
+void VideoEncoderFactory::AddCustomFactory(
+    std::unique_ptr<webrtc::VideoEncoderFactory> factory) {
+  internal_factory_->AddCustomFactory(std::move(factory));
+}
+
 std::vector<webrtc::SdpVideoFormat> VideoEncoderFactory::GetSupportedFormats()
     const {
   return internal_factory_->GetSupportedFormats();